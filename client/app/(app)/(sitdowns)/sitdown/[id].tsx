@@ -54,6 +54,7 @@ export default function SitdownScreen() {
     removeParticipant,
     leaveSitDown,
     toggleAdmin,
+    setContextBudget,
   } = useSitDownData(id);
 
   const [showMembers, setShowMembers] = useState(false);
@@ -79,6 +80,8 @@ export default function SitdownScreen() {
     return buildMemberOwnerMap(participantMembers, dons);
   }, [participants, participantMembers]);
 
+  const isAdmin = !!participants.find((p) => p.user_id === user?.id)?.is_admin;
+
   const handleToggleMembers = useCallback(() => {
     setShowMembers((s) => !s);
   }, []);
@@ -196,6 +199,17 @@ export default function SitdownScreen() {
           }
         }
       : undefined,
+    contextTokenBudget: sitDown.context_token_budget ?? null,
+    onSetContextBudget: sitDown.created_by === user?.id || isAdmin
+      ? async (budget: number | null) => {
+          try {
+            await setContextBudget(budget);
+            toast.success(budget ? 'Context budget set.' : 'Context budget follows each model.');
+          } catch (e) {
+            toast.error(e instanceof Error ? e.message : "Couldn't change the context budget.");
+          }
+        }
+      : undefined,
     onLeave: sitDown.is_direct
       ? undefined
       : async () => {
@@ -236,6 +250,13 @@ export default function SitdownScreen() {
                   {sitDown.description}
                 </Text>
               )}
+              {sitDown.forked_from_sit_down_id && (
+                <Pressable onPress={() => router.push(`/sitdown/${sitDown.forked_from_sit_down_id}`)} hitSlop={4}>
+                  <Text className="text-[10px] text-stone-500">
+                    Forked sit-down · <Text className="text-gold-600">view original</Text>
+                  </Text>
+                </Pressable>
+              )}
             </View>
           </View>
 
@@ -253,6 +274,7 @@ export default function SitdownScreen() {
         <ChatView
           sitDownId={sitDown.id}
           userId={user?.id}
+          isAdmin={isAdmin}
           members={participantMembers}
           memberOwnerMap={memberOwnerMap}
           messages={messages}
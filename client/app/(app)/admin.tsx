@@ -5,8 +5,12 @@ import { BackgroundWatermark } from '../../components/BackgroundWatermark';
 import { useAuth } from '../../contexts/AuthContext';
 import { ModelCatalogManager } from '../../components/admin/ModelCatalogManager';
 import { UserTierManager } from '../../components/admin/UserTierManager';
+import { UsageReport } from '../../components/admin/UsageReport';
+import { QuotaManager } from '../../components/admin/QuotaManager';
+import { TierManager } from '../../components/admin/TierManager';
+import { AuditLog } from '../../components/admin/AuditLog';
 
-type Tab = 'catalog' | 'users';
+type Tab = 'catalog' | 'users' | 'tiers' | 'usage' | 'quotas' | 'audit';
 
 export default function AdminScreen() {
   const { isGodfather } = useAuth();
@@ -31,7 +35,7 @@ export default function AdminScreen() {
           <View className="mb-8">
             <Text className="font-serif text-3xl font-bold text-stone-100">Admin</Text>
             <Text className="mt-1 text-sm text-stone-400">
-              Manage the model catalog and user tiers.
+              Manage the model catalog, user tiers and quotas, and see who is spending what.
             </Text>
           </View>
 
@@ -61,11 +65,63 @@ export default function AdminScreen() {
                 Users
               </Text>
             </Pressable>
+            <Pressable
+              onPress={() => setTab('tiers')}
+              className={`flex-1 items-center rounded-md px-4 py-2 ${
+                tab === 'tiers' ? 'bg-stone-800' : ''
+              }`}
+            >
+              <Text className={`text-sm font-medium ${
+                tab === 'tiers' ? 'text-gold-500' : 'text-stone-400'
+              }`}>
+                Tiers
+              </Text>
+            </Pressable>
+            <Pressable
+              onPress={() => setTab('usage')}
+              className={`flex-1 items-center rounded-md px-4 py-2 ${
+                tab === 'usage' ? 'bg-stone-800' : ''
+              }`}
+            >
+              <Text className={`text-sm font-medium ${
+                tab === 'usage' ? 'text-gold-500' : 'text-stone-400'
+              }`}>
+                Usage
+              </Text>
+            </Pressable>
+            <Pressable
+              onPress={() => setTab('quotas')}
+              className={`flex-1 items-center rounded-md px-4 py-2 ${
+                tab === 'quotas' ? 'bg-stone-800' : ''
+              }`}
+            >
+              <Text className={`text-sm font-medium ${
+                tab === 'quotas' ? 'text-gold-500' : 'text-stone-400'
+              }`}>
+                Quotas
+              </Text>
+            </Pressable>
+            <Pressable
+              onPress={() => setTab('audit')}
+              className={`flex-1 items-center rounded-md px-4 py-2 ${
+                tab === 'audit' ? 'bg-stone-800' : ''
+              }`}
+            >
+              <Text className={`text-sm font-medium ${
+                tab === 'audit' ? 'text-gold-500' : 'text-stone-400'
+              }`}>
+                Audit
+              </Text>
+            </Pressable>
           </View>
 
           {/* Content */}
           {tab === 'catalog' && <ModelCatalogManager />}
           {tab === 'users' && <UserTierManager />}
+          {tab === 'tiers' && <TierManager />}
+          {tab === 'usage' && <UsageReport />}
+          {tab === 'quotas' && <QuotaManager />}
+          {tab === 'audit' && <AuditLog />}
         </View>
       </ScrollView>
     </View>
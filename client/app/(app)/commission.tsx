@@ -1,10 +1,12 @@
 import { useState } from 'react';
 import { View, Text, Pressable, ScrollView } from 'react-native';
-import { MoreVertical, Trash2, UserPlus, MessageCircle } from 'lucide-react-native';
+import { MoreVertical, Trash2, UserPlus, MessageCircle, Handshake } from 'lucide-react-native';
 import { useRouter } from 'expo-router';
 import { useCommission } from '../../hooks/useCommission';
 import { useBackRoomSitDowns } from '../../hooks/useBackRoomSitDowns';
 import { InviteToCommissionModal } from '../../components/commission/InviteToCommissionModal';
+import { LendSoldierModal } from '../../components/commission/LendSoldierModal';
+import { useAuth } from '../../contexts/AuthContext';
 import { Dropdown } from '../../components/ui/Dropdown';
 import { toast } from '../../lib/toast';
 import { confirmAlert } from '../../lib/alert';
@@ -12,10 +14,29 @@ import { BackgroundWatermark } from '../../components/BackgroundWatermark';
 
 export default function CommissionScreen() {
   const router = useRouter();
-  const { contacts, pendingInvites, sentInvites, acceptInvite, declineInvite, removeContact } = useCommission();
+  const { user } = useAuth();
+  const {
+    contacts, pendingInvites, sentInvites, acceptInvite, declineInvite, removeContact,
+    soldierLoans, respondToLoan, revokeLoan,
+  } = useCommission();
   const { openOrCreateBackRoom, markAsRead } = useBackRoomSitDowns();
   const [showInvite, setShowInvite] = useState(false);
   const [menuOpen, setMenuOpen] = useState<string | null>(null);
+  const [lendTo, setLendTo] = useState<{ id: string; name: string } | null>(null);
+
+  const loanOffers = soldierLoans.filter((l) => l.status === 'offered' && l.borrower_id === user?.id);
+  const openLoans = soldierLoans.filter((l) => !loanOffers.includes(l));
+
+  async function handleRevokeLoan(loanId: string, soldierName: string) {
+    const confirmed = await confirmAlert('End loan', `End the loan of ${soldierName}?`);
+    if (!confirmed) return;
+    try {
+      await revokeLoan(loanId);
+      toast.success('The loan is over.');
+    } catch {
+      toast.error("Couldn't end the loan.");
+    }
+  }
 
   async function handleRemoveContact(contactUserId: string, contactName: string) {
     setMenuOpen(null);
@@ -155,6 +176,17 @@ export default function CommissionScreen() {
                     <MessageCircle size={14} color="#d97706" />
                     <Text className="text-sm text-gold-500">Pull aside</Text>
                   </Pressable>
+                  <Pressable
+                    onPress={() => {
+                      setMenuOpen(null);
+                      setLendTo({ id: c.contact_user_id, name: contactName });
+                    }}
+                    className="flex-row items-center gap-2 px-3 py-1.5"
+                    style={{ width: 144 }}
+                  >
+                    <Handshake size={14} color="#a8a29e" />
+                    <Text className="text-sm text-stone-300">Lend a soldier</Text>
+                  </Pressable>
                   <Pressable
                     onPress={() => handleRemoveContact(c.contact_user_id, contactName)}
                     className="flex-row items-center gap-2 px-3 py-1.5"
@@ -174,6 +206,75 @@ export default function CommissionScreen() {
           )}
         </View>
 
+        {/* Soldier loans */}
+        {(loanOffers.length > 0 || openLoans.length > 0) && (
+          <View className="mt-6 gap-2">
+            <Text className="text-xs font-semibold uppercase tracking-wider text-stone-500">
+              Soldier Loans
+            </Text>
+            {loanOffers.map((loan) => (
+              <View key={loan.id} className="rounded-lg border border-gold-600/30 bg-gold-600/10 px-4 py-3">
+                <Text className="mb-2 text-sm text-gold-500">
+                  <Text className="font-semibold">{loan.lender?.display_name ?? 'A Don'}</Text>
+                  {' offers you '}
+                  <Text className="font-semibold">{loan.soldier?.name ?? 'a soldier'}</Text>
+                  {` until ${new Date(loan.expires_at).toLocaleDateString()}`}
+                </Text>
+                <View className="flex-row gap-2">
+                  <Pressable
+                    onPress={async () => {
+                      try {
+                        await respondToLoan(loan.id, true);
+                        toast.success(`${loan.soldier?.name ?? 'The soldier'} has joined your crews.`);
+                      } catch {
+                        toast.error("Couldn't accept the loan.");
+                      }
+                    }}
+                    className="rounded bg-gold-600 px-3 py-1.5"
+                  >
+                    <Text className="text-sm font-semibold text-stone-950">Accept</Text>
+                  </Pressable>
+                  <Pressable
+                    onPress={async () => {
+                      try {
+                        await respondToLoan(loan.id, false);
+                        toast.success('Offer declined.');
+                      } catch {
+                        toast.error("Couldn't decline the loan.");
+                      }
+                    }}
+                    className="rounded border border-stone-700 px-3 py-1.5"
+                  >
+                    <Text className="text-sm text-stone-400">Decline</Text>
+                  </Pressable>
+                </View>
+              </View>
+            ))}
+            {openLoans.map((loan) => {
+              const lending = loan.lender_id === user?.id;
+              const otherDon = lending ? loan.borrower?.display_name : loan.lender?.display_name;
+              const soldierName = loan.soldier?.name ?? 'Soldier';
+              return (
+                <View key={loan.id} className="flex-row items-center gap-3 rounded-lg bg-stone-900 px-4 py-3">
+                  <Text className="text-base">{loan.soldier?.avatar_url || '\u{1F5E1}'}</Text>
+                  <View className="min-w-0 flex-1">
+                    <Text numberOfLines={1} className="text-sm text-stone-300">
+                      {soldierName} {lending ? '→' : '←'} {otherDon ?? 'Don'}
+                    </Text>
+                    <Text className="text-[10px] text-stone-600">
+                      {loan.status === 'offered' ? 'Awaiting acceptance' : `${loan.invocations} jobs`}
+                      {` · until ${new Date(loan.expires_at).toLocaleDateString()}`}
+                    </Text>
+                  </View>
+                  <Pressable onPress={() => handleRevokeLoan(loan.id, soldierName)} hitSlop={6}>
+                    <Text className="text-xs text-red-400">End</Text>
+                  </Pressable>
+                </View>
+              );
+            })}
+          </View>
+        )}
+
         {/* Invite Button */}
         <Pressable
           onPress={() => setShowInvite(true)}
@@ -188,6 +289,13 @@ export default function CommissionScreen() {
         visible={showInvite}
         onClose={() => setShowInvite(false)}
       />
+
+      <LendSoldierModal
+        visible={!!lendTo}
+        onClose={() => setLendTo(null)}
+        borrowerId={lendTo?.id ?? ''}
+        borrowerName={lendTo?.name ?? ''}
+      />
     </View>
   );
 }
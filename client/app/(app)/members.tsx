@@ -9,9 +9,10 @@ import { MemberEditor } from '../../components/members/MemberEditor';
 import { CaporegimeCard } from '../../components/members/CaporegimeCard';
 import { InformantCard } from '../../components/members/InformantCard';
 import { InformantUsage } from '../../components/members/InformantUsage';
+import { NotificationBanner } from '../../components/members/NotificationBanner';
 import { Dropdown } from '../../components/ui/Dropdown';
 import { MEMBER_TYPE_DESCRIPTIONS } from '../../config/constants';
-import type { Member, MemberType, SoldierType, SoldierConfig } from '../../lib/types';
+import type { Member, MemberType, PlanMode, SoldierType, SoldierConfig } from '../../lib/types';
 import { toast } from '../../lib/toast';
 import { confirmAlert } from '../../lib/alert';
 import { BackgroundWatermark } from '../../components/BackgroundWatermark';
@@ -101,9 +102,11 @@ export default function MembersScreen() {
   async function handleSave(data: {
     name: string;
     catalog_model_id?: string;
+    fallback_model_ids?: string[];
     system_prompt: string;
     member_type?: MemberType;
-    caporegime_id?: string;
+    caporegime_id?: string | null;
+    plan_mode?: PlanMode;
     avatar_url?: string;
     soldier_type?: SoldierType;
     soldier_config?: SoldierConfig;
@@ -113,6 +116,7 @@ export default function MembersScreen() {
         const updates: Record<string, unknown> = {
           name: data.name,
           catalog_model_id: data.catalog_model_id,
+          fallback_model_ids: data.fallback_model_ids,
           system_prompt: data.system_prompt,
           avatar_url: data.avatar_url,
         };
@@ -120,9 +124,13 @@ export default function MembersScreen() {
           updates.soldier_type = data.soldier_type;
           updates.soldier_config = data.soldier_config;
         }
+        if (editing.member_type === 'caporegime') {
+          updates.caporegime_id = data.caporegime_id ?? null;
+          updates.plan_mode = data.plan_mode;
+        }
         await updateMember(editing.id, updates);
         // Reload crew if editing a soldier
-        if (editing.caporegime_id) {
+        if (editing.member_type === 'soldier' && editing.caporegime_id) {
           loadCrew(editing.caporegime_id);
         }
         toast.success(`${data.name} has new orders.`);
@@ -138,7 +146,7 @@ export default function MembersScreen() {
         const label = data.member_type === 'soldier' ? 'soldier' : data.member_type === 'caporegime' ? 'captain' : 'member';
         toast.success(`${data.name} has joined the Family as ${label}.`);
         // Reload crew if we added a soldier
-        if (data.caporegime_id) {
+        if (data.member_type === 'soldier' && data.caporegime_id) {
           loadCrew(data.caporegime_id);
         }
       }
@@ -247,6 +255,8 @@ export default function MembersScreen() {
           </Pressable>
         </View>
 
+        <NotificationBanner />
+
         {isLoading ? (
           <View className="items-center justify-center py-12">
             <ActivityIndicator color="#78716c" />
@@ -297,6 +307,9 @@ export default function MembersScreen() {
                       onDeleteSoldier={handleDeleteSoldier}
                       soldiers={crewMap[member.id] || []}
                       loadingSoldiers={crewLoading[member.id]}
+                      captains={caporegimes.filter((c) => c.caporegime_id === member.id)}
+                      reportsTo={caporegimes.find((c) => c.id === member.caporegime_id)}
+                      onEditCaptain={(captain) => setEditing(captain)}
                     />
                   ))}
                 </View>
@@ -366,6 +379,7 @@ export default function MembersScreen() {
         onClose={handleClose}
         forceMemberType={editorMemberType}
         caporegimeId={editorCaporegimeId}
+        caporegimeOptions={caporegimes}
       />
 
       {/* Informant Token Display Modal */}
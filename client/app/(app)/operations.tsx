@@ -1,7 +1,9 @@
 import { useState } from 'react';
 import { View, Text, Pressable, ScrollView, ActivityIndicator } from 'react-native';
-import { ChevronDown, ChevronUp, Clock, CheckCircle, XCircle } from 'lucide-react-native';
+import * as Clipboard from 'expo-clipboard';
+import { ChevronDown, ChevronUp, Clock, CheckCircle, XCircle, PauseCircle, MinusCircle, Activity } from 'lucide-react-native';
 import { useOperations } from '../../hooks/useOperations';
+import { toast } from '../../lib/toast';
 import { BackgroundWatermark } from '../../components/BackgroundWatermark';
 import type { Operation, BookkeeperEntry } from '../../lib/types';
 
@@ -9,12 +11,16 @@ const STATUS_COLORS: Record<string, string> = {
   running: 'bg-blue-600',
   completed: 'bg-green-700',
   failed: 'bg-red-700',
+  awaiting_approval: 'bg-amber-600',
+  cancelled: 'bg-stone-600',
 };
 
 const STATUS_ICONS: Record<string, typeof Clock> = {
   running: Clock,
   completed: CheckCircle,
   failed: XCircle,
+  awaiting_approval: PauseCircle,
+  cancelled: MinusCircle,
 };
 
 function formatTime(ts: string) {
@@ -22,11 +28,30 @@ function formatTime(ts: string) {
   return d.toLocaleString(undefined, { month: 'short', day: 'numeric', hour: '2-digit', minute: '2-digit' });
 }
 
-function OperationCard({ operation }: { operation: Operation }) {
+function OperationCard({
+  operation,
+  onExportTrace,
+}: {
+  operation: Operation;
+  onExportTrace: (operationId: string) => Promise<string>;
+}) {
   const [expanded, setExpanded] = useState(false);
+  const [exporting, setExporting] = useState(false);
   const StatusIcon = STATUS_ICONS[operation.status] ?? Clock;
   const memberName = operation.member?.name ?? 'Unknown';
 
+  async function handleExportTrace() {
+    setExporting(true);
+    try {
+      await Clipboard.setStringAsync(await onExportTrace(operation.id));
+      toast.success('Trace copied as OTLP JSON.');
+    } catch {
+      toast.error("Couldn't export the trace.");
+    } finally {
+      setExporting(false);
+    }
+  }
+
   return (
     <Pressable
       onPress={() => setExpanded(!expanded)}
@@ -40,9 +65,16 @@ function OperationCard({ operation }: { operation: Operation }) {
 
         {/* Info */}
         <View className="min-w-0 flex-1">
-          <Text className="text-sm font-medium text-stone-100" numberOfLines={1}>
-            {memberName}
-          </Text>
+          <View className="flex-row items-center gap-2">
+            <Text className="text-sm font-medium text-stone-100" numberOfLines={1}>
+              {memberName}
+            </Text>
+            {operation.parent_operation_id && (
+              <View className="rounded bg-stone-800 px-1.5 py-0.5">
+                <Text className="text-[9px] font-semibold text-stone-400">SUB-OP · L{(operation.depth ?? 0) + 1}</Text>
+              </View>
+            )}
+          </View>
           <Text className="text-xs text-stone-500" numberOfLines={1}>
             {operation.task_summary || 'No summary'}
           </Text>
@@ -60,6 +92,17 @@ function OperationCard({ operation }: { operation: Operation }) {
 
       {expanded && (
         <View className="border-t border-stone-800 px-4 py-3 gap-2">
+          {operation.plan && operation.plan.steps.length > 0 && (
+            <View>
+              <Text className="text-xs font-semibold text-stone-500 mb-1">Plan</Text>
+              {operation.plan.steps.map((step, i) => (
+                <Text key={step.id} className="text-[10px] text-stone-400">
+                  {i + 1}. [{step.status}] {step.description}
+                </Text>
+              ))}
+            </View>
+          )}
+
           {operation.result_content && (
             <View>
               <Text className="text-xs font-semibold text-stone-500 mb-1">Result</Text>
@@ -74,10 +117,23 @@ function OperationCard({ operation }: { operation: Operation }) {
               </Text>
               {operation.tool_calls.map((tc, i) => {
                 const call = tc as Record<string, unknown>;
+                const args = call.arguments as Record<string, unknown> | undefined;
                 return (
-                  <Text key={i} className="text-[10px] text-stone-400 font-mono">
-                    {call.name as string ?? 'unknown'} (turn {call.turn as number ?? '?'})
-                  </Text>
+                  <View key={i}>
+                    <Text className="text-[10px] text-stone-400 font-mono">
+                      {call.name as string ?? 'unknown'} (turn {call.turn as number ?? '?'})
+                    </Text>
+                    {call.name === 'consult_member' && (
+                      <View className="ml-2 mb-1 border-l border-stone-700 pl-2">
+                        <Text className="text-[10px] text-stone-500" selectable>
+                          Asked {args?.member_name as string}: {args?.question as string}
+                        </Text>
+                        {typeof call.answer === 'string' && (
+                          <Text className="text-[10px] text-stone-400" selectable>{call.answer}</Text>
+                        )}
+                      </View>
+                    )}
+                  </View>
                 );
               })}
             </View>
@@ -95,8 +151,25 @@ function OperationCard({ operation }: { operation: Operation }) {
                   Out: {(operation.usage as Record<string, number>).output_tokens.toLocaleString()} tokens
                 </Text>
               )}
+              {(operation.usage as Record<string, number>).cost_usd != null && (
+                <Text className="text-[10px] text-stone-600">
+                  ~${Number((operation.usage as Record<string, number>).cost_usd).toFixed(4)}
+                </Text>
+              )}
             </View>
           )}
+
+          {operation.status !== 'running' && !operation.parent_operation_id && (
+            <Pressable
+              onPress={handleExportTrace}
+              disabled={exporting}
+              className="flex-row items-center gap-1.5 self-start pt-1"
+              hitSlop={8}
+            >
+              {exporting ? <ActivityIndicator size="small" color="#a8a29e" /> : <Activity size={12} color="#a8a29e" />}
+              <Text className="text-[11px] text-stone-400">Copy trace (OTLP JSON)</Text>
+            </Pressable>
+          )}
         </View>
       )}
     </Pressable>
@@ -156,7 +229,7 @@ function EntryCard({ entry }: { entry: BookkeeperEntry & { member?: { id: string
 type Tab = 'operations' | 'entries';
 
 export default function OperationsScreen() {
-  const { operations, bookkeeperEntries, loadingOps, loadingEntries } = useOperations();
+  const { operations, bookkeeperEntries, loadingOps, loadingEntries, exportTrace } = useOperations();
   const [filter, setFilter] = useState<string | null>(null);
   const [tab, setTab] = useState<Tab>('operations');
 
@@ -226,7 +299,7 @@ export default function OperationsScreen() {
             ) : (
               <View className="gap-2">
                 {filtered.map((op) => (
-                  <OperationCard key={op.id} operation={op} />
+                  <OperationCard key={op.id} operation={op} onExportTrace={exportTrace} />
                 ))}
               </View>
             )}
@@ -21,7 +21,7 @@ import { getSupabase } from '../../lib/realtime';
 import { toast } from '../../lib/toast';
 import { BackgroundWatermark } from '../../components/BackgroundWatermark';
 import { UserAvatar } from '../../components/common/UserAvatar';
-import { TIER_LABELS, TIER_COLORS } from '../../config/constants';
+import { TierBadge } from '../../components/ui/TierBadge';
 import { RunYourFamilyButton } from '../../components/common/RunYourFamilyButton';
 
 const SETTINGS_API_REF = 'formula:local.settings-api:0.1.0';
@@ -263,13 +263,7 @@ export default function SettingsScreen() {
                       {profile?.display_name ?? 'Don'}
                     </Text>
                     <View className="mt-1 flex-row items-center gap-2">
-                      <View className={`rounded px-1.5 py-0.5 ${TIER_COLORS[tier]}`}>
-                        <Text className={`text-[10px] font-semibold ${
-                          tier === 'associate' ? 'text-stone-300' : 'text-stone-950'
-                        }`}>
-                          {TIER_LABELS[tier]}
-                        </Text>
-                      </View>
+                      <TierBadge tier={tier} />
                       <View className={`flex-row items-center gap-1 rounded px-1.5 py-0.5 ${
                         realtimeConnected ? 'bg-emerald-900/50' : 'bg-red-900/50'
                       }`}>
@@ -10,25 +10,71 @@ import {
   Platform,
   ActivityIndicator,
 } from 'react-native';
-import { X, ChevronDown } from 'lucide-react-native';
-import type { Provider } from '../../lib/types';
+import { X, ChevronDown, RefreshCw } from 'lucide-react-native';
+import type { ListedModel, ModelCapabilities, Provider, UserTier } from '../../lib/types';
 import { PROVIDER_LABELS } from '../../config/constants';
 import { useModels } from '../../hooks/useModels';
+import { useTiers } from '../../hooks/useTiers';
 import { Dropdown } from '../ui/Dropdown';
+import { CapabilityPicker } from './CapabilityPicker';
 
 interface AddModelModalProps {
   visible: boolean;
-  onAdd: (data: { provider: Provider; alias: string; model: string; min_tier: 'boss' | 'associate'; sort_order: number }) => Promise<void>;
+  onAdd: (data: {
+    provider: Provider;
+    alias: string;
+    model: string;
+    min_tier: UserTier;
+    sort_order: number;
+    input_price_per_million: number | null;
+    output_price_per_million: number | null;
+    capabilities: ModelCapabilities;
+  }) => Promise<void>;
   onClose: () => void;
 }
 
+function formatTokens(n: number) {
+  if (n >= 1_000_000) return `${(n / 1_000_000).toFixed(1)}M`;
+  if (n >= 1000) return `${Math.round(n / 1000)}k`;
+  return String(n);
+}
+
+/** What the provider's listing says about a model, on one line */
+function describeListing(m: ListedModel) {
+  const parts: string[] = [];
+  if (m.display_name !== m.id) parts.push(m.display_name);
+  if (m.context_window != null) parts.push(`${formatTokens(m.context_window)} context`);
+  if (m.max_output != null) parts.push(`${formatTokens(m.max_output)} max output`);
+  if (m.pricing) parts.push(`$${m.pricing.input_per_million} / $${m.pricing.output_per_million} per 1M`);
+  return parts.join(' · ');
+}
+
+/** Catalog price input: blank means unpriced */
+export function parsePrice(text: string): number | null {
+  const value = parseFloat(text);
+  return text.trim() === '' || isNaN(value) ? null : value;
+}
+
 export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
-  const { models, availableProviders, loading: modelsLoading, error: modelsError } = useModels();
+  const {
+    models,
+    details,
+    capabilities: detected,
+    availableProviders,
+    loading: modelsLoading,
+    refresh: refreshModels,
+    refreshing,
+    error: modelsError,
+  } = useModels();
+  const { tiers, minTierLabel } = useTiers();
   const [provider, setProvider] = useState<Provider>(availableProviders[0] ?? 'claude');
   const [model, setModel] = useState('');
   const [alias, setAlias] = useState('');
-  const [minTier, setMinTier] = useState<'boss' | 'associate'>('associate');
+  const [minTier, setMinTier] = useState<UserTier>('associate');
   const [sortOrder, setSortOrder] = useState('0');
+  const [inputPrice, setInputPrice] = useState('');
+  const [outputPrice, setOutputPrice] = useState('');
+  const [capabilities, setCapabilities] = useState<ModelCapabilities>({});
   const [saving, setSaving] = useState(false);
 
   const [showProviderPicker, setShowProviderPicker] = useState(false);
@@ -44,6 +90,16 @@ export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
     }
   }, [effectiveModel, model]);
 
+  const listing = details[provider]?.[effectiveModel];
+
+  // Start from whatever the provider's model listing reveals
+  useEffect(() => {
+    setCapabilities(detected[provider]?.[effectiveModel] ?? {});
+    const pricing = details[provider]?.[effectiveModel]?.pricing;
+    setInputPrice(pricing ? String(pricing.input_per_million) : '');
+    setOutputPrice(pricing ? String(pricing.output_per_million) : '');
+  }, [detected, details, provider, effectiveModel]);
+
   // Reset form when modal opens
   useEffect(() => {
     if (visible) {
@@ -52,6 +108,8 @@ export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
       setAlias('');
       setMinTier('associate');
       setSortOrder('0');
+      setInputPrice('');
+      setOutputPrice('');
     }
   }, [visible, availableProviders]);
 
@@ -71,7 +129,16 @@ export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
     if (!alias.trim() || !effectiveModel) return;
     setSaving(true);
     try {
-      await onAdd({ provider, alias: alias.trim(), model: effectiveModel, min_tier: minTier, sort_order: parseInt(sortOrder) || 0 });
+      await onAdd({
+        provider,
+        alias: alias.trim(),
+        model: effectiveModel,
+        min_tier: minTier,
+        sort_order: parseInt(sortOrder) || 0,
+        input_price_per_million: parsePrice(inputPrice),
+        output_price_per_million: parsePrice(outputPrice),
+        capabilities,
+      });
     } finally {
       setSaving(false);
     }
@@ -184,12 +251,32 @@ export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
                               className={`px-3 py-2.5 ${m === effectiveModel ? 'bg-stone-700' : ''}`}
                             >
                               <Text className="text-sm text-stone-100" numberOfLines={1}>{m}</Text>
+                              {details[provider]?.[m] && details[provider]![m].display_name !== m && (
+                                <Text className="text-[10px] text-stone-500" numberOfLines={1}>
+                                  {details[provider]![m].display_name}
+                                </Text>
+                              )}
                             </Pressable>
                           ))}
                         </Dropdown>
                       </View>
                     </View>
 
+                    <View className="-mt-2 flex-row items-center gap-2">
+                      <Text className="min-w-0 flex-1 text-[11px] text-stone-500" numberOfLines={2}>
+                        {listing ? describeListing(listing) : ''}
+                      </Text>
+                      <Pressable
+                        onPress={() => refreshModels()}
+                        disabled={refreshing}
+                        className={`flex-row items-center gap-1 ${refreshing ? 'opacity-40' : ''}`}
+                        hitSlop={6}
+                      >
+                        <RefreshCw size={11} color="#78716c" />
+                        <Text className="text-[11px] text-stone-500">{refreshing ? 'Refreshing...' : 'Refresh list'}</Text>
+                      </Pressable>
+                    </View>
+
                     {/* Alias */}
                     <View>
                       <Text className="mb-1 text-sm font-medium text-stone-300">
@@ -221,24 +308,21 @@ export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
                               className="flex-row items-center justify-between rounded-lg border border-stone-700 bg-stone-800 px-3 py-2.5"
                             >
                               <Text className="text-sm text-stone-100">
-                                {minTier === 'boss' ? 'Boss (Boss + Godfather)' : 'Associate (everyone)'}
+                                {minTierLabel(minTier)}
                               </Text>
                               <ChevronDown size={16} color="#a8a29e" />
                             </Pressable>
                           }
                         >
-                          <Pressable
-                            onPress={() => { setMinTier('associate'); setShowTierPicker(false); }}
-                            className={`px-3 py-2.5 ${minTier === 'associate' ? 'bg-stone-700' : ''}`}
-                          >
-                            <Text className="text-sm text-stone-100">Associate (everyone)</Text>
-                          </Pressable>
-                          <Pressable
-                            onPress={() => { setMinTier('boss'); setShowTierPicker(false); }}
-                            className={`px-3 py-2.5 ${minTier === 'boss' ? 'bg-stone-700' : ''}`}
-                          >
-                            <Text className="text-sm text-stone-100">Boss (Boss + Godfather)</Text>
-                          </Pressable>
+                          {[...tiers].reverse().map((t) => (
+                            <Pressable
+                              key={t.key}
+                              onPress={() => { setMinTier(t.key); setShowTierPicker(false); }}
+                              className={`px-3 py-2.5 ${minTier === t.key ? 'bg-stone-700' : ''}`}
+                            >
+                              <Text className="text-sm text-stone-100">{minTierLabel(t.key)}</Text>
+                            </Pressable>
+                          ))}
                         </Dropdown>
                       </View>
                       <View className="flex-1">
@@ -251,6 +335,38 @@ export function AddModelModal({ visible, onAdd, onClose }: AddModelModalProps) {
                         />
                       </View>
                     </View>
+
+                    {/* Pricing (USD per million tokens; blank leaves the model unpriced) */}
+                    <View className="flex-row gap-3">
+                      <View className="flex-1">
+                        <Text className="mb-1 text-sm font-medium text-stone-300">Input $ / 1M</Text>
+                        <TextInput
+                          value={inputPrice}
+                          onChangeText={setInputPrice}
+                          keyboardType="decimal-pad"
+                          placeholder="e.g. 3.00"
+                          placeholderTextColor="#57534e"
+                          className="w-full rounded-lg border border-stone-700 bg-stone-800 px-3 py-2.5 text-stone-100"
+                        />
+                      </View>
+                      <View className="flex-1">
+                        <Text className="mb-1 text-sm font-medium text-stone-300">Output $ / 1M</Text>
+                        <TextInput
+                          value={outputPrice}
+                          onChangeText={setOutputPrice}
+                          keyboardType="decimal-pad"
+                          placeholder="e.g. 15.00"
+                          placeholderTextColor="#57534e"
+                          className="w-full rounded-lg border border-stone-700 bg-stone-800 px-3 py-2.5 text-stone-100"
+                        />
+                      </View>
+                    </View>
+
+                    {/* Capabilities (pre-filled from the provider where it says; "?" is unknown) */}
+                    <View>
+                      <Text className="mb-1 text-sm font-medium text-stone-300">Capabilities</Text>
+                      <CapabilityPicker value={capabilities} onChange={setCapabilities} />
+                    </View>
                   </>
                 )}
 
@@ -0,0 +1,118 @@
+import { useState } from 'react';
+import { View, Text, Pressable, ActivityIndicator } from 'react-native';
+import { ChevronDown, ChevronRight } from 'lucide-react-native';
+import { useAuditLog } from '../../hooks/useAuditLog';
+import type { AuditLogEntry, AuditTargetType } from '../../lib/types';
+
+const TARGET_FILTERS: { value: AuditTargetType | null; label: string }[] = [
+  { value: null, label: 'All' },
+  { value: 'profile', label: 'Users' },
+  { value: 'catalog', label: 'Catalog' },
+  { value: 'tier', label: 'Tiers' },
+  { value: 'quota', label: 'Quotas' },
+];
+
+/** Fields whose value differs between the two snapshots */
+function changedFields(entry: AuditLogEntry): string[] {
+  const before = entry.before ?? {};
+  const after = entry.after ?? {};
+  const keys = new Set([...Object.keys(before), ...Object.keys(after)]);
+  return [...keys].filter(
+    (k) => k !== 'updated_at' && JSON.stringify(before[k]) !== JSON.stringify(after[k]),
+  );
+}
+
+function Snapshot({ label, value }: { label: string; value: Record<string, unknown> | null }) {
+  return (
+    <View className="min-w-0 flex-1">
+      <Text className="mb-1 text-[10px] uppercase tracking-wider text-stone-500">{label}</Text>
+      <Text className="font-mono text-[11px] text-stone-300">
+        {value ? JSON.stringify(value, null, 2) : '—'}
+      </Text>
+    </View>
+  );
+}
+
+function AuditRow({ entry }: { entry: AuditLogEntry }) {
+  const [expanded, setExpanded] = useState(false);
+  const changed = entry.before && entry.after ? changedFields(entry) : [];
+
+  return (
+    <View className="bg-stone-900 px-4 py-2.5">
+      <Pressable onPress={() => setExpanded(!expanded)} className="flex-row items-center gap-3">
+        {expanded ? <ChevronDown size={14} color="#78716c" /> : <ChevronRight size={14} color="#78716c" />}
+        <View className="min-w-0 flex-1">
+          <Text className="text-sm text-stone-100" numberOfLines={1}>
+            {entry.action}
+            <Text className="text-stone-500"> · {entry.target_type} {entry.target_id}</Text>
+          </Text>
+          {changed.length > 0 && (
+            <Text className="text-[10px] text-stone-500" numberOfLines={1}>{changed.join(', ')}</Text>
+          )}
+        </View>
+        <View className="items-end">
+          <Text className="text-xs text-stone-400">{entry.actor_name ?? entry.actor_id?.slice(0, 8) ?? 'System'}</Text>
+          <Text className="text-[10px] text-stone-600">{new Date(entry.created_at).toLocaleString()}</Text>
+        </View>
+      </Pressable>
+      {expanded && (
+        <View className="mt-2 flex-row gap-4 rounded bg-stone-950 p-3">
+          <Snapshot label="Before" value={entry.before} />
+          <Snapshot label="After" value={entry.after} />
+        </View>
+      )}
+    </View>
+  );
+}
+
+export function AuditLog() {
+  const [targetType, setTargetType] = useState<AuditTargetType | null>(null);
+  const { entries, loading, hasMore, loadingMore, loadMore } = useAuditLog(targetType);
+
+  return (
+    <View className="gap-4">
+      <Text className="text-sm text-stone-400">
+        Every tier, catalog, quota and user-tier change made from this page, newest first. Entries cannot be edited or removed.
+      </Text>
+
+      <View className="flex-row flex-wrap gap-1.5">
+        {TARGET_FILTERS.map((f) => (
+          <Pressable
+            key={f.label}
+            onPress={() => setTargetType(f.value)}
+            className={`rounded-lg px-3 py-1.5 ${targetType === f.value ? 'bg-stone-700' : 'bg-stone-800/50'}`}
+          >
+            <Text className={`text-xs ${targetType === f.value ? 'text-stone-100' : 'text-stone-500'}`}>{f.label}</Text>
+          </Pressable>
+        ))}
+      </View>
+
+      {loading ? (
+        <View className="items-center justify-center gap-2 py-8">
+          <ActivityIndicator color="#78716c" />
+          <Text className="text-sm text-stone-500">Loading audit log...</Text>
+        </View>
+      ) : entries.length === 0 ? (
+        <Text className="py-8 text-center text-sm text-stone-500">No changes recorded yet.</Text>
+      ) : (
+        <View className="overflow-hidden rounded-lg border border-stone-800">
+          {entries.map((e, i) => (
+            <View key={e.id} className={i > 0 ? 'border-t border-stone-800' : ''}>
+              <AuditRow entry={e} />
+            </View>
+          ))}
+        </View>
+      )}
+
+      {hasMore && (
+        <Pressable
+          onPress={loadMore}
+          disabled={loadingMore}
+          className="items-center rounded-lg border border-stone-800 py-2"
+        >
+          <Text className="text-xs text-stone-400">{loadingMore ? 'Loading...' : 'Load more'}</Text>
+        </Pressable>
+      )}
+    </View>
+  );
+}
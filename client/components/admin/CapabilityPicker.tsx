@@ -0,0 +1,56 @@
+import { View, Text, Pressable } from 'react-native';
+import { CAPABILITY_LABELS, MODEL_CAPABILITIES } from '../../config/constants';
+import type { ModelCapabilities, ModelCapability } from '../../lib/types';
+
+/** Tap cycles unknown -> supported -> unsupported -> unknown */
+function cycle(capabilities: ModelCapabilities, capability: ModelCapability): ModelCapabilities {
+  const next = { ...capabilities };
+  if (next[capability] === undefined) next[capability] = true;
+  else if (next[capability]) next[capability] = false;
+  else delete next[capability];
+  return next;
+}
+
+export function CapabilityPicker({
+  value,
+  onChange,
+}: {
+  value: ModelCapabilities;
+  onChange: (value: ModelCapabilities) => void;
+}) {
+  return (
+    <View className="flex-row flex-wrap items-center gap-1.5">
+      {MODEL_CAPABILITIES.map((c) => {
+        const state = value[c];
+        return (
+          <Pressable
+            key={c}
+            onPress={() => onChange(cycle(value, c))}
+            className={`rounded-lg px-2.5 py-1 ${
+              state === true ? 'bg-emerald-900/60' : state === false ? 'bg-red-900/40' : 'bg-stone-800/50'
+            }`}
+          >
+            <Text className={`text-xs ${
+              state === true ? 'text-emerald-300' : state === false ? 'text-red-300 line-through' : 'text-stone-500'
+            }`}>
+              {CAPABILITY_LABELS[c]}{state === undefined ? '?' : ''}
+            </Text>
+          </Pressable>
+        );
+      })}
+    </View>
+  );
+}
+
+/** Compact read-only summary for catalog rows: supported capabilities only */
+export function CapabilitySummary({ value }: { value: ModelCapabilities }) {
+  const supported = MODEL_CAPABILITIES.filter((c) => value[c] === true);
+  const missing = MODEL_CAPABILITIES.filter((c) => value[c] === false);
+  if (supported.length === 0 && missing.length === 0) return null;
+  return (
+    <Text className="text-[10px] text-stone-500" numberOfLines={1}>
+      {supported.map((c) => CAPABILITY_LABELS[c]).join(' · ')}
+      {missing.length > 0 ? `${supported.length > 0 ? ' · ' : ''}no ${missing.map((c) => CAPABILITY_LABELS[c].toLowerCase()).join(', ')}` : ''}
+    </Text>
+  );
+}
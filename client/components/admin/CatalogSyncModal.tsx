@@ -0,0 +1,165 @@
+import { useEffect, useState } from 'react';
+import { Modal, View, Text, Pressable, ActivityIndicator, ScrollView } from 'react-native';
+import { X, ArrowRightLeft } from 'lucide-react-native';
+import { cyfrCall } from '../../lib/cyfr';
+import { getAccessToken } from '../../lib/supabase';
+import { PROVIDER_LABELS } from '../../config/constants';
+import type { CatalogModel, CatalogSyncReport, Provider } from '../../lib/types';
+import { toast } from '../../lib/toast';
+
+const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
+
+interface CatalogSyncModalProps {
+  visible: boolean;
+  catalogModels: CatalogModel[];
+  onClose: () => void;
+  onChanged: () => void;
+  onMigrate: (entry: CatalogModel) => void;
+}
+
+async function runSyncReport(autoDeactivate: boolean) {
+  const accessToken = getAccessToken();
+  if (!accessToken) throw new Error('Not authenticated');
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: ADMIN_API_REF,
+    input: {
+      action: 'catalog_sync_report',
+      access_token: accessToken,
+      auto_deactivate: autoDeactivate,
+    },
+    type: 'formula',
+    timeout: 120000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return res?.report as CatalogSyncReport;
+}
+
+/** Compare the catalog with what providers list now: vanished models and newer versions. */
+export function CatalogSyncModal({ visible, catalogModels, onClose, onChanged, onMigrate }: CatalogSyncModalProps) {
+  const [report, setReport] = useState<CatalogSyncReport | null>(null);
+  const [loading, setLoading] = useState(false);
+
+  async function load(autoDeactivate: boolean) {
+    setLoading(true);
+    try {
+      const next = await runSyncReport(autoDeactivate);
+      setReport(next);
+      if (next.deactivated_count > 0) {
+        toast.success(`${next.deactivated_count} vanished model${next.deactivated_count !== 1 ? 's' : ''} deactivated.`);
+        onChanged();
+      }
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : 'Failed to check providers.');
+    }
+    setLoading(false);
+  }
+
+  useEffect(() => {
+    if (visible) load(false);
+    else setReport(null);
+  }, [visible]);
+
+  const flagged = report?.entries.filter((e) => e.status === 'vanished' || e.suggestions.length > 0) ?? [];
+  const activeVanished = report?.entries.filter((e) => e.status === 'vanished' && e.is_active).length ?? 0;
+  const unchecked = Object.entries(report?.unchecked_providers ?? {}) as [Provider, string][];
+
+  return (
+    <Modal visible={visible} animationType="fade" transparent onRequestClose={onClose}>
+      <View className="flex-1 items-center justify-center bg-black/60 px-4">
+        <View className="w-full max-w-lg rounded-xl border border-stone-800 bg-stone-900">
+          <View className="flex-row items-center justify-between border-b border-stone-800 px-5 py-4">
+            <Text className="font-serif text-lg font-bold text-stone-100">Catalog Sync</Text>
+            <Pressable onPress={onClose} hitSlop={8}>
+              <X size={20} color="#a8a29e" />
+            </Pressable>
+          </View>
+
+          <View className="gap-4 p-5">
+            {loading && !report ? (
+              <View className="items-center py-6">
+                <ActivityIndicator color="#78716c" />
+                <Text className="mt-2 text-xs text-stone-500">Asking every provider for its models...</Text>
+              </View>
+            ) : report ? (
+              <>
+                <View className="flex-row flex-wrap gap-2">
+                  {(Object.entries(report.providers) as [Provider, { listed_count: number; uncatalogued_count: number }][]).map(
+                    ([provider, counts]) => (
+                      <View key={provider} className="rounded bg-stone-800 px-2 py-1">
+                        <Text className="text-[10px] text-stone-300">
+                          {PROVIDER_LABELS[provider]}: {counts.listed_count} listed · {counts.uncatalogued_count} not in catalog
+                        </Text>
+                      </View>
+                    ),
+                  )}
+                </View>
+
+                {unchecked.length > 0 && (
+                  <Text className="text-xs text-stone-500">
+                    Not checked: {unchecked.map(([p, err]) => `${PROVIDER_LABELS[p]} (${err})`).join(', ')}
+                  </Text>
+                )}
+
+                {flagged.length === 0 ? (
+                  <Text className="text-sm text-stone-400">Every catalog model is still listed and up to date.</Text>
+                ) : (
+                  <ScrollView style={{ maxHeight: 320 }} className="rounded-lg border border-stone-800">
+                    {flagged.map((e, i) => {
+                      const entry = catalogModels.find((m) => m.id === e.id);
+                      return (
+                        <View key={e.id} className={`gap-1 px-3 py-2 ${i > 0 ? 'border-t border-stone-800' : ''}`}>
+                          <View className="flex-row items-center gap-2">
+                            <Text className="min-w-0 flex-1 text-sm text-stone-100" numberOfLines={1}>
+                              {PROVIDER_LABELS[e.provider]} / {e.alias}
+                              <Text className="text-xs text-stone-500"> · {e.model}</Text>
+                            </Text>
+                            {e.status === 'vanished' && (
+                              <View className="rounded bg-red-900/60 px-1.5 py-0.5">
+                                <Text className="text-[10px] font-semibold text-red-200">
+                                  {e.deactivated ? 'Vanished · deactivated' : e.is_active ? 'Vanished' : 'Vanished · inactive'}
+                                </Text>
+                              </View>
+                            )}
+                            {entry && (
+                              <Pressable onPress={() => onMigrate(entry)} hitSlop={6}>
+                                <ArrowRightLeft size={14} color="#78716c" />
+                              </Pressable>
+                            )}
+                          </View>
+                          {e.suggestions.length > 0 && (
+                            <Text className="text-[11px] text-stone-400">
+                              Newer: {e.suggestions.map((s) => `${s.model}${s.catalog_id ? ' (in catalog)' : ''}`).join(', ')}
+                            </Text>
+                          )}
+                        </View>
+                      );
+                    })}
+                  </ScrollView>
+                )}
+              </>
+            ) : null}
+
+            <View className="flex-row justify-end gap-2">
+              <Pressable onPress={() => load(false)} disabled={loading} className="rounded-lg px-4 py-2">
+                <Text className={`text-sm text-stone-400 ${loading ? 'opacity-40' : ''}`}>Recheck</Text>
+              </Pressable>
+              <Pressable
+                onPress={() => load(true)}
+                disabled={activeVanished === 0 || loading}
+                className={`rounded-lg bg-gold-600 px-4 py-2 ${activeVanished === 0 || loading ? 'opacity-30' : ''}`}
+              >
+                <Text className="text-sm font-semibold text-stone-950">
+                  {loading && report ? 'Working...' : `Deactivate vanished${activeVanished > 0 ? ` (${activeVanished})` : ''}`}
+                </Text>
+              </Pressable>
+            </View>
+          </View>
+        </View>
+      </View>
+    </Modal>
+  );
+}
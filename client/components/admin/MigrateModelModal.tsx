@@ -0,0 +1,194 @@
+import { useState } from 'react';
+import { Modal, View, Text, Pressable, ActivityIndicator, ScrollView } from 'react-native';
+import { X, Check } from 'lucide-react-native';
+import { cyfrCall } from '../../lib/cyfr';
+import { getAccessToken } from '../../lib/supabase';
+import { PROVIDER_LABELS } from '../../config/constants';
+import type { CatalogModel, CatalogMigrationMember, CatalogMigrationReport } from '../../lib/types';
+import { toast } from '../../lib/toast';
+
+const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
+
+interface MigrateModelModalProps {
+  source: CatalogModel | null;
+  catalogModels: CatalogModel[];
+  onClose: () => void;
+  onMigrated: () => void;
+}
+
+async function runMigration(fromId: string, toId: string, dryRun: boolean, checkTier: boolean) {
+  const accessToken = getAccessToken();
+  if (!accessToken) throw new Error('Not authenticated');
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: ADMIN_API_REF,
+    input: {
+      action: 'catalog_migrate',
+      access_token: accessToken,
+      from_catalog_id: fromId,
+      to_catalog_id: toId,
+      dry_run: dryRun,
+      check_tier: checkTier,
+    },
+    type: 'formula',
+    timeout: 30000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return res?.migration as CatalogMigrationReport;
+}
+
+/** Why a member stays put: the capabilities the target lacks, or the Don's tier. */
+function skipLabel(member: CatalogMigrationMember) {
+  if (member.skip_reason === 'capabilities') {
+    return `no ${member.missing_capabilities.join(', ').replace(/_/g, ' ')}`;
+  }
+  return member.owner_tier;
+}
+
+/** Move every member and soldier on `source` to another catalog entry: preview first, then apply. */
+export function MigrateModelModal({ source, catalogModels, onClose, onMigrated }: MigrateModelModalProps) {
+  const [targetId, setTargetId] = useState<string | null>(null);
+  const [checkTier, setCheckTier] = useState(true);
+  const [report, setReport] = useState<CatalogMigrationReport | null>(null);
+  const [loading, setLoading] = useState(false);
+
+  const targets = catalogModels.filter((m) => m.is_active && m.id !== source?.id);
+
+  function handleClose() {
+    setTargetId(null);
+    setCheckTier(true);
+    setReport(null);
+    setLoading(false);
+    onClose();
+  }
+
+  async function preview(toId: string, tierCheck: boolean) {
+    if (!source) return;
+    setLoading(true);
+    setReport(null);
+    try {
+      setReport(await runMigration(source.id, toId, true, tierCheck));
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : 'Failed to preview migration.');
+    }
+    setLoading(false);
+  }
+
+  async function apply() {
+    if (!source || !targetId) return;
+    setLoading(true);
+    try {
+      const done = await runMigration(source.id, targetId, false, checkTier);
+      toast.success(
+        `${done.migrated_count} member${done.migrated_count !== 1 ? 's' : ''} moved to ${done.to.alias}. ${done.notified_dons} Don${done.notified_dons !== 1 ? 's' : ''} notified.`,
+      );
+      onMigrated();
+      handleClose();
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : 'Failed to migrate members.');
+      setLoading(false);
+    }
+  }
+
+  return (
+    <Modal visible={!!source} animationType="fade" transparent onRequestClose={handleClose}>
+      <View className="flex-1 items-center justify-center bg-black/60 px-4">
+        <View className="w-full max-w-lg rounded-xl border border-stone-800 bg-stone-900">
+          <View className="flex-row items-center justify-between border-b border-stone-800 px-5 py-4">
+            <Text className="font-serif text-lg font-bold text-stone-100">Migrate Members</Text>
+            <Pressable onPress={handleClose} hitSlop={8}>
+              <X size={20} color="#a8a29e" />
+            </Pressable>
+          </View>
+
+          {source && (
+            <View className="gap-4 p-5">
+              <Text className="text-xs text-stone-500">
+                Every member and soldier on {PROVIDER_LABELS[source.provider]} / {source.alias}, as primary model or
+                fallback, moves to the model you pick. Their Dons are notified.
+              </Text>
+
+              <View>
+                <Text className="mb-1 text-sm font-medium text-stone-300">Move to</Text>
+                <ScrollView style={{ maxHeight: 180 }} className="rounded-lg border border-stone-700 bg-stone-800">
+                  {targets.map((m) => (
+                    <Pressable
+                      key={m.id}
+                      onPress={() => { setTargetId(m.id); preview(m.id, checkTier); }}
+                      className={`px-3 py-2 ${m.id === targetId ? 'bg-stone-700' : ''}`}
+                    >
+                      <Text className="text-sm text-stone-100">{PROVIDER_LABELS[m.provider]} / {m.alias}</Text>
+                      <Text className="text-[10px] text-stone-500">{m.model}</Text>
+                    </Pressable>
+                  ))}
+                </ScrollView>
+              </View>
+
+              <Pressable
+                onPress={() => {
+                  setCheckTier(!checkTier);
+                  if (targetId) preview(targetId, !checkTier);
+                }}
+                className="flex-row items-center gap-2"
+              >
+                <View className={`h-4 w-4 items-center justify-center rounded border ${checkTier ? 'border-gold-600 bg-gold-600' : 'border-stone-600'}`}>
+                  {checkTier && <Check size={12} color="#0c0a09" />}
+                </View>
+                <Text className="text-xs text-stone-300">Skip members whose Don's tier can't use the new model</Text>
+              </Pressable>
+
+              {loading && !report ? (
+                <ActivityIndicator color="#78716c" />
+              ) : report ? (
+                <View className="gap-2">
+                  <Text className="text-xs text-stone-400">
+                    {report.migrated_count} will move{report.skipped_count > 0 ? `, ${report.skipped_count} skipped` : ''}.
+                  </Text>
+                  {report.members.length > 0 && (
+                    <ScrollView style={{ maxHeight: 200 }} className="rounded-lg border border-stone-800">
+                      {report.members.map((m, i) => (
+                        <View
+                          key={m.id}
+                          className={`flex-row items-center gap-2 px-3 py-1.5 ${i > 0 ? 'border-t border-stone-800' : ''} ${m.skipped ? 'opacity-50' : ''}`}
+                        >
+                          <Text className="min-w-0 flex-1 text-xs text-stone-200" numberOfLines={1}>
+                            {m.name}
+                            <Text className="text-stone-500"> · {m.member_type}</Text>
+                          </Text>
+                          <Text className="text-[10px] text-stone-500">
+                            {m.uses_primary ? 'primary' : 'fallback'}
+                          </Text>
+                          <Text className="w-28 text-right text-[10px] text-stone-400" numberOfLines={1}>
+                            {m.skipped ? `skipped · ${skipLabel(m)}` : m.owner_name}
+                          </Text>
+                        </View>
+                      ))}
+                    </ScrollView>
+                  )}
+                </View>
+              ) : null}
+
+              <View className="flex-row justify-end gap-2">
+                <Pressable onPress={handleClose} className="rounded-lg px-4 py-2">
+                  <Text className="text-sm text-stone-400">Cancel</Text>
+                </Pressable>
+                <Pressable
+                  onPress={apply}
+                  disabled={!report || report.migrated_count === 0 || loading}
+                  className={`rounded-lg bg-gold-600 px-4 py-2 ${!report || report.migrated_count === 0 || loading ? 'opacity-30' : ''}`}
+                >
+                  <Text className="text-sm font-semibold text-stone-950">
+                    {loading && report ? 'Migrating...' : 'Migrate'}
+                  </Text>
+                </Pressable>
+              </View>
+            </View>
+          )}
+        </View>
+      </View>
+    </Modal>
+  );
+}
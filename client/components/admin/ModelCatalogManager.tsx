@@ -7,27 +7,48 @@ import {
   FlatList,
   ActivityIndicator,
 } from 'react-native';
-import { Plus, Pencil, Trash2, ToggleLeft, ToggleRight, Loader } from 'lucide-react-native';
+import { Plus, Pencil, Trash2, ToggleLeft, ToggleRight, Loader, ArrowRightLeft, RefreshCw } from 'lucide-react-native';
 import { cyfrCall } from '../../lib/cyfr';
 import { getAccessToken } from '../../lib/supabase';
 import { useAuth } from '../../contexts/AuthContext';
 import { useModelCatalog } from '../../hooks/useModelCatalog';
 import { useModels } from '../../hooks/useModels';
+import { useTiers } from '../../hooks/useTiers';
 import { PROVIDER_LABELS, PROVIDER_COLORS } from '../../config/constants';
-import { AddModelModal } from './AddModelModal';
-import type { CatalogModel, Provider } from '../../lib/types';
+import { AddModelModal, parsePrice } from './AddModelModal';
+import { MigrateModelModal } from './MigrateModelModal';
+import { CatalogSyncModal } from './CatalogSyncModal';
+import { CapabilityPicker, CapabilitySummary } from './CapabilityPicker';
+import { RoutingEditor } from './RoutingEditor';
+import type { CatalogModel, CatalogTarget, ModelCapabilities, Provider, UserTier } from '../../lib/types';
 import { toast } from '../../lib/toast';
 import { confirmAlert } from '../../lib/alert';
 import { Dropdown } from '../ui/Dropdown';
 
 const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
 
-function EditRow({ entry, onSaved, onCancel }: { entry: CatalogModel; onSaved: () => void; onCancel: () => void }) {
+function EditRow({
+  entry,
+  catalogModels,
+  onSaved,
+  onCancel,
+}: {
+  entry: CatalogModel;
+  catalogModels: CatalogModel[];
+  onSaved: () => void;
+  onCancel: () => void;
+}) {
   const { models, loading: modelsLoading } = useModels();
+  const { tiers, minTierLabel } = useTiers();
   const [alias, setAlias] = useState(entry.alias);
   const [model, setModel] = useState(entry.model);
   const [minTier, setMinTier] = useState(entry.min_tier);
   const [sortOrder, setSortOrder] = useState(String(entry.sort_order));
+  const [inputPrice, setInputPrice] = useState(entry.input_price_per_million?.toString() ?? '');
+  const [outputPrice, setOutputPrice] = useState(entry.output_price_per_million?.toString() ?? '');
+  const [capabilities, setCapabilities] = useState<ModelCapabilities>(entry.capabilities ?? {});
+  const [targets, setTargets] = useState<CatalogTarget[]>(entry.targets ?? []);
+  const [sticky, setSticky] = useState(entry.sticky_routing ?? false);
   const [saving, setSaving] = useState(false);
   const [showModelPicker, setShowModelPicker] = useState(false);
   const [showTierPicker, setShowTierPicker] = useState(false);
@@ -50,7 +71,16 @@ function EditRow({ entry, onSaved, onCancel }: { entry: CatalogModel; onSaved: (
           action: 'catalog_update',
           access_token: accessToken,
           catalog_id: entry.id,
-          catalog_updates: { alias, model, min_tier: minTier, sort_order: parseInt(sortOrder) || 0 },
+          catalog_updates: {
+            alias,
+            model,
+            min_tier: minTier,
+            sort_order: parseInt(sortOrder) || 0,
+            input_price_per_million: parsePrice(inputPrice),
+            output_price_per_million: parsePrice(outputPrice),
+            capabilities,
+            sticky_routing: sticky,
+          },
         },
         type: 'formula',
         timeout: 30000,
@@ -59,6 +89,23 @@ function EditRow({ entry, onSaved, onCancel }: { entry: CatalogModel; onSaved: (
       const res = result as Record<string, unknown> | null;
       if (res?.error) throw new Error((res.error as Record<string, string>).message);
 
+      if (JSON.stringify(targets) !== JSON.stringify(entry.targets ?? [])) {
+        const targetsResult = await cyfrCall('execution', {
+          action: 'run',
+          reference: ADMIN_API_REF,
+          input: {
+            action: 'catalog_set_targets',
+            access_token: accessToken,
+            catalog_id: entry.id,
+            targets,
+          },
+          type: 'formula',
+          timeout: 30000,
+        });
+        const targetsRes = targetsResult as Record<string, unknown> | null;
+        if (targetsRes?.error) throw new Error((targetsRes.error as Record<string, string>).message);
+      }
+
       toast.success('Catalog entry updated.');
       onSaved();
     } catch {
@@ -130,23 +177,20 @@ function EditRow({ entry, onSaved, onCancel }: { entry: CatalogModel; onSaved: (
                 className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5"
               >
                 <Text className="text-sm text-stone-100">
-                  {minTier === 'boss' ? 'Boss+' : 'Associate (everyone)'}
+                  {minTierLabel(minTier)}
                 </Text>
               </Pressable>
             }
           >
-            <Pressable
-              onPress={() => { setMinTier('associate'); setShowTierPicker(false); }}
-              className={`px-2 py-2 ${minTier === 'associate' ? 'bg-stone-700' : ''}`}
-            >
-              <Text className="text-sm text-stone-100">Associate (everyone)</Text>
-            </Pressable>
-            <Pressable
-              onPress={() => { setMinTier('boss'); setShowTierPicker(false); }}
-              className={`px-2 py-2 ${minTier === 'boss' ? 'bg-stone-700' : ''}`}
-            >
-              <Text className="text-sm text-stone-100">Boss+</Text>
-            </Pressable>
+            {[...tiers].reverse().map((t) => (
+              <Pressable
+                key={t.key}
+                onPress={() => { setMinTier(t.key); setShowTierPicker(false); }}
+                className={`px-2 py-2 ${minTier === t.key ? 'bg-stone-700' : ''}`}
+              >
+                <Text className="text-sm text-stone-100">{minTierLabel(t.key)}</Text>
+              </Pressable>
+            ))}
           </Dropdown>
         </View>
         <View className="flex-1">
@@ -159,6 +203,45 @@ function EditRow({ entry, onSaved, onCancel }: { entry: CatalogModel; onSaved: (
           />
         </View>
       </View>
+      <View className="flex-row gap-3">
+        <View className="flex-1">
+          <Text className="mb-1 text-xs text-stone-400">Input $ / 1M tokens</Text>
+          <TextInput
+            value={inputPrice}
+            onChangeText={setInputPrice}
+            keyboardType="decimal-pad"
+            placeholder="Unpriced"
+            placeholderTextColor="#57534e"
+            className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+          />
+        </View>
+        <View className="flex-1">
+          <Text className="mb-1 text-xs text-stone-400">Output $ / 1M tokens</Text>
+          <TextInput
+            value={outputPrice}
+            onChangeText={setOutputPrice}
+            keyboardType="decimal-pad"
+            placeholder="Unpriced"
+            placeholderTextColor="#57534e"
+            className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+          />
+        </View>
+      </View>
+      <View>
+        <Text className="mb-1 text-xs text-stone-400">Capabilities</Text>
+        <CapabilityPicker value={capabilities} onChange={setCapabilities} />
+      </View>
+      <View>
+        <Text className="mb-1 text-xs text-stone-400">Routing</Text>
+        <RoutingEditor
+          entry={entry}
+          catalogModels={catalogModels}
+          targets={targets}
+          sticky={sticky}
+          onChangeTargets={setTargets}
+          onChangeSticky={setSticky}
+        />
+      </View>
       <View className="flex-row justify-end gap-2">
         <Pressable onPress={onCancel} className="rounded border border-stone-700 px-3 py-1.5">
           <Text className="text-xs text-stone-300">Cancel</Text>
@@ -180,10 +263,23 @@ function EditRow({ entry, onSaved, onCancel }: { entry: CatalogModel; onSaved: (
 export function ModelCatalogManager() {
   const { user } = useAuth();
   const { catalogModels, loading, refetch } = useModelCatalog();
+  const { tiers, tierLabel } = useTiers();
+  const lowestTier = tiers[tiers.length - 1]?.key ?? 'associate';
   const [showAdd, setShowAdd] = useState(false);
   const [editingId, setEditingId] = useState<string | null>(null);
+  const [migrating, setMigrating] = useState<CatalogModel | null>(null);
+  const [showSync, setShowSync] = useState(false);
 
-  async function handleAdd(data: { provider: Provider; alias: string; model: string; min_tier: 'boss' | 'associate'; sort_order: number }) {
+  async function handleAdd(data: {
+    provider: Provider;
+    alias: string;
+    model: string;
+    min_tier: UserTier;
+    sort_order: number;
+    input_price_per_million: number | null;
+    output_price_per_million: number | null;
+    capabilities: ModelCapabilities;
+  }) {
     if (!user) return;
 
     const accessToken = getAccessToken();
@@ -283,7 +379,7 @@ export function ModelCatalogManager() {
 
     const label = `${PROVIDER_LABELS[entry.provider]} / ${entry.alias}`;
     const msg = affectedCount > 0
-      ? `Delete "${label}"? ${affectedCount} member${affectedCount !== 1 ? 's' : ''} will need a new model assigned.`
+      ? `Delete "${label}"? ${affectedCount} member${affectedCount !== 1 ? 's' : ''} will need a new model assigned. Use Migrate first to move them in one step.`
       : `Delete "${label}"?`;
 
     const confirmed = await confirmAlert('Delete Model', msg);
@@ -305,13 +401,22 @@ export function ModelCatalogManager() {
         <Text className="text-sm text-stone-400">
           {catalogModels.length} model{catalogModels.length !== 1 ? 's' : ''} in catalog
         </Text>
-        <Pressable
-          onPress={() => setShowAdd(true)}
-          className="flex-row items-center gap-2 rounded-lg bg-gold-600 px-3 py-2"
-        >
-          <Plus size={16} color="#0c0a09" />
-          <Text className="text-sm font-semibold text-stone-950">Add Model</Text>
-        </Pressable>
+        <View className="flex-row items-center gap-2">
+          <Pressable
+            onPress={() => setShowSync(true)}
+            className="flex-row items-center gap-2 rounded-lg border border-stone-700 px-3 py-2"
+          >
+            <RefreshCw size={14} color="#a8a29e" />
+            <Text className="text-sm text-stone-300">Sync</Text>
+          </Pressable>
+          <Pressable
+            onPress={() => setShowAdd(true)}
+            className="flex-row items-center gap-2 rounded-lg bg-gold-600 px-3 py-2"
+          >
+            <Plus size={16} color="#0c0a09" />
+            <Text className="text-sm font-semibold text-stone-950">Add Model</Text>
+          </Pressable>
+        </View>
       </View>
 
       {catalogModels.length === 0 ? (
@@ -331,6 +436,7 @@ export function ModelCatalogManager() {
               {editingId === entry.id ? (
                 <EditRow
                   entry={entry}
+                  catalogModels={catalogModels}
                   onSaved={() => {
                     setEditingId(null);
                     refetch();
@@ -352,15 +458,27 @@ export function ModelCatalogManager() {
                   </View>
                   <View className="flex-1 min-w-0">
                     <Text className="font-medium text-stone-100">{entry.alias}</Text>
-                    <Text className="text-xs text-stone-500" numberOfLines={1}>{entry.model}</Text>
+                    <Text className="text-xs text-stone-500" numberOfLines={1}>
+                      {entry.model}
+                      {entry.input_price_per_million != null && entry.output_price_per_million != null
+                        ? ` · $${entry.input_price_per_million} / $${entry.output_price_per_million} per 1M`
+                        : ' · unpriced'}
+                    </Text>
+                    {(entry.targets?.length ?? 0) > 0 && (
+                      <Text className="text-[10px] text-gold-500">
+                        {'\u2192'} {entry.targets.length} model{entry.targets.length !== 1 ? 's' : ''} by weight
+                        {entry.sticky_routing ? ' · sticky' : ''}
+                      </Text>
+                    )}
+                    <CapabilitySummary value={entry.capabilities ?? {}} />
                   </View>
                   <View className={`rounded px-1.5 py-0.5 ${
-                    entry.min_tier === 'boss' ? 'bg-stone-400' : 'bg-stone-700'
+                    entry.min_tier !== lowestTier ? 'bg-stone-400' : 'bg-stone-700'
                   }`}>
                     <Text className={`text-[10px] font-semibold ${
-                      entry.min_tier === 'boss' ? 'text-stone-950' : 'text-stone-300'
+                      entry.min_tier !== lowestTier ? 'text-stone-950' : 'text-stone-300'
                     }`}>
-                      {entry.min_tier === 'boss' ? 'Boss+' : 'All'}
+                      {entry.min_tier !== lowestTier ? `${tierLabel(entry.min_tier)}+` : 'All'}
                     </Text>
                   </View>
                   <View className="flex-row items-center gap-1">
@@ -374,6 +492,12 @@ export function ModelCatalogManager() {
                         <ToggleLeft size={16} color="#78716c" />
                       )}
                     </Pressable>
+                    <Pressable
+                      onPress={() => setMigrating(entry)}
+                      className="rounded p-1.5"
+                    >
+                      <ArrowRightLeft size={14} color="#78716c" />
+                    </Pressable>
                     <Pressable
                       onPress={() => setEditingId(entry.id)}
                       className="rounded p-1.5"
@@ -399,6 +523,24 @@ export function ModelCatalogManager() {
         onAdd={handleAdd}
         onClose={() => setShowAdd(false)}
       />
+
+      <CatalogSyncModal
+        visible={showSync}
+        catalogModels={catalogModels}
+        onClose={() => setShowSync(false)}
+        onChanged={refetch}
+        onMigrate={(entry) => {
+          setShowSync(false);
+          setMigrating(entry);
+        }}
+      />
+
+      <MigrateModelModal
+        source={migrating}
+        catalogModels={catalogModels}
+        onClose={() => setMigrating(null)}
+        onMigrated={refetch}
+      />
     </View>
   );
 }
@@ -0,0 +1,266 @@
+import { useState } from 'react';
+import { View, Text, TextInput, Pressable, ActivityIndicator } from 'react-native';
+import { X } from 'lucide-react-native';
+import { useQuotas, type QuotaInput } from '../../hooks/useQuotas';
+import { useAdminUsers } from '../../hooks/useAdminUsers';
+import { useTiers } from '../../hooks/useTiers';
+import type { QuotaPeriod, UsageQuota } from '../../lib/types';
+import { toast } from '../../lib/toast';
+import { Dropdown } from '../ui/Dropdown';
+
+const PERIODS: { value: QuotaPeriod; label: string }[] = [
+  { value: 'day', label: 'Daily' },
+  { value: 'month', label: 'Monthly' },
+];
+
+/** Blank means no limit on that measure */
+function parseLimit(text: string): number | null {
+  const value = parseFloat(text);
+  return text.trim() === '' || isNaN(value) ? null : value;
+}
+
+function QuotaRow({
+  label,
+  quota,
+  onSave,
+  onClear,
+}: {
+  label: string;
+  quota: UsageQuota | undefined;
+  onSave: (maxTokens: number | null, maxCost: number | null) => Promise<void>;
+  onClear: () => Promise<void>;
+}) {
+  const savedTokens = quota?.max_tokens?.toString() ?? '';
+  const savedCost = quota?.max_cost_usd?.toString() ?? '';
+  const [tokens, setTokens] = useState(savedTokens);
+  const [cost, setCost] = useState(savedCost);
+  const [saving, setSaving] = useState(false);
+  const dirty = tokens !== savedTokens || cost !== savedCost;
+
+  async function save() {
+    const maxTokens = parseLimit(tokens);
+    const maxCost = parseLimit(cost);
+    setSaving(true);
+    try {
+      if (maxTokens == null && maxCost == null) {
+        if (quota) await onClear();
+        toast.success(`${label}: no limit.`);
+      } else {
+        await onSave(maxTokens == null ? null : Math.floor(maxTokens), maxCost);
+        toast.success(`${label} quota saved.`);
+      }
+    } catch {
+      toast.error('Failed to save quota.');
+    }
+    setSaving(false);
+  }
+
+  return (
+    <View className="flex-row items-center gap-3 bg-stone-900 px-4 py-2.5">
+      <Text className="min-w-0 flex-1 text-sm text-stone-100" numberOfLines={1}>{label}</Text>
+      <TextInput
+        value={tokens}
+        onChangeText={setTokens}
+        keyboardType="numeric"
+        placeholder="No token limit"
+        placeholderTextColor="#57534e"
+        className="w-32 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <TextInput
+        value={cost}
+        onChangeText={setCost}
+        keyboardType="decimal-pad"
+        placeholder="No $ limit"
+        placeholderTextColor="#57534e"
+        className="w-24 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <Pressable
+        onPress={save}
+        disabled={!dirty || saving}
+        className={`rounded bg-gold-600 px-3 py-1.5 ${!dirty || saving ? 'opacity-30' : ''}`}
+      >
+        <Text className="text-xs font-semibold text-stone-950">{saving ? 'Saving...' : 'Save'}</Text>
+      </Pressable>
+    </View>
+  );
+}
+
+function AddOverride({ onAdd }: { onAdd: (quota: QuotaInput) => Promise<void> }) {
+  const { users } = useAdminUsers();
+  const { tierLabel } = useTiers();
+  const [userId, setUserId] = useState<string | null>(null);
+  const [period, setPeriod] = useState<QuotaPeriod>('day');
+  const [tokens, setTokens] = useState('');
+  const [cost, setCost] = useState('');
+  const [showUserPicker, setShowUserPicker] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  const selected = users.find((u) => u.id === userId);
+  const maxTokens = parseLimit(tokens);
+  const maxCost = parseLimit(cost);
+  const canAdd = !!userId && (maxTokens != null || maxCost != null) && !saving;
+
+  async function add() {
+    if (!userId) return;
+    setSaving(true);
+    try {
+      await onAdd({
+        user_id: userId,
+        period,
+        max_tokens: maxTokens == null ? null : Math.floor(maxTokens),
+        max_cost_usd: maxCost,
+      });
+      toast.success(`Override set for ${selected?.display_name ?? 'user'}.`);
+      setTokens('');
+      setCost('');
+    } catch {
+      toast.error('Failed to set override.');
+    }
+    setSaving(false);
+  }
+
+  return (
+    <View className="flex-row flex-wrap items-center gap-2">
+      <Dropdown
+        open={showUserPicker}
+        onClose={() => setShowUserPicker(false)}
+        maxHeight={240}
+        trigger={
+          <Pressable
+            onPress={() => setShowUserPicker(!showUserPicker)}
+            className="w-40 rounded border border-stone-700 bg-stone-800 px-2 py-1.5"
+          >
+            <Text className="text-sm text-stone-100" numberOfLines={1}>
+              {selected?.display_name ?? 'Choose a Don'}
+            </Text>
+          </Pressable>
+        }
+      >
+        {users.map((u) => (
+          <Pressable
+            key={u.id}
+            onPress={() => { setUserId(u.id); setShowUserPicker(false); }}
+            className={`px-3 py-2 ${u.id === userId ? 'bg-stone-700' : ''}`}
+          >
+            <Text className="text-sm text-stone-100">{u.display_name}</Text>
+            <Text className="text-[10px] text-stone-500">{tierLabel(u.tier)}</Text>
+          </Pressable>
+        ))}
+      </Dropdown>
+      {PERIODS.map((p) => (
+        <Pressable
+          key={p.value}
+          onPress={() => setPeriod(p.value)}
+          className={`rounded-lg px-3 py-1.5 ${period === p.value ? 'bg-stone-700' : 'bg-stone-800/50'}`}
+        >
+          <Text className={`text-xs ${period === p.value ? 'text-stone-100' : 'text-stone-500'}`}>{p.label}</Text>
+        </Pressable>
+      ))}
+      <TextInput
+        value={tokens}
+        onChangeText={setTokens}
+        keyboardType="numeric"
+        placeholder="Tokens"
+        placeholderTextColor="#57534e"
+        className="w-28 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <TextInput
+        value={cost}
+        onChangeText={setCost}
+        keyboardType="decimal-pad"
+        placeholder="$"
+        placeholderTextColor="#57534e"
+        className="w-20 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <Pressable
+        onPress={add}
+        disabled={!canAdd}
+        className={`rounded bg-gold-600 px-3 py-1.5 ${canAdd ? '' : 'opacity-30'}`}
+      >
+        <Text className="text-xs font-semibold text-stone-950">{saving ? 'Adding...' : 'Add override'}</Text>
+      </Pressable>
+    </View>
+  );
+}
+
+export function QuotaManager() {
+  const { quotas, loading, setQuota, deleteQuota } = useQuotas();
+  const { tiers } = useTiers();
+
+  if (loading) {
+    return (
+      <View className="items-center justify-center gap-2 py-8">
+        <ActivityIndicator color="#78716c" />
+        <Text className="text-sm text-stone-500">Loading quotas...</Text>
+      </View>
+    );
+  }
+
+  const overrides = quotas.filter((q) => q.user_id);
+
+  return (
+    <View className="gap-6">
+      <Text className="text-sm text-stone-400">
+        Limits on tokens or estimated spend per Don, checked before any member is invoked. Leave both blank for no limit.
+        A Don's override replaces their tier's quota for that period.
+      </Text>
+
+      <View className="gap-2">
+        <Text className="text-xs font-semibold uppercase tracking-wider text-stone-500">Tier quotas</Text>
+        <View className="overflow-hidden rounded-lg border border-stone-800">
+          {tiers.flatMap((tier) => PERIODS.map((p) => ({ tier: tier.key, name: tier.display_name, p }))).map(({ tier, name, p }, i) => {
+            const quota = quotas.find((q) => q.tier === tier && q.period === p.value);
+            return (
+              <View key={`${tier}-${p.value}-${quota?.updated_at ?? ''}`} className={i > 0 ? 'border-t border-stone-800' : ''}>
+                <QuotaRow
+                  label={`${name} · ${p.label}`}
+                  quota={quota}
+                  onSave={(maxTokens, maxCost) =>
+                    setQuota({ tier, period: p.value, max_tokens: maxTokens, max_cost_usd: maxCost })
+                  }
+                  onClear={() => (quota ? deleteQuota(quota.id) : Promise.resolve())}
+                />
+              </View>
+            );
+          })}
+        </View>
+      </View>
+
+      <View className="gap-2">
+        <Text className="text-xs font-semibold uppercase tracking-wider text-stone-500">Overrides</Text>
+        {overrides.length > 0 && (
+          <View className="overflow-hidden rounded-lg border border-stone-800">
+            {overrides.map((q, i) => (
+              <View key={`${q.id}-${q.updated_at}`} className={`flex-row items-center ${i > 0 ? 'border-t border-stone-800' : ''}`}>
+                <View className="flex-1">
+                  <QuotaRow
+                    label={`${q.profile?.display_name ?? 'Unknown'} · ${q.period === 'day' ? 'Daily' : 'Monthly'}`}
+                    quota={q}
+                    onSave={(maxTokens, maxCost) =>
+                      setQuota({ user_id: q.user_id!, period: q.period, max_tokens: maxTokens, max_cost_usd: maxCost })
+                    }
+                    onClear={() => deleteQuota(q.id)}
+                  />
+                </View>
+                <Pressable
+                  onPress={async () => {
+                    try {
+                      await deleteQuota(q.id);
+                      toast.success('Override removed.');
+                    } catch {
+                      toast.error('Failed to remove override.');
+                    }
+                  }}
+                  className="bg-stone-900 px-3 py-3.5"
+                >
+                  <X size={14} color="#78716c" />
+                </Pressable>
+              </View>
+            ))}
+          </View>
+        )}
+        <AddOverride onAdd={setQuota} />
+      </View>
+    </View>
+  );
+}
@@ -0,0 +1,108 @@
+import { useState } from 'react';
+import { View, Text, TextInput, Pressable } from 'react-native';
+import { Plus, X, Check } from 'lucide-react-native';
+import { PROVIDER_LABELS } from '../../config/constants';
+import type { CatalogModel, CatalogTarget } from '../../lib/types';
+import { Dropdown } from '../ui/Dropdown';
+
+interface RoutingEditorProps {
+  entry: CatalogModel;
+  catalogModels: CatalogModel[];
+  targets: CatalogTarget[];
+  sticky: boolean;
+  onChangeTargets: (targets: CatalogTarget[]) => void;
+  onChangeSticky: (sticky: boolean) => void;
+}
+
+/** Weighted targets of a catalog alias. With none, the entry answers with its own model. */
+export function RoutingEditor({ entry, catalogModels, targets, sticky, onChangeTargets, onChangeSticky }: RoutingEditorProps) {
+  const [showPicker, setShowPicker] = useState(false);
+
+  const available = catalogModels.filter(
+    (m) => m.id !== entry.id && !targets.some((t) => t.target_catalog_id === m.id),
+  );
+  const totalWeight = targets.reduce((sum, t) => sum + t.weight, 0);
+
+  function label(id: string) {
+    const m = catalogModels.find((c) => c.id === id);
+    return m ? `${PROVIDER_LABELS[m.provider]} / ${m.alias}` : 'Removed model';
+  }
+
+  function setWeight(id: string, text: string) {
+    const weight = Math.max(1, parseInt(text) || 1);
+    onChangeTargets(targets.map((t) => (t.target_catalog_id === id ? { ...t, weight } : t)));
+  }
+
+  return (
+    <View className="gap-2">
+      {targets.length === 0 ? (
+        <Text className="text-xs text-stone-500">Answers with {entry.model}. Add targets to split traffic across models.</Text>
+      ) : (
+        targets.map((t) => (
+          <View key={t.target_catalog_id} className="flex-row items-center gap-2">
+            <Text className="min-w-0 flex-1 text-sm text-stone-100" numberOfLines={1}>
+              {label(t.target_catalog_id)}
+            </Text>
+            <TextInput
+              value={String(t.weight)}
+              onChangeText={(text) => setWeight(t.target_catalog_id, text)}
+              keyboardType="numeric"
+              className="w-14 rounded border border-stone-700 bg-stone-800 px-2 py-1 text-right text-sm text-stone-100"
+            />
+            <Text className="w-10 text-right text-[10px] text-stone-500">
+              {Math.round((t.weight / totalWeight) * 100)}%
+            </Text>
+            <Pressable
+              onPress={() => onChangeTargets(targets.filter((x) => x.target_catalog_id !== t.target_catalog_id))}
+              hitSlop={6}
+            >
+              <X size={14} color="#78716c" />
+            </Pressable>
+          </View>
+        ))
+      )}
+
+      <View className="flex-row items-center justify-between">
+        <Dropdown
+          open={showPicker}
+          onClose={() => setShowPicker(false)}
+          maxHeight={192}
+          trigger={
+            <Pressable
+              onPress={() => setShowPicker(!showPicker)}
+              disabled={available.length === 0}
+              className={`flex-row items-center gap-1 ${available.length === 0 ? 'opacity-40' : ''}`}
+            >
+              <Plus size={12} color="#d97706" />
+              <Text className="text-xs text-gold-600">Add target</Text>
+            </Pressable>
+          }
+        >
+          {available.map((m) => (
+            <Pressable
+              key={m.id}
+              onPress={() => {
+                onChangeTargets([...targets, { target_catalog_id: m.id, weight: 1 }]);
+                setShowPicker(false);
+              }}
+              className="px-2 py-2"
+            >
+              <Text className="text-sm text-stone-100" numberOfLines={1}>
+                {PROVIDER_LABELS[m.provider]} / {m.alias}
+              </Text>
+            </Pressable>
+          ))}
+        </Dropdown>
+
+        {targets.length > 0 && (
+          <Pressable onPress={() => onChangeSticky(!sticky)} className="flex-row items-center gap-2">
+            <View className={`h-4 w-4 items-center justify-center rounded border ${sticky ? 'border-gold-600 bg-gold-600' : 'border-stone-600'}`}>
+              {sticky && <Check size={12} color="#0c0a09" />}
+            </View>
+            <Text className="text-xs text-stone-300">Sticky per member</Text>
+          </Pressable>
+        )}
+      </View>
+    </View>
+  );
+}
@@ -0,0 +1,228 @@
+import { useState } from 'react';
+import { View, Text, TextInput, Pressable, ActivityIndicator } from 'react-native';
+import { Trash2 } from 'lucide-react-native';
+import { useTiers, RESERVED_TIERS } from '../../hooks/useTiers';
+import { PROVIDERS, PROVIDER_LABELS } from '../../config/constants';
+import type { Provider, Tier } from '../../lib/types';
+import { toast } from '../../lib/toast';
+import { confirmAlert } from '../../lib/alert';
+
+/** Blank means unlimited */
+function parseLimit(text: string): number | null {
+  const value = parseInt(text, 10);
+  return text.trim() === '' || isNaN(value) ? null : Math.max(0, value);
+}
+
+function TierRow({ tier }: { tier: Tier }) {
+  const { updateTier, deleteTier } = useTiers();
+  const [displayName, setDisplayName] = useState(tier.display_name);
+  const [rank, setRank] = useState(String(tier.rank));
+  const [maxMembers, setMaxMembers] = useState(tier.max_members?.toString() ?? '');
+  const [maxCaporegimes, setMaxCaporegimes] = useState(tier.max_caporegimes?.toString() ?? '');
+  const [providers, setProviders] = useState<Provider[] | null>(tier.allowed_providers);
+  const [saving, setSaving] = useState(false);
+  const reserved = RESERVED_TIERS.includes(tier.key);
+
+  function toggleProvider(p: Provider) {
+    const current = providers ?? PROVIDERS;
+    const next = current.includes(p) ? current.filter((x) => x !== p) : [...current, p];
+    setProviders(next.length === PROVIDERS.length ? null : next);
+  }
+
+  async function save() {
+    setSaving(true);
+    try {
+      await updateTier(tier.key, {
+        display_name: displayName.trim(),
+        rank: parseInt(rank, 10) || 0,
+        max_members: parseLimit(maxMembers),
+        max_caporegimes: parseLimit(maxCaporegimes),
+        allowed_providers: providers,
+      });
+      toast.success(`${displayName.trim()} tier saved.`);
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : 'Failed to save tier.');
+    }
+    setSaving(false);
+  }
+
+  async function remove() {
+    const confirmed = await confirmAlert(
+      'Delete tier',
+      `Delete the ${tier.display_name} tier? Its quotas go with it.`,
+    );
+    if (!confirmed) return;
+    try {
+      await deleteTier(tier.key);
+      toast.success('Tier deleted.');
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : 'Failed to delete tier.');
+    }
+  }
+
+  return (
+    <View className="gap-3 rounded-lg border border-stone-800 bg-stone-900 p-4">
+      <View className="flex-row items-center gap-3">
+        <View className="flex-1">
+          <Text className="mb-1 text-xs text-stone-400">Display name</Text>
+          <TextInput
+            value={displayName}
+            onChangeText={setDisplayName}
+            className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+          />
+        </View>
+        <View className="w-20">
+          <Text className="mb-1 text-xs text-stone-400">Rank</Text>
+          <TextInput
+            value={rank}
+            onChangeText={setRank}
+            keyboardType="numeric"
+            className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+          />
+        </View>
+        <View className="w-24">
+          <Text className="mb-1 text-xs text-stone-400">Max members</Text>
+          <TextInput
+            value={maxMembers}
+            onChangeText={setMaxMembers}
+            keyboardType="numeric"
+            placeholder="Unlimited"
+            placeholderTextColor="#57534e"
+            className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+          />
+        </View>
+        <View className="w-24">
+          <Text className="mb-1 text-xs text-stone-400">Max capos</Text>
+          <TextInput
+            value={maxCaporegimes}
+            onChangeText={setMaxCaporegimes}
+            keyboardType="numeric"
+            placeholder="Unlimited"
+            placeholderTextColor="#57534e"
+            className="rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+          />
+        </View>
+      </View>
+
+      <View className="flex-row flex-wrap items-center gap-1.5">
+        <Text className="mr-1 text-xs text-stone-400">Providers</Text>
+        {PROVIDERS.map((p) => {
+          const on = providers == null || providers.includes(p);
+          return (
+            <Pressable
+              key={p}
+              onPress={() => toggleProvider(p)}
+              className={`rounded-lg px-2.5 py-1 ${on ? 'bg-stone-700' : 'bg-stone-800/50'}`}
+            >
+              <Text className={`text-xs ${on ? 'text-stone-100' : 'text-stone-600 line-through'}`}>{PROVIDER_LABELS[p]}</Text>
+            </Pressable>
+          );
+        })}
+      </View>
+
+      <View className="flex-row items-center justify-between">
+        <Text className="text-[10px] text-stone-600">
+          {tier.key}{reserved ? ' · reserved' : ''}
+        </Text>
+        <View className="flex-row items-center gap-2">
+          {!reserved && (
+            <Pressable onPress={remove} className="rounded p-1.5">
+              <Trash2 size={14} color="#ef4444" />
+            </Pressable>
+          )}
+          <Pressable
+            onPress={save}
+            disabled={saving || !displayName.trim()}
+            className={`rounded bg-gold-600 px-3 py-1.5 ${saving || !displayName.trim() ? 'opacity-50' : ''}`}
+          >
+            <Text className="text-xs font-semibold text-stone-950">{saving ? 'Saving...' : 'Save'}</Text>
+          </Pressable>
+        </View>
+      </View>
+    </View>
+  );
+}
+
+function AddTier() {
+  const { createTier } = useTiers();
+  const [key, setKey] = useState('');
+  const [displayName, setDisplayName] = useState('');
+  const [rank, setRank] = useState('');
+  const [saving, setSaving] = useState(false);
+  const canAdd = !!key.trim() && !!displayName.trim() && rank.trim() !== '' && !saving;
+
+  async function add() {
+    setSaving(true);
+    try {
+      await createTier({ key: key.trim(), display_name: displayName.trim(), rank: parseInt(rank, 10) || 0 });
+      toast.success(`${displayName.trim()} tier created.`);
+      setKey('');
+      setDisplayName('');
+      setRank('');
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : 'Failed to create tier.');
+    }
+    setSaving(false);
+  }
+
+  return (
+    <View className="flex-row flex-wrap items-center gap-2">
+      <TextInput
+        value={key}
+        onChangeText={(t) => setKey(t.toLowerCase())}
+        autoCapitalize="none"
+        placeholder="key, e.g. capo"
+        placeholderTextColor="#57534e"
+        className="w-32 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <TextInput
+        value={displayName}
+        onChangeText={setDisplayName}
+        placeholder="Display name"
+        placeholderTextColor="#57534e"
+        className="w-40 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <TextInput
+        value={rank}
+        onChangeText={setRank}
+        keyboardType="numeric"
+        placeholder="Rank"
+        placeholderTextColor="#57534e"
+        className="w-20 rounded border border-stone-700 bg-stone-800 px-2 py-1.5 text-sm text-stone-100"
+      />
+      <Pressable
+        onPress={add}
+        disabled={!canAdd}
+        className={`rounded bg-gold-600 px-3 py-1.5 ${canAdd ? '' : 'opacity-30'}`}
+      >
+        <Text className="text-xs font-semibold text-stone-950">{saving ? 'Adding...' : 'Add tier'}</Text>
+      </Pressable>
+    </View>
+  );
+}
+
+export function TierManager() {
+  const { tiers, loading } = useTiers();
+
+  if (loading) {
+    return (
+      <View className="items-center justify-center gap-2 py-8">
+        <ActivityIndicator color="#78716c" />
+        <Text className="text-sm text-stone-500">Loading tiers...</Text>
+      </View>
+    );
+  }
+
+  return (
+    <View className="gap-4">
+      <Text className="text-sm text-stone-400">
+        Higher rank outranks lower: a catalog model is open to its minimum tier and every tier above it.
+        Leave limits blank for unlimited. Quotas are set on the Quotas tab.
+      </Text>
+      {tiers.map((t) => (
+        <TierRow key={`${t.key}-${t.updated_at}`} tier={t} />
+      ))}
+      <AddTier />
+    </View>
+  );
+}
@@ -0,0 +1,107 @@
+import { useState } from 'react';
+import { View, Text, Pressable, ActivityIndicator } from 'react-native';
+import { useUsageReport } from '../../hooks/useUsageReport';
+import type { UsageGroupBy } from '../../lib/types';
+
+const GROUPS: { value: UsageGroupBy; label: string }[] = [
+  { value: 'don', label: 'By Don' },
+  { value: 'member', label: 'By member' },
+  { value: 'model', label: 'By model' },
+  { value: 'day', label: 'By day' },
+];
+
+const PERIODS = [7, 30, 90];
+
+function formatTokens(n: number) {
+  if (n >= 1_000_000) return `${(n / 1_000_000).toFixed(1)}M`;
+  if (n >= 1000) return `${(n / 1000).toFixed(1)}k`;
+  return String(n);
+}
+
+function formatCost(usd: number | null) {
+  if (usd == null) return '—';
+  if (usd > 0 && usd < 0.01) return '<$0.01';
+  return `$${usd.toFixed(2)}`;
+}
+
+export function UsageReport() {
+  const [groupBy, setGroupBy] = useState<UsageGroupBy>('don');
+  const [days, setDays] = useState(30);
+  const { rows, loading, error } = useUsageReport(groupBy, days);
+
+  const totalIn = rows.reduce((sum, r) => sum + Number(r.input_tokens), 0);
+  const totalOut = rows.reduce((sum, r) => sum + Number(r.output_tokens), 0);
+  const totalCost = rows.reduce((sum, r) => sum + Number(r.cost_usd ?? 0), 0);
+  const unpriced = rows.reduce((sum, r) => sum + Number(r.unpriced_responses), 0);
+
+  return (
+    <View className="gap-4">
+      <View className="flex-row flex-wrap items-center justify-between gap-2">
+        <View className="flex-row gap-1">
+          {GROUPS.map((g) => (
+            <Pressable
+              key={g.value}
+              onPress={() => setGroupBy(g.value)}
+              className={`rounded-lg px-3 py-1.5 ${groupBy === g.value ? 'bg-stone-700' : 'bg-stone-800/50'}`}
+            >
+              <Text className={`text-xs ${groupBy === g.value ? 'text-stone-100' : 'text-stone-500'}`}>{g.label}</Text>
+            </Pressable>
+          ))}
+        </View>
+        <View className="flex-row gap-1">
+          {PERIODS.map((d) => (
+            <Pressable
+              key={d}
+              onPress={() => setDays(d)}
+              className={`rounded-lg px-3 py-1.5 ${days === d ? 'bg-stone-700' : 'bg-stone-800/50'}`}
+            >
+              <Text className={`text-xs ${days === d ? 'text-stone-100' : 'text-stone-500'}`}>{d}d</Text>
+            </Pressable>
+          ))}
+        </View>
+      </View>
+
+      <Text className="text-sm text-stone-400">
+        {formatTokens(totalIn)} in · {formatTokens(totalOut)} out · ~{formatCost(totalCost)} over the last {days} days
+      </Text>
+      {unpriced > 0 && (
+        <Text className="text-xs text-stone-500">
+          {unpriced} response{unpriced === 1 ? '' : 's'} on unpriced models not counted in cost — set prices in the Catalog tab.
+        </Text>
+      )}
+
+      {loading ? (
+        <View className="items-center justify-center gap-2 py-8">
+          <ActivityIndicator color="#78716c" />
+          <Text className="text-sm text-stone-500">Tallying the books...</Text>
+        </View>
+      ) : error ? (
+        <Text className="text-sm text-red-400">Couldn't load usage.</Text>
+      ) : rows.length === 0 ? (
+        <Text className="text-sm text-stone-500">No AI responses in this period.</Text>
+      ) : (
+        <View className="overflow-hidden rounded-lg border border-stone-800">
+          {rows.map((r, i) => (
+            <View
+              key={r.key}
+              className={`flex-row items-center gap-3 bg-stone-900 px-4 py-2.5 ${i > 0 ? 'border-t border-stone-800' : ''}`}
+            >
+              <View className="min-w-0 flex-1">
+                <Text className="text-sm text-stone-100" numberOfLines={1}>{r.label ?? r.key}</Text>
+                {groupBy === 'model' && (
+                  <Text className="text-[10px] text-stone-500" numberOfLines={1}>{r.key}</Text>
+                )}
+              </View>
+              <Text className="w-20 text-right text-xs text-stone-500">{r.responses} resp.</Text>
+              <Text className="w-16 text-right text-xs text-stone-300">{formatTokens(Number(r.input_tokens))}</Text>
+              <Text className="w-16 text-right text-xs text-stone-300">{formatTokens(Number(r.output_tokens))}</Text>
+              <Text className="w-16 text-right text-xs text-emerald-400">
+                {formatCost(r.cost_usd == null ? null : Number(r.cost_usd))}
+              </Text>
+            </View>
+          ))}
+        </View>
+      )}
+    </View>
+  );
+}
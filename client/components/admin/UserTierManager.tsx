@@ -2,16 +2,16 @@ import { useState } from 'react';
 import { View, Text, Pressable, FlatList, ActivityIndicator } from 'react-native';
 import { useAdminUsers } from '../../hooks/useAdminUsers';
 import { useAuth } from '../../contexts/AuthContext';
-import { TIER_LABELS, TIER_COLORS } from '../../config/constants';
+import { useTiers } from '../../hooks/useTiers';
 import type { UserTier } from '../../lib/types';
 import { toast } from '../../lib/toast';
 import { Dropdown } from '../ui/Dropdown';
-
-const TIERS: UserTier[] = ['godfather', 'boss', 'associate'];
+import { TierBadge } from '../ui/TierBadge';
 
 export function UserTierManager() {
   const { user } = useAuth();
   const { users, loading, updateTier } = useAdminUsers();
+  const { tiers, tierLabel } = useTiers();
   const [tierPickerUserId, setTierPickerUserId] = useState<string | null>(null);
 
   async function handleTierChange(userId: string, tier: UserTier) {
@@ -68,28 +68,22 @@ export function UserTierManager() {
                   onPress={() => setTierPickerUserId(tierPickerUserId === u.id ? null : u.id)}
                   className="rounded border border-stone-700 bg-stone-800 px-2 py-1"
                 >
-                  <Text className="text-sm text-stone-100">{TIER_LABELS[u.tier]}</Text>
+                  <Text className="text-sm text-stone-100">{tierLabel(u.tier)}</Text>
                 </Pressable>
               }
             >
-              {TIERS.map((t) => (
+              {tiers.map((t) => (
                 <Pressable
-                  key={t}
-                  onPress={() => handleTierChange(u.id, t)}
-                  className={`px-3 py-2 ${t === u.tier ? 'bg-stone-700' : ''}`}
+                  key={t.key}
+                  onPress={() => handleTierChange(u.id, t.key)}
+                  className={`px-3 py-2 ${t.key === u.tier ? 'bg-stone-700' : ''}`}
                 >
-                  <Text className="text-sm text-stone-100">{TIER_LABELS[t]}</Text>
+                  <Text className="text-sm text-stone-100">{t.display_name}</Text>
                 </Pressable>
               ))}
             </Dropdown>
 
-            <View className={`rounded px-2 py-0.5 ${TIER_COLORS[u.tier]}`}>
-              <Text className={`text-[10px] font-semibold ${
-                u.tier === 'associate' ? 'text-stone-300' : 'text-stone-950'
-              }`}>
-                {TIER_LABELS[u.tier]}
-              </Text>
-            </View>
+            <TierBadge tier={u.tier} />
           </View>
         )}
       />
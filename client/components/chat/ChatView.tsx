@@ -1,12 +1,18 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
 import { FlatList, Pressable, View, Text } from 'react-native';
 import { ChevronDown } from 'lucide-react-native';
+import { router } from 'expo-router';
+import { useQueryClient } from '@tanstack/react-query';
 import { useSendMessage } from '../../hooks/useSendMessage';
 import { useMemberProgress, type MemberProgress } from '../../hooks/useMemberProgress';
+import { usePlanActions } from '../../hooks/usePlanActions';
+import { useMessageActions } from '../../hooks/useMessageActions';
 import { MessageBubble } from './MessageBubble';
 import { MessageComposer } from './MessageComposer';
 import { MemberProgressCard } from './MemberProgressCard';
+import { ThreadPanel } from './ThreadPanel';
 import { toast } from '../../lib/toast';
+import { confirmAlert } from '../../lib/alert';
 import type { Message, Member } from '../../lib/types';
 import type { FriendlyError } from '../../lib/error-messages';
 
@@ -23,6 +29,8 @@ interface CompletedProgress {
 interface ChatViewProps {
   sitDownId: string;
   userId?: string;
+  /** Sit-down admins may edit and delete anyone's messages */
+  isAdmin?: boolean;
   members: Member[];
   memberOwnerMap?: Map<string, string>;
   onToggleMembers?: () => void;
@@ -38,11 +46,12 @@ interface ChatViewProps {
 export function ChatView({
   sitDownId,
   userId,
+  isAdmin,
   members,
   memberOwnerMap,
   onToggleMembers,
   showMembers,
-  messages,
+  messages: allMessages,
   lastReadAt,
   enteredAt,
   messagesError,
@@ -50,9 +59,32 @@ export function ChatView({
   onRetryMessages,
 }: ChatViewProps) {
   const send = useSendMessage(sitDownId);
+  const planActions = usePlanActions(sitDownId);
+  const messageActions = useMessageActions(sitDownId);
+  const queryClient = useQueryClient();
   const memberProgress = useMemberProgress(sitDownId);
   const flatListRef = useRef<FlatList<Message>>(null);
 
+  // Interim progress updates fold under the caporegime report that references them
+  const { messages, progressByReport } = useMemo(() => {
+    const byId = new Map(allMessages.map((m) => [m.id, m]));
+    const folded = new Set<string>();
+    const byReport = new Map<string, Message[]>();
+    for (const m of allMessages) {
+      const ids = (m.metadata as Record<string, unknown> | undefined)?.progress_message_ids;
+      if (!Array.isArray(ids)) continue;
+      const updates = ids
+        .map((id) => byId.get(id as string))
+        .filter((u): u is Message => !!u);
+      updates.forEach((u) => folded.add(u.id));
+      if (updates.length > 0) byReport.set(m.id, updates);
+    }
+    return {
+      messages: folded.size > 0 ? allMessages.filter((m) => !folded.has(m.id)) : allMessages,
+      progressByReport: byReport,
+    };
+  }, [allMessages]);
+
   // Persist completed progress so it survives the 60s cleanup in useMemberProgress
   const completedProgressRef = useRef<Map<string, CompletedProgress>>(new Map());
 
@@ -82,6 +114,7 @@ export function ChatView({
   }, [memberProgress]);
   const [showScrollButton, setShowScrollButton] = useState(false);
   const [replyTo, setReplyTo] = useState<Message | null>(null);
+  const [threadRootId, setThreadRootId] = useState<string | null>(null);
 
   const displayError = send.error;
   const clearError = () => {
@@ -198,7 +231,10 @@ export function ChatView({
   async function handleSend(content: string) {
     const replyId = replyTo?.id;
     setReplyTo(null);
+    sendContent(content, replyId);
+  }
 
+  function sendContent(content: string, replyId?: string) {
     // Fire-and-forget: formula saves message to DB, realtime delivers it to UI.
     // No refetch — it races with realtime and causes messages to disappear/reappear.
     send
@@ -213,19 +249,133 @@ export function ChatView({
       });
   }
 
-  const renderItem = useCallback(
-    ({ item }: { item: Message }) => {
+  // Approval resumes the operation in the background; its report arrives via realtime
+  const handleReviewPlan = useCallback(
+    async (operationId: string, approved: boolean) => {
+      planActions.reviewPlan(operationId, approved).catch((err) => {
+        toast.error(err instanceof Error ? err.message : "Couldn't update the plan.");
+      });
+    },
+    [planActions],
+  );
+
+  const handleSavePlanAsJob = useCallback(
+    async (operationId: string) => {
+      try {
+        const { jobName, skippedSteps } = await planActions.savePlanAsJob(operationId);
+        toast.success(
+          skippedSteps.length > 0
+            ? `Saved "${jobName}" — ${skippedSteps.length} step(s) without a soldier were left out.`
+            : `Saved "${jobName}" as a job.`,
+        );
+      } catch (err) {
+        toast.error(err instanceof Error ? err.message : "Couldn't save the job.");
+      }
+    },
+    [planActions],
+  );
+
+  const handleEditMessage = useCallback(
+    async (message: Message, content: string, rerunMentions: boolean) => {
+      try {
+        await messageActions.editMessage(message.id, content, rerunMentions);
+      } catch (err) {
+        toast.error(err instanceof Error ? err.message : "Couldn't edit the message.");
+        throw err;
+      }
+    },
+    [messageActions],
+  );
+
+  const handleDeleteMessage = useCallback(
+    async (message: Message) => {
+      const confirmed = await confirmAlert('Delete Message', 'Delete this message for everyone in the sit-down?');
+      if (!confirmed) return;
+      messageActions.deleteMessage(message.id).catch((err) => {
+        toast.error(err instanceof Error ? err.message : "Couldn't delete the message.");
+      });
+    },
+    [messageActions],
+  );
+
+  const handleRegenerate = useCallback(
+    async (message: Message) => {
+      try {
+        await messageActions.regenerate(message.id);
+      } catch (err) {
+        toast.error(err instanceof Error ? err.message : "Couldn't regenerate the answer.");
+        throw err;
+      }
+    },
+    [messageActions],
+  );
+
+  const handleSelectVersion = useCallback(
+    (message: Message, version: number) => {
+      messageActions.selectVersion(message.id, version).catch((err) => {
+        toast.error(err instanceof Error ? err.message : "Couldn't switch versions.");
+      });
+    },
+    [messageActions],
+  );
+
+  const handleFork = useCallback(
+    async (message: Message) => {
+      try {
+        const fork = await messageActions.forkSitDown(message.id);
+        queryClient.invalidateQueries({ queryKey: ['sitDowns'] });
+        queryClient.invalidateQueries({ queryKey: ['commission', 'state'] });
+        toast.success('A new table, same faces.');
+        router.push(`/sitdown/${fork.id}`);
+      } catch (err) {
+        toast.error(err instanceof Error ? err.message : "Couldn't fork the sit-down.");
+      }
+    },
+    [messageActions, queryClient],
+  );
+
+  const renderBubble = useCallback(
+    (item: Message, inThread = false) => {
       const msgReplyToId = (item.metadata as Record<string, unknown>)?.reply_to_id as
         | string
         | undefined;
-      const msgReplyTo = msgReplyToId ? messages.find((m) => m.id === msgReplyToId) : undefined;
+      const msgReplyTo = msgReplyToId ? allMessages.find((m) => m.id === msgReplyToId) : undefined;
+      const progress = messageProgressMap.get(item.id);
+      const canManage = !!userId && (
+        !!isAdmin
+        || (item.sender_type === 'don' ? item.sender_user_id === userId : item.member?.owner_id === userId)
+      );
+
+      return (
+        <MessageBubble
+          message={item}
+          replyTo={inThread ? undefined : msgReplyTo}
+          onReply={inThread ? undefined : setReplyTo}
+          onScrollToMessage={scrollToMessage}
+          progress={progress}
+          progressUpdates={progressByReport.get(item.id)}
+          userId={userId}
+          onReviewPlan={handleReviewPlan}
+          onSavePlanAsJob={handleSavePlanAsJob}
+          canManage={canManage}
+          onEdit={handleEditMessage}
+          onDelete={handleDeleteMessage}
+          onRegenerate={handleRegenerate}
+          onSelectVersion={handleSelectVersion}
+          onFork={handleFork}
+          onOpenThread={inThread ? undefined : (m) => setThreadRootId(m.thread_id ?? m.id)}
+        />
+      );
+    },
+    [allMessages, scrollToMessage, messageProgressMap, progressByReport, userId, isAdmin, handleReviewPlan, handleSavePlanAsJob, handleEditMessage, handleDeleteMessage, handleRegenerate, handleSelectVersion, handleFork],
+  );
 
+  const renderItem = useCallback(
+    ({ item }: { item: Message }) => {
       // In original (non-inverted) order, check if this message is the first unread
       const originalIndex = messageIndexMap.get(item.id);
       const showDivider = firstUnreadIndex >= 0 && originalIndex === firstUnreadIndex;
 
-      const progress = messageProgressMap.get(item.id);
-
       return (
         <View>
           {showDivider && (
@@ -235,17 +385,11 @@ export function ChatView({
               <View className="flex-1 h-px bg-stone-700" />
             </View>
           )}
-          <MessageBubble
-            message={item}
-            replyTo={msgReplyTo}
-            onReply={setReplyTo}
-            onScrollToMessage={scrollToMessage}
-            progress={progress}
-          />
+          {renderBubble(item)}
         </View>
       );
     },
-    [messages, scrollToMessage, firstUnreadIndex, messageIndexMap, messageProgressMap],
+    [firstUnreadIndex, messageIndexMap, renderBubble],
   );
 
   const keyExtractor = useCallback((item: Message) => item.id, []);
@@ -358,6 +502,17 @@ export function ChatView({
         onCancelReply={() => setReplyTo(null)}
         memberOwnerMap={memberOwnerMap}
       />
+
+      <ThreadPanel
+        sitDownId={sitDownId}
+        rootId={threadRootId}
+        liveMessages={allMessages}
+        members={members}
+        memberOwnerMap={memberOwnerMap}
+        renderMessage={(m) => renderBubble(m, true)}
+        onSend={sendContent}
+        onClose={() => setThreadRootId(null)}
+      />
     </View>
   );
 }
@@ -1,11 +1,12 @@
-import { useCallback, useRef } from 'react';
-import { View, Text, Pressable, Animated } from 'react-native';
-import { Reply } from 'lucide-react-native';
+import { useCallback, useRef, useState } from 'react';
+import { View, Text, TextInput, Pressable, Animated } from 'react-native';
+import { Reply, ChevronDown, ChevronUp, ChevronLeft, ChevronRight, Radio, Pencil, Trash2, Check, RefreshCw, GitBranch, MessageSquare } from 'lucide-react-native';
 import { formatDistanceToNow } from 'date-fns';
 import { MessageContent } from './MessageContent';
+import { PlanCard } from './PlanCard';
 import { UserAvatar } from '../common/UserAvatar';
 import { PROVIDER_COLORS, PROVIDER_LABELS } from '../../config/constants';
-import type { Message } from '../../lib/types';
+import type { CatalogRouting, Message, MessageEdit, MessageVersion, OperationPlan } from '../../lib/types';
 
 interface CompletedProgress {
   statusText: string;
@@ -19,10 +20,31 @@ interface MessageBubbleProps {
   onReply?: (message: Message) => void;
   onScrollToMessage?: (messageId: string) => void;
   progress?: CompletedProgress;
+  /** Interim `post_update` messages folded under this report */
+  progressUpdates?: Message[];
+  userId?: string;
+  onReviewPlan?: (operationId: string, approved: boolean) => Promise<void>;
+  onSavePlanAsJob?: (operationId: string) => Promise<void>;
+  /** Sender of the message or a sit-down admin: may edit and delete it */
+  canManage?: boolean;
+  onEdit?: (message: Message, content: string, rerunMentions: boolean) => Promise<void>;
+  onDelete?: (message: Message) => void;
+  onRegenerate?: (message: Message) => Promise<void>;
+  onSelectVersion?: (message: Message, version: number) => void;
+  onFork?: (message: Message) => void;
+  onOpenThread?: (message: Message) => void;
 }
 
-export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, progress }: MessageBubbleProps) {
+export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, progress, progressUpdates, userId, onReviewPlan, onSavePlanAsJob, canManage, onEdit, onDelete, onRegenerate, onSelectVersion, onFork, onOpenThread }: MessageBubbleProps) {
   const isDon = message.sender_type === 'don';
+  const [showUpdates, setShowUpdates] = useState(false);
+  const [showActions, setShowActions] = useState(false);
+  const [showHistory, setShowHistory] = useState(false);
+  const [editing, setEditing] = useState(false);
+  const [draft, setDraft] = useState('');
+  const [rerunMentions, setRerunMentions] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [regenerating, setRegenerating] = useState(false);
   const time = formatDistanceToNow(new Date(message.created_at), { addSuffix: true });
   const highlightOpacity = useRef(new Animated.Value(0)).current;
 
@@ -46,12 +68,43 @@ export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, pr
     onScrollToMessage?.(replyTo.id);
   }
 
+  const isDeleted = !!message.deleted_at;
+  const manageable = !!canManage && !isDeleted;
+
   function handleLongPress() {
-    onReply?.(message);
+    if (manageable || onFork) setShowActions(!showActions);
+    else onReply?.(message);
+  }
+
+  function startEditing() {
+    setDraft(message.content);
+    setRerunMentions(false);
+    setShowActions(false);
+    setEditing(true);
+  }
+
+  async function saveEdit() {
+    const content = draft.trim();
+    if (!onEdit || !content) return;
+    if (content === message.content && !rerunMentions) {
+      setEditing(false);
+      return;
+    }
+    setSaving(true);
+    try {
+      await onEdit(message, content, isDon && rerunMentions);
+      setEditing(false);
+    } catch {
+      // Keep the draft open; the caller reports the error
+    } finally {
+      setSaving(false);
+    }
   }
 
   const replySnippet = replyTo
-    ? replyTo.content.length > 120
+    ? replyTo.deleted_at
+      ? 'Message deleted'
+      : replyTo.content.length > 120
       ? replyTo.content.slice(0, 120) + '...'
       : replyTo.content
     : null;
@@ -83,8 +136,199 @@ export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, pr
     </Pressable>
   );
 
+  async function regenerate() {
+    if (!onRegenerate) return;
+    setShowActions(false);
+    setRegenerating(true);
+    try {
+      await onRegenerate(message);
+    } catch {
+      // The caller reports the error
+    } finally {
+      setRegenerating(false);
+    }
+  }
+
+  // Caporegime reports come from a whole operation, so only single answers can be regenerated
+  const canRegenerate = manageable && !isDon && !!onRegenerate && message.member?.member_type !== 'caporegime'
+    && !(message.metadata as Record<string, unknown> | undefined)?.operation_id;
+  const versions = ((message.metadata as Record<string, unknown> | undefined)?.versions as MessageVersion[] | undefined) ?? [];
+  const selectedVersion = ((message.metadata as Record<string, unknown> | undefined)?.selected_version as number | undefined)
+    ?? versions.length - 1;
+
+  const versionPicker = versions.length > 1 && (
+    <View className="flex-row items-center gap-1">
+      <Pressable
+        onPress={() => onSelectVersion?.(message, selectedVersion - 1)}
+        disabled={!canManage || !onSelectVersion || selectedVersion <= 0}
+        className={!canManage || selectedVersion <= 0 ? 'opacity-30' : ''}
+        hitSlop={6}
+      >
+        <ChevronLeft size={12} color="#a8a29e" />
+      </Pressable>
+      <Text className="text-[10px] text-stone-500">
+        {selectedVersion + 1}/{versions.length}
+      </Text>
+      <Pressable
+        onPress={() => onSelectVersion?.(message, selectedVersion + 1)}
+        disabled={!canManage || !onSelectVersion || selectedVersion >= versions.length - 1}
+        className={!canManage || selectedVersion >= versions.length - 1 ? 'opacity-30' : ''}
+        hitSlop={6}
+      >
+        <ChevronRight size={12} color="#a8a29e" />
+      </Pressable>
+    </View>
+  );
+
+  const edits = ((message.metadata as Record<string, unknown> | undefined)?.edits as MessageEdit[] | undefined) ?? [];
+
+  const editedMark = message.edited_at && (
+    <Pressable onPress={() => setShowHistory(!showHistory)} hitSlop={6}>
+      <Text className="text-[10px] text-stone-600">(edited)</Text>
+    </Pressable>
+  );
+
+  const editHistory = showHistory && edits.length > 0 && (
+    <View className="mt-1 gap-1 border-l border-stone-700 pl-2">
+      {edits.map((e, i) => (
+        <Text key={i} className="text-[11px] text-stone-500">
+          <Text className="text-[10px] text-stone-600">
+            {formatDistanceToNow(new Date(e.edited_at), { addSuffix: true })}:{' '}
+          </Text>
+          {e.content}
+        </Text>
+      ))}
+    </View>
+  );
+
+  // Thread roots show their reply count; replies link to the thread they belong to
+  const replyCount = message.thread_reply_count ?? 0;
+  const threadLink = onOpenThread && (replyCount > 0 || message.thread_id) && (
+    <Pressable onPress={() => onOpenThread(message)} className="mt-1 flex-row items-center gap-1 self-start" hitSlop={6}>
+      <MessageSquare size={11} color="#d97706" />
+      <Text className="text-[11px] text-gold-600">
+        {message.thread_id
+          ? 'View thread'
+          : `${replyCount} repl${replyCount === 1 ? 'y' : 'ies'}`}
+      </Text>
+      {!message.thread_id && message.thread_last_reply_at && (
+        <Text className="text-[10px] text-stone-600">
+          · last {formatDistanceToNow(new Date(message.thread_last_reply_at), { addSuffix: true })}
+        </Text>
+      )}
+    </Pressable>
+  );
+
+  const actionBar = showActions && (
+    <View className="mt-1 flex-row gap-4">
+      {onReply && (
+        <Pressable onPress={() => { setShowActions(false); onReply(message); }} className="flex-row items-center gap-1" hitSlop={6}>
+          <Reply size={12} color="#a8a29e" />
+          <Text className="text-xs text-stone-400">Reply</Text>
+        </Pressable>
+      )}
+      {canRegenerate && (
+        <Pressable onPress={regenerate} className="flex-row items-center gap-1" hitSlop={6}>
+          <RefreshCw size={12} color="#a8a29e" />
+          <Text className="text-xs text-stone-400">Regenerate</Text>
+        </Pressable>
+      )}
+      {onFork && (
+        <Pressable onPress={() => { setShowActions(false); onFork(message); }} className="flex-row items-center gap-1" hitSlop={6}>
+          <GitBranch size={12} color="#a8a29e" />
+          <Text className="text-xs text-stone-400">Fork from here</Text>
+        </Pressable>
+      )}
+      {manageable && onEdit && (
+        <Pressable onPress={startEditing} className="flex-row items-center gap-1" hitSlop={6}>
+          <Pencil size={12} color="#a8a29e" />
+          <Text className="text-xs text-stone-400">Edit</Text>
+        </Pressable>
+      )}
+      {manageable && onDelete && (
+        <Pressable onPress={() => { setShowActions(false); onDelete(message); }} className="flex-row items-center gap-1" hitSlop={6}>
+          <Trash2 size={12} color="#f87171" />
+          <Text className="text-xs text-red-400">Delete</Text>
+        </Pressable>
+      )}
+    </View>
+  );
+
+  const editor = editing && (
+    <View className="mt-1 gap-2">
+      <TextInput
+        value={draft}
+        onChangeText={setDraft}
+        multiline
+        autoFocus
+        editable={!saving}
+        className="rounded-lg border border-stone-700 bg-stone-800 px-3 py-2 text-sm text-stone-100"
+      />
+      <View className="flex-row items-center justify-between">
+        {isDon ? (
+          <Pressable onPress={() => setRerunMentions(!rerunMentions)} className="flex-row items-center gap-2">
+            <View className={`h-4 w-4 items-center justify-center rounded border ${rerunMentions ? 'border-gold-600 bg-gold-600' : 'border-stone-600'}`}>
+              {rerunMentions && <Check size={12} color="#0c0a09" />}
+            </View>
+            <Text className="text-xs text-stone-300">Re-run mentions</Text>
+          </Pressable>
+        ) : (
+          <View />
+        )}
+        <View className="flex-row gap-2">
+          <Pressable onPress={() => setEditing(false)} disabled={saving} className="rounded-lg px-3 py-1.5">
+            <Text className="text-xs text-stone-400">Cancel</Text>
+          </Pressable>
+          <Pressable
+            onPress={saveEdit}
+            disabled={saving || !draft.trim()}
+            className={`rounded-lg bg-gold-600 px-3 py-1.5 ${saving || !draft.trim() ? 'opacity-40' : ''}`}
+          >
+            <Text className="text-xs font-semibold text-stone-950">{saving ? 'Saving...' : 'Save'}</Text>
+          </Pressable>
+        </View>
+      </View>
+    </View>
+  );
+
   const provider = message.member?.catalog_model?.provider;
 
+  // Caporegime plans render as a step list with approval controls for the owning Don
+  const metadata = message.metadata as Record<string, unknown> | undefined;
+  const plan = metadata?.type === 'plan' ? (metadata.plan as OperationPlan | undefined) : undefined;
+  const planOperationId = metadata?.operation_id as string | undefined;
+  const canManagePlan = !!userId && message.member?.owner_id === userId;
+  // A weighted alias answered with one of its target models
+  const routing = metadata?.routing as CatalogRouting | undefined;
+
+  // Deleted messages stay in place so replies to them still make sense
+  if (isDeleted) {
+    return (
+      <View className="flex-row items-center gap-2 px-4 py-1 pl-[60px]">
+        <Trash2 size={11} color="#57534e" />
+        <Text className="text-xs italic text-stone-600">
+          {isDon ? message.profile?.display_name ?? 'Don' : message.member?.name ?? 'Member'}: message deleted
+        </Text>
+      </View>
+    );
+  }
+
+  // Interim progress from a running operation: a compact line rather than a full bubble
+  if (metadata?.type === 'progress') {
+    return (
+      <View className="flex-row items-start gap-2 px-4 py-1 pl-[60px]">
+        <View className="pt-0.5">
+          <Radio size={11} color="#78716c" />
+        </View>
+        <Text className="flex-1 text-xs text-stone-400">
+          <Text className="font-semibold text-stone-500">{message.member?.name ?? 'Caporegime'}: </Text>
+          {message.content}
+          <Text className="text-[10px] text-stone-600">  {time}</Text>
+        </Text>
+      </View>
+    );
+  }
+
   if (isDon) {
     return (
       <Pressable onLongPress={handleLongPress} delayLongPress={400}>
@@ -110,14 +354,22 @@ export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, pr
                 {message.profile?.display_name ?? 'Don'}
               </Text>
               <Text className="text-[10px] text-stone-600">{time}</Text>
+              {editedMark}
             </View>
             {replyQuote}
-            <View className="mt-0.5 flex-row items-end">
-              <View className="flex-1">
-                <MessageContent content={message.content} />
+            {editing ? (
+              editor
+            ) : (
+              <View className="mt-0.5 flex-row items-end">
+                <View className="flex-1">
+                  <MessageContent content={message.content} />
+                </View>
+                {replyButton}
               </View>
-              {replyButton}
-            </View>
+            )}
+            {editHistory}
+            {threadLink}
+            {actionBar}
           </View>
         </View>
       </Pressable>
@@ -163,7 +415,15 @@ export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, pr
                 </Text>
               </View>
             )}
+            {routing && (
+              <Text className="text-[10px] text-stone-500">
+                {routing.alias} {'\u2192'} {routing.target_alias}
+              </Text>
+            )}
             <Text className="text-[10px] text-stone-600">{time}</Text>
+            {editedMark}
+            {versionPicker}
+            {regenerating && <Text className="text-[10px] text-yellow-500/60">Regenerating...</Text>}
           </View>
           {progress && (
             <View className="flex-row items-center gap-1.5 mt-0.5">
@@ -174,12 +434,52 @@ export function MessageBubble({ message, replyTo, onReply, onScrollToMessage, pr
             </View>
           )}
           {replyQuote}
-          <View className="mt-1 rounded-lg bg-stone-700/25 px-3 py-2 flex-row items-end">
-            <View className="flex-1">
-              <MessageContent content={message.content} />
+          {progressUpdates && progressUpdates.length > 0 && (
+            <View className="mt-0.5">
+              <Pressable
+                onPress={() => setShowUpdates(!showUpdates)}
+                className="flex-row items-center gap-1 self-start"
+                hitSlop={8}
+              >
+                {showUpdates ? <ChevronUp size={10} color="#78716c" /> : <ChevronDown size={10} color="#78716c" />}
+                <Text className="text-[10px] text-stone-500">
+                  {progressUpdates.length} progress update{progressUpdates.length === 1 ? '' : 's'}
+                </Text>
+              </Pressable>
+              {showUpdates && (
+                <View className="mt-1 gap-1 border-l border-stone-700 pl-2">
+                  {progressUpdates.map((u) => (
+                    <Text key={u.id} className="text-[11px] text-stone-400">
+                      {u.content}
+                    </Text>
+                  ))}
+                </View>
+              )}
             </View>
-            {replyButton}
-          </View>
+          )}
+          {editing ? (
+            editor
+          ) : (
+            <View className="mt-1 rounded-lg bg-stone-700/25 px-3 py-2 flex-row items-end">
+              <View className="flex-1">
+                {plan && planOperationId ? (
+                  <PlanCard
+                    plan={plan}
+                    planStatus={(metadata?.plan_status as 'awaiting_approval' | 'approved' | 'rejected') ?? 'approved'}
+                    canManage={canManagePlan}
+                    onReview={onReviewPlan && ((approved) => onReviewPlan(planOperationId, approved))}
+                    onSaveAsJob={onSavePlanAsJob && (() => onSavePlanAsJob(planOperationId))}
+                  />
+                ) : (
+                  <MessageContent content={message.content} />
+                )}
+              </View>
+              {replyButton}
+            </View>
+          )}
+          {editHistory}
+          {threadLink}
+          {actionBar}
         </View>
       </View>
     </Pressable>
@@ -0,0 +1,119 @@
+import { useState } from 'react';
+import { View, Text, Pressable, ActivityIndicator } from 'react-native';
+import { Circle, CircleDot, CheckCircle, XCircle, MinusCircle, Save } from 'lucide-react-native';
+import type { OperationPlan, PlanStepStatus } from '../../lib/types';
+
+type PlanStatus = 'awaiting_approval' | 'approved' | 'rejected';
+
+interface PlanCardProps {
+  plan: OperationPlan;
+  planStatus: PlanStatus;
+  /** Only the Don who owns the caporegime can approve, reject or save the plan */
+  canManage: boolean;
+  onReview?: (approved: boolean) => Promise<void>;
+  onSaveAsJob?: () => Promise<void>;
+}
+
+const STEP_ICONS: Record<PlanStepStatus, { icon: typeof Circle; color: string }> = {
+  pending: { icon: Circle, color: '#57534e' },
+  in_progress: { icon: CircleDot, color: '#eab308' },
+  done: { icon: CheckCircle, color: '#22c55e' },
+  skipped: { icon: MinusCircle, color: '#78716c' },
+  failed: { icon: XCircle, color: '#ef4444' },
+};
+
+const PLAN_STATUS_LABELS: Record<PlanStatus, string> = {
+  awaiting_approval: 'Awaiting your approval',
+  approved: 'Approved',
+  rejected: 'Rejected',
+};
+
+export function PlanCard({ plan, planStatus, canManage, onReview, onSaveAsJob }: PlanCardProps) {
+  const [busy, setBusy] = useState<'approve' | 'reject' | 'save' | null>(null);
+  const doneCount = plan.steps.filter((s) => s.status === 'done').length;
+
+  async function run(kind: 'approve' | 'reject' | 'save', action?: () => Promise<void>) {
+    if (!action || busy) return;
+    setBusy(kind);
+    try {
+      await action();
+    } finally {
+      setBusy(null);
+    }
+  }
+
+  return (
+    <View className="gap-2">
+      <View className="flex-row items-center justify-between gap-2">
+        <Text className="flex-1 text-sm font-semibold text-stone-100">{plan.summary}</Text>
+        <Text className="text-[10px] text-stone-500">
+          {planStatus === 'approved' ? `${doneCount}/${plan.steps.length} done` : PLAN_STATUS_LABELS[planStatus]}
+        </Text>
+      </View>
+
+      <View className="gap-1.5">
+        {plan.steps.map((step, i) => {
+          const { icon: Icon, color } = STEP_ICONS[step.status] ?? STEP_ICONS.pending;
+          return (
+            <View key={step.id} className="flex-row gap-2">
+              <View className="pt-0.5">
+                <Icon size={12} color={color} />
+              </View>
+              <View className="min-w-0 flex-1">
+                <Text className={`text-xs ${step.status === 'skipped' ? 'text-stone-500 line-through' : 'text-stone-300'}`}>
+                  {i + 1}. {step.description}
+                  {step.soldier ? <Text className="text-stone-500"> — {step.soldier}</Text> : null}
+                </Text>
+                {step.expected_output && (
+                  <Text className="text-[11px] text-stone-500">→ {step.expected_output}</Text>
+                )}
+                {step.note && (
+                  <Text className="text-[11px] italic text-stone-500">{step.note}</Text>
+                )}
+              </View>
+            </View>
+          );
+        })}
+      </View>
+
+      {canManage && planStatus === 'awaiting_approval' && (
+        <View className="flex-row gap-2 pt-1">
+          <Pressable
+            onPress={() => run('approve', onReview && (() => onReview(true)))}
+            disabled={busy !== null}
+            className={`rounded-lg bg-gold-600 px-3 py-1.5 ${busy ? 'opacity-50' : ''}`}
+          >
+            {busy === 'approve' ? (
+              <ActivityIndicator size="small" color="#0c0a09" />
+            ) : (
+              <Text className="text-xs font-semibold text-stone-950">Approve</Text>
+            )}
+          </Pressable>
+          <Pressable
+            onPress={() => run('reject', onReview && (() => onReview(false)))}
+            disabled={busy !== null}
+            className={`rounded-lg border border-stone-700 px-3 py-1.5 ${busy ? 'opacity-50' : ''}`}
+          >
+            <Text className="text-xs text-stone-300">Reject</Text>
+          </Pressable>
+        </View>
+      )}
+
+      {canManage && planStatus !== 'rejected' && onSaveAsJob && (
+        <Pressable
+          onPress={() => run('save', onSaveAsJob)}
+          disabled={busy !== null}
+          className="flex-row items-center gap-1.5 self-start pt-1"
+          hitSlop={8}
+        >
+          {busy === 'save' ? (
+            <ActivityIndicator size="small" color="#a8a29e" />
+          ) : (
+            <Save size={12} color="#a8a29e" />
+          )}
+          <Text className="text-[11px] text-stone-400">Save as job</Text>
+        </Pressable>
+      )}
+    </View>
+  );
+}
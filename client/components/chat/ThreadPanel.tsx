@@ -0,0 +1,70 @@
+import type { ReactNode } from 'react';
+import { Modal, View, Text, Pressable, ActivityIndicator, ScrollView, KeyboardAvoidingView, Platform } from 'react-native';
+import { X } from 'lucide-react-native';
+import { useThread } from '../../hooks/useThread';
+import { MessageComposer } from './MessageComposer';
+import type { Message, Member } from '../../lib/types';
+
+interface ThreadPanelProps {
+  sitDownId: string;
+  rootId: string | null;
+  liveMessages: Message[];
+  members: Member[];
+  memberOwnerMap?: Map<string, string>;
+  renderMessage: (message: Message) => ReactNode;
+  onSend: (content: string, replyToId: string) => void;
+  onClose: () => void;
+}
+
+/** One thread of a sit-down. Members mentioned here answer from the thread alone. */
+export function ThreadPanel({ sitDownId, rootId, liveMessages, members, memberOwnerMap, renderMessage, onSend, onClose }: ThreadPanelProps) {
+  const { messages, loading, error } = useThread(sitDownId, rootId, liveMessages);
+  const replies = Math.max(messages.length - 1, 0);
+
+  return (
+    <Modal visible={!!rootId} animationType="fade" transparent onRequestClose={onClose}>
+      <View className="flex-1 items-center justify-center bg-black/60 px-4 py-8">
+        <KeyboardAvoidingView
+          behavior={Platform.OS === 'ios' ? 'padding' : 'height'}
+          className="w-full max-w-2xl flex-1 rounded-xl border border-stone-800 bg-stone-950"
+        >
+          <View className="flex-row items-center justify-between border-b border-stone-800 px-5 py-4">
+            <Text className="font-serif text-lg font-bold text-stone-100">
+              Thread
+              <Text className="text-xs font-normal text-stone-500">  {replies} repl{replies === 1 ? 'y' : 'ies'}</Text>
+            </Text>
+            <Pressable onPress={onClose} hitSlop={8}>
+              <X size={20} color="#a8a29e" />
+            </Pressable>
+          </View>
+
+          {loading && messages.length === 0 ? (
+            <View className="flex-1 items-center justify-center">
+              <ActivityIndicator color="#78716c" />
+            </View>
+          ) : error && messages.length === 0 ? (
+            <View className="flex-1 items-center justify-center px-6">
+              <Text className="text-sm text-red-400">{error.message}</Text>
+            </View>
+          ) : (
+            <ScrollView className="flex-1" contentContainerStyle={{ paddingVertical: 8 }}>
+              {messages.map((m, i) => (
+                <View key={m.id} className={i === 0 && messages.length > 1 ? 'border-b border-stone-800 pb-2 mb-2' : ''}>
+                  {renderMessage(m)}
+                </View>
+              ))}
+            </ScrollView>
+          )}
+
+          {rootId && (
+            <MessageComposer
+              members={members}
+              onSend={(content) => onSend(content, rootId)}
+              memberOwnerMap={memberOwnerMap}
+            />
+          )}
+        </KeyboardAvoidingView>
+      </View>
+    </Modal>
+  );
+}
@@ -0,0 +1,144 @@
+import { useState } from 'react';
+import { Modal, View, Text, Pressable, ActivityIndicator, ScrollView } from 'react-native';
+import { X } from 'lucide-react-native';
+import { showToast } from '../../lib/toast';
+import { useCommission } from '../../hooks/useCommission';
+import { useMembers } from '../../hooks/useMembers';
+
+interface LendSoldierModalProps {
+  visible: boolean;
+  onClose: () => void;
+  borrowerId: string;
+  borrowerName: string;
+}
+
+const DURATIONS = [
+  { label: '1 day', days: 1 },
+  { label: '1 week', days: 7 },
+  { label: '30 days', days: 30 },
+];
+
+export function LendSoldierModal({ visible, onClose, borrowerId, borrowerName }: LendSoldierModalProps) {
+  const { lendSoldier } = useCommission();
+  const { members: allSoldiers } = useMembers('soldier');
+  // External soldiers hold your API credentials, so they stay home
+  const soldiers = allSoldiers.filter((s) => s.soldier_type !== 'external');
+  const [soldierId, setSoldierId] = useState<string | null>(null);
+  const [days, setDays] = useState(7);
+  const [loading, setLoading] = useState(false);
+  const [inlineError, setInlineError] = useState<string | null>(null);
+
+  function handleClose() {
+    setSoldierId(null);
+    setDays(7);
+    setLoading(false);
+    setInlineError(null);
+    onClose();
+  }
+
+  async function handleSubmit() {
+    if (!soldierId) return;
+    setInlineError(null);
+    setLoading(true);
+    try {
+      const expiresAt = new Date(Date.now() + days * 24 * 60 * 60 * 1000).toISOString();
+      await lendSoldier({ soldierId, borrowerId, expiresAt });
+      showToast({ type: 'success', text1: `Offer sent to ${borrowerName}.` });
+      handleClose();
+    } catch (err: unknown) {
+      const upper = (err instanceof Error ? err.message : String(err)).toUpperCase();
+      if (upper.includes('LOAN_ALREADY_OPEN')) {
+        setInlineError(`${borrowerName} already has this soldier, or an offer for it.`);
+      } else if (upper.includes('SOLDIER_NOT_LENDABLE')) {
+        setInlineError('External soldiers carry your API credentials and cannot be lent.');
+      } else if (upper.includes('NOT_A_COMMISSION_CONTACT')) {
+        setInlineError(`${borrowerName} is no longer in your Commission.`);
+      } else {
+        setInlineError("Couldn't send the offer.");
+      }
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  return (
+    <Modal visible={visible} animationType="slide" transparent={true} onRequestClose={handleClose}>
+      <View className="flex-1 items-center justify-center bg-black/60 px-4">
+        <View className="w-full max-w-md rounded-xl border border-stone-800 bg-stone-900">
+          {/* Header */}
+          <View className="flex-row items-center justify-between border-b border-stone-800 px-5 py-4">
+            <Text className="font-serif text-lg font-bold text-stone-100">Lend a Soldier</Text>
+            <Pressable onPress={handleClose} hitSlop={8}>
+              <X size={20} color="#a8a29e" />
+            </Pressable>
+          </View>
+
+          <View className="p-5 gap-4">
+            <Text className="text-xs text-stone-500">
+              {borrowerName}'s caporegimes can delegate to this soldier once they accept. It runs on your model, and
+              every job it takes is counted on the loan. External soldiers can't be lent.
+            </Text>
+
+            {/* Soldier */}
+            <View>
+              <Text className="mb-1 text-sm font-medium text-stone-300">Soldier</Text>
+              {soldiers.length === 0 ? (
+                <Text className="text-xs text-stone-600">You have no soldiers to lend.</Text>
+              ) : (
+                <ScrollView style={{ maxHeight: 200 }} className="rounded-lg border border-stone-700 bg-stone-800">
+                  {soldiers.map((s) => (
+                    <Pressable
+                      key={s.id}
+                      onPress={() => setSoldierId(s.id)}
+                      className={`flex-row items-center gap-2 px-3 py-2.5 ${s.id === soldierId ? 'bg-stone-700' : ''}`}
+                    >
+                      <Text className="text-base">{s.avatar_url || '\u{1F5E1}'}</Text>
+                      <Text className="text-sm text-stone-100">{s.name}</Text>
+                    </Pressable>
+                  ))}
+                </ScrollView>
+              )}
+            </View>
+
+            {/* Duration */}
+            <View>
+              <Text className="mb-1 text-sm font-medium text-stone-300">For</Text>
+              <View className="flex-row gap-2">
+                {DURATIONS.map((d) => (
+                  <Pressable
+                    key={d.days}
+                    onPress={() => setDays(d.days)}
+                    className={`flex-1 items-center rounded-lg border px-3 py-2 ${
+                      days === d.days ? 'border-gold-600 bg-stone-700' : 'border-stone-700 bg-stone-800'
+                    }`}
+                  >
+                    <Text className="text-sm text-stone-100">{d.label}</Text>
+                  </Pressable>
+                ))}
+              </View>
+            </View>
+
+            {inlineError && <Text className="text-xs text-red-400">{inlineError}</Text>}
+
+            <View className="flex-row justify-end gap-2 pt-2">
+              <Pressable onPress={handleClose} className="rounded-lg border border-stone-700 px-4 py-2">
+                <Text className="text-sm text-stone-300">Cancel</Text>
+              </Pressable>
+              <Pressable
+                onPress={handleSubmit}
+                disabled={loading || !soldierId}
+                className={`rounded-lg bg-gold-600 px-4 py-2 ${loading || !soldierId ? 'opacity-50' : ''}`}
+              >
+                {loading ? (
+                  <ActivityIndicator size="small" color="#0c0a09" />
+                ) : (
+                  <Text className="text-sm font-semibold text-stone-950">Offer</Text>
+                )}
+              </Pressable>
+            </View>
+          </View>
+        </View>
+      </View>
+    </Modal>
+  );
+}
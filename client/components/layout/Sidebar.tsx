@@ -31,7 +31,7 @@ import { CreateSitdownModal } from '../sitdown/CreateSitdownModal';
 import { CreateCommissionSitDownModal } from '../commission/CreateCommissionSitDownModal';
 import { InviteToCommissionModal } from '../commission/InviteToCommissionModal';
 import { BackRoomListItem } from '../sitdowns/BackRoomListItem';
-import { TIER_LABELS, TIER_COLORS } from '../../config/constants';
+import { TierBadge } from '../ui/TierBadge';
 import * as Clipboard from 'expo-clipboard';
 import { toast } from '../../lib/toast';
 import { confirmAlert } from '../../lib/alert';
@@ -606,13 +606,7 @@ export function Sidebar(props: DrawerContentComponentProps) {
                   {profile?.display_name ?? 'Don'}
                 </Text>
                 <View className="flex-row items-center gap-1.5 flex-wrap mt-0.5">
-                  <View className={`rounded px-1.5 py-0.5 ${TIER_COLORS[tier]}`}>
-                    <Text className={`text-[9px] font-semibold ${
-                      tier === 'associate' ? 'text-stone-300' : 'text-stone-950'
-                    }`}>
-                      {TIER_LABELS[tier]}
-                    </Text>
-                  </View>
+                  <TierBadge tier={tier} textSize="text-[9px]" />
                   <View className={`flex-row items-center gap-1 rounded px-1.5 py-0.5 ${
                     realtimeConnected
                       ? 'bg-emerald-900/50'
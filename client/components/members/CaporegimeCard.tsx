@@ -14,6 +14,11 @@ interface CaporegimeCardProps {
   onDeleteSoldier?: (soldier: Member) => void;
   soldiers?: Member[];
   loadingSoldiers?: boolean;
+  /** Sub-caporegimes serving in this crew */
+  captains?: Member[];
+  /** The caporegime this one reports to, if any */
+  reportsTo?: Member;
+  onEditCaptain?: (captain: Member) => void;
 }
 
 export function CaporegimeCard({
@@ -25,7 +30,11 @@ export function CaporegimeCard({
   onDeleteSoldier,
   soldiers = [],
   loadingSoldiers,
+  captains = [],
+  reportsTo,
+  onEditCaptain,
 }: CaporegimeCardProps) {
+  const crewSize = soldiers.length + captains.length;
   const [expanded, setExpanded] = useState(false);
   const provider = member.catalog_model?.provider;
   const alias = member.catalog_model?.alias;
@@ -46,6 +55,11 @@ export function CaporegimeCard({
             <View className="rounded bg-amber-900/50 px-1.5 py-0.5">
               <Text className="text-[9px] font-semibold text-amber-400">CAPO</Text>
             </View>
+            {reportsTo && (
+              <Text className="text-[10px] text-stone-500" numberOfLines={1}>
+                reports to {reportsTo.name}
+              </Text>
+            )}
           </View>
           {provider ? (
             <View className="mt-0.5 flex-row items-center gap-2">
@@ -72,8 +86,8 @@ export function CaporegimeCard({
             className="flex-row items-center gap-1 rounded-md p-2"
           >
             <Users size={14} color="#78716c" />
-            {soldiers.length > 0 && (
-              <Text className="text-xs text-stone-500">{soldiers.length}</Text>
+            {crewSize > 0 && (
+              <Text className="text-xs text-stone-500">{crewSize}</Text>
             )}
             {expanded ? <ChevronUp size={14} color="#78716c" /> : <ChevronDown size={14} color="#78716c" />}
           </Pressable>
@@ -95,7 +109,7 @@ export function CaporegimeCard({
         <View className="border-t border-stone-800 bg-stone-950/50 px-4 py-3">
           <View className="flex-row items-center justify-between mb-2">
             <Text className="text-xs font-semibold uppercase tracking-wider text-stone-500">
-              Crew ({soldiers.length})
+              Crew ({crewSize})
             </Text>
             {onAddSoldier && (
               <Pressable
@@ -108,9 +122,22 @@ export function CaporegimeCard({
             )}
           </View>
 
+          {captains.length > 0 && (
+            <View className="mb-2 gap-1.5">
+              {captains.map((captain) => (
+                <MemberCard
+                  key={captain.id}
+                  member={captain}
+                  compact
+                  onEdit={onEditCaptain ? () => onEditCaptain(captain) : undefined}
+                />
+              ))}
+            </View>
+          )}
+
           {loadingSoldiers ? (
             <ActivityIndicator size="small" color="#78716c" />
-          ) : soldiers.length === 0 ? (
+          ) : crewSize === 0 ? (
             <Text className="text-xs text-stone-600">No soldiers in this crew yet.</Text>
           ) : (
             <View className="gap-1.5">
@@ -13,11 +13,16 @@ interface MemberCardProps {
 export function MemberCard({ member, onEdit, onDelete, compact }: MemberCardProps) {
   const provider = member.catalog_model?.provider;
   const alias = member.catalog_model?.alias;
+  const isReagent = member.soldier_type === 'reagent';
 
   if (compact) {
     return (
       <View className="flex-row items-center gap-2 rounded-lg bg-stone-800 px-3 py-2">
-        {provider ? (
+        {isReagent ? (
+          <View className="h-5 w-5 items-center justify-center rounded bg-stone-600">
+            <Text className="text-[10px] font-bold text-white">R</Text>
+          </View>
+        ) : provider ? (
           <View className={`h-5 w-5 items-center justify-center rounded ${PROVIDER_COLORS[provider]}`}>
             <Text className="text-[10px] font-bold text-white">
               {provider[0].toUpperCase()}
@@ -57,7 +62,11 @@ export function MemberCard({ member, onEdit, onDelete, compact }: MemberCardProp
       {/* Name + model info */}
       <View className="min-w-0 flex-1">
         <Text className="font-medium text-stone-100">{member.name}</Text>
-        {provider ? (
+        {isReagent ? (
+          <Text className="mt-0.5 text-xs text-stone-500" numberOfLines={1}>
+            {member.soldier_config?.reagent_ref ?? 'Reagent'}
+          </Text>
+        ) : provider ? (
           <View className="mt-0.5 flex-row items-center gap-2">
             <View className={`rounded px-1.5 py-0.5 ${PROVIDER_COLORS[provider]}`}>
               <Text className="text-[10px] font-semibold text-white">
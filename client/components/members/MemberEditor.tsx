@@ -11,8 +11,8 @@ import {
   ActivityIndicator,
 } from 'react-native';
 import { X, ChevronDown, AlertTriangle, Plus, Trash2 } from 'lucide-react-native';
-import type { Provider, Member, MemberType, SoldierType, SoldierConfig, SoldierSecret } from '../../lib/types';
-import { PROVIDER_LABELS, MEMBER_TEMPLATES, CAPOREGIME_TEMPLATES, BOOKKEEPER_TEMPLATES, SOLDIER_TEMPLATES, MEMBER_TYPE_LABELS, MEMBER_TYPE_DESCRIPTIONS, SOLDIER_TYPE_LABELS, SOLDIER_TYPE_DESCRIPTIONS, EXTERNAL_SOLDIER_SYSTEM_PROMPT } from '../../config/constants';
+import type { CatalogModel, Provider, Member, MemberType, PlanMode, SoldierType, SoldierConfig, SoldierSecret } from '../../lib/types';
+import { PROVIDER_LABELS, MEMBER_TEMPLATES, CAPOREGIME_TEMPLATES, BOOKKEEPER_TEMPLATES, SOLDIER_TEMPLATES, MEMBER_TYPE_LABELS, MEMBER_TYPE_DESCRIPTIONS, SOLDIER_TYPE_LABELS, SOLDIER_TYPE_DESCRIPTIONS, EXTERNAL_SOLDIER_SYSTEM_PROMPT, requiredCapability } from '../../config/constants';
 import { useModelCatalog } from '../../hooks/useModelCatalog';
 import { Dropdown } from '../ui/Dropdown';
 import { EmojiPicker } from '../ui/EmojiPicker';
@@ -27,15 +27,31 @@ const DEFAULT_ROLE_EMOJI: Record<string, string> = {
   soldier: '\u{1F9E0}',
 };
 
+const PLAN_MODES: PlanMode[] = ['off', 'auto', 'approval'];
+
+const PLAN_MODE_LABELS: Record<PlanMode, string> = {
+  off: 'Off',
+  auto: 'Plan first',
+  approval: 'Ask me',
+};
+
+const PLAN_MODE_DESCRIPTIONS: Record<PlanMode, string> = {
+  off: 'Goes straight to work on your orders.',
+  auto: 'Drafts a step-by-step plan, posts it, then carries it out.',
+  approval: 'Drafts a plan and waits for your approval before starting.',
+};
+
 interface MemberEditorProps {
   visible: boolean;
   member: Member | null;
   onSave: (data: {
     name: string;
     catalog_model_id?: string;
+    fallback_model_ids?: string[];
     system_prompt: string;
     member_type?: MemberType;
-    caporegime_id?: string;
+    caporegime_id?: string | null;
+    plan_mode?: PlanMode;
     avatar_url?: string;
     soldier_type?: SoldierType;
     soldier_config?: SoldierConfig;
@@ -45,9 +61,11 @@ interface MemberEditorProps {
   forceMemberType?: MemberType;
   /** Pre-set caporegime_id for soldier creation */
   caporegimeId?: string;
+  /** Caporegimes a caporegime can report to */
+  caporegimeOptions?: Member[];
 }
 
-export function MemberEditor({ visible, member, onSave, onClose, forceMemberType, caporegimeId }: MemberEditorProps) {
+export function MemberEditor({ visible, member, onSave, onClose, forceMemberType, caporegimeId, caporegimeOptions = [] }: MemberEditorProps) {
   const { modelsByProvider, availableProviders, loading: catalogLoading, error: catalogError, refetch: refetchCatalog } = useModelCatalog();
 
   const isEditing = useRef(false);
@@ -65,17 +83,30 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
   const [avatarEmoji, setAvatarEmoji] = useState(member?.avatar_url ?? DEFAULT_ROLE_EMOJI[initialRole] ?? '');
   const [provider, setProvider] = useState<Provider>(initialProvider);
   const [catalogModelId, setCatalogModelId] = useState(member?.catalog_model_id ?? '');
+  const [fallbackModelIds, setFallbackModelIds] = useState<string[]>(member?.fallback_model_ids ?? []);
   const [systemPrompt, setSystemPrompt] = useState(member?.system_prompt ?? '');
   const [saving, setSaving] = useState(false);
   const [soldierType, setSoldierType] = useState<SoldierType>(member?.soldier_type ?? 'default');
   const [docsUrl, setDocsUrl] = useState(member?.soldier_config?.docs_url ?? '');
   const [secrets, setSecrets] = useState<SoldierSecret[]>(member?.soldier_config?.secrets ?? []);
+  const [reagentRef, setReagentRef] = useState(member?.soldier_config?.reagent_ref ?? '');
 
   const [showProviderPicker, setShowProviderPicker] = useState(false);
   const [showModelPicker, setShowModelPicker] = useState(false);
   const [showSoldierTypePicker, setShowSoldierTypePicker] = useState(false);
+  const [showFallbackPicker, setShowFallbackPicker] = useState(false);
+  const [reportsToId, setReportsToId] = useState<string | null>(member?.caporegime_id ?? null);
+  const [showReportsToPicker, setShowReportsToPicker] = useState(false);
+  const [planMode, setPlanMode] = useState<PlanMode>(member?.plan_mode ?? 'off');
+
+  // Hide catalog models that explicitly lack what this role needs (members-api refuses them)
+  const roleCapability = requiredCapability(
+    forceMemberType === 'soldier' || member?.member_type === 'soldier' ? 'soldier' : memberType,
+    soldierType,
+  );
+  const fitsRole = (m: CatalogModel) => !roleCapability || m.capabilities?.[roleCapability] !== false;
 
-  const providerModels = modelsByProvider[provider] ?? [];
+  const providerModels = (modelsByProvider[provider] ?? []).filter(fitsRole);
 
   const effectiveCatalogModelId = catalogModelId && providerModels.some((a) => a.id === catalogModelId)
     ? catalogModelId
@@ -98,6 +129,11 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
       setAvatarEmoji(member?.avatar_url ?? DEFAULT_ROLE_EMOJI[role] ?? '');
       setProvider(member?.catalog_model?.provider ?? availableProviders[0] ?? 'claude');
       setCatalogModelId(member?.catalog_model_id ?? '');
+      setFallbackModelIds(member?.fallback_model_ids ?? []);
+      setShowFallbackPicker(false);
+      setReportsToId(member?.caporegime_id ?? null);
+      setShowReportsToPicker(false);
+      setPlanMode(member?.plan_mode ?? 'off');
       setSystemPrompt(member?.system_prompt ?? '');
       setMemberType(
         (forceMemberType as CreatableMemberType) ?? (member?.member_type as CreatableMemberType) ?? 'consul'
@@ -105,6 +141,7 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
       setSoldierType(member?.soldier_type ?? 'default');
       setDocsUrl(member?.soldier_config?.docs_url ?? '');
       setSecrets(member?.soldier_config?.secrets ?? []);
+      setReagentRef(member?.soldier_config?.reagent_ref ?? '');
       setShowSoldierTypePicker(false);
     }
     prevVisible.current = visible;
@@ -119,11 +156,31 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
 
   function handleModelChange(id: string) {
     setCatalogModelId(id);
+    setFallbackModelIds((ids) => ids.filter((f) => f !== id));
     setShowModelPicker(false);
   }
 
+  const allModels = availableProviders.flatMap((p) => modelsByProvider[p] ?? []).filter(fitsRole);
+  const fallbackCandidates = allModels.filter(
+    (m) => m.id !== effectiveCatalogModelId && !fallbackModelIds.includes(m.id)
+  );
+
+  function handleAddFallback(id: string) {
+    setFallbackModelIds((ids) => [...ids, id]);
+    setShowFallbackPicker(false);
+  }
+
+  function handleRemoveFallback(id: string) {
+    setFallbackModelIds((ids) => ids.filter((f) => f !== id));
+  }
+
   const isInformant = (forceMemberType ?? memberType) === 'informant';
-  const needsModel = !isInformant;
+  const isCaporegimeForm = (forceMemberType ?? memberType) === 'caporegime';
+  const reportsToOptions = caporegimeOptions.filter((c) => c.id !== member?.id);
+  const reportsToName = reportsToOptions.find((c) => c.id === reportsToId)?.name ?? 'Nobody (top level)';
+  const isSoldierForm = forceMemberType === 'soldier' || member?.member_type === 'soldier';
+  const isReagentSoldier = isSoldierForm && soldierType === 'reagent';
+  const needsModel = !isInformant && !isReagentSoldier;
 
   // Get templates for current role
   const templates = forceMemberType === 'soldier'
@@ -137,15 +194,18 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
   async function handleSubmit() {
     if (!name.trim()) return;
     if (needsModel && !effectiveCatalogModelId) return;
+    if (isReagentSoldier && !reagentRef.trim().startsWith('reagent:')) return;
 
     setSaving(true);
     try {
       const data: {
         name: string;
         catalog_model_id?: string;
+        fallback_model_ids?: string[];
         system_prompt: string;
         member_type?: MemberType;
-        caporegime_id?: string;
+        caporegime_id?: string | null;
+        plan_mode?: PlanMode;
         avatar_url?: string;
         soldier_type?: SoldierType;
         soldier_config?: SoldierConfig;
@@ -157,6 +217,9 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
 
       if (needsModel && effectiveCatalogModelId) {
         data.catalog_model_id = effectiveCatalogModelId;
+        data.fallback_model_ids = fallbackModelIds.filter(
+          (id) => id !== effectiveCatalogModelId && allModels.some((m) => m.id === id)
+        );
       }
 
       if (!isEditing.current) {
@@ -166,9 +229,17 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
         }
       }
 
+      // Sub-caporegimes serve in another caporegime's crew; null detaches on edit
+      if (isCaporegimeForm && (reportsToId || isEditing.current)) {
+        data.caporegime_id = reportsToId;
+      }
+
+      if (isCaporegimeForm) {
+        data.plan_mode = planMode;
+      }
+
       // Include soldier-specific fields
-      const isSoldier = forceMemberType === 'soldier' || member?.member_type === 'soldier';
-      if (isSoldier) {
+      if (isSoldierForm) {
         data.soldier_type = soldierType;
         if (soldierType === 'external') {
           const filteredSecrets = secrets.filter((s) => s.name.trim() && s.value.trim());
@@ -176,6 +247,11 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
             docs_url: docsUrl || undefined,
             secrets: filteredSecrets.length > 0 ? filteredSecrets : undefined,
           };
+        } else if (soldierType === 'reagent') {
+          data.soldier_config = {
+            ...member?.soldier_config,
+            reagent_ref: reagentRef.trim(),
+          };
         }
       }
 
@@ -197,7 +273,9 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
         ? 'New Informant'
         : 'Recruit Member';
 
-  const submitDisabled = saving || !name.trim() || (needsModel && (catalogLoading || availableProviders.length === 0));
+  const submitDisabled = saving || !name.trim()
+    || (needsModel && (catalogLoading || availableProviders.length === 0))
+    || (isReagentSoldier && !reagentRef.trim().startsWith('reagent:'));
 
   return (
     <Modal
@@ -237,7 +315,7 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
             {/* Form */}
             <ScrollView className="max-h-[70vh]" keyboardShouldPersistTaps="handled">
               <View className="p-5 gap-4">
-                {member && !member.catalog_model && !isInformant && (
+                {member && !member.catalog_model && needsModel && (
                   <View className="flex-row items-center gap-1.5">
                     <AlertTriangle size={12} color="#d97706" />
                     <Text className="text-xs text-amber-500">Model removed — pick a new one</Text>
@@ -332,7 +410,7 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
                 )}
 
                 {/* Soldier Type (soldier creation/edit only) */}
-                {(forceMemberType === 'soldier' || member?.member_type === 'soldier') && (
+                {isSoldierForm && (
                   <View>
                     <Text className="mb-1 text-sm font-medium text-stone-300">Soldier Type</Text>
                     <Dropdown
@@ -354,7 +432,7 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
                         </Pressable>
                       }
                     >
-                      {(['default', 'external'] as SoldierType[]).map((st) => (
+                      {(['default', 'external', 'reagent'] as SoldierType[]).map((st) => (
                         <Pressable
                           key={st}
                           onPress={() => {
@@ -375,8 +453,28 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
                   </View>
                 )}
 
+                {/* Reagent Soldier Config */}
+                {isReagentSoldier && (
+                  <View className="gap-3 rounded-lg border border-stone-700/50 bg-stone-800/30 p-3">
+                    <Text className="text-xs font-medium text-stone-400">Reagent</Text>
+                    <View>
+                      <Text className="mb-1 text-xs text-stone-400">Reagent reference</Text>
+                      <TextInput
+                        value={reagentRef}
+                        onChangeText={setReagentRef}
+                        placeholder="reagent:local.csv-parse"
+                        placeholderTextColor="#57534e"
+                        autoCapitalize="none"
+                        autoCorrect={false}
+                        className="w-full rounded-lg border border-stone-700 bg-stone-800 px-3 py-2 text-sm text-stone-100"
+                      />
+                      <Text className="mt-1 text-xs text-stone-500">Receives the rendered prompt (and item) as compute input — no model, no cost</Text>
+                    </View>
+                  </View>
+                )}
+
                 {/* External Soldier Config */}
-                {(forceMemberType === 'soldier' || member?.member_type === 'soldier') && soldierType === 'external' && (
+                {isSoldierForm && soldierType === 'external' && (
                   <View className="gap-3 rounded-lg border border-stone-700/50 bg-stone-800/30 p-3">
                     <Text className="text-xs font-medium text-stone-400">API Configuration</Text>
                     <View>
@@ -545,9 +643,124 @@ export function MemberEditor({ visible, member, onSave, onClose, forceMemberType
                         </View>
                       </View>
                     )}
+
+                    {/* Fallback chain */}
+                    {!catalogLoading && allModels.length > 1 && (
+                      <View>
+                        <Text className="mb-1 text-sm font-medium text-stone-300">Fallback Models</Text>
+                        <Text className="mb-2 text-xs text-stone-500">
+                          Tried in order when the primary model is down or rate-limited.
+                        </Text>
+                        {fallbackModelIds.map((id, index) => {
+                          const m = allModels.find((c) => c.id === id);
+                          return (
+                            <View
+                              key={id}
+                              className="mb-2 flex-row items-center justify-between rounded-lg border border-stone-700 bg-stone-800 px-3 py-2"
+                            >
+                              <Text className="text-sm text-stone-100" numberOfLines={1}>
+                                {index + 1}. {m ? `${PROVIDER_LABELS[m.provider]} · ${m.alias}` : 'Unavailable model'}
+                              </Text>
+                              <Pressable onPress={() => handleRemoveFallback(id)} hitSlop={8}>
+                                <X size={14} color="#a8a29e" />
+                              </Pressable>
+                            </View>
+                          );
+                        })}
+                        {fallbackCandidates.length > 0 && (
+                          <Dropdown
+                            open={showFallbackPicker}
+                            onClose={() => setShowFallbackPicker(false)}
+                            trigger={
+                              <Pressable
+                                onPress={() => setShowFallbackPicker(!showFallbackPicker)}
+                                className="flex-row items-center gap-1.5 self-start rounded-lg border border-dashed border-stone-700 px-3 py-2"
+                              >
+                                <Plus size={14} color="#a8a29e" />
+                                <Text className="text-sm text-stone-400">Add fallback</Text>
+                              </Pressable>
+                            }
+                          >
+                            {fallbackCandidates.map((m) => (
+                              <Pressable
+                                key={m.id}
+                                onPress={() => handleAddFallback(m.id)}
+                                className="px-3 py-2.5"
+                              >
+                                <Text className="text-sm text-stone-100">
+                                  {PROVIDER_LABELS[m.provider]} · {m.alias}
+                                </Text>
+                              </Pressable>
+                            ))}
+                          </Dropdown>
+                        )}
+                      </View>
+                    )}
                   </>
                 )}
 
+                {/* Reports to (sub-caporegimes) */}
+                {isCaporegimeForm && reportsToOptions.length > 0 && (
+                  <View>
+                    <Text className="mb-1 text-sm font-medium text-stone-300">Reports To</Text>
+                    <Text className="mb-2 text-xs text-stone-500">
+                      Joins that caporegime's crew and takes delegated work with their own soldiers.
+                    </Text>
+                    <Dropdown
+                      open={showReportsToPicker}
+                      onClose={() => setShowReportsToPicker(false)}
+                      trigger={
+                        <Pressable
+                          onPress={() => setShowReportsToPicker(!showReportsToPicker)}
+                          className="flex-row items-center justify-between rounded-lg border border-stone-700 bg-stone-800 px-3 py-2.5"
+                        >
+                          <Text className="text-sm text-stone-100">{reportsToName}</Text>
+                          <ChevronDown size={16} color="#a8a29e" />
+                        </Pressable>
+                      }
+                    >
+                      <Pressable
+                        onPress={() => { setReportsToId(null); setShowReportsToPicker(false); }}
+                        className={`px-3 py-2.5 ${reportsToId === null ? 'bg-stone-700' : ''}`}
+                      >
+                        <Text className="text-sm text-stone-100">Nobody (top level)</Text>
+                      </Pressable>
+                      {reportsToOptions.map((c) => (
+                        <Pressable
+                          key={c.id}
+                          onPress={() => { setReportsToId(c.id); setShowReportsToPicker(false); }}
+                          className={`px-3 py-2.5 ${c.id === reportsToId ? 'bg-stone-700' : ''}`}
+                        >
+                          <Text className="text-sm text-stone-100">{c.name}</Text>
+                        </Pressable>
+                      ))}
+                    </Dropdown>
+                  </View>
+                )}
+
+                {/* Plan mode (caporegimes) */}
+                {isCaporegimeForm && (
+                  <View>
+                    <Text className="mb-1 text-sm font-medium text-stone-300">Planning</Text>
+                    <Text className="mb-2 text-xs text-stone-500">
+                      {PLAN_MODE_DESCRIPTIONS[planMode]}
+                    </Text>
+                    <View className="flex-row gap-2">
+                      {PLAN_MODES.map((mode) => (
+                        <Pressable
+                          key={mode}
+                          onPress={() => setPlanMode(mode)}
+                          className={`flex-1 items-center rounded-lg border px-3 py-2 ${
+                            planMode === mode ? 'border-gold-600 bg-stone-700' : 'border-stone-700 bg-stone-800'
+                          }`}
+                        >
+                          <Text className="text-sm text-stone-100">{PLAN_MODE_LABELS[mode]}</Text>
+                        </Pressable>
+                      ))}
+                    </View>
+                  </View>
+                )}
+
                 {/* System Prompt */}
                 {needsModel && (
                   <View>
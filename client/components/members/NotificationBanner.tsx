@@ -0,0 +1,26 @@
+import { View, Text, Pressable } from 'react-native';
+import { Bell, X } from 'lucide-react-native';
+import { useNotifications } from '../../hooks/useNotifications';
+
+export function NotificationBanner() {
+  const { notifications, dismiss } = useNotifications();
+
+  if (notifications.length === 0) return null;
+
+  return (
+    <View className="mb-6 gap-2">
+      {notifications.map((n) => (
+        <View key={n.id} className="flex-row items-start gap-3 rounded-lg border border-gold-600/30 bg-gold-600/10 p-3">
+          <Bell size={14} color="#f59e0b" />
+          <View className="min-w-0 flex-1">
+            <Text className="text-sm font-medium text-stone-100">{n.title}</Text>
+            <Text className="mt-0.5 text-xs text-stone-400">{n.body}</Text>
+          </View>
+          <Pressable onPress={() => dismiss(n.id).catch(() => {})} hitSlop={8}>
+            <X size={14} color="#78716c" />
+          </Pressable>
+        </View>
+      ))}
+    </View>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { View, Text, Pressable, ScrollView } from 'react-native';
+import { View, Text, Pressable, ScrollView, TextInput } from 'react-native';
 import { Plus, UserPlus, X } from 'lucide-react-native';
 import { useAuth } from '../../contexts/AuthContext';
 import { UserAvatar } from '../common/UserAvatar';
@@ -18,6 +18,8 @@ interface MemberListProps {
   onAddUser?: (userId: string) => Promise<void>;
   onRemoveParticipant?: (participantId: string) => Promise<void>;
   onToggleAdmin?: (userId: string) => Promise<void>;
+  contextTokenBudget?: number | null;
+  onSetContextBudget?: (budget: number | null) => Promise<void>;
   onLeave?: () => Promise<void>;
 }
 
@@ -32,11 +34,15 @@ export function MemberList({
   onAddUser,
   onRemoveParticipant,
   onToggleAdmin,
+  contextTokenBudget,
+  onSetContextBudget,
   onLeave,
 }: MemberListProps) {
   const { user } = useAuth();
   const [adding, setAdding] = useState(false);
   const [togglingAdmin, setTogglingAdmin] = useState<string | null>(null);
+  const [budgetDraft, setBudgetDraft] = useState(contextTokenBudget ? String(contextTokenBudget) : '');
+  const [savingBudget, setSavingBudget] = useState(false);
 
   const callerIsAdmin = isCommission && participants.find((p) => p.user_id === user?.id)?.is_admin;
 
@@ -125,6 +131,20 @@ export function MemberList({
     }
   }
 
+  async function handleSaveBudget(budget: number | null) {
+    if (!onSetContextBudget) return;
+    setSavingBudget(true);
+    try {
+      await onSetContextBudget(budget);
+      setBudgetDraft(budget ? String(budget) : '');
+    } finally {
+      setSavingBudget(false);
+    }
+  }
+
+  const draftBudget = budgetDraft.trim() ? Number(budgetDraft.replace(/[,_\s]/g, '')) : null;
+  const draftValid = draftBudget === null || (Number.isInteger(draftBudget) && draftBudget >= 1000 && draftBudget <= 1000000);
+
   return (
     <ScrollView className="flex-1" keyboardShouldPersistTaps="handled">
       <View className="gap-3 p-1">
@@ -384,6 +404,45 @@ export function MemberList({
           );
         })()}
 
+        {/* Context budget: how much history members are given */}
+        {(onSetContextBudget || contextTokenBudget) && (
+          <View>
+            <Text className="text-xs font-semibold uppercase tracking-wider text-stone-500 mb-1.5 px-1">
+              Context
+            </Text>
+            {onSetContextBudget ? (
+              <View className="gap-1 px-1">
+                <View className="flex-row items-center gap-2">
+                  <TextInput
+                    value={budgetDraft}
+                    onChangeText={setBudgetDraft}
+                    placeholder="Follow each member's model"
+                    placeholderTextColor="#57534e"
+                    keyboardType="number-pad"
+                    className={`flex-1 rounded-md border px-2 py-1 text-xs text-stone-200 ${draftValid ? 'border-stone-700' : 'border-red-800'}`}
+                  />
+                  <Pressable
+                    onPress={() => handleSaveBudget(draftBudget)}
+                    disabled={savingBudget || !draftValid || draftBudget === (contextTokenBudget ?? null)}
+                    className={`rounded-md bg-stone-800 px-2 py-1 ${savingBudget || !draftValid || draftBudget === (contextTokenBudget ?? null) ? 'opacity-50' : ''}`}
+                  >
+                    <Text className="text-xs text-gold-600">Save</Text>
+                  </Pressable>
+                </View>
+                <Text className="text-[10px] text-stone-500">
+                  {draftValid
+                    ? 'Tokens of history members are given. Leave empty to size it by their model.'
+                    : 'Between 1,000 and 1,000,000 tokens.'}
+                </Text>
+              </View>
+            ) : (
+              <Text className="px-1 text-xs text-stone-400">
+                {contextTokenBudget!.toLocaleString()} tokens of history
+              </Text>
+            )}
+          </View>
+        )}
+
         {/* Invite Don to commission sit-down */}
         {isCommission && addableContacts && addableContacts.length > 0 && onAddUser && (
           <View>
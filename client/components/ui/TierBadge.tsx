@@ -0,0 +1,21 @@
+import { View, Text } from 'react-native';
+import { useTiers } from '../../hooks/useTiers';
+import type { UserTier } from '../../lib/types';
+
+// The reserved tiers keep their colors; custom tiers share a neutral badge
+const TIER_COLORS: Record<string, { badge: string; text: string }> = {
+  godfather: { badge: 'bg-gold-600', text: 'text-stone-950' },
+  boss: { badge: 'bg-stone-400', text: 'text-stone-950' },
+};
+const DEFAULT_COLORS = { badge: 'bg-stone-700', text: 'text-stone-300' };
+
+export function TierBadge({ tier, textSize = 'text-[10px]' }: { tier: UserTier; textSize?: string }) {
+  const { tierLabel } = useTiers();
+  const colors = TIER_COLORS[tier] ?? DEFAULT_COLORS;
+
+  return (
+    <View className={`rounded px-1.5 py-0.5 ${colors.badge}`}>
+      <Text className={`${textSize} font-semibold ${colors.text}`}>{tierLabel(tier)}</Text>
+    </View>
+  );
+}
@@ -1,4 +1,4 @@
-import type { Provider, MemberTemplate, MemberType, UserTier, SoldierType } from '../lib/types';
+import type { Provider, MemberTemplate, MemberType, ModelCapability, SoldierType } from '../lib/types';
 
 export const PROVIDERS: Provider[] = ['claude', 'openai', 'gemini', 'grok', 'openrouter'];
 
@@ -25,6 +25,24 @@ export const PROVIDER_LABELS: Record<Provider, string> = {
   openrouter: 'OpenRouter',
 };
 
+export const MODEL_CAPABILITIES: ModelCapability[] = ['tools', 'vision', 'web_search', 'long_context'];
+
+export const CAPABILITY_LABELS: Record<ModelCapability, string> = {
+  tools: 'Tools',
+  vision: 'Vision',
+  web_search: 'Web search',
+  long_context: 'Long context',
+};
+
+/** What each role's model must not lack (members-api enforces the same rules) */
+export function requiredCapability(memberType: MemberType, soldierType?: SoldierType): ModelCapability | null {
+  if (memberType === 'caporegime' || memberType === 'bookkeeper') return 'tools';
+  if (memberType === 'soldier' && soldierType === 'external') return 'tools';
+  if (memberType === 'soldier' && soldierType === 'reagent') return null;
+  if (memberType === 'soldier' || memberType === 'consul') return 'web_search';
+  return null;
+}
+
 export const PROVIDER_COLORS: Record<Provider, string> = {
   claude: 'bg-orange-600',
   openai: 'bg-emerald-600',
@@ -33,18 +51,6 @@ export const PROVIDER_COLORS: Record<Provider, string> = {
   openrouter: 'bg-violet-600',
 };
 
-export const TIER_LABELS: Record<UserTier, string> = {
-  godfather: 'Godfather',
-  boss: 'Boss',
-  associate: 'Associate',
-};
-
-export const TIER_COLORS: Record<UserTier, string> = {
-  godfather: 'bg-gold-600 text-stone-950',
-  boss: 'bg-stone-400 text-stone-950',
-  associate: 'bg-stone-700 text-stone-300',
-};
-
 export const MEMBER_TEMPLATES: MemberTemplate[] = [
   {
     name: 'Il Consigliere',
@@ -201,11 +207,13 @@ Style: Go beyond retrieval — analyze. "Looking at the records together, a patt
 export const SOLDIER_TYPE_LABELS: Record<SoldierType, string> = {
   default: 'Default',
   external: 'External API',
+  reagent: 'Reagent',
 };
 
 export const SOLDIER_TYPE_DESCRIPTIONS: Record<SoldierType, string> = {
   default: 'Single-shot LLM response with web search',
   external: 'Agentic — calls external APIs via web catalyst',
+  reagent: 'Deterministic — runs a CYFR reagent, no LLM',
 };
 
 export const SOLDIER_TEMPLATES: MemberTemplate[] = [
@@ -3,7 +3,7 @@ import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { cyfrCall } from '../lib/cyfr';
 import { getAccessToken } from '../lib/supabase';
 import { useAuth } from './AuthContext';
-import type { CommissionContact, SitDown, BackRoomSitDown } from '../lib/types';
+import type { CommissionContact, SitDown, BackRoomSitDown, SoldierLoan } from '../lib/types';
 
 const COMMISSION_API_REF = 'formula:local.commission-api:0.1.0';
 
@@ -13,6 +13,7 @@ export interface CommissionData {
   sentInvites: CommissionContact[];
   commissionSitDowns: SitDown[];
   backRoomSitDowns: BackRoomSitDown[];
+  soldierLoans: SoldierLoan[];
 }
 
 interface CommissionState extends CommissionData {
@@ -51,6 +52,7 @@ export function CommissionProvider({ children }: { children: ReactNode }) {
         sentInvites: (res?.sent_invites as CommissionContact[]) || [],
         commissionSitDowns: (res?.commission_sit_downs as SitDown[]) || [],
         backRoomSitDowns: (res?.back_room_sit_downs as BackRoomSitDown[]) || [],
+        soldierLoans: (res?.soldier_loans as SoldierLoan[]) || [],
       };
     },
     staleTime: 60_000,
@@ -93,6 +95,7 @@ export function CommissionProvider({ children }: { children: ReactNode }) {
     sentInvites: data?.sentInvites ?? [],
     commissionSitDowns: data?.commissionSitDowns ?? [],
     backRoomSitDowns: data?.backRoomSitDowns ?? [],
+    soldierLoans: data?.soldierLoans ?? [],
     loading: isLoading,
     refetch,
     markSitDownAsRead,
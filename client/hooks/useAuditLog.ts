@@ -0,0 +1,57 @@
+import { useInfiniteQuery } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import type { AuditLogEntry, AuditTargetType } from '../lib/types';
+
+const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
+
+interface AuditLogPage {
+  entries: AuditLogEntry[];
+  next_before_id: number | null;
+}
+
+async function adminCall(input: Record<string, unknown>) {
+  const accessToken = getAccessToken();
+  if (!accessToken) throw new Error('Not authenticated');
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: ADMIN_API_REF,
+    input: { ...input, access_token: accessToken },
+    type: 'formula',
+    timeout: 30000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return res;
+}
+
+/** Godfather-only: admin mutations, newest first, paged by id. */
+export function useAuditLog(targetType: AuditTargetType | null) {
+  const query = useInfiniteQuery<AuditLogPage, Error, { pages: AuditLogPage[] }, (string | null)[], number | null>({
+    queryKey: ['auditLog', targetType],
+    queryFn: async ({ pageParam }) => {
+      const res = await adminCall({
+        action: 'list_audit_log',
+        filters: { target_type: targetType ?? undefined, before_id: pageParam ?? undefined },
+      });
+      return {
+        entries: (res?.entries as AuditLogEntry[]) ?? [],
+        next_before_id: (res?.next_before_id as number | null) ?? null,
+      };
+    },
+    initialPageParam: null,
+    getNextPageParam: (lastPage) => lastPage.next_before_id ?? undefined,
+    enabled: !!getAccessToken(),
+    staleTime: 30_000,
+  });
+
+  return {
+    entries: query.data?.pages.flatMap((p) => p.entries) ?? [],
+    loading: query.isLoading,
+    hasMore: !!query.hasNextPage,
+    loadingMore: query.isFetchingNextPage,
+    loadMore: () => query.fetchNextPage(),
+  };
+}
@@ -1,12 +1,12 @@
 import { cyfrCall } from '../lib/cyfr';
 import { getAccessToken } from '../lib/supabase';
-import type { CommissionContact } from '../lib/types';
+import type { CommissionContact, SoldierLoan } from '../lib/types';
 import { useCommissionContext } from '../contexts/CommissionContext';
 
 const COMMISSION_API_REF = 'formula:local.commission-api:0.1.0';
 
 export function useCommission() {
-  const { contacts, pendingInvites, sentInvites, loading, refetch } = useCommissionContext();
+  const { contacts, pendingInvites, sentInvites, soldierLoans, loading, refetch } = useCommissionContext();
 
   async function inviteByEmail(email: string) {
     const accessToken = getAccessToken();
@@ -83,6 +83,76 @@ export function useCommission() {
     await refetch();
   }
 
+  async function lendSoldier(loan: {
+    soldierId: string;
+    borrowerId: string;
+    expiresAt: string;
+    caporegimeId?: string;
+    sitDownId?: string;
+  }) {
+    const accessToken = getAccessToken();
+    if (!accessToken) throw new Error('Not authenticated');
+
+    const result = await cyfrCall('execution', {
+      action: 'run',
+      reference: COMMISSION_API_REF,
+      input: {
+        action: 'lend_soldier',
+        access_token: accessToken,
+        soldier_id: loan.soldierId,
+        borrower_id: loan.borrowerId,
+        expires_at: loan.expiresAt,
+        caporegime_id: loan.caporegimeId ?? null,
+        sit_down_id: loan.sitDownId ?? null,
+      },
+      type: 'formula',
+      timeout: 30000,
+    });
+
+    const res = result as Record<string, unknown> | null;
+    if (res?.error) throw new Error((res.error as Record<string, string>).message);
+
+    await refetch();
+    return res?.loan as SoldierLoan;
+  }
+
+  async function respondToLoan(loanId: string, accept: boolean) {
+    const accessToken = getAccessToken();
+    if (!accessToken) throw new Error('Not authenticated');
+
+    const result = await cyfrCall('execution', {
+      action: 'run',
+      reference: COMMISSION_API_REF,
+      input: { action: 'respond_loan', access_token: accessToken, loan_id: loanId, accept },
+      type: 'formula',
+      timeout: 30000,
+    });
+
+    const res = result as Record<string, unknown> | null;
+    if (res?.error) throw new Error((res.error as Record<string, string>).message);
+
+    await refetch();
+    return res?.loan as SoldierLoan;
+  }
+
+  async function revokeLoan(loanId: string) {
+    const accessToken = getAccessToken();
+    if (!accessToken) throw new Error('Not authenticated');
+
+    const result = await cyfrCall('execution', {
+      action: 'run',
+      reference: COMMISSION_API_REF,
+      input: { action: 'revoke_loan', access_token: accessToken, loan_id: loanId },
+      type: 'formula',
+      timeout: 30000,
+    });
+
+    const res = result as Record<string, unknown> | null;
+    if (res?.error) throw new Error((res.error as Record<string, string>).message);
+
+    await refetch();
+  }
+
   return {
     contacts,
     pendingInvites,
@@ -92,6 +162,10 @@ export function useCommission() {
     acceptInvite,
     declineInvite,
     removeContact,
+    soldierLoans,
+    lendSoldier,
+    respondToLoan,
+    revokeLoan,
     refetch,
   };
 }
@@ -1,5 +1,9 @@
 import { useEffect, useMemo, useState } from 'react';
-import { onMemberProgress } from '../lib/realtime-hub';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import { onGlobalReconnect, onMemberProgress } from '../lib/realtime-hub';
+
+const SIT_DOWN_REF = 'formula:local.sit-down:0.1.0';
 
 export interface ProgressEvent {
   kind: string;
@@ -34,13 +38,45 @@ export interface MemberProgress {
 
 const STALE_TIMEOUT_MS = 120_000;
 
-// Key for the progress map: combines execution_id + member_id so that
+// Key for the progress map: combines the run (or execution) id + member_id so that
 // concurrent requests for the same member get separate entries, AND
 // multiple members in the same execution (e.g. @all) also get separate entries.
-function progressKey(data: { execution_id?: string; member_id?: string }): string {
-  const execId = data.execution_id as string;
+// run_id is set server-side, so live and replayed events for a response share a key.
+function progressKey(data: { run_id?: string; execution_id?: string; member_id?: string }): string {
+  const runId = (data.run_id || data.execution_id) as string;
   const memId = data.member_id as string;
-  return execId ? `${execId}:${memId}` : memId || '';
+  return runId ? `${runId}:${memId}` : memId || '';
+}
+
+interface PersistedEvent {
+  sequence: number;
+  payload: Record<string, unknown>;
+  created_at: string;
+}
+
+async function fetchEvents(
+  sitDownId: string,
+  sinceSequence: number | null,
+): Promise<{ events: PersistedEvent[]; hasMore: boolean }> {
+  const accessToken = getAccessToken();
+  if (!accessToken) return { events: [], hasMore: false };
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: SIT_DOWN_REF,
+    input: {
+      action: 'list_events',
+      access_token: accessToken,
+      sit_down_id: sitDownId,
+      ...(sinceSequence !== null ? { since_sequence: sinceSequence } : {}),
+    },
+    type: 'formula',
+    timeout: 30000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return { events: (res?.events as PersistedEvent[]) ?? [], hasMore: res?.has_more === true };
 }
 
 export function useMemberProgress(sitDownId: string | undefined) {
@@ -49,7 +85,19 @@ export function useMemberProgress(sitDownId: string | undefined) {
   useEffect(() => {
     if (!sitDownId) return;
 
-    const unsubscribe = onMemberProgress(sitDownId, (data) => {
+    let cancelled = false;
+    // Sequences already applied, live or replayed, so neither path double-counts
+    const seen = new Set<number>();
+    let lastSequence: number | null = null;
+
+    function applyEvent(data: Record<string, any>, now: number) {
+      const sequence = data.sequence as number | undefined;
+      if (typeof sequence === 'number') {
+        if (seen.has(sequence)) return;
+        seen.add(sequence);
+        if (lastSequence === null || sequence > lastSequence) lastSequence = sequence;
+      }
+
       const kind = data.kind as string;
       const memberId = data.member_id as string;
       const memberName = data.member_name as string;
@@ -102,7 +150,7 @@ export function useMemberProgress(sitDownId: string | undefined) {
             isError: false,
             toolsUsed: [],
             totalTokens: 0,
-            startedAt: Date.now(),
+            startedAt: now,
           };
           const next = new Map(prev);
           next.set(key, {
@@ -110,7 +158,7 @@ export function useMemberProgress(sitDownId: string | undefined) {
             completed: true,
             isError: true,
             statusText: errorMessage,
-            events: [...base.events, { kind, message: errorMessage, timestamp: Date.now() }],
+            events: [...base.events, { kind, message: errorMessage, timestamp: now }],
           });
           return next;
         });
@@ -126,8 +174,6 @@ export function useMemberProgress(sitDownId: string | undefined) {
         return;
       }
 
-      const now = Date.now();
-
       setEntries((prev) => {
         const next = new Map(prev);
         const existing = next.get(key) || {
@@ -142,7 +188,7 @@ export function useMemberProgress(sitDownId: string | undefined) {
           isError: false,
           toolsUsed: [],
           totalTokens: 0,
-          startedAt: Date.now(),
+          startedAt: now,
         };
 
         let updated = { ...existing };
@@ -198,10 +244,64 @@ export function useMemberProgress(sitDownId: string | undefined) {
         next.set(key, updated);
         return next;
       });
+    }
+
+    // Rebuild progress for responses still running when this view opened. Runs that
+    // already finished are skipped: their messages are in the list already.
+    async function replay() {
+      try {
+        const { events: rows } = await fetchEvents(sitDownId!, null);
+        if (cancelled) return;
+        const cutoff = Date.now() - STALE_TIMEOUT_MS;
+        const finished = new Set(
+          rows
+            .filter((r) => r.payload.kind === 'message_inserted' || r.payload.kind === 'error')
+            .map((r) => progressKey(r.payload)),
+        );
+        for (const row of rows) {
+          const at = new Date(row.created_at).getTime();
+          if (at < cutoff || finished.has(progressKey(row.payload))) {
+            seen.add(row.sequence);
+            continue;
+          }
+          applyEvent({ ...row.payload, sequence: row.sequence }, at);
+        }
+        if (rows.length > 0 && (lastSequence === null || rows[rows.length - 1].sequence > lastSequence)) {
+          lastSequence = rows[rows.length - 1].sequence;
+        }
+      } catch {
+        // Replay is best-effort; live events still arrive
+      }
+    }
+
+    // Catch up on anything broadcast while the realtime channel was down
+    async function catchUp() {
+      if (lastSequence === null) return replay();
+      try {
+        let hasMore = true;
+        while (hasMore && !cancelled) {
+          const page = await fetchEvents(sitDownId!, lastSequence);
+          if (cancelled) return;
+          for (const row of page.events) {
+            applyEvent({ ...row.payload, sequence: row.sequence }, new Date(row.created_at).getTime());
+          }
+          hasMore = page.hasMore && page.events.length > 0;
+        }
+      } catch {
+        // Best-effort, as above
+      }
+    }
+
+    const unsubscribe = onMemberProgress(sitDownId, (data) => applyEvent(data, Date.now()));
+    const unsubscribeReconnect = onGlobalReconnect(() => {
+      catchUp();
     });
+    replay();
 
     return () => {
+      cancelled = true;
       unsubscribe();
+      unsubscribeReconnect();
       setEntries(new Map());
     };
   }, [sitDownId]);
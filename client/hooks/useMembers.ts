@@ -1,7 +1,7 @@
 import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { cyfrCall } from '../lib/cyfr';
 import { getAccessToken } from '../lib/supabase';
-import type { Member, MemberType, SoldierType, SoldierConfig } from '../lib/types';
+import type { Member, MemberType, PlanMode, SoldierType, SoldierConfig } from '../lib/types';
 import { useAuth } from '../contexts/AuthContext';
 
 const MEMBERS_API_REF = 'formula:local.members-api:0.1.0';
@@ -41,10 +41,12 @@ export function useMembers(memberType?: MemberType) {
   async function createMember(member: {
     name: string;
     catalog_model_id?: string;
+    fallback_model_ids?: string[];
     system_prompt: string;
     avatar_url?: string;
     member_type?: MemberType;
-    caporegime_id?: string;
+    caporegime_id?: string | null;
+    plan_mode?: PlanMode;
     soldier_type?: SoldierType;
     soldier_config?: SoldierConfig;
   }) {
@@ -68,7 +70,7 @@ export function useMembers(memberType?: MemberType) {
     return created;
   }
 
-  async function updateMember(id: string, updates: Partial<Pick<Member, 'name' | 'catalog_model_id' | 'system_prompt' | 'avatar_url' | 'soldier_type' | 'soldier_config'>>) {
+  async function updateMember(id: string, updates: Partial<Pick<Member, 'name' | 'catalog_model_id' | 'fallback_model_ids' | 'system_prompt' | 'avatar_url' | 'soldier_type' | 'soldier_config' | 'plan_mode'>>) {
     const accessToken = getAccessToken();
     if (!accessToken) throw new Error('Not authenticated');
 
@@ -0,0 +1,151 @@
+import { useCallback } from 'react';
+import { cyfrCall, cyfrCallStream, CyfrError } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import { broadcastMemberProgress } from '../lib/realtime-hub';
+import type { SitDown } from '../lib/types';
+
+const SIT_DOWN_REF = 'formula:local.sit-down:0.1.0';
+
+// Actions that invoke members are streamed like send_message to relay progress
+function runStreamed(input: Record<string, unknown>): Promise<void> {
+  const executionId = `${Date.now().toString(36)}-${Math.random().toString(36).slice(2, 8)}`;
+
+  return new Promise<void>((resolve, reject) => {
+    cyfrCallStream(
+      'execution',
+      {
+        action: 'run',
+        reference: SIT_DOWN_REF,
+        input,
+        type: 'formula',
+        timeout: 600000,
+      },
+      {
+        onEmit: (data) => {
+          broadcastMemberProgress({ ...data, execution_id: executionId });
+        },
+        onComplete: (data) => {
+          if (data.status === 'error' || data.type === 'execution_failed') {
+            const errPayload = data.message ?? data.error;
+            const errMsg = typeof errPayload === 'string'
+              ? errPayload
+              : (errPayload as Record<string, string>)?.message ?? 'Execution failed';
+            reject(new CyfrError(-33100, errMsg));
+            return;
+          }
+          const res = (data.status === 'completed' && data.result ? data.result : data) as Record<string, unknown>;
+          if (res?.error) {
+            const err = res.error;
+            reject(new Error(typeof err === 'string' ? err : (err as Record<string, string>).message));
+            return;
+          }
+          resolve();
+        },
+        onError: (err) => reject(err),
+      },
+    ).catch(reject);
+  });
+}
+
+/**
+ * Edit, delete and regenerate messages, allowed for the sender (or the owner
+ * of the member that wrote it) and for sit-down admins. Any participant can
+ * fork the sit-down from a message.
+ */
+export function useMessageActions(sitDownId: string | undefined) {
+  const editMessage = useCallback(
+    async (messageId: string, content: string, rerunMentions = false): Promise<void> => {
+      if (!sitDownId) throw new Error('Missing context');
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      await runStreamed({
+        action: 'edit_message',
+        access_token: accessToken,
+        sit_down_id: sitDownId,
+        message_id: messageId,
+        content,
+        rerun_mentions: rerunMentions,
+      });
+    },
+    [sitDownId],
+  );
+
+  /** Ask the member for another answer, stored as a new version of the same message. */
+  const regenerate = useCallback(
+    async (messageId: string): Promise<void> => {
+      if (!sitDownId) throw new Error('Missing context');
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      await runStreamed({
+        action: 'regenerate',
+        access_token: accessToken,
+        sit_down_id: sitDownId,
+        message_id: messageId,
+      });
+    },
+    [sitDownId],
+  );
+
+  const runAction = useCallback(
+    async (input: Record<string, unknown>): Promise<void> => {
+      if (!sitDownId) throw new Error('Missing context');
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      const result = await cyfrCall('execution', {
+        action: 'run',
+        reference: SIT_DOWN_REF,
+        input: { ...input, access_token: accessToken, sit_down_id: sitDownId },
+        type: 'formula',
+        timeout: 30000,
+      });
+
+      const res = result as Record<string, unknown> | null;
+      if (res?.error) throw new Error((res.error as Record<string, string>).message);
+    },
+    [sitDownId],
+  );
+
+  const deleteMessage = useCallback(
+    (messageId: string) => runAction({ action: 'delete_message', message_id: messageId }),
+    [runAction],
+  );
+
+  /** Make one of a regenerated message's versions the one members see from now on. */
+  const selectVersion = useCallback(
+    (messageId: string, version: number) => runAction({ action: 'select_version', message_id: messageId, version }),
+    [runAction],
+  );
+
+  /** Start a new sit-down with the same participants and the history up to this message. */
+  const forkSitDown = useCallback(
+    async (messageId: string, name?: string): Promise<SitDown> => {
+      if (!sitDownId) throw new Error('Missing context');
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      const result = await cyfrCall('execution', {
+        action: 'run',
+        reference: SIT_DOWN_REF,
+        input: {
+          action: 'fork_sit_down',
+          access_token: accessToken,
+          sit_down_id: sitDownId,
+          message_id: messageId,
+          ...(name && { name }),
+        },
+        type: 'formula',
+        timeout: 30000,
+      });
+
+      const res = result as Record<string, unknown> | null;
+      if (res?.error) throw new Error((res.error as Record<string, string>).message);
+      return res?.sit_down as SitDown;
+    },
+    [sitDownId],
+  );
+
+  return { editMessage, deleteMessage, regenerate, selectVersion, forkSitDown };
+}
@@ -1,12 +1,20 @@
-import { useQuery } from '@tanstack/react-query';
+import { useState } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
 import { cyfrCall } from '../lib/cyfr';
-import type { Provider } from '../lib/types';
+import type { ListedModel, ModelCapabilities, Provider } from '../lib/types';
 import { PROVIDERS } from '../config/constants';
 
 interface ModelsState {
   models: Record<Provider, string[]>;
+  /** Normalized listing per provider and model id (context window, pricing, ...) */
+  details: Partial<Record<Provider, Record<string, ListedModel>>>;
+  /** What each provider's listing reveals per model id, for pre-filling catalog entries */
+  capabilities: Partial<Record<Provider, Record<string, ModelCapabilities>>>;
   availableProviders: Provider[];
   loading: boolean;
+  /** Bypass list-models' cache and fetch every provider live */
+  refresh: () => Promise<void>;
+  refreshing: boolean;
   error: string | null;
 }
 
@@ -20,62 +28,70 @@ const EMPTY_MODELS: Record<Provider, string[]> = {
 
 function normalizeModels(result: Record<string, unknown>): {
   models: Record<Provider, string[]>;
+  details: Partial<Record<Provider, Record<string, ListedModel>>>;
+  capabilities: Partial<Record<Provider, Record<string, ModelCapabilities>>>;
   availableProviders: Provider[];
 } {
   const errors = (result.errors ?? {}) as Record<string, unknown>;
   const modelsMap = (result.models ?? {}) as Record<string, unknown>;
 
   const models: Record<Provider, string[]> = { ...EMPTY_MODELS };
+  const details: Partial<Record<Provider, Record<string, ListedModel>>> = {};
+  const capabilities: Partial<Record<Provider, Record<string, ModelCapabilities>>> = {};
   const available: Provider[] = [];
 
   for (const p of PROVIDERS) {
     if (errors[p]) continue;
 
-    const providerData = modelsMap[p] as Record<string, unknown> | undefined;
-    if (!providerData) continue;
+    const listed = modelsMap[p] as ListedModel[] | undefined;
+    if (!listed || listed.length === 0) continue;
 
-    let ids: string[] = [];
-
-    if (p === 'gemini') {
-      const geminiModels = providerData.models as Array<{ name: string }> | undefined;
-      if (geminiModels) {
-        ids = geminiModels.map((m) => m.name.replace(/^models\//, ''));
-      }
-    } else {
-      const data = providerData.data as Array<{ id: string }> | undefined;
-      if (data) {
-        ids = data.map((m) => m.id);
-      }
-    }
-
-    if (ids.length > 0) {
-      models[p] = ids.sort((a, b) => a.localeCompare(b));
-      available.push(p);
-    }
+    // list-models already sorts by id
+    models[p] = listed.map((m) => m.id);
+    details[p] = Object.fromEntries(listed.map((m) => [m.id, m]));
+    capabilities[p] = Object.fromEntries(listed.map((m) => [m.id, m.capabilities ?? {}]));
+    available.push(p);
   }
 
-  return { models, availableProviders: available };
+  return { models, details, capabilities, availableProviders: available };
+}
+
+async function fetchModels(refresh: boolean) {
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: 'formula:local.list-models:0.5.0',
+    input: refresh ? { refresh: true } : {},
+    type: 'formula',
+  });
+  return normalizeModels(result as Record<string, unknown>);
 }
 
 export function useModels(): ModelsState {
+  const queryClient = useQueryClient();
+  const [refreshing, setRefreshing] = useState(false);
   const { data, isLoading, error } = useQuery({
     queryKey: ['models'],
-    queryFn: async () => {
-      const result = await cyfrCall('execution', {
-        action: 'run',
-        reference: 'formula:local.list-models:0.5.0',
-        input: {},
-        type: 'formula',
-      });
-      return normalizeModels(result as Record<string, unknown>);
-    },
+    queryFn: () => fetchModels(false),
     staleTime: Infinity,
   });
 
+  async function refresh() {
+    setRefreshing(true);
+    try {
+      queryClient.setQueryData(['models'], await fetchModels(true));
+    } finally {
+      setRefreshing(false);
+    }
+  }
+
   return {
     models: data?.models ?? EMPTY_MODELS,
+    details: data?.details ?? {},
+    capabilities: data?.capabilities ?? {},
     availableProviders: data?.availableProviders ?? [],
     loading: isLoading,
+    refresh,
+    refreshing,
     error: error ? 'Failed to load models from providers' : null,
   };
 }
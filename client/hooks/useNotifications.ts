@@ -0,0 +1,47 @@
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import type { AppNotification } from '../lib/types';
+import { useAuth } from '../contexts/AuthContext';
+
+const MEMBERS_API_REF = 'formula:local.members-api:0.1.0';
+
+async function membersCall(input: Record<string, unknown>) {
+  const accessToken = getAccessToken();
+  if (!accessToken) throw new Error('Not authenticated');
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: MEMBERS_API_REF,
+    input: { ...input, access_token: accessToken },
+    type: 'formula',
+    timeout: 30000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return res;
+}
+
+/** Unread notifications for the signed-in Don, e.g. members moved to another model. */
+export function useNotifications() {
+  const { user } = useAuth();
+  const queryClient = useQueryClient();
+
+  const { data: notifications = [] } = useQuery<AppNotification[]>({
+    queryKey: ['notifications'],
+    queryFn: async () => {
+      const res = await membersCall({ action: 'list_notifications' });
+      return (res?.notifications as AppNotification[]) || [];
+    },
+    staleTime: 60_000,
+    enabled: !!user,
+  });
+
+  async function dismiss(notificationId: string) {
+    queryClient.setQueryData<AppNotification[]>(['notifications'], (old) => old?.filter((n) => n.id !== notificationId));
+    await membersCall({ action: 'dismiss_notification', notification_id: notificationId });
+  }
+
+  return { notifications, dismiss };
+}
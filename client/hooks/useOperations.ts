@@ -1,10 +1,13 @@
-import { useEffect } from 'react';
+import { useCallback, useEffect } from 'react';
 import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
 import { getAccessToken } from '../lib/supabase';
 import { getSupabase } from '../lib/realtime';
 import type { Operation, BookkeeperEntry } from '../lib/types';
 import { useAuth } from '../contexts/AuthContext';
 
+const SIT_DOWN_REF = 'formula:local.sit-down:0.1.0';
+
 export function useOperations() {
   const { user } = useAuth();
   const queryClient = useQueryClient();
@@ -75,8 +78,27 @@ export function useOperations() {
     };
   }, [user, queryClient]);
 
+  // OTLP/JSON for the operation and its sub-operations, for Jaeger or another trace viewer
+  const exportTrace = useCallback(async (operationId: string): Promise<string> => {
+    const accessToken = getAccessToken();
+    if (!accessToken) throw new Error('Not authenticated');
+
+    const result = await cyfrCall('execution', {
+      action: 'run',
+      reference: SIT_DOWN_REF,
+      input: { action: 'export_trace', access_token: accessToken, operation_id: operationId },
+      type: 'formula',
+      timeout: 30000,
+    });
+
+    const res = result as Record<string, unknown> | null;
+    if (res?.error) throw new Error((res.error as Record<string, string>).message);
+    return JSON.stringify(res, null, 2);
+  }, []);
+
   return {
     operations,
+    exportTrace,
     bookkeeperEntries,
     loading: loadingOps || loadingEntries,
     loadingOps,
@@ -0,0 +1,89 @@
+import { useCallback } from 'react';
+import { cyfrCall, cyfrCallStream, CyfrError } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import { broadcastMemberProgress } from '../lib/realtime-hub';
+
+const SIT_DOWN_REF = 'formula:local.sit-down:0.1.0';
+
+/** Approve/reject a caporegime's plan and save plans as reusable jobs. */
+export function usePlanActions(sitDownId: string | undefined) {
+  // Approving resumes the whole operation, so stream it like send_message to relay progress
+  const reviewPlan = useCallback(
+    async (operationId: string, approved: boolean): Promise<void> => {
+      if (!sitDownId) throw new Error('Missing context');
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      const executionId = `${Date.now().toString(36)}-${Math.random().toString(36).slice(2, 8)}`;
+
+      await new Promise<void>((resolve, reject) => {
+        cyfrCallStream(
+          'execution',
+          {
+            action: 'run',
+            reference: SIT_DOWN_REF,
+            input: {
+              action: 'review_plan',
+              access_token: accessToken,
+              sit_down_id: sitDownId,
+              operation_id: operationId,
+              approved,
+            },
+            type: 'formula',
+            timeout: 600000,
+          },
+          {
+            onEmit: (data) => {
+              broadcastMemberProgress({ ...data, execution_id: executionId });
+            },
+            onComplete: (data) => {
+              if (data.status === 'error' || data.type === 'execution_failed') {
+                const errPayload = data.message ?? data.error;
+                const errMsg = typeof errPayload === 'string'
+                  ? errPayload
+                  : (errPayload as Record<string, string>)?.message ?? 'Execution failed';
+                reject(new CyfrError(-33100, errMsg));
+                return;
+              }
+              const res = (data.status === 'completed' && data.result ? data.result : data) as Record<string, unknown>;
+              if (res?.error) {
+                reject(new Error((res.error as Record<string, string>).message));
+                return;
+              }
+              resolve();
+            },
+            onError: (err) => reject(err),
+          },
+        ).catch(reject);
+      });
+    },
+    [sitDownId],
+  );
+
+  const savePlanAsJob = useCallback(
+    async (operationId: string, name?: string): Promise<{ jobName: string; skippedSteps: string[] }> => {
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      const result = await cyfrCall('execution', {
+        action: 'run',
+        reference: SIT_DOWN_REF,
+        input: { action: 'save_plan_as_job', access_token: accessToken, operation_id: operationId, ...(name && { name }) },
+        type: 'formula',
+        timeout: 60000,
+      });
+
+      const res = result as Record<string, unknown> | null;
+      if (res?.error) throw new Error((res.error as Record<string, string>).message);
+
+      const job = res?.job as Record<string, unknown> | undefined;
+      return {
+        jobName: (job?.name as string) ?? 'Job',
+        skippedSteps: (res?.skipped_steps as string[]) ?? [],
+      };
+    },
+    [],
+  );
+
+  return { reviewPlan, savePlanAsJob };
+}
@@ -0,0 +1,58 @@
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import type { QuotaPeriod, UsageQuota, UserTier } from '../lib/types';
+
+const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
+
+export interface QuotaInput {
+  tier?: UserTier;
+  user_id?: string;
+  period: QuotaPeriod;
+  max_tokens: number | null;
+  max_cost_usd: number | null;
+}
+
+async function adminCall(input: Record<string, unknown>) {
+  const accessToken = getAccessToken();
+  if (!accessToken) throw new Error('Not authenticated');
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: ADMIN_API_REF,
+    input: { ...input, access_token: accessToken },
+    type: 'formula',
+    timeout: 30000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return res;
+}
+
+/** Godfather-only: tier quotas and per-user overrides. */
+export function useQuotas() {
+  const queryClient = useQueryClient();
+
+  const { data: quotas = [], isLoading: loading } = useQuery<UsageQuota[]>({
+    queryKey: ['usageQuotas'],
+    queryFn: async () => {
+      if (!getAccessToken()) return [];
+      const res = await adminCall({ action: 'quota_list' });
+      return (res?.quotas as UsageQuota[]) || [];
+    },
+    staleTime: 60_000,
+  });
+
+  async function setQuota(quota: QuotaInput) {
+    await adminCall({ action: 'quota_set', quota });
+    await queryClient.invalidateQueries({ queryKey: ['usageQuotas'] });
+  }
+
+  async function deleteQuota(quotaId: string) {
+    await adminCall({ action: 'quota_delete', quota_id: quotaId });
+    queryClient.setQueryData<UsageQuota[]>(['usageQuotas'], (old) => old?.filter((q) => q.id !== quotaId));
+  }
+
+  return { quotas, loading, setQuota, deleteQuota };
+}
@@ -27,8 +27,9 @@ export function useSendMessage(sitDownId: string | undefined) {
       // Read cached sit-down data to skip redundant server-side DB fetches
       const cachedData = queryClient.getQueryData<{ participants: unknown[]; messages: unknown[] }>(['sitDown', 'enter', sitDownId]);
 
-      // Only send the most recent messages to avoid bloating the payload
-      const MAX_CONTEXT_MESSAGES = 25;
+      // Only send the most recent messages to avoid bloating the payload;
+      // the server trims them further to each member's token budget
+      const MAX_CONTEXT_MESSAGES = 200;
       const recentMessages = cachedData?.messages
         ? cachedData.messages.slice(-MAX_CONTEXT_MESSAGES)
         : undefined;
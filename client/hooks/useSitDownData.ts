@@ -231,6 +231,22 @@ export function useSitDownData(sitDownId: string | undefined) {
           });
         },
       )
+      // --- messages UPDATE (filtered) — metadata changes such as plan progress ---
+      .on(
+        'postgres_changes',
+        { event: 'UPDATE', schema: 'public', table: 'messages', filter: `sit_down_id=eq.${sitDownId}` },
+        (payload) => {
+          const updated = payload.new as Message;
+          queryClient.setQueryData<EnterSitDownData>(['sitDown', 'enter', sitDownId], (old) => {
+            if (!old) return old;
+            return {
+              ...old,
+              // Keep the hydrated sender info; only row columns change
+              messages: old.messages.map((m) => (m.id === updated.id ? { ...m, ...updated, profile: m.profile, member: m.member } : m)),
+            };
+          });
+        },
+      )
       // --- participants DELETE (filtered) ---
       .on(
         'postgres_changes',
@@ -364,6 +380,29 @@ export function useSitDownData(sitDownId: string | undefined) {
     await refreshParticipants();
   }
 
+  /** Override the tokens of history members get here, or pass null to follow their models. */
+  async function setContextBudget(budget: number | null) {
+    if (!sitDownId) throw new Error('Missing context');
+    const accessToken = getAccessToken();
+    if (!accessToken) throw new Error('Not authenticated');
+
+    const result = await cyfrCall('execution', {
+      action: 'run',
+      reference: SIT_DOWN_REF,
+      input: { action: 'set_context_budget', access_token: accessToken, sit_down_id: sitDownId, context_token_budget: budget },
+      type: 'formula',
+      timeout: 30000,
+    });
+
+    const res = result as Record<string, unknown> | null;
+    if (res?.error) throw new Error((res.error as Record<string, string>).message);
+
+    queryClient.setQueryData<EnterSitDownData>(['sitDown', 'enter', sitDownId], (old) => {
+      if (!old?.sit_down) return old;
+      return { ...old, sit_down: { ...old.sit_down, context_token_budget: budget } };
+    });
+  }
+
   async function leaveSitDown() {
     if (!sitDownId) throw new Error('Missing context');
     const accessToken = getAccessToken();
@@ -498,6 +537,7 @@ export function useSitDownData(sitDownId: string | undefined) {
     removeParticipant,
     leaveSitDown,
     toggleAdmin,
+    setContextBudget,
     // Pagination
     loadOlderMessages,
     canLoadMore,
@@ -0,0 +1,47 @@
+import { useMemo } from 'react';
+import { useQuery } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import type { Message } from '../lib/types';
+
+const SIT_DOWN_REF = 'formula:local.sit-down:0.1.0';
+
+/**
+ * A thread's root and replies, oldest first. History comes from `list_thread`;
+ * replies that arrive afterwards are picked up from the live sit-down messages.
+ */
+export function useThread(sitDownId: string | undefined, rootId: string | null, liveMessages: Message[]) {
+  const { data, isLoading, error } = useQuery<Message[]>({
+    queryKey: ['sitDown', 'thread', sitDownId, rootId],
+    queryFn: async () => {
+      const accessToken = getAccessToken();
+      if (!accessToken) throw new Error('Not authenticated');
+
+      const result = await cyfrCall('execution', {
+        action: 'run',
+        reference: SIT_DOWN_REF,
+        input: { action: 'list_thread', access_token: accessToken, sit_down_id: sitDownId, message_id: rootId },
+        type: 'formula',
+        timeout: 30000,
+      });
+
+      const res = result as Record<string, unknown> | null;
+      if (res?.error) throw new Error((res.error as Record<string, string>).message);
+      return (res?.messages as Message[]) ?? [];
+    },
+    enabled: !!sitDownId && !!rootId,
+  });
+
+  const messages = useMemo(() => {
+    if (!rootId) return [];
+    const byId = new Map<string, Message>();
+    for (const m of data ?? []) byId.set(m.id, m);
+    // Live copies win: they carry edits, deletes and new reply counts
+    for (const m of liveMessages) {
+      if (m.id === rootId || m.thread_id === rootId) byId.set(m.id, m);
+    }
+    return [...byId.values()].sort((a, b) => a.created_at.localeCompare(b.created_at));
+  }, [data, liveMessages, rootId]);
+
+  return { messages, loading: isLoading, error: error as Error | null };
+}
@@ -0,0 +1,74 @@
+import { useCallback } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import type { Tier, UserTier } from '../lib/types';
+
+const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
+
+/** Tiers that can be edited but never deleted */
+export const RESERVED_TIERS: UserTier[] = ['godfather', 'associate'];
+
+export type TierInput = Partial<Omit<Tier, 'created_at' | 'updated_at'>>;
+
+async function adminCall(input: Record<string, unknown>) {
+  const accessToken = getAccessToken();
+  if (!accessToken) throw new Error('Not authenticated');
+
+  const result = await cyfrCall('execution', {
+    action: 'run',
+    reference: ADMIN_API_REF,
+    input: { ...input, access_token: accessToken },
+    type: 'formula',
+    timeout: 30000,
+  });
+
+  const res = result as Record<string, unknown> | null;
+  if (res?.error) throw new Error((res.error as Record<string, string>).message);
+  return res;
+}
+
+/** Configured tiers, highest rank first. Management calls are Godfather-only. */
+export function useTiers() {
+  const queryClient = useQueryClient();
+
+  const { data: tiers = [], isLoading: loading } = useQuery<Tier[]>({
+    queryKey: ['tiers'],
+    queryFn: async () => {
+      if (!getAccessToken()) return [];
+      const res = await adminCall({ action: 'tier_list' });
+      return (res?.tiers as Tier[]) || [];
+    },
+    staleTime: 5 * 60_000,
+  });
+
+  const tierLabel = useCallback(
+    (key: UserTier) => tiers.find((t) => t.key === key)?.display_name ?? key.charAt(0).toUpperCase() + key.slice(1),
+    [tiers],
+  );
+
+  /** How a catalog model's min_tier reads: "Associate (everyone)" for the lowest tier, else "Boss+" */
+  const minTierLabel = useCallback(
+    (key: UserTier) => (tiers.length > 0 && tiers[tiers.length - 1].key === key ? `${tierLabel(key)} (everyone)` : `${tierLabel(key)}+`),
+    [tiers, tierLabel],
+  );
+
+  const invalidate = () => queryClient.invalidateQueries({ queryKey: ['tiers'] });
+
+  async function createTier(tier: TierInput) {
+    await adminCall({ action: 'tier_create', tier });
+    await invalidate();
+  }
+
+  async function updateTier(key: UserTier, updates: TierInput) {
+    await adminCall({ action: 'tier_update', tier_key: key, tier_updates: updates });
+    await invalidate();
+  }
+
+  async function deleteTier(key: UserTier) {
+    await adminCall({ action: 'tier_delete', tier_key: key });
+    await invalidate();
+  }
+
+  return { tiers, loading, tierLabel, minTierLabel, createTier, updateTier, deleteTier };
+}
@@ -0,0 +1,33 @@
+import { useQuery } from '@tanstack/react-query';
+import { cyfrCall } from '../lib/cyfr';
+import { getAccessToken } from '../lib/supabase';
+import type { UsageGroupBy, UsageReportRow } from '../lib/types';
+
+const ADMIN_API_REF = 'formula:local.admin-api:0.1.0';
+
+/** Godfather-only token usage totals over the last `days`. */
+export function useUsageReport(groupBy: UsageGroupBy, days: number) {
+  const { data: rows = [], isLoading: loading, error } = useQuery<UsageReportRow[]>({
+    queryKey: ['usageReport', groupBy, days],
+    queryFn: async () => {
+      const accessToken = getAccessToken();
+      if (!accessToken) return [];
+
+      const result = await cyfrCall('execution', {
+        action: 'run',
+        reference: ADMIN_API_REF,
+        input: { action: 'usage_report', access_token: accessToken, group_by: groupBy, days },
+        type: 'formula',
+        timeout: 30000,
+      });
+
+      const res = result as Record<string, unknown> | null;
+      if (res?.error) throw new Error((res.error as Record<string, string>).message);
+
+      return (res?.rows as UsageReportRow[]) || [];
+    },
+    staleTime: 60_000,
+  });
+
+  return { rows, loading, error };
+}
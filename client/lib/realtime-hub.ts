@@ -294,10 +294,21 @@ export function broadcastMemberProgress(payload: Record<string, unknown>) {
   });
 }
 
+// Broadcasts sent while the channel was down are lost; listeners catch up from persisted state
+const reconnectListeners = new Set<() => void>();
+
+export function onGlobalReconnect(handler: () => void): () => void {
+  reconnectListeners.add(handler);
+  return () => {
+    reconnectListeners.delete(handler);
+  };
+}
+
 export function reconnectGlobalChannel(userId: string, queryClient: QueryClient) {
   // Tear down and re-establish the global channel to ensure a fresh subscription
   stopGlobalChannel();
   startGlobalChannel(userId, queryClient);
+  reconnectListeners.forEach((fn) => fn());
 }
 
 export function stopGlobalChannel() {
@@ -1,17 +1,166 @@
-export type UserTier = 'godfather' | 'boss' | 'associate';
+/** A tier key from the `tiers` table. `godfather` (admin) and `associate` (signup default) always exist */
+export type UserTier = string;
+
+export interface Tier {
+  key: UserTier;
+  display_name: string;
+  /** Higher outranks lower; a catalog model's min_tier is met by any tier of equal or higher rank */
+  rank: number;
+  /** Null limits are unlimited */
+  max_members: number | null;
+  max_caporegimes: number | null;
+  /** Null allows every provider */
+  allowed_providers: Provider[] | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export type ModelCapability = 'tools' | 'vision' | 'web_search' | 'long_context';
+
+/** true/false per capability; a missing key is unknown and treated as supported */
+export type ModelCapabilities = Partial<Record<ModelCapability, boolean>>;
+
+/** One model from list-models, normalized across providers; null means the listing doesn't say */
+export interface ListedModel {
+  id: string;
+  display_name: string;
+  context_window: number | null;
+  max_output: number | null;
+  input_modalities: string[] | null;
+  output_modalities: string[] | null;
+  tools: boolean | null;
+  /** USD per million tokens */
+  pricing: { input_per_million: number; output_per_million: number } | null;
+  capabilities: ModelCapabilities;
+}
 
 export interface CatalogModel {
   id: string;
   provider: Provider;
   alias: string;
   model: string;
-  min_tier: 'boss' | 'associate';
+  min_tier: UserTier;
   is_active: boolean;
   sort_order: number;
+  /** USD per million tokens; null means unpriced (costs show as unknown) */
+  input_price_per_million: number | null;
+  output_price_per_million: number | null;
+  capabilities: ModelCapabilities;
+  /** Weighted alias: each response is answered by one of these entries instead of `model` */
+  targets: CatalogTarget[];
+  /** Keep each member on the same target instead of drawing per response */
+  sticky_routing: boolean;
   added_by: string;
   created_at: string;
 }
 
+export interface CatalogTarget {
+  target_catalog_id: string;
+  weight: number;
+}
+
+/** Recorded in message metadata when a weighted alias picked the model that answered */
+export interface CatalogRouting {
+  catalog_id: string;
+  alias: string;
+  target_catalog_id: string;
+  target_alias: string;
+  sticky: boolean;
+}
+
+export type UsageGroupBy = 'don' | 'member' | 'model' | 'day';
+
+/** One group in admin-api's usage_report (`key` is a user id, member id, provider/model or YYYY-MM-DD) */
+export interface UsageReportRow {
+  key: string;
+  label: string | null;
+  responses: number;
+  input_tokens: number;
+  output_tokens: number;
+  /** Estimated USD; null when none of the group's responses were on a priced model */
+  cost_usd: number | null;
+  unpriced_responses: number;
+}
+
+export type QuotaPeriod = 'day' | 'month';
+
+/** A tier's quota (`tier` set) or one user's override (`user_id` set) for a period */
+export interface UsageQuota {
+  id: string;
+  tier: UserTier | null;
+  user_id: string | null;
+  period: QuotaPeriod;
+  max_tokens: number | null;
+  max_cost_usd: number | null;
+  updated_at: string;
+  profile?: Pick<Profile, 'id' | 'display_name' | 'tier'> | null;
+}
+
+export interface AppNotification {
+  id: string;
+  kind: string;
+  title: string;
+  body: string;
+  data: Record<string, unknown>;
+  created_at: string;
+}
+
+export interface CatalogMigrationMember {
+  id: string;
+  name: string;
+  member_type: MemberType;
+  owner_id: string;
+  owner_name: string;
+  owner_tier: UserTier;
+  uses_primary: boolean;
+  uses_fallback: boolean;
+  skipped: boolean;
+  skip_reason: 'capabilities' | 'tier' | null;
+  missing_capabilities: string[];
+}
+
+export interface CatalogMigrationReport {
+  dry_run: boolean;
+  from: Pick<CatalogModel, 'id' | 'alias' | 'provider' | 'model'>;
+  to: Pick<CatalogModel, 'id' | 'alias' | 'provider' | 'model'>;
+  members: CatalogMigrationMember[];
+  migrated_count: number;
+  skipped_count: number;
+  notified_dons: number;
+}
+
+/** One catalog entry in admin-api's catalog_sync_report */
+export interface CatalogSyncEntry extends Pick<CatalogModel, 'id' | 'provider' | 'alias' | 'model' | 'is_active'> {
+  /** `unchecked` when the provider's model list could not be fetched */
+  status: 'listed' | 'vanished' | 'unchecked';
+  /** Newer models of the same family, newest first; `catalog_id` is set when already in the catalog */
+  suggestions: { model: string; catalog_id: string | null }[];
+  deactivated: boolean;
+}
+
+export interface CatalogSyncReport {
+  entries: CatalogSyncEntry[];
+  providers: Partial<Record<Provider, { listed_count: number; uncatalogued_count: number }>>;
+  unchecked_providers: Partial<Record<Provider, string>>;
+  vanished_count: number;
+  suggestion_count: number;
+  deactivated_count: number;
+}
+
+export type AuditTargetType = 'profile' | 'catalog' | 'tier' | 'quota';
+
+export interface AuditLogEntry {
+  id: number;
+  actor_id: string | null;
+  actor_name: string | null;
+  action: string;
+  target_type: AuditTargetType;
+  target_id: string;
+  before: Record<string, unknown> | null;
+  after: Record<string, unknown> | null;
+  created_at: string;
+}
+
 export interface Profile {
   id: string;
   display_name: string;
@@ -22,7 +171,10 @@ export interface Profile {
 
 export type MemberType = 'consul' | 'informant' | 'caporegime' | 'soldier' | 'bookkeeper';
 
-export type SoldierType = 'default' | 'external';
+export type SoldierType = 'default' | 'external' | 'reagent';
+
+/** Caporegime planning phase: off, plan then execute, or plan and wait for the Don */
+export type PlanMode = 'off' | 'auto' | 'approval';
 
 export interface SoldierSecret {
   name: string;
@@ -32,6 +184,12 @@ export interface SoldierSecret {
 export interface SoldierConfig {
   docs_url?: string;
   secrets?: SoldierSecret[];
+  /** Reagent soldiers: CYFR reagent reference, e.g. `reagent:local.csv-parse` */
+  reagent_ref?: string;
+  /** Reagent soldiers: static config passed to the reagent's `compute` input */
+  config?: Record<string, unknown>;
+  /** JSON Schema the soldier's result must match — results become structured JSON */
+  output_schema?: Record<string, unknown>;
 }
 
 export interface Member {
@@ -39,6 +197,8 @@ export interface Member {
   owner_id: string;
   name: string;
   catalog_model_id: string | null;
+  /** Ordered catalog models tried when the primary hits a provider or rate-limit error */
+  fallback_model_ids?: string[];
   system_prompt: string;
   avatar_url: string | null;
   created_at: string;
@@ -49,6 +209,24 @@ export interface Member {
   catalog_model?: CatalogModel;
   soldier_type?: SoldierType;
   soldier_config?: SoldierConfig;
+  plan_mode?: PlanMode;
+}
+
+export type PlanStepStatus = 'pending' | 'in_progress' | 'done' | 'skipped' | 'failed';
+
+export interface PlanStep {
+  id: string;
+  description: string;
+  soldier?: string;
+  expected_output?: string;
+  status: PlanStepStatus;
+  note?: string;
+}
+
+export interface OperationPlan {
+  summary: string;
+  steps: PlanStep[];
+  message_id?: string;
 }
 
 export interface Operation {
@@ -57,13 +235,18 @@ export interface Operation {
   owner_id: string;
   sit_down_id: string | null;
   trigger_message_id: string | null;
-  status: 'running' | 'completed' | 'failed';
+  status: 'running' | 'awaiting_approval' | 'completed' | 'failed' | 'cancelled';
   task_summary: string | null;
   result_content: string | null;
   turns_used: number;
   tool_calls: unknown[];
   usage: Record<string, unknown>;
   cron_job_id: string | null;
+  parent_operation_id?: string | null;
+  plan?: OperationPlan | null;
+  /** Timed spans recorded while the operation ran (exported as an OTLP trace) */
+  spans?: unknown[];
+  depth?: number;
   started_at: string;
   completed_at: string | null;
   member?: Member;
@@ -90,6 +273,10 @@ export interface SitDown {
   created_by: string;
   is_commission: boolean;
   is_direct?: boolean;
+  forked_from_sit_down_id?: string | null;
+  forked_from_message_id?: string | null;
+  /** Tokens of history members get here; null follows each member's model */
+  context_token_budget?: number | null;
   created_at: string;
   unread_count?: number;
 }
@@ -126,10 +313,31 @@ export interface Message {
   mentions: string[];
   metadata: Record<string, unknown>;
   created_at: string;
+  edited_at?: string | null;
+  deleted_at?: string | null;
+  /** Root of the thread this reply belongs to; null on the main timeline */
+  thread_id?: string | null;
+  thread_reply_count?: number;
+  thread_last_reply_at?: string | null;
   profile?: Profile;
   member?: Member;
 }
 
+/** A previous version of an edited message, kept in `metadata.edits` (oldest first). */
+export interface MessageEdit {
+  content: string;
+  mentions: string[];
+  edited_at: string;
+  edited_by: string;
+}
+
+/** One answer of a regenerated member message, kept in `metadata.versions`; `metadata.selected_version` is canonical. */
+export interface MessageVersion {
+  content: string;
+  metadata: { provider?: string; model?: string; fallback_from?: { provider: string; model: string }; routing?: CatalogRouting };
+  created_at: string;
+}
+
 export type Provider = 'claude' | 'openai' | 'gemini' | 'grok' | 'openrouter';
 
 export type ContactStatus = 'pending' | 'accepted' | 'declined';
@@ -145,6 +353,27 @@ export interface CommissionContact {
   contact_profile?: Profile;
 }
 
+export type SoldierLoanStatus = 'offered' | 'active' | 'declined' | 'revoked';
+
+export interface SoldierLoan {
+  id: string;
+  soldier_id: string;
+  lender_id: string;
+  borrower_id: string;
+  caporegime_id: string | null;
+  sit_down_id: string | null;
+  status: SoldierLoanStatus;
+  expires_at: string;
+  invocations: number;
+  last_used_at: string | null;
+  created_at: string;
+  responded_at: string | null;
+  soldier?: Pick<Member, 'id' | 'name' | 'avatar_url'>;
+  caporegime?: Pick<Member, 'id' | 'name'> | null;
+  lender?: Pick<Profile, 'id' | 'display_name'>;
+  borrower?: Pick<Profile, 'id' | 'display_name'>;
+}
+
 export interface MemberTemplate {
   name: string;
   slug: string;
@@ -26,6 +26,12 @@ impl Guest for Component {
 bindings::export!(Component with_types_in bindings);
 
 const SUPABASE_REF: &str = "catalyst:moonmoon69.supabase";
+const LIST_MODELS_REF: &str = "formula:local.list-models:0.5.0";
+const PROVIDERS: [&str; 5] = ["claude", "openai", "gemini", "grok", "openrouter"];
+/// Keys allowed in `model_catalog.capabilities` (see migration 036)
+const CAPABILITIES: [&str; 4] = ["tools", "vision", "web_search", "long_context"];
+/// Tiers that can be edited but never deleted (see migration 033)
+const RESERVED_TIERS: [&str; 2] = ["godfather", "associate"];
 
 fn handle_request(input: &str) -> Result<String, String> {
     let parsed: Value =
@@ -54,6 +60,28 @@ fn handle_request(input: &str) -> Result<String, String> {
                 .ok_or("Missing required 'tier'")?;
             update_tier(access_token, user_id, tier)
         }
+        "tier_list" => tier_list(access_token),
+        "tier_create" => {
+            let tier = parsed.get("tier").ok_or("Missing required 'tier'")?;
+            tier_create(access_token, tier)
+        }
+        "tier_update" => {
+            let tier_key = parsed
+                .get("tier_key")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'tier_key'")?;
+            let updates = parsed
+                .get("tier_updates")
+                .ok_or("Missing required 'tier_updates'")?;
+            tier_update(access_token, tier_key, updates)
+        }
+        "tier_delete" => {
+            let tier_key = parsed
+                .get("tier_key")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'tier_key'")?;
+            tier_delete(access_token, tier_key)
+        }
         "catalog_list" => catalog_list(access_token),
         "catalog_add" => {
             let entry = parsed
@@ -85,6 +113,37 @@ fn handle_request(input: &str) -> Result<String, String> {
                 .ok_or("Missing required 'catalog_id'")?;
             catalog_delete(access_token, catalog_id)
         }
+        "catalog_migrate" => {
+            let from_id = parsed
+                .get("from_catalog_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'from_catalog_id'")?;
+            let to_id = parsed
+                .get("to_catalog_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'to_catalog_id'")?;
+            let dry_run = parsed.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(true);
+            let check_tier = parsed.get("check_tier").and_then(|v| v.as_bool()).unwrap_or(true);
+            catalog_migrate(access_token, from_id, to_id, dry_run, check_tier)
+        }
+        "catalog_set_targets" => {
+            let catalog_id = parsed
+                .get("catalog_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'catalog_id'")?;
+            let targets = parsed
+                .get("targets")
+                .and_then(|v| v.as_array())
+                .ok_or("Missing required 'targets'")?;
+            catalog_set_targets(access_token, catalog_id, targets)
+        }
+        "catalog_sync_report" => {
+            let auto_deactivate = parsed
+                .get("auto_deactivate")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            catalog_sync_report(access_token, auto_deactivate)
+        }
         "catalog_toggle" => {
             let catalog_id = parsed
                 .get("catalog_id")
@@ -92,6 +151,30 @@ fn handle_request(input: &str) -> Result<String, String> {
                 .ok_or("Missing required 'catalog_id'")?;
             catalog_toggle(access_token, catalog_id)
         }
+        "usage_report" => {
+            let group_by = parsed
+                .get("group_by")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'group_by'")?;
+            let days = parsed.get("days").and_then(|v| v.as_u64()).unwrap_or(30);
+            usage_report(access_token, group_by, days)
+        }
+        "quota_list" => quota_list(access_token),
+        "list_audit_log" => {
+            let filters = parsed.get("filters").cloned().unwrap_or(json!({}));
+            list_audit_log(access_token, &filters)
+        }
+        "quota_set" => {
+            let quota = parsed.get("quota").ok_or("Missing required 'quota'")?;
+            quota_set(access_token, quota)
+        }
+        "quota_delete" => {
+            let quota_id = parsed
+                .get("quota_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'quota_id'")?;
+            quota_delete(access_token, quota_id)
+        }
         _ => Err(format!("Unknown action: {action}")),
     }
 }
@@ -163,14 +246,62 @@ fn fetch_user_tier(access_token: &str) -> Result<String, String> {
     Ok(tier.to_string())
 }
 
-/// Map tier names to numeric levels for comparison.
-fn tier_level(tier: &str) -> u8 {
-    match tier {
-        "godfather" => 3,
-        "boss" => 2,
-        "associate" => 1,
-        _ => 0,
+/// Every tier, highest rank first.
+fn fetch_tiers(access_token: &str) -> Result<Vec<Value>, String> {
+    let tiers = supabase_call(
+        "db.select",
+        json!({
+            "table": "tiers",
+            "select": "*",
+            "order": [{ "column": "rank", "direction": "desc" }],
+            "access_token": access_token
+        }),
+    )?;
+    Ok(tiers.as_array().cloned().unwrap_or_default())
+}
+
+fn find_tier<'a>(tiers: &'a [Value], key: &str) -> Option<&'a Value> {
+    tiers.iter().find(|t| t.get("key").and_then(|v| v.as_str()) == Some(key))
+}
+
+/// Unknown tiers rank below every real one.
+fn tier_rank(tiers: &[Value], key: &str) -> i64 {
+    find_tier(tiers, key)
+        .and_then(|t| t.get("rank"))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(i64::MIN)
+}
+
+fn require_tier(tiers: &[Value], key: &str) -> Result<(), String> {
+    if find_tier(tiers, key).is_none() {
+        return Err(format!("Invalid tier: {key}"));
     }
+    Ok(())
+}
+
+/// First row of a select/insert/update/delete result, or Null.
+fn first_row(rows: &Value) -> Value {
+    rows.as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+/// The row where `column = value`, or Null.
+fn fetch_row(table: &str, column: &str, value: &str, access_token: &str) -> Result<Value, String> {
+    let rows = supabase_call(
+        "db.select",
+        json!({
+            "table": table,
+            "select": "*",
+            "filters": [
+                { "column": column, "op": "eq", "value": value }
+            ],
+            "limit": 1,
+            "access_token": access_token
+        }),
+    )?;
+    Ok(first_row(&rows))
 }
 
 fn list_users(access_token: &str) -> Result<String, String> {
@@ -190,13 +321,8 @@ fn list_users(access_token: &str) -> Result<String, String> {
 }
 
 fn update_tier(access_token: &str, user_id: &str, tier: &str) -> Result<String, String> {
-    // Validate tier value
-    match tier {
-        "godfather" | "boss" | "associate" => {}
-        _ => return Err(format!("Invalid tier: {tier}. Must be godfather, boss, or associate")),
-    }
-
     let caller = verify_godfather(access_token)?;
+    require_tier(&fetch_tiers(access_token)?, tier)?;
     let caller_id = caller
         .get("id")
         .and_then(|v| v.as_str())
@@ -222,6 +348,162 @@ fn update_tier(access_token: &str, user_id: &str, tier: &str) -> Result<String,
     Ok(json!({ "updated": updated }).to_string())
 }
 
+// ---------------------------------------------------------------------------
+// Tier management
+// ---------------------------------------------------------------------------
+
+/// Every tier, highest rank first. Open to any signed-in user so the client
+/// can label tiers; limits are not secret.
+fn tier_list(access_token: &str) -> Result<String, String> {
+    fetch_user(access_token)?;
+    Ok(json!({ "tiers": fetch_tiers(access_token)? }).to_string())
+}
+
+/// Validate the editable tier fields present in `input` into a row body.
+fn tier_body(input: &Value) -> Result<Value, String> {
+    let mut body = json!({});
+
+    if let Some(name) = input.get("display_name") {
+        let name = name.as_str().map(str::trim).unwrap_or("");
+        if name.is_empty() {
+            return Err("Tier display name cannot be empty".to_string());
+        }
+        body["display_name"] = json!(name);
+    }
+    if let Some(rank) = input.get("rank") {
+        body["rank"] = json!(rank.as_i64().ok_or("'rank' must be an integer")?);
+    }
+    for field in ["max_members", "max_caporegimes"] {
+        match input.get(field) {
+            None => {}
+            Some(Value::Null) => body[field] = Value::Null,
+            Some(v) => {
+                let limit = v
+                    .as_u64()
+                    .ok_or_else(|| format!("'{field}' must be a non-negative integer or null"))?;
+                body[field] = json!(limit);
+            }
+        }
+    }
+    match input.get("allowed_providers") {
+        None => {}
+        Some(Value::Null) => body["allowed_providers"] = Value::Null,
+        Some(v) => {
+            let providers: Vec<&str> = v
+                .as_array()
+                .ok_or("'allowed_providers' must be an array of providers or null")?
+                .iter()
+                .map(|p| p.as_str().unwrap_or(""))
+                .collect();
+            if let Some(bad) = providers.iter().find(|p| !PROVIDERS.contains(p)) {
+                return Err(format!("Invalid provider: {bad}"));
+            }
+            body["allowed_providers"] = json!(providers);
+        }
+    }
+
+    Ok(body)
+}
+
+fn tier_create(access_token: &str, tier: &Value) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let key = tier
+        .get("key")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'tier.key'")?;
+    let valid_key = key.len() <= 32
+        && key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid_key {
+        return Err("Tier key must be lowercase letters, digits or underscores, starting with a letter".to_string());
+    }
+    if tier.get("display_name").is_none() || tier.get("rank").is_none() {
+        return Err("Missing required 'tier.display_name' or 'tier.rank'".to_string());
+    }
+
+    let mut body = tier_body(tier)?;
+    body["key"] = json!(key);
+
+    let inserted = supabase_call(
+        "db.insert",
+        json!({
+            "table": "tiers",
+            "body": body,
+            "access_token": access_token
+        }),
+    )?;
+
+    let created = first_row(&inserted);
+
+    Ok(json!({ "tier": created }).to_string())
+}
+
+/// Keys are fixed once created; everything else is editable.
+fn tier_update(access_token: &str, tier_key: &str, updates: &Value) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let mut body = tier_body(updates)?;
+    if body.as_object().map(|o| o.is_empty()).unwrap_or(true) {
+        return Err("No valid fields to update".to_string());
+    }
+    body["updated_at"] = json!("now()");
+
+    let updated = supabase_call(
+        "db.update",
+        json!({
+            "table": "tiers",
+            "body": body,
+            "filters": [
+                { "column": "key", "op": "eq", "value": tier_key }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "updated": updated }).to_string())
+}
+
+/// Only unused tiers can go: no users hold them and no catalog entry requires them.
+fn tier_delete(access_token: &str, tier_key: &str) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    if RESERVED_TIERS.contains(&tier_key) {
+        return Err(format!("The {tier_key} tier is reserved and cannot be deleted"));
+    }
+
+    for (table, column, what) in [("profiles", "tier", "users"), ("model_catalog", "min_tier", "catalog entries")] {
+        let rows = supabase_call(
+            "db.select",
+            json!({
+                "table": table,
+                "select": "id",
+                "filters": [
+                    { "column": column, "op": "eq", "value": tier_key }
+                ],
+                "access_token": access_token
+            }),
+        )?;
+        let count = rows.as_array().map(|a| a.len()).unwrap_or(0);
+        if count > 0 {
+            return Err(format!("{count} {what} still use this tier; move them to another tier first"));
+        }
+    }
+
+    supabase_call(
+        "db.delete",
+        json!({
+            "table": "tiers",
+            "filters": [
+                { "column": "key", "op": "eq", "value": tier_key }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "deleted": true }).to_string())
+}
+
 // ---------------------------------------------------------------------------
 // Model catalog operations
 // ---------------------------------------------------------------------------
@@ -229,6 +511,11 @@ fn update_tier(access_token: &str, user_id: &str, tier: &str) -> Result<String,
 fn catalog_list(access_token: &str) -> Result<String, String> {
     let tier = fetch_user_tier(access_token)?;
     let is_godfather = tier == "godfather";
+    let tiers = fetch_tiers(access_token)?;
+    let allowed_providers = find_tier(&tiers, &tier)
+        .and_then(|t| t.get("allowed_providers"))
+        .and_then(|v| v.as_array())
+        .cloned();
 
     let catalog = supabase_call(
         "db.select",
@@ -242,9 +529,10 @@ fn catalog_list(access_token: &str) -> Result<String, String> {
             "access_token": access_token
         }),
     )?;
+    let catalog = attach_catalog_targets(catalog, access_token)?;
 
     // Godfathers see everything (including inactive); others see only
-    // active models whose min_tier they meet.
+    // active models whose min_tier they meet, from their allowed providers.
     let filtered: Vec<Value> = if is_godfather {
         catalog.as_array().cloned().unwrap_or_default()
     } else {
@@ -265,7 +553,11 @@ fn catalog_list(access_token: &str) -> Result<String, String> {
                     .get("min_tier")
                     .and_then(|v| v.as_str())
                     .unwrap_or("associate");
-                tier_level(&tier) >= tier_level(min_tier)
+                let provider_allowed = allowed_providers
+                    .as_ref()
+                    .map(|allowed| allowed.iter().any(|p| Some(p) == entry.get("provider")))
+                    .unwrap_or(true);
+                provider_allowed && tier_rank(&tiers, &tier) >= tier_rank(&tiers, min_tier)
             })
             .collect()
     };
@@ -317,19 +609,26 @@ fn catalog_add(access_token: &str, entry: &Value) -> Result<String, String> {
         return Err("Alias cannot be empty".to_string());
     }
 
-    match provider {
-        "claude" | "openai" | "gemini" | "grok" | "openrouter" => {}
-        _ => return Err(format!("Invalid provider: {provider}")),
+    if !PROVIDERS.contains(&provider) {
+        return Err(format!("Invalid provider: {provider}"));
     }
 
     let min_tier = entry
         .get("min_tier")
         .and_then(|v| v.as_str())
         .unwrap_or("associate");
+    require_tier(&fetch_tiers(access_token)?, min_tier)?;
     let sort_order = entry
         .get("sort_order")
         .and_then(|v| v.as_i64())
         .unwrap_or(0);
+    let input_price = parse_price(entry, "input_price_per_million")?;
+    let output_price = parse_price(entry, "output_price_per_million")?;
+    let capabilities = parse_capabilities(entry)?;
+    let sticky_routing = entry
+        .get("sticky_routing")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
 
     let inserted = supabase_call(
         "db.insert",
@@ -341,17 +640,17 @@ fn catalog_add(access_token: &str, entry: &Value) -> Result<String, String> {
                 "model": model,
                 "min_tier": min_tier,
                 "sort_order": sort_order,
+                "input_price_per_million": input_price,
+                "output_price_per_million": output_price,
+                "capabilities": capabilities,
+                "sticky_routing": sticky_routing,
                 "added_by": caller_id
             },
             "access_token": access_token
         }),
     )?;
 
-    let created = inserted
-        .as_array()
-        .and_then(|arr| arr.first())
-        .cloned()
-        .unwrap_or(Value::Null);
+    let created = first_row(&inserted);
 
     Ok(json!({ "entry": created }).to_string())
 }
@@ -368,6 +667,17 @@ fn catalog_update(access_token: &str, catalog_id: &str, updates: &Value) -> Resu
             body[*field] = val.clone();
         }
     }
+    for field in ["input_price_per_million", "output_price_per_million"] {
+        if updates.get(field).is_some() {
+            body[field] = parse_price(updates, field)?;
+        }
+    }
+    if updates.get("capabilities").is_some() {
+        body["capabilities"] = parse_capabilities(updates)?;
+    }
+    if let Some(sticky) = updates.get("sticky_routing") {
+        body["sticky_routing"] = json!(sticky.as_bool().ok_or("'sticky_routing' must be true or false")?);
+    }
 
     if body.as_object().map(|o| o.is_empty()).unwrap_or(true) {
         return Err("No valid fields to update".to_string());
@@ -382,10 +692,7 @@ fn catalog_update(access_token: &str, catalog_id: &str, updates: &Value) -> Resu
 
     // Validate min_tier if provided
     if let Some(min_tier) = updates.get("min_tier").and_then(|v| v.as_str()) {
-        match min_tier {
-            "boss" | "associate" => {}
-            _ => return Err(format!("Invalid min_tier: {min_tier}")),
-        }
+        require_tier(&fetch_tiers(access_token)?, min_tier)?;
     }
 
     let updated = supabase_call(
@@ -403,6 +710,104 @@ fn catalog_update(access_token: &str, catalog_id: &str, updates: &Value) -> Resu
     Ok(json!({ "updated": updated }).to_string())
 }
 
+/// Reads an optional USD-per-million-tokens price. Null (or absent) means
+/// the model is unpriced and its cost estimates stay null.
+fn parse_price(entry: &Value, field: &str) -> Result<Value, String> {
+    match entry.get(field) {
+        None | Some(Value::Null) => Ok(Value::Null),
+        Some(v) => match v.as_f64() {
+            Some(price) if price >= 0.0 && price.is_finite() => Ok(json!(price)),
+            _ => Err(format!("'{field}' must be a non-negative number or null")),
+        },
+    }
+}
+
+/// Reads an optional `capabilities` object of known keys to booleans. Keys left
+/// out are unknown, which members-api treats as supported.
+fn parse_capabilities(entry: &Value) -> Result<Value, String> {
+    let capabilities = match entry.get("capabilities") {
+        None | Some(Value::Null) => return Ok(json!({})),
+        Some(v) => v.as_object().ok_or("'capabilities' must be an object of booleans")?,
+    };
+    for (key, value) in capabilities {
+        if !CAPABILITIES.contains(&key.as_str()) {
+            return Err(format!("Unknown capability: {key}. Must be one of {}", CAPABILITIES.join(", ")));
+        }
+        if !value.is_boolean() {
+            return Err(format!("Capability '{key}' must be true or false"));
+        }
+    }
+    Ok(Value::Object(capabilities.clone()))
+}
+
+/// Adds each entry's weighted `targets` (`[{target_catalog_id, weight}]`,
+/// empty for plain entries) from `model_catalog_targets`.
+fn attach_catalog_targets(catalog: Value, access_token: &str) -> Result<Value, String> {
+    let Value::Array(mut entries) = catalog else { return Ok(catalog) };
+
+    let targets = supabase_call(
+        "db.select",
+        json!({
+            "table": "model_catalog_targets",
+            "select": "catalog_id,target_catalog_id,weight",
+            "order": [{ "column": "weight", "direction": "desc" }],
+            "access_token": access_token
+        }),
+    )?;
+    let targets = targets.as_array().cloned().unwrap_or_default();
+
+    for entry in entries.iter_mut() {
+        let own: Vec<Value> = targets
+            .iter()
+            .filter(|t| t.get("catalog_id") == entry.get("id"))
+            .map(|t| json!({ "target_catalog_id": t.get("target_catalog_id"), "weight": t.get("weight") }))
+            .collect();
+        entry["targets"] = json!(own);
+    }
+
+    Ok(Value::Array(entries))
+}
+
+/// Replace the weighted targets of a catalog alias. An empty list turns it
+/// back into a plain entry that answers with its own model. Targets must be
+/// other existing entries, each listed once, with a positive integer weight.
+/// `set_catalog_targets()` also checks each one can serve the alias (migration
+/// 052) and swaps the whole list in one transaction.
+fn catalog_set_targets(access_token: &str, catalog_id: &str, targets: &[Value]) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let mut rows: Vec<Value> = Vec::new();
+    for target in targets {
+        let target_id = target
+            .get("target_catalog_id")
+            .and_then(|v| v.as_str())
+            .ok_or("Each target needs a 'target_catalog_id'")?;
+        let weight = target
+            .get("weight")
+            .and_then(|v| v.as_i64())
+            .filter(|w| *w > 0)
+            .ok_or("Each target needs a positive integer 'weight'")?;
+        if target_id == catalog_id {
+            return Err("A catalog entry cannot route to itself".to_string());
+        }
+        if rows.iter().any(|r| r.get("target_catalog_id").and_then(|v| v.as_str()) == Some(target_id)) {
+            return Err("Each target can only be listed once".to_string());
+        }
+        rows.push(json!({ "target_catalog_id": target_id, "weight": weight }));
+    }
+
+    let saved = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "set_catalog_targets",
+            "body": { "p_catalog_id": catalog_id, "p_targets": rows },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "targets": saved }).to_string())
+}
+
 fn catalog_delete_preview(access_token: &str, catalog_id: &str) -> Result<String, String> {
     verify_godfather(access_token)?;
 
@@ -423,6 +828,35 @@ fn catalog_delete_preview(access_token: &str, catalog_id: &str) -> Result<String
     Ok(json!({ "affected_member_count": count }).to_string())
 }
 
+/// Move every member and soldier using `from_id` (as primary or fallback) onto
+/// `to_id` in one transaction and notify their Dons. Dry runs only report.
+/// With `check_tier`, members whose Don's tier can't use the target are skipped.
+fn catalog_migrate(
+    access_token: &str,
+    from_id: &str,
+    to_id: &str,
+    dry_run: bool,
+    check_tier: bool,
+) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let report = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "migrate_catalog_members",
+            "body": {
+                "p_from": from_id,
+                "p_to": to_id,
+                "p_dry_run": dry_run,
+                "p_check_tier": check_tier
+            },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "migration": report }).to_string())
+}
+
 fn catalog_delete(access_token: &str, catalog_id: &str) -> Result<String, String> {
     verify_godfather(access_token)?;
 
@@ -444,22 +878,10 @@ fn catalog_toggle(access_token: &str, catalog_id: &str) -> Result<String, String
     verify_godfather(access_token)?;
 
     // Fetch current state
-    let entries = supabase_call(
-        "db.select",
-        json!({
-            "table": "model_catalog",
-            "select": "id,is_active",
-            "filters": [
-                { "column": "id", "op": "eq", "value": catalog_id }
-            ],
-            "access_token": access_token
-        }),
-    )?;
-
-    let entry = entries
-        .as_array()
-        .and_then(|arr| arr.first())
-        .ok_or("Catalog entry not found")?;
+    let entry = fetch_row("model_catalog", "id", catalog_id, access_token)?;
+    if entry.is_null() {
+        return Err("Catalog entry not found".to_string());
+    }
 
     let current_active = entry
         .get("is_active")
@@ -481,6 +903,448 @@ fn catalog_toggle(access_token: &str, catalog_id: &str) -> Result<String, String
     Ok(json!({ "updated": updated }).to_string())
 }
 
+// ---------------------------------------------------------------------------
+// Catalog sync
+// ---------------------------------------------------------------------------
+
+/// Version words that don't make a different model (`-latest`, `-preview`, ...).
+const VERSION_QUALIFIERS: [&str; 6] = ["latest", "preview", "exp", "experimental", "beta", "alpha"];
+
+/// Diff the catalog against the providers' live model lists. Entries whose model
+/// the provider no longer lists are `vanished`; entries on a provider whose list
+/// failed are `unchecked` and never flagged. Every entry gets newer models of the
+/// same family as `suggestions`, newest first. With `auto_deactivate`, active
+/// vanished entries are switched off.
+fn catalog_sync_report(access_token: &str, auto_deactivate: bool) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let listing = list_models_call()?;
+    let mut listed: serde_json::Map<String, Value> = serde_json::Map::new();
+    let mut unchecked: serde_json::Map<String, Value> = serde_json::Map::new();
+    for provider in PROVIDERS {
+        if let Some(err) = listing.get("errors").and_then(|e| e.get(provider)) {
+            unchecked.insert(provider.to_string(), err.clone());
+            continue;
+        }
+        let ids = listed_model_ids(listing.get("models").and_then(|m| m.get(provider)));
+        if ids.is_empty() {
+            unchecked.insert(provider.to_string(), json!("No models listed"));
+        } else {
+            listed.insert(provider.to_string(), json!(ids));
+        }
+    }
+
+    let catalog = supabase_call(
+        "db.select",
+        json!({
+            "table": "model_catalog",
+            "select": "id,provider,alias,model,is_active",
+            "order": [
+                { "column": "sort_order", "direction": "asc" },
+                { "column": "created_at", "direction": "asc" }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+    let catalog = catalog.as_array().cloned().unwrap_or_default();
+
+    let mut entries: Vec<Value> = Vec::new();
+    let mut deactivated_count = 0;
+    for entry in &catalog {
+        let provider = entry.get("provider").and_then(|v| v.as_str()).unwrap_or("");
+        let model = entry.get("model").and_then(|v| v.as_str()).unwrap_or("");
+        let Some(ids) = listed.get(provider).and_then(|v| v.as_array()) else {
+            let mut row = entry.clone();
+            row["status"] = json!("unchecked");
+            row["suggestions"] = json!([]);
+            row["deactivated"] = json!(false);
+            entries.push(row);
+            continue;
+        };
+        let ids: Vec<&str> = ids.iter().filter_map(|v| v.as_str()).collect();
+        let vanished = !ids.contains(&model);
+
+        let suggestions: Vec<Value> = newer_models(model, &ids)
+            .into_iter()
+            .take(3)
+            .map(|id| {
+                let catalog_id = catalog
+                    .iter()
+                    .find(|c| {
+                        c.get("provider").and_then(|v| v.as_str()) == Some(provider)
+                            && c.get("model").and_then(|v| v.as_str()) == Some(id)
+                    })
+                    .and_then(|c| c.get("id").cloned())
+                    .unwrap_or(Value::Null);
+                json!({ "model": id, "catalog_id": catalog_id })
+            })
+            .collect();
+
+        let mut deactivated = false;
+        let active = entry.get("is_active").and_then(|v| v.as_bool()).unwrap_or(true);
+        if vanished && active && auto_deactivate {
+            let catalog_id = entry.get("id").and_then(|v| v.as_str()).unwrap_or("");
+            supabase_call(
+                "db.update",
+                json!({
+                    "table": "model_catalog",
+                    "body": { "is_active": false },
+                    "filters": [
+                        { "column": "id", "op": "eq", "value": catalog_id }
+                    ],
+                    "access_token": access_token
+                }),
+            )?;
+            deactivated = true;
+            deactivated_count += 1;
+        }
+
+        let mut row = entry.clone();
+        row["status"] = json!(if vanished { "vanished" } else { "listed" });
+        row["suggestions"] = json!(suggestions);
+        row["deactivated"] = json!(deactivated);
+        if deactivated {
+            row["is_active"] = json!(false);
+        }
+        entries.push(row);
+    }
+
+    // Listed models nobody has put in the catalog yet, per provider
+    let mut providers: serde_json::Map<String, Value> = serde_json::Map::new();
+    for (provider, ids) in &listed {
+        let ids = ids.as_array().cloned().unwrap_or_default();
+        let uncatalogued = ids
+            .iter()
+            .filter(|id| {
+                !catalog.iter().any(|c| {
+                    c.get("provider").and_then(|v| v.as_str()) == Some(provider.as_str()) && c.get("model") == Some(*id)
+                })
+            })
+            .count();
+        providers.insert(
+            provider.clone(),
+            json!({ "listed_count": ids.len(), "uncatalogued_count": uncatalogued }),
+        );
+    }
+
+    let vanished_count = entries.iter().filter(|e| e["status"] == "vanished").count();
+    let suggestion_count = entries
+        .iter()
+        .filter(|e| e["suggestions"].as_array().map(|s| !s.is_empty()).unwrap_or(false))
+        .count();
+
+    Ok(json!({
+        "report": {
+            "entries": entries,
+            "providers": providers,
+            "unchecked_providers": unchecked,
+            "vanished_count": vanished_count,
+            "suggestion_count": suggestion_count,
+            "deactivated_count": deactivated_count
+        }
+    })
+    .to_string())
+}
+
+/// Model ids out of one provider's normalized list-models entry.
+fn listed_model_ids(models: Option<&Value>) -> Vec<String> {
+    models
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m.get("id").and_then(|v| v.as_str()))
+        .map(|id| id.to_string())
+        .collect()
+}
+
+/// A model id split into the words naming its family and the numbers ordering
+/// it: version parts, then a release date (`20241022`, `2024-08-06`, `0613`,
+/// `05-06`). `claude-3-5-sonnet-20241022` and `claude-sonnet-4-5` are both
+/// family "claude sonnet", versions [3, 5] and [4, 5].
+struct ModelVersion {
+    family: String,
+    version: Vec<u64>,
+    date: u64,
+}
+
+fn parse_model_version(id: &str) -> ModelVersion {
+    let tokens: Vec<&str> = id
+        .split(['-', '.', '/', ':', '_', '@'])
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut family: Vec<String> = Vec::new();
+    let mut version: Vec<u64> = Vec::new();
+    let mut date = String::new();
+    for token in tokens {
+        if !token.chars().all(|c| c.is_ascii_digit()) {
+            let word = token.to_lowercase();
+            if !VERSION_QUALIFIERS.contains(&word.as_str()) {
+                family.push(word);
+            }
+        } else if token.len() >= 4 || (token.len() == 2 && token.starts_with('0')) || !date.is_empty() {
+            date.push_str(token);
+        } else {
+            version.push(token.parse().unwrap_or(0));
+        }
+    }
+
+    ModelVersion {
+        family: family.join(" "),
+        version,
+        date: date.parse().unwrap_or(0),
+    }
+}
+
+/// Listed ids of the same family as `model` that are newer, newest first. An
+/// undated model (usually a moving alias) only counts higher versions as newer.
+fn newer_models<'a>(model: &str, ids: &[&'a str]) -> Vec<&'a str> {
+    let current = parse_model_version(model);
+    if current.family.is_empty() {
+        return Vec::new();
+    }
+
+    let mut newer: Vec<(&str, ModelVersion)> = ids
+        .iter()
+        .filter(|id| **id != model)
+        .map(|id| (*id, parse_model_version(id)))
+        .filter(|(_, v)| v.family == current.family)
+        .filter(|(_, v)| match v.version.cmp(&current.version) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => current.date > 0 && v.date > current.date,
+            std::cmp::Ordering::Less => false,
+        })
+        .collect();
+    newer.sort_by(|(a_id, a), (b_id, b)| {
+        (&b.version, b.date)
+            .cmp(&(&a.version, a.date))
+            .then(a_id.len().cmp(&b_id.len()))
+    });
+    newer.into_iter().map(|(id, _)| id).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Usage reporting
+// ---------------------------------------------------------------------------
+
+/// Token usage across every Don over the last `days`, grouped by don, member, model or day.
+fn usage_report(access_token: &str, group_by: &str, days: u64) -> Result<String, String> {
+    match group_by {
+        "don" | "member" | "model" | "day" => {}
+        _ => return Err(format!("Invalid group_by: {group_by}. Must be don, member, model, or day")),
+    }
+    if !(1..=366).contains(&days) {
+        return Err("'days' must be between 1 and 366".to_string());
+    }
+
+    verify_godfather(access_token)?;
+
+    let rows = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "usage_report",
+            "body": { "p_group_by": group_by, "p_days": days },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "group_by": group_by, "days": days, "rows": rows }).to_string())
+}
+
+/// Tier quotas and per-user overrides, with the overridden user's profile.
+fn quota_list(access_token: &str) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let quotas = supabase_call(
+        "db.select",
+        json!({
+            "table": "usage_quotas",
+            "select": "*,profile:profiles(id,display_name,tier)",
+            "order": [
+                { "column": "tier", "direction": "asc" },
+                { "column": "period", "direction": "asc" }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "quotas": quotas }).to_string())
+}
+
+/// Create or replace the quota for one tier or one user and period.
+/// `max_tokens` and `max_cost_usd` are each optional, but one is required.
+fn quota_set(access_token: &str, quota: &Value) -> Result<String, String> {
+    let caller = verify_godfather(access_token)?;
+    let caller_id = caller
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or("Could not determine user ID")?;
+
+    let tier = quota.get("tier").and_then(|v| v.as_str());
+    let user_id = quota.get("user_id").and_then(|v| v.as_str());
+    let (scope_column, scope_value) = match (tier, user_id) {
+        (Some(t), None) => {
+            require_tier(&fetch_tiers(access_token)?, t)?;
+            ("tier", t)
+        }
+        (None, Some(u)) => ("user_id", u),
+        _ => return Err("Quota needs exactly one of 'tier' or 'user_id'".to_string()),
+    };
+
+    let period = quota
+        .get("period")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'quota.period'")?;
+    if period != "day" && period != "month" {
+        return Err(format!("Invalid period: {period}. Must be day or month"));
+    }
+
+    let max_tokens = match quota.get("max_tokens") {
+        None | Some(Value::Null) => Value::Null,
+        Some(v) => json!(v.as_u64().ok_or("'max_tokens' must be a non-negative integer or null")?),
+    };
+    let max_cost_usd = parse_price(quota, "max_cost_usd")?;
+    if max_tokens.is_null() && max_cost_usd.is_null() {
+        return Err("Set 'max_tokens', 'max_cost_usd' or both".to_string());
+    }
+
+    let saved = supabase_call(
+        "db.upsert",
+        json!({
+            "table": "usage_quotas",
+            "on_conflict": format!("{scope_column},period"),
+            "body": {
+                scope_column: scope_value,
+                "period": period,
+                "max_tokens": max_tokens,
+                "max_cost_usd": max_cost_usd,
+                "updated_by": caller_id,
+                "updated_at": "now()"
+            },
+            "access_token": access_token
+        }),
+    )?;
+
+    let quota = first_row(&saved);
+
+    Ok(json!({ "quota": quota }).to_string())
+}
+
+fn quota_delete(access_token: &str, quota_id: &str) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    supabase_call(
+        "db.delete",
+        json!({
+            "table": "usage_quotas",
+            "filters": [
+                { "column": "id", "op": "eq", "value": quota_id }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "deleted": true }).to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Audit log
+// ---------------------------------------------------------------------------
+
+const AUDIT_PAGE_DEFAULT: u64 = 50;
+const AUDIT_PAGE_MAX: u64 = 200;
+
+/// Newest first. Optional filters: `actor_id`, `action`, `target_type`,
+/// `target_id`, `since` / `until` (ISO timestamps), and `before_id` to page
+/// past the last entry of the previous page.
+fn list_audit_log(access_token: &str, filters: &Value) -> Result<String, String> {
+    verify_godfather(access_token)?;
+
+    let limit = filters
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(AUDIT_PAGE_DEFAULT)
+        .clamp(1, AUDIT_PAGE_MAX);
+
+    let mut query_filters: Vec<Value> = Vec::new();
+    for field in ["actor_id", "action", "target_type", "target_id"] {
+        if let Some(value) = filters.get(field).and_then(|v| v.as_str()).filter(|v| !v.is_empty()) {
+            query_filters.push(json!({ "column": field, "op": "eq", "value": value }));
+        }
+    }
+    if let Some(since) = filters.get("since").and_then(|v| v.as_str()) {
+        query_filters.push(json!({ "column": "created_at", "op": "gte", "value": since }));
+    }
+    if let Some(until) = filters.get("until").and_then(|v| v.as_str()) {
+        query_filters.push(json!({ "column": "created_at", "op": "lt", "value": until }));
+    }
+    if let Some(before_id) = filters.get("before_id").and_then(|v| v.as_i64()) {
+        query_filters.push(json!({ "column": "id", "op": "lt", "value": before_id }));
+    }
+
+    let entries = supabase_call(
+        "db.select",
+        json!({
+            "table": "admin_audit_log",
+            "select": "*",
+            "filters": query_filters,
+            "order": [{ "column": "id", "direction": "desc" }],
+            "limit": limit + 1,
+            "access_token": access_token
+        }),
+    )?;
+    let mut entries = entries.as_array().cloned().unwrap_or_default();
+    let has_more = entries.len() as u64 > limit;
+    entries.truncate(limit as usize);
+
+    // Actors are looked up separately: the log keeps ids of deleted accounts too
+    let mut actor_ids: Vec<&str> = entries
+        .iter()
+        .filter_map(|e| e.get("actor_id").and_then(|v| v.as_str()))
+        .collect();
+    actor_ids.sort_unstable();
+    actor_ids.dedup();
+    let actors = if actor_ids.is_empty() {
+        json!([])
+    } else {
+        supabase_call(
+            "db.select",
+            json!({
+                "table": "profiles",
+                "select": "id,display_name",
+                "filters": [
+                    { "column": "id", "op": "in", "value": format!("({})", actor_ids.join(",")) }
+                ],
+                "access_token": access_token
+            }),
+        )?
+    };
+    let actors = actors.as_array().cloned().unwrap_or_default();
+
+    let entries: Vec<Value> = entries
+        .into_iter()
+        .map(|mut entry| {
+            let actor_id = entry.get("actor_id").and_then(|v| v.as_str()).unwrap_or("");
+            let name = actors
+                .iter()
+                .find(|a| a.get("id").and_then(|v| v.as_str()) == Some(actor_id))
+                .and_then(|a| a.get("display_name"))
+                .cloned()
+                .unwrap_or(Value::Null);
+            entry["actor_name"] = name;
+            entry
+        })
+        .collect();
+
+    let next_before_id = if has_more {
+        entries.last().and_then(|e| e.get("id")).cloned().unwrap_or(Value::Null)
+    } else {
+        Value::Null
+    };
+
+    Ok(json!({ "entries": entries, "next_before_id": next_before_id }).to_string())
+}
+
 // ---------------------------------------------------------------------------
 // Sub-component invocations
 // ---------------------------------------------------------------------------
@@ -522,6 +1386,42 @@ fn supabase_call(operation: &str, params: Value) -> Result<Value, String> {
     Ok(result.get("data").cloned().unwrap_or(Value::Null))
 }
 
+/// Run list-models live over every provider (bypassing its cache). Per-provider
+/// failures come back in its `errors` map; only a failure of the formula
+/// itself is an error here.
+fn list_models_call() -> Result<Value, String> {
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": LIST_MODELS_REF,
+            "input": { "refresh": true },
+            "type": "formula"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse list-models response: {e}"))?;
+
+    if let Some(err) = response.get("error") {
+        return Err(format!("list-models invoke error: {err}"));
+    }
+
+    let output = response.get("output").cloned().unwrap_or(Value::Null);
+    let raw = output.get("result").cloned().unwrap_or(Value::Null);
+    let result = match &raw {
+        Value::String(s) => serde_json::from_str::<Value>(s).unwrap_or(raw.clone()),
+        _ => raw,
+    };
+
+    if let Some(err) = result.get("errors").and_then(|e| e.get("formula")) {
+        return Err(format!("list-models failed: {err}"));
+    }
+
+    Ok(result)
+}
+
 fn fetch_user(access_token: &str) -> Result<Value, String> {
     let request = json!({
         "tool": "execution",
@@ -1,6 +1,7 @@
 use serde_json::{json, Value};
 
 use crate::bindings::cyfr::formula::invoke;
+use crate::provider_errors::provider_error;
 
 pub const SUPABASE_REF: &str = "catalyst:moonmoon69.supabase";
 const STORAGE_BUCKET: &str = "bookkeeper-files";
@@ -147,12 +148,30 @@ pub fn invoke_catalyst(catalyst_ref: &str, catalyst_input: &Value) -> Result<Val
             .or_else(|| err.get("error").and_then(|e| e.get("message")))
             .and_then(|v| v.as_str())
             .unwrap_or(&fallback);
-        return Err(err_msg.to_string());
+        return Err(provider_error(&catalyst_result, err, err_msg));
     }
 
     Ok(catalyst_result.get("data").cloned().unwrap_or(Value::Null))
 }
 
+/// Primary `(catalyst_ref, model)` followed by the `fallback_models` sit-down resolved
+/// from the member's fallback chain.
+pub fn model_chain(parsed: &Value, catalyst_ref: &str, model: &str) -> Vec<(String, String)> {
+    let mut chain = vec![(catalyst_ref.to_string(), model.to_string())];
+    if let Some(fallbacks) = parsed.get("fallback_models").and_then(|v| v.as_array()) {
+        for fb in fallbacks {
+            let fb_ref = fb.get("catalyst_ref").and_then(|v| v.as_str());
+            let fb_model = fb.get("model").and_then(|v| v.as_str());
+            if let (Some(r), Some(m)) = (fb_ref, fb_model) {
+                if !chain.iter().any(|(cr, cm)| cr == r && cm == m) {
+                    chain.push((r.to_string(), m.to_string()));
+                }
+            }
+        }
+    }
+    chain
+}
+
 // ---------------------------------------------------------------------------
 // Content extraction (multi-provider)
 // ---------------------------------------------------------------------------
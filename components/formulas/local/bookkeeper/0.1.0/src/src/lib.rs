@@ -1,10 +1,13 @@
 #[allow(warnings)]
 mod bindings;
+mod events;
 mod helpers;
+mod provider_errors;
 mod tools;
 
 use bindings::exports::cyfr::formula::run::Guest;
-use bindings::cyfr::formula::invoke;
+use events::{emit_event, flush_events, set_run_id};
+use helpers::supabase_call;
 
 use serde_json::{json, Value};
 use std::collections::HashSet;
@@ -13,7 +16,9 @@ struct Component;
 
 impl Guest for Component {
     fn run(input: String) -> String {
-        match handle_request(&input) {
+        let result = handle_request(&input);
+        flush_events();
+        match result {
             Ok(output) => output,
             Err(e) => json!({
                 "error": {
@@ -54,22 +59,6 @@ fn handle_request(input: &str) -> Result<String, String> {
     }
 }
 
-// ---------------------------------------------------------------------------
-// Emit helper
-// ---------------------------------------------------------------------------
-
-// Fire-and-forget: broadcast failure is acceptable, message delivery is DB-backed
-fn emit_event(sit_down_id: &str, member_id: &str, member_name: &str, event: Value, _access_token: &str) {
-    let mut payload = event;
-    if let Some(obj) = payload.as_object_mut() {
-        obj.insert("member_id".into(), json!(member_id));
-        obj.insert("member_name".into(), json!(member_name));
-        obj.insert("sit_down_id".into(), json!(sit_down_id));
-    }
-
-    let _ = invoke::emit(&payload.to_string());
-}
-
 // ---------------------------------------------------------------------------
 // Respond action — tool-assisted single-turn
 // ---------------------------------------------------------------------------
@@ -102,6 +91,7 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
     let sit_down_id = parsed.get("sit_down_id").and_then(|v| v.as_str()).unwrap_or("");
     let member_name = member.get("name").and_then(|v| v.as_str()).unwrap_or("Bookkeeper");
     let owner_id = member.get("owner_id").and_then(|v| v.as_str()).unwrap_or("");
+    set_run_id(parsed.get("run_id").and_then(|v| v.as_str()).unwrap_or(""));
 
     if member_id.is_empty() {
         return Err("Missing required 'member_id' — cannot scope data access".to_string());
@@ -138,10 +128,11 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
     // 2. Build enriched system prompt
     let enriched_system = build_bookkeeper_system(member_name, entry_count, &common_tags, system);
 
-    // 3. Build tool definitions
-    let tools_for_llm = tools::build_tool_definitions(catalyst_ref);
+    // 3. Model chain: primary first, then fallbacks on provider/rate-limit errors
+    let chain = helpers::model_chain(parsed, catalyst_ref, model);
+    let mut active = 0;
 
-    // 4. Mini tool loop (max 3 rounds)
+    // 4. Mini tool loop
     let mut messages = conversation;
     let mut total_input_tokens: u64 = 0;
     let mut total_output_tokens: u64 = 0;
@@ -150,15 +141,38 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
 
     for round in 0..MAX_TOOL_ROUNDS {
         let turn = (round + 1) as u64;
-        let provider_label = extract_provider_label(catalyst_ref);
-        emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": format!("Calling {}...", provider_label)}), access_token);
         emit_event(sit_down_id, member_id, member_name, json!({"kind": "turn_start", "turn": turn}), access_token);
 
-        let catalyst_input = tools::build_provider_request_with_tools(
-            catalyst_ref, model, &messages, &enriched_system, &tools_for_llm, 4096,
-        );
+        let data = loop {
+            let (chain_ref, chain_model) = &chain[active];
+            let provider_label = extract_provider_label(chain_ref);
+            emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": format!("Calling {}...", provider_label)}), access_token);
 
-        let data = helpers::invoke_catalyst(catalyst_ref, &catalyst_input)?;
+            let tools_for_llm = tools::build_tool_definitions(chain_ref);
+            let catalyst_input = tools::build_provider_request_with_tools(
+                chain_ref, chain_model, &messages, &enriched_system, &tools_for_llm, 4096,
+            );
+
+            match helpers::invoke_catalyst(chain_ref, &catalyst_input) {
+                Ok(data) => break data,
+                Err(e) if provider_errors::is_retryable_provider_error(&e) => {
+                    // Once tool turns are in the history they're in this provider's
+                    // wire format, so only same-provider fallbacks can pick up mid-loop.
+                    let next = chain
+                        .iter()
+                        .enumerate()
+                        .skip(active + 1)
+                        .find(|(_, (r, _))| round == 0 || r == chain_ref)
+                        .map(|(i, _)| i);
+                    match next {
+                        Some(i) => active = i,
+                        None => return Err(e),
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        let catalyst_ref = chain[active].0.as_str();
 
         // Track usage
         if let Some(usage) = data.get("usage") {
@@ -248,7 +262,8 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
             "input_tokens": total_input_tokens,
             "output_tokens": total_output_tokens
         },
-        "entries_used": tool_call_count
+        "entries_used": tool_call_count,
+        "answered_by": { "catalyst_ref": chain[active].0, "model": chain[active].1 }
     })
     .to_string())
 }
@@ -672,6 +687,10 @@ fn handle_update_operation(parsed: &Value) -> Result<String, String> {
     if let Some(usage) = parsed.get("usage") {
         if usage.is_object() {
             body["usage"] = usage.clone();
+            // The database prices usage from the model that answered it
+            if let Some(answered_by) = parsed.get("answered_by").filter(|v| v.is_object()) {
+                body["usage"]["answered_by"] = answered_by.clone();
+            }
         }
     }
 
@@ -0,0 +1,122 @@
+//! Member events: persisted through `record_member_events`, then broadcast.
+//!
+//! caporegime, bookkeeper and consul each carry this file as `src/events.rs`,
+//! byte for byte the same. Change all three together.
+
+use serde_json::{json, Value};
+use std::cell::RefCell;
+
+use crate::bindings::cyfr::formula::invoke;
+use crate::supabase_call;
+
+thread_local! {
+    /// Groups this response's persisted events (`member_events.run_id`). Set once per request.
+    static RUN_ID: RefCell<String> = const { RefCell::new(String::new()) };
+    /// A text_delta waiting to go out with the next event, and the token it came with.
+    static PENDING_DELTA: RefCell<Option<(Value, String)>> = const { RefCell::new(None) };
+}
+
+/// A held-back delta is sent once it grows past this many bytes.
+const DELTA_FLUSH_CHARS: usize = 2000;
+
+pub fn set_run_id(run_id: &str) {
+    RUN_ID.with(|r| *r.borrow_mut() = run_id.to_string());
+}
+
+pub fn current_run_id() -> String {
+    RUN_ID.with(|r| r.borrow().clone())
+}
+
+// Fire-and-forget: broadcast failure is acceptable, message delivery is DB-backed.
+// No sit-down means nobody is listening (nested runs, private consults, jobs without a sit-down).
+pub fn emit_event(sit_down_id: &str, member_id: &str, member_name: &str, event: Value, access_token: &str) {
+    if sit_down_id.is_empty() {
+        return;
+    }
+    let mut payload = event;
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert("member_id".into(), json!(member_id));
+        obj.insert("member_name".into(), json!(member_name));
+        obj.insert("sit_down_id".into(), json!(sit_down_id));
+        obj.insert("run_id".into(), json!(current_run_id()));
+    }
+
+    // Text deltas are held back, merged per member and turn, and go out with the
+    // next event, so streamed text never costs a round-trip of its own
+    let pending = PENDING_DELTA.with(|p| p.borrow_mut().take()).map(|(held, _)| held);
+    let mut batch: Vec<Value> = Vec::new();
+    if payload.get("kind").and_then(|v| v.as_str()) == Some("text_delta") {
+        let held = match pending {
+            Some(mut prev) if ["member_id", "sit_down_id", "turn"].iter().all(|k| prev.get(k) == payload.get(k)) => {
+                let merged = format!(
+                    "{}{}",
+                    prev.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+                    payload.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+                );
+                prev["content"] = json!(merged);
+                prev
+            }
+            Some(prev) => {
+                batch.push(prev);
+                payload
+            }
+            None => payload,
+        };
+        if held.get("content").and_then(|v| v.as_str()).map(|c| c.len()).unwrap_or(0) < DELTA_FLUSH_CHARS {
+            PENDING_DELTA.with(|p| *p.borrow_mut() = Some((held, access_token.to_string())));
+            return;
+        }
+        batch.push(held);
+    } else {
+        batch.extend(pending);
+        batch.push(payload);
+    }
+    publish_events(batch, access_token);
+}
+
+/// Send out a delta still held back when the response ends.
+pub fn flush_events() {
+    if let Some((held, access_token)) = PENDING_DELTA.with(|p| p.borrow_mut().take()) {
+        publish_events(vec![held], &access_token);
+    }
+}
+
+/// Persist `batch` in one call, then broadcast it. `record_member_events` runs
+/// with the Don's token and refuses members that don't sit at that table for
+/// them; a refused batch is still broadcast, just without sequences.
+fn publish_events(mut batch: Vec<Value>, access_token: &str) {
+    let run_id = current_run_id();
+
+    // Persist first so the live events carry the sequence a replaying client dedupes on
+    if !run_id.is_empty() {
+        let events: Vec<Value> = batch
+            .iter()
+            .map(|payload| json!({
+                "sit_down_id": payload.get("sit_down_id"),
+                "member_id": payload.get("member_id"),
+                "run_id": run_id,
+                "kind": payload.get("kind").and_then(|v| v.as_str()).unwrap_or("status"),
+                "payload": payload
+            }))
+            .collect();
+        let recorded = supabase_call(
+            "db.rpc",
+            json!({
+                "function": "record_member_events",
+                "body": { "p_events": events },
+                "access_token": access_token
+            }),
+        );
+        if let Ok(Value::Array(sequences)) = recorded {
+            for (payload, sequence) in batch.iter_mut().zip(sequences) {
+                if let Some(sequence) = sequence.as_i64() {
+                    payload["sequence"] = json!(sequence);
+                }
+            }
+        }
+    }
+
+    for payload in batch {
+        let _ = invoke::emit(&payload.to_string());
+    }
+}
@@ -1,11 +1,14 @@
 use serde_json::{json, Value};
+use std::cell::RefCell;
 
 use crate::bindings::cyfr::formula::invoke;
+use crate::provider_errors::{is_retryable_provider_error, provider_error};
 
 pub const SUPABASE_REF: &str = "catalyst:moonmoon69.supabase";
 pub const WEB_CATALYST_REF: &str = "catalyst:moonmoon69.web";
 
 const MAX_EXTERNAL_TURNS: usize = 10;
+const MAX_SCHEMA_RETRIES: usize = 2;
 
 fn supabase_call_once(operation: &str, params: Value) -> Result<Value, String> {
     let request = json!({
@@ -51,6 +54,225 @@ pub fn supabase_call(operation: &str, params: Value) -> Result<Value, String> {
     }
 }
 
+/// The user `access_token` belongs to, as Supabase Auth verifies it.
+pub fn caller_id(access_token: &str) -> Result<String, String> {
+    let user = supabase_call("auth.user", json!({ "access_token": access_token }))?;
+    user.get("id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "Could not determine user ID from token".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Usage quotas
+// ---------------------------------------------------------------------------
+
+/// The caller's first used-up quota (see `check_quota()` in migration 032),
+/// or None when they are within every limit.
+pub fn check_quota(access_token: &str) -> Result<Option<Value>, String> {
+    let status = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "check_quota",
+            "body": {},
+            "access_token": access_token
+        }),
+    )?;
+    Ok(status.get("exceeded").filter(|v| v.is_object()).cloned())
+}
+
+/// Same wording as sit-down, so a refused job reads like a refused message.
+pub fn quota_message(exceeded: &Value) -> String {
+    let period = match exceeded.get("period").and_then(|v| v.as_str()) {
+        Some("month") => "this month",
+        _ => "today",
+    };
+    let source = exceeded.get("source").and_then(|v| v.as_str()).unwrap_or("tier");
+    let resets_at = exceeded.get("resets_at").and_then(|v| v.as_str()).unwrap_or("the next period");
+    let limit = exceeded.get("limit").cloned().unwrap_or(Value::Null);
+    let used = exceeded.get("used").cloned().unwrap_or(Value::Null);
+    let amount = if exceeded.get("metric").and_then(|v| v.as_str()) == Some("cost") {
+        format!(
+            "${:.2} of ${:.2} spent",
+            used.as_f64().unwrap_or(0.0),
+            limit.as_f64().unwrap_or(0.0)
+        )
+    } else {
+        format!("{used} of {limit} tokens used")
+    };
+    format!("Quota exceeded: {amount} {period} ({source} quota). Resets at {resets_at}.")
+}
+
+// ---------------------------------------------------------------------------
+// Usage accounting
+// ---------------------------------------------------------------------------
+
+thread_local! {
+    /// Tokens spent on direct catalyst calls during this request, per `(catalyst_ref, model)`.
+    static CATALYST_USAGE: RefCell<Vec<(ModelRef, u64, u64)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Count a catalyst call's tokens against `(catalyst_ref, model)`.
+fn add_catalyst_usage(catalyst_ref: &str, model: &str, input_tokens: u64, output_tokens: u64) {
+    if input_tokens == 0 && output_tokens == 0 {
+        return;
+    }
+    CATALYST_USAGE.with(|u| {
+        let mut usage = u.borrow_mut();
+        match usage.iter_mut().find(|(m, _, _)| m.0 == catalyst_ref && m.1 == model) {
+            Some(entry) => {
+                entry.1 += input_tokens;
+                entry.2 += output_tokens;
+            }
+            None => usage.push(((catalyst_ref.to_string(), model.to_string()), input_tokens, output_tokens)),
+        }
+    });
+}
+
+/// Take the tokens counted so far as `[{catalyst_ref, model, input_tokens, output_tokens}]`.
+pub fn take_catalyst_usage() -> Vec<Value> {
+    CATALYST_USAGE.with(|u| u.borrow_mut().drain(..).collect::<Vec<_>>())
+        .into_iter()
+        .map(|((catalyst_ref, model), input_tokens, output_tokens)| json!({
+            "catalyst_ref": catalyst_ref,
+            "model": model,
+            "input_tokens": input_tokens,
+            "output_tokens": output_tokens
+        }))
+        .collect()
+}
+
+/// Fold in the `catalyst_usage` a spawned soldier task reported.
+pub fn merge_catalyst_usage(reported: &Value) {
+    for entry in reported.as_array().into_iter().flatten() {
+        add_catalyst_usage(
+            entry.get("catalyst_ref").and_then(|v| v.as_str()).unwrap_or(""),
+            entry.get("model").and_then(|v| v.as_str()).unwrap_or(""),
+            entry.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+            entry.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+        );
+    }
+}
+
+/// Write the tokens counted so far to `usage_ledger`, one row per model, for
+/// `user_id`. Written with the service role, as clients can't record usage.
+/// Best-effort, like sit-down's `record_usage`.
+pub fn record_catalyst_usage(user_id: &str, member_id: &str, sit_down_id: &str, operation_id: &str) {
+    let rows: Vec<Value> = take_catalyst_usage()
+        .into_iter()
+        .map(|entry| {
+            let catalyst_ref = entry.get("catalyst_ref").and_then(|v| v.as_str()).unwrap_or("");
+            json!({
+                "user_id": user_id,
+                "member_id": member_id,
+                "sit_down_id": Some(sit_down_id).filter(|s| !s.is_empty()),
+                "operation_id": Some(operation_id).filter(|s| !s.is_empty()),
+                "provider": catalyst_ref.rsplit('.').next().unwrap_or(catalyst_ref),
+                "model": entry.get("model"),
+                "input_tokens": entry.get("input_tokens"),
+                "output_tokens": entry.get("output_tokens")
+            })
+        })
+        .collect();
+    if rows.is_empty() {
+        return;
+    }
+    let _ = supabase_call(
+        "db.insert",
+        json!({
+            "table": "usage_ledger",
+            "body": rows,
+            "service_role": true
+        }),
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Model fallback chains
+// ---------------------------------------------------------------------------
+
+/// A `(catalyst_ref, model)` pair — one link in a fallback chain.
+pub type ModelRef = (String, String);
+
+/// Primary `(catalyst_ref, model)` followed by the `fallback_models` sit-down resolved
+/// from the member's fallback chain.
+pub fn model_chain(parsed: &Value, catalyst_ref: &str, model: &str) -> Vec<ModelRef> {
+    let mut chain = vec![(catalyst_ref.to_string(), model.to_string())];
+    push_fallbacks(&mut chain, parsed.get("fallback_models"));
+    chain
+}
+
+/// A soldier's chain: its catalog model, then `fallback_models` attached by
+/// `fetch_crew_info`. The primary may be missing if it was removed from the catalog.
+pub fn soldier_models(soldier: &Value) -> Result<Vec<ModelRef>, String> {
+    let mut chain: Vec<ModelRef> = Vec::new();
+    let catalog_model = soldier.get("catalog_model").cloned().unwrap_or(Value::Null);
+    if let (Some(provider), Some(model)) = (
+        catalog_model.get("provider").and_then(|v| v.as_str()),
+        catalog_model.get("model").and_then(|v| v.as_str()),
+    ) {
+        chain.push((format!("catalyst:moonmoon69.{}", provider), model.to_string()));
+    }
+    push_fallbacks(&mut chain, soldier.get("fallback_models"));
+
+    if chain.is_empty() {
+        let name = soldier.get("name").and_then(|v| v.as_str()).unwrap_or("Soldier");
+        return Err(format!("{name}'s model has been removed. Please assign a new model."));
+    }
+    Ok(chain)
+}
+
+fn push_fallbacks(chain: &mut Vec<ModelRef>, fallbacks: Option<&Value>) {
+    let Some(fallbacks) = fallbacks.and_then(|v| v.as_array()) else { return };
+    for fb in fallbacks {
+        let fb_ref = fb
+            .get("catalyst_ref")
+            .and_then(|v| v.as_str())
+            .map(|r| r.to_string())
+            .or_else(|| fb.get("provider").and_then(|v| v.as_str()).map(|p| format!("catalyst:moonmoon69.{}", p)));
+        let fb_model = fb.get("model").and_then(|v| v.as_str());
+        if let (Some(r), Some(m)) = (fb_ref, fb_model) {
+            if !chain.iter().any(|(cr, cm)| *cr == r && cm == m) {
+                chain.push((r, m.to_string()));
+            }
+        }
+    }
+}
+
+/// Next usable chain index after `active`. Once tool turns are in the history
+/// they're in the active provider's wire format, so mid-conversation only
+/// same-provider fallbacks can pick up.
+pub fn next_fallback(chain: &[ModelRef], active: usize, mid_conversation: bool) -> Option<usize> {
+    let current_ref = &chain[active].0;
+    chain
+        .iter()
+        .enumerate()
+        .skip(active + 1)
+        .find(|(_, (r, _))| !mid_conversation || r == current_ref)
+        .map(|(i, _)| i)
+}
+
+/// Run a single-shot call against each model in the chain until one answers.
+/// Non-retryable errors fail immediately.
+pub fn with_fallback<T>(
+    chain: &[ModelRef],
+    mut call: impl FnMut(&str, &str) -> Result<T, String>,
+) -> Result<(T, ModelRef), String> {
+    let mut last_err = "No model available".to_string();
+    for (catalyst_ref, model) in chain {
+        match call(catalyst_ref, model) {
+            Ok(v) => return Ok((v, (catalyst_ref.clone(), model.clone()))),
+            Err(e) if is_retryable_provider_error(&e) => last_err = e,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_err)
+}
+
+pub fn answered_by_json(answered_by: &ModelRef) -> Value {
+    json!({ "catalyst_ref": answered_by.0, "model": answered_by.1 })
+}
+
 pub fn invoke_catalyst(catalyst_ref: &str, catalyst_input: &Value) -> Result<Value, String> {
     let request = json!({
         "tool": "execution",
@@ -87,10 +309,17 @@ pub fn invoke_catalyst(catalyst_ref: &str, catalyst_input: &Value) -> Result<Val
             .or_else(|| err.get("error").and_then(|e| e.get("message")))
             .and_then(|v| v.as_str())
             .unwrap_or(&fallback);
-        return Err(err_msg.to_string());
+        return Err(provider_error(&catalyst_result, err, err_msg));
     }
 
-    Ok(catalyst_result.get("data").cloned().unwrap_or(Value::Null))
+    let data = catalyst_result.get("data").cloned().unwrap_or(Value::Null);
+    add_catalyst_usage(
+        catalyst_ref,
+        catalyst_input.pointer("/params/model").and_then(|v| v.as_str()).unwrap_or(""),
+        data.pointer("/usage/input_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+        data.pointer("/usage/output_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+    );
+    Ok(data)
 }
 
 pub fn extract_content(data: &Value, catalyst_ref: &str) -> String {
@@ -201,6 +430,22 @@ pub fn insert_ai_message(
     Ok(message_id)
 }
 
+/// Merge `patch` into a member message's metadata (plan progress, status flags).
+pub fn update_message_metadata(message_id: &str, patch: &Value, access_token: &str) -> Result<(), String> {
+    supabase_call(
+        "db.rpc",
+        json!({
+            "function": "update_ai_message_metadata",
+            "body": {
+                "p_message_id": message_id,
+                "p_metadata": patch
+            },
+            "access_token": access_token
+        }),
+    )?;
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Soldier invocation (direct, no consul hop)
 // ---------------------------------------------------------------------------
@@ -208,48 +453,284 @@ pub fn insert_ai_message(
 /// Build a single-turn LLM request with native web search tools (no caporegime tools).
 /// Replicates consul's build_provider_request for soldier delegation.
 /// Invoke a soldier directly (no consul hop).
-/// Dispatches based on soldier_type: "default" or "external".
-/// Both paths use `build_soldier_request` which adds native web search tools.
+/// Dispatches based on soldier_type: "default", "external" or "reagent".
+/// Both LLM paths use `build_soldier_request` which adds native web search tools.
 /// Default gets web search only, external additionally gets `http_request`.
+/// Reagent soldiers skip the LLM entirely and run a CYFR reagent.
+/// Returns the content and the model that answered (`None` for reagents).
 pub fn invoke_soldier(
     soldier: &Value,
     task: &str,
     access_token: &str,
-) -> Result<String, String> {
+) -> Result<(String, Option<ModelRef>), String> {
+    let soldier = &with_lent_soldier_details(soldier, access_token)?;
+    record_loan_usage(soldier, access_token);
     let soldier_type = soldier.get("soldier_type").and_then(|v| v.as_str()).unwrap_or("default");
 
     match soldier_type {
-        "external" => invoke_external_soldier(soldier, task, access_token),
-        _ => invoke_default_soldier(soldier, task, access_token),
+        "external" => invoke_external_soldier(soldier, task, access_token).map(|(c, m)| (c, Some(m))),
+        "reagent" => {
+            let output = invoke_reagent_soldier(soldier, task)?;
+            Ok((reagent_output_to_string(&output), None))
+        }
+        _ => invoke_default_soldier(soldier, task, access_token).map(|(c, m)| (c, Some(m))),
+    }
+}
+
+/// Borrowed soldiers come from `list_borrowed_soldiers` without their prompt or
+/// full config. Fill those in for the run once `borrowed_soldier_id` confirms,
+/// with the borrower's token, that the loan is theirs and still active.
+fn with_lent_soldier_details(soldier: &Value, access_token: &str) -> Result<Value, String> {
+    let Some(loan_id) = soldier.get("loan_id").and_then(|v| v.as_str()) else {
+        return Ok(soldier.clone());
+    };
+    let soldier_id = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "borrowed_soldier_id",
+            "body": { "p_loan_id": loan_id },
+            "access_token": access_token
+        }),
+    )?;
+    let soldier_id = soldier_id.as_str().ok_or("This soldier's loan is no longer active")?;
+
+    let row = supabase_call(
+        "db.select",
+        json!({
+            "table": "members",
+            "select": "system_prompt,soldier_config",
+            "filters": [
+                { "column": "id", "op": "eq", "value": soldier_id }
+            ],
+            "service_role": true
+        }),
+    )?
+    .as_array()
+    .and_then(|arr| arr.first())
+    .cloned()
+    .ok_or("Lent soldier not found")?;
+
+    let mut lent = soldier.clone();
+    lent["system_prompt"] = row.get("system_prompt").cloned().unwrap_or(Value::Null);
+    lent["soldier_config"] = row.get("soldier_config").cloned().unwrap_or(Value::Null);
+    Ok(lent)
+}
+
+/// Borrowed soldiers (tagged with `loan_id` by `fetch_crew_info`) count each
+/// delegation against the loan so the lending Don can see how it was used.
+fn record_loan_usage(soldier: &Value, access_token: &str) {
+    let Some(loan_id) = soldier.get("loan_id").and_then(|v| v.as_str()) else { return };
+    let _ = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "record_soldier_loan_usage",
+            "body": { "p_loan_id": loan_id },
+            "access_token": access_token
+        }),
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Structured soldier output (schema-validated JSON)
+// ---------------------------------------------------------------------------
+
+/// The output schema a soldier declares in `soldier_config.output_schema`, if any.
+pub fn soldier_output_schema(soldier: &Value) -> Option<Value> {
+    soldier
+        .get("soldier_config")
+        .and_then(|c| c.get("output_schema"))
+        .filter(|s| s.is_object())
+        .cloned()
+}
+
+/// Invoke a soldier and return JSON matching `schema`.
+/// Default soldiers answer directly in structured mode; external soldiers run their
+/// normal tool loop first, then a structuring pass turns the findings into JSON.
+/// Reagent output is validated as-is (deterministic, so no retry).
+pub fn invoke_soldier_structured(
+    soldier: &Value,
+    task: &str,
+    schema: &Value,
+    access_token: &str,
+) -> Result<(Value, Option<ModelRef>), String> {
+    let soldier = &with_lent_soldier_details(soldier, access_token)?;
+    record_loan_usage(soldier, access_token);
+    let soldier_type = soldier.get("soldier_type").and_then(|v| v.as_str()).unwrap_or("default");
+
+    if soldier_type == "reagent" {
+        let output = invoke_reagent_soldier(soldier, task)?;
+        return validate_structured(&output, schema)
+            .map(|_| (output, None))
+            .map_err(|e| format!("Reagent output does not match output_schema: {e}"));
+    }
+
+    let mut chain = soldier_models(soldier)?;
+    let system_prompt = soldier.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
+
+    let mut messages = vec![json!({"role": "user", "content": task})];
+    if soldier_type == "external" {
+        let (findings, answered_by) = invoke_external_soldier(soldier, task, access_token)?;
+        messages.push(json!({"role": "assistant", "content": findings}));
+        messages.push(json!({"role": "user", "content": "Return your final result as JSON matching the required output schema."}));
+        // Structure with the model that produced the findings, keeping the rest as fallbacks
+        if let Some(pos) = chain.iter().position(|m| *m == answered_by) {
+            chain.drain(..pos);
+        }
+    }
+
+    let (output, answered_by) = with_fallback(&chain, |catalyst_ref, model| {
+        request_structured(catalyst_ref, model, system_prompt, messages.clone(), schema)
+    })?;
+    Ok((output, Some(answered_by)))
+}
+
+/// Request structured output, validating against `schema` and retrying with the
+/// validation errors appended to the last user message on mismatch.
+pub fn request_structured(
+    catalyst_ref: &str,
+    model: &str,
+    system: &str,
+    messages: Vec<Value>,
+    schema: &Value,
+) -> Result<Value, String> {
+    let mut attempt_messages = messages.clone();
+    let mut last_error = String::new();
+
+    for attempt in 0..=MAX_SCHEMA_RETRIES {
+        let catalyst_input = crate::tools::build_structured_request(
+            catalyst_ref, model, &attempt_messages, system, schema, 4096,
+        );
+        let data = invoke_catalyst(catalyst_ref, &catalyst_input)?;
+
+        let output = crate::tools::extract_structured_output(&data, catalyst_ref, schema);
+        match output.and_then(|v| validate_structured(&v, schema).map(|_| v)) {
+            Ok(v) => return Ok(v),
+            Err(e) => last_error = e,
+        }
+
+        if attempt < MAX_SCHEMA_RETRIES {
+            // Providers disagree on consecutive user turns, so fold the feedback into the last one
+            attempt_messages = messages.clone();
+            if let Some(last) = attempt_messages.last_mut() {
+                let original = last.get("content").and_then(|v| v.as_str()).unwrap_or("");
+                last["content"] = json!(format!(
+                    "{original}\n\n[Your previous output was rejected: {last_error}. \
+                     Respond with JSON that matches the required output schema exactly.]"
+                ));
+            }
+        }
+    }
+
+    Err(format!("Output did not match output_schema after {} attempts: {last_error}", MAX_SCHEMA_RETRIES + 1))
+}
+
+fn validate_structured(value: &Value, schema: &Value) -> Result<(), String> {
+    crate::schema::validate(value, schema).map_err(|errors| errors.join("; "))
+}
+
+// ---------------------------------------------------------------------------
+// Reagent invocation (deterministic compute, no LLM)
+// ---------------------------------------------------------------------------
+
+/// Reagent soldier: runs the reagent configured in `soldier_config.reagent_ref`.
+fn invoke_reagent_soldier(soldier: &Value, task: &str) -> Result<Value, String> {
+    let soldier_config = soldier.get("soldier_config").cloned().unwrap_or(json!({}));
+    let reagent_ref = soldier_config
+        .get("reagent_ref")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    let input = build_reagent_input(task, None, soldier_config.get("config"));
+    invoke_reagent(reagent_ref, &input)
+}
+
+/// Build the `compute` input for a reagent: the rendered prompt, the current
+/// for_each item (if any), and optional static config from the soldier/step.
+pub fn build_reagent_input(prompt: &str, item: Option<&Value>, config: Option<&Value>) -> Value {
+    let mut input = json!({ "prompt": prompt });
+    if let Some(item) = item {
+        input["item"] = item.clone();
+    }
+    if let Some(config) = config {
+        input["config"] = config.clone();
+    }
+    input
+}
+
+/// Invoke a reagent by reference (e.g. `reagent:local.csv-parse`).
+pub fn invoke_reagent(reagent_ref: &str, input: &Value) -> Result<Value, String> {
+    if !reagent_ref.starts_with("reagent:") {
+        return Err(format!("Invalid reagent reference '{}' (expected 'reagent:<namespace>.<name>')", reagent_ref));
+    }
+
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": reagent_ref,
+            "input": input,
+            "type": "reagent"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse reagent response: {e}"))?;
+
+    if let Some(err) = response.get("error") {
+        return Err(format!("Reagent invoke error: {err}"));
+    }
+
+    let output = response.get("output").cloned().unwrap_or(Value::Null);
+    let raw_result = output.get("result").cloned().unwrap_or(output);
+    let result = match &raw_result {
+        Value::String(s) => serde_json::from_str::<Value>(s).unwrap_or(raw_result.clone()),
+        _ => raw_result,
+    };
+
+    if let Some(err) = result.get("error") {
+        let fallback = err.to_string();
+        let msg = err.get("message").and_then(|v| v.as_str()).unwrap_or(&fallback);
+        return Err(format!("Reagent error: {msg}"));
+    }
+
+    Ok(result)
+}
+
+/// Flatten reagent output for text consumers (Brain-mode tool results, spawned tasks).
+pub fn reagent_output_to_string(output: &Value) -> String {
+    match output {
+        Value::String(s) => s.clone(),
+        _ => serde_json::to_string_pretty(output).unwrap_or_default(),
     }
 }
 
 /// Default soldier: single-turn LLM call with native web search tools only.
+/// Walks the soldier's fallback chain on provider/rate-limit errors.
 fn invoke_default_soldier(
     soldier: &Value,
     task: &str,
     _access_token: &str,
-) -> Result<String, String> {
-    let catalog_model = soldier.get("catalog_model").cloned().unwrap_or(Value::Null);
-    let provider = catalog_model.get("provider").and_then(|v| v.as_str()).unwrap_or("claude");
-    let model = catalog_model.get("model").and_then(|v| v.as_str()).unwrap_or("claude-sonnet-4-6");
+) -> Result<(String, ModelRef), String> {
+    let chain = soldier_models(soldier)?;
     let system_prompt = soldier.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
 
-    let catalyst_ref = format!("catalyst:moonmoon69.{}", provider);
     let messages = vec![json!({"role": "user", "content": task})];
 
-    // No custom tools — just native web search from build_provider_request_with_tools
-    let catalyst_input = crate::tools::build_soldier_request(
-        &catalyst_ref, model, &messages, system_prompt, &[], 4096,
-    );
-    let data = invoke_catalyst(&catalyst_ref, &catalyst_input)?;
-    let content = extract_content(&data, &catalyst_ref);
+    let (data, answered_by) = with_fallback(&chain, |catalyst_ref, model| {
+        // No custom tools — just native web search from build_provider_request_with_tools
+        let catalyst_input = crate::tools::build_soldier_request(
+            catalyst_ref, model, &messages, system_prompt, &[], 4096,
+        );
+        invoke_catalyst(catalyst_ref, &catalyst_input)
+    })?;
+    let content = extract_content(&data, &answered_by.0);
 
     if content.is_empty() {
         return Err("Empty response from soldier".to_string());
     }
 
-    Ok(content)
+    Ok((content, answered_by))
 }
 
 /// External soldier: mini agentic loop with `http_request` custom tool + native web search.
@@ -258,14 +739,12 @@ fn invoke_external_soldier(
     soldier: &Value,
     task: &str,
     _access_token: &str,
-) -> Result<String, String> {
-    let catalog_model = soldier.get("catalog_model").cloned().unwrap_or(Value::Null);
-    let provider = catalog_model.get("provider").and_then(|v| v.as_str()).unwrap_or("claude");
-    let model = catalog_model.get("model").and_then(|v| v.as_str()).unwrap_or("claude-sonnet-4-6");
+) -> Result<(String, ModelRef), String> {
+    let chain = soldier_models(soldier)?;
+    let mut active = 0;
     let system_prompt = soldier.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
     let soldier_config = soldier.get("soldier_config").cloned().unwrap_or(json!({}));
 
-    let catalyst_ref = format!("catalyst:moonmoon69.{}", provider);
     let custom_tools = vec![build_web_tool_definition()];
 
     // Build enriched system prompt with docs and secret names (not values!)
@@ -300,13 +779,23 @@ fn invoke_external_soldier(
     let mut messages: Vec<Value> = vec![json!({"role": "user", "content": task})];
     let mut all_text = String::new();
 
-    for _turn in 0..MAX_EXTERNAL_TURNS {
-        // No native web search for external soldiers — http_request is their only tool
-        let catalyst_input = crate::tools::build_provider_request_with_tools(
-            &catalyst_ref, model, &messages, &enriched_system, &custom_tools, 4096,
-        );
-
-        let data = invoke_catalyst(&catalyst_ref, &catalyst_input)?;
+    for turn in 0..MAX_EXTERNAL_TURNS {
+        let data = loop {
+            let (catalyst_ref, model) = &chain[active];
+            // No native web search for external soldiers — http_request is their only tool
+            let catalyst_input = crate::tools::build_provider_request_with_tools(
+                catalyst_ref, model, &messages, &enriched_system, &custom_tools, 4096,
+            );
+
+            match invoke_catalyst(catalyst_ref, &catalyst_input) {
+                Ok(data) => break data,
+                Err(e) if is_retryable_provider_error(&e) => {
+                    active = next_fallback(&chain, active, turn > 0).ok_or(e)?;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        let catalyst_ref = chain[active].0.clone();
 
         let turn_text = crate::tools::extract_text(&data, &catalyst_ref);
         if !turn_text.is_empty() {
@@ -351,7 +840,7 @@ fn invoke_external_soldier(
         return Err("Empty response from external soldier".to_string());
     }
 
-    Ok(all_text)
+    Ok((all_text, chain[active].clone()))
 }
 
 
@@ -465,23 +954,30 @@ fn execute_web_tool(args: &Value, soldier_config: &Value) -> String {
 }
 
 /// Spawn a soldier invocation (for parallel delegation via self-invoke).
+/// With an `output_schema`, the task's content is the validated JSON, serialized.
 /// Returns the task_id for await_all.
 pub fn spawn_soldier(
     soldier: &Value,
     task: &str,
+    output_schema: Option<&Value>,
     access_token: &str,
 ) -> String {
+    let mut input = json!({
+        "action": "invoke_soldier",
+        "soldier": soldier,
+        "task": task,
+        "access_token": access_token
+    });
+    if let Some(schema) = output_schema {
+        input["output_schema"] = schema.clone();
+    }
+
     let request = json!({
         "tool": "execution",
         "action": "run",
         "args": {
             "reference": "formula:local.caporegime",
-            "input": {
-                "action": "invoke_soldier",
-                "soldier": soldier,
-                "task": task,
-                "access_token": access_token
-            },
+            "input": input,
             "type": "formula"
         }
     });
@@ -491,8 +987,8 @@ pub fn spawn_soldier(
     spawn.get("task_id").and_then(|v| v.as_str()).unwrap_or("").to_string()
 }
 
-/// Unwrap a formula invocation response to get the content text.
-pub fn unwrap_formula_response(response: &Value) -> Result<String, String> {
+/// Unwrap a formula invocation response to its result object.
+pub fn unwrap_formula_result(response: &Value) -> Result<Value, String> {
     if let Some(err) = response.get("error") {
         return Err(format!("Formula invoke error: {err}"));
     }
@@ -508,17 +1004,11 @@ pub fn unwrap_formula_response(response: &Value) -> Result<String, String> {
         return Err(format!("Formula error: {}", err.get("message").and_then(|v| v.as_str()).unwrap_or(&err.to_string())));
     }
 
-    let data = result.get("data").unwrap_or(&result);
-    if let Some(content) = data.get("content").and_then(|v| v.as_str()) {
-        return Ok(content.to_string());
-    }
-
-    // Fallback: return the whole thing as string
-    Ok(serde_json::to_string_pretty(data).unwrap_or_default())
+    Ok(result.get("data").cloned().unwrap_or(result))
 }
 
-/// Await all spawned tasks and return results in order.
-pub fn await_all_tasks(task_ids: &[String]) -> Vec<Result<String, String>> {
+/// Await all spawned tasks and return their result objects in order.
+pub fn await_all_tasks(task_ids: &[String]) -> Vec<Result<Value, String>> {
     let valid_ids: Vec<&str> = task_ids.iter()
         .filter(|id| !id.is_empty())
         .map(|id| id.as_str())
@@ -533,13 +1023,13 @@ pub fn await_all_tasks(task_ids: &[String]) -> Vec<Result<String, String>> {
     let await_resp: Value = serde_json::from_str(&await_str).unwrap_or(json!({}));
 
     let result_arr = await_resp.get("results").and_then(|v| v.as_array()).cloned().unwrap_or_default();
-    let mut result_map: std::collections::HashMap<String, Result<String, String>> = std::collections::HashMap::new();
+    let mut result_map: std::collections::HashMap<String, Result<Value, String>> = std::collections::HashMap::new();
 
     for r in &result_arr {
         if let Some(tid) = r.get("task_id").and_then(|v| v.as_str()) {
             let status = r.get("status").and_then(|v| v.as_str()).unwrap_or("error");
             let output = if status == "completed" {
-                unwrap_formula_response(r)
+                unwrap_formula_result(r)
             } else {
                 Err(format!("Task error: {}", r.get("error").map(|e| e.to_string()).unwrap_or_default()))
             };
@@ -616,6 +1106,110 @@ pub fn invoke_bookkeeper(
     Ok(result.get("data").cloned().unwrap_or(result))
 }
 
+/// Run a sub-caporegime's Brain mode on `task` as a nested operation under `run`
+/// (the parent's `crew_info.run`). Returns the nested run's
+/// `{content, operation_id, turns, usage, answered_by}`.
+pub fn invoke_captain(captain: &Value, task: &str, run: &Value, owner_id: &str, access_token: &str) -> Result<Value, String> {
+    let chain = soldier_models(captain)?;
+    let (catalyst_ref, model) = &chain[0];
+    let fallback_models: Vec<Value> = chain[1..]
+        .iter()
+        .map(|(r, m)| json!({"catalyst_ref": r, "model": m}))
+        .collect();
+
+    let name = captain.get("name").and_then(|v| v.as_str()).unwrap_or("Caporegime");
+    let parent_name = run.get("member_name").and_then(|v| v.as_str()).unwrap_or("your caporegime");
+    let prompt = captain.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
+    let system = format!(
+        "Your name is \"{name}\". You are a caporegime reporting to {parent_name}, who assigned you the task below. \
+         Use your own crew to complete it and reply with a complete report — it goes back to {parent_name}, not the user.\n\n{prompt}"
+    );
+
+    let depth = run.get("depth").and_then(|v| v.as_u64()).unwrap_or(0);
+    let remaining_turns = run.get("remaining_turns").and_then(|v| v.as_u64()).unwrap_or(1).max(1);
+
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": "formula:local.caporegime",
+            "input": {
+                "action": "respond",
+                "catalyst_ref": catalyst_ref,
+                "model": model,
+                "fallback_models": fallback_models,
+                "system": system,
+                "conversation": [{"role": "user", "content": task}],
+                "member": captain,
+                "member_id": captain.get("id").cloned().unwrap_or(Value::Null),
+                "sit_down_id": run.get("sit_down_id").cloned().unwrap_or(Value::Null),
+                "access_token": access_token,
+                "context": {"owner_id": owner_id},
+                "parent_operation_id": run.get("operation_id").cloned().unwrap_or(Value::Null),
+                "depth": depth + 1,
+                "lineage": run.get("lineage").cloned().unwrap_or(json!([])),
+                "max_turns": remaining_turns
+            },
+            "type": "formula"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse caporegime response: {e}"))?;
+    unwrap_formula_result(&response)
+}
+
+/// Ask a consul or bookkeeper sitting at the table a question on the caporegime's
+/// behalf. No `sit_down_id` is passed, so the exchange stays out of the live feed;
+/// it is recorded in the operation log instead.
+pub fn invoke_table_member(member: &Value, question: &str, run: &Value, access_token: &str) -> Result<Value, String> {
+    let chain = soldier_models(member)?;
+    let (catalyst_ref, model) = &chain[0];
+    let fallback_models: Vec<Value> = chain[1..]
+        .iter()
+        .map(|(r, m)| json!({"catalyst_ref": r, "model": m}))
+        .collect();
+
+    let name = member.get("name").and_then(|v| v.as_str()).unwrap_or("Consul");
+    let member_type = member.get("member_type").and_then(|v| v.as_str()).unwrap_or("consul");
+    let caporegime_name = run.get("member_name").and_then(|v| v.as_str()).unwrap_or("A caporegime");
+    let prompt = member.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
+    let system = format!(
+        "Your name is \"{name}\". {caporegime_name}, a caporegime in the Family, is consulting you in the middle of \
+         an operation for the Don. Answer the question directly — your reply goes back to {caporegime_name}, not the table.\n\n{prompt}"
+    );
+    let reference = match member_type {
+        "bookkeeper" => "formula:local.bookkeeper",
+        _ => "formula:local.consul",
+    };
+
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": reference,
+            "input": {
+                "action": "respond",
+                "catalyst_ref": catalyst_ref,
+                "model": model,
+                "fallback_models": fallback_models,
+                "system": system,
+                "conversation": [{"role": "user", "content": format!("[{caporegime_name} asks]: {question}")}],
+                "member": member,
+                "member_id": member.get("id").cloned().unwrap_or(Value::Null),
+                "access_token": access_token
+            },
+            "type": "formula"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse {member_type} response: {e}"))?;
+    unwrap_formula_result(&response)
+}
+
 // ---------------------------------------------------------------------------
 // Job CRUD helpers
 // ---------------------------------------------------------------------------
@@ -1,10 +1,16 @@
 #[allow(warnings)]
 mod bindings;
+mod events;
 mod helpers;
+mod plan;
+mod provider_errors;
+mod schema;
 mod tools;
+mod trace;
 
 use bindings::exports::cyfr::formula::run::Guest;
-use bindings::cyfr::formula::invoke;
+use events::{current_run_id, emit_event, flush_events, set_run_id};
+use helpers::supabase_call;
 
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -13,7 +19,9 @@ struct Component;
 
 impl Guest for Component {
     fn run(input: String) -> String {
-        match handle_request(&input) {
+        let result = handle_request(&input);
+        flush_events();
+        match result {
             Ok(output) => output,
             Err(e) => json!({
                 "error": {
@@ -30,18 +38,8 @@ bindings::export!(Component with_types_in bindings);
 
 const DEFAULT_MAX_TURNS: usize = 30;
 const DEFAULT_MAX_TOKENS: u64 = 16384;
-
-// Fire-and-forget: broadcast failure is acceptable, message delivery is DB-backed
-fn emit_event(sit_down_id: &str, member_id: &str, member_name: &str, event: Value, _access_token: &str) {
-    let mut payload = event;
-    if let Some(obj) = payload.as_object_mut() {
-        obj.insert("member_id".into(), json!(member_id));
-        obj.insert("member_name".into(), json!(member_name));
-        obj.insert("sit_down_id".into(), json!(sit_down_id));
-    }
-
-    let _ = invoke::emit(&payload.to_string());
-}
+/// Levels in a crew hierarchy, counting the top caporegime (must match members-api).
+pub const MAX_CREW_DEPTH: usize = 3;
 
 fn handle_request(input: &str) -> Result<String, String> {
     let parsed: Value =
@@ -56,6 +54,8 @@ fn handle_request(input: &str) -> Result<String, String> {
         "respond" => handle_respond(&parsed),
         "execute_job" => handle_execute_job(&parsed),
         "invoke_soldier" => handle_invoke_soldier(&parsed),
+        "save_plan_as_job" => handle_save_plan_as_job(&parsed),
+        "export_trace" => handle_export_trace(&parsed),
         _ => Err(format!("Unknown action: {action}")),
     }
 }
@@ -80,7 +80,7 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .unwrap_or("");
 
-    let conversation = parsed
+    let mut conversation = parsed
         .get("conversation")
         .and_then(|v| v.as_array())
         .cloned()
@@ -101,23 +101,52 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
         .and_then(|v| v.as_u64())
         .unwrap_or(DEFAULT_MAX_TURNS as u64) as usize;
 
-    emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": "Assessing the task..."}), access_token);
-
-    // 1. Create operation record
-    let operation = helpers::supabase_call(
-        "db.insert",
-        json!({
-            "table": "operations",
-            "body": {
-                "member_id": member_id,
-                "owner_id": owner_id,
-                "sit_down_id": sit_down_id,
-                "status": "running",
-                "task_summary": get_last_user_message(&conversation)
-            },
-            "access_token": access_token
-        }),
-    );
+    // Nested runs: a parent caporegime delegated this task. They report back to the
+    // parent rather than the sit-down, so events and report messages are suppressed.
+    let parent_operation_id = parsed.get("parent_operation_id").and_then(|v| v.as_str());
+    let depth = parsed.get("depth").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    let nested = parent_operation_id.is_some();
+    let events_sid = if nested { "" } else { sit_down_id };
+
+    // Plan-then-execute: 'auto' drafts a plan and proceeds, 'approval' waits for the Don.
+    // An approved plan comes back here with the paused operation's id.
+    let plan_mode = member.get("plan_mode").and_then(|v| v.as_str()).unwrap_or("off");
+    let approved_operation_id = parsed.get("approved_operation_id").and_then(|v| v.as_str());
+    set_run_id(parsed.get("run_id").and_then(|v| v.as_str()).unwrap_or(""));
+
+    emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": "Assessing the task..."}), access_token);
+
+    // 1. Create operation record (or resume the one paused for plan approval)
+    let mut plan: Option<Value> = None;
+    let mut recorder = trace::Recorder::default();
+    let operation = if let Some(op_id) = approved_operation_id {
+        // sit-down's review_plan has already moved it from awaiting_approval to running
+        let paused = plan::fetch_operation_plan(op_id, access_token)?;
+        if paused.get("status").and_then(|v| v.as_str()) != Some("running") {
+            return Err("This plan is no longer awaiting approval".to_string());
+        }
+        plan = paused.get("plan").cloned().filter(|p| p.is_object());
+        recorder = trace::Recorder::from_existing(paused.get("spans"));
+        conversation.push(json!({"role": "user", "content": "[The Don approved your plan. Carry it out now.]"}));
+        Ok(json!([paused]))
+    } else {
+        helpers::supabase_call(
+            "db.insert",
+            json!({
+                "table": "operations",
+                "body": {
+                    "member_id": member_id,
+                    "owner_id": owner_id,
+                    "sit_down_id": sit_down_id,
+                    "status": "running",
+                    "task_summary": get_last_user_message(&conversation),
+                    "parent_operation_id": parent_operation_id,
+                    "depth": depth
+                },
+                "access_token": access_token
+            }),
+        )
+    };
 
     let operation_id = operation
         .as_ref()
@@ -128,26 +157,142 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
+    if current_run_id().is_empty() {
+        set_run_id(&operation_id);
+    }
+    // RLS only lets the token's own user insert or read the operation, so its
+    // owner_id is who this run's catalyst calls are billed to
+    let billed_to = operation
+        .as_ref()
+        .ok()
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|row| row.get("owner_id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
 
     // 2. Fetch crew info
-    let crew_info = fetch_crew_info(member_id, owner_id, access_token);
+    let mut crew_info = fetch_crew_info(member_id, owner_id, sit_down_id, access_token);
+
+    // Sub-caporegimes: drop any already in the delegation chain, and all of them once
+    // the next level would exceed the depth limit
+    let mut lineage: Vec<Value> = parsed.get("lineage").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    lineage.push(json!(member_id));
+    let captains: Vec<Value> = if depth + 1 < MAX_CREW_DEPTH {
+        crew_info.get("captains").and_then(|v| v.as_array()).cloned().unwrap_or_default()
+            .into_iter()
+            .filter(|c| c.get("id").map(|id| !lineage.contains(id)).unwrap_or(false))
+            .collect()
+    } else {
+        vec![]
+    };
+    crew_info["captains"] = json!(captains);
+    crew_info["table"] = table_members(&context, member_id, owner_id, access_token);
+    crew_info["run"] = json!({
+        "operation_id": operation_id,
+        "sit_down_id": sit_down_id,
+        "member_name": member_name,
+        "depth": depth,
+        "lineage": lineage,
+        "remaining_turns": max_turns
+    });
 
     let soldier_count = crew_info.get("soldiers").and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0);
     let bookkeeper_count = crew_info.get("bookkeepers").and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0);
-    emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": format!("Briefing crew: {} soldiers, {} bookkeepers available", soldier_count, bookkeeper_count)}), access_token);
+    let briefing = if captains.is_empty() {
+        format!("Briefing crew: {} soldiers, {} bookkeepers available", soldier_count, bookkeeper_count)
+    } else {
+        format!("Briefing crew: {} soldiers, {} caporegimes, {} bookkeepers available", soldier_count, captains.len(), bookkeeper_count)
+    };
+    emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": briefing}), access_token);
 
     // 3. Build enriched system prompt
-    let enriched_system = build_enriched_system(system, &crew_info, member_name);
-
-    // 4. Build tool definitions (raw, provider-formatting happens in request builder)
-    let tools_for_llm = tools::build_tool_definitions();
+    let mut enriched_system = build_enriched_system(system, &crew_info, member_name, None);
+    let chain = helpers::model_chain(parsed, catalyst_ref, model);
+
+    // 4. Optional planning phase — the plan is stored on the operation and posted to the sit-down
+    let mut planning_error: Option<String> = None;
+    if plan.is_none() && !nested && plan_mode != "off" {
+        emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": "Drafting a plan..."}), access_token);
+
+        let plan_span = recorder.start(None, "catalyst", "plan", json!({ "gen_ai.operation.name": "plan" }));
+        let planned = plan::generate_plan(&chain, &enriched_system, &conversation);
+        let plan_attributes = match &planned {
+            Ok((_, answered_by)) => json!({ "gen_ai.system": answered_by.0, "gen_ai.response.model": answered_by.1 }),
+            Err(_) => json!({}),
+        };
+        recorder.end(plan_span, plan_attributes, planned.as_ref().err().map(|e| e.as_str()));
+
+        match planned {
+            Ok((mut drafted, answered_by)) => {
+                let awaiting = plan_mode == "approval";
+                let mut plan_metadata = json!({
+                    "provider": answered_by.0,
+                    "model": answered_by.1,
+                    "type": "plan",
+                    "operation_id": operation_id,
+                    "plan": drafted,
+                    "plan_status": if awaiting { "awaiting_approval" } else { "approved" }
+                });
+                if let Some(rid) = reply_to_id {
+                    plan_metadata["reply_to_id"] = json!(rid);
+                }
+                let plan_message_id = helpers::insert_ai_message(
+                    sit_down_id, member_id, &plan::render_plan(&drafted), &plan_metadata, access_token,
+                ).unwrap_or_default();
+                drafted["message_id"] = json!(plan_message_id);
+                let _ = plan::save_plan(&operation_id, &drafted, access_token);
+                emit_event(events_sid, member_id, member_name, json!({"kind": "message_inserted", "message_id": plan_message_id}), access_token);
+
+                if awaiting {
+                    helpers::record_catalyst_usage(&billed_to, member_id, sit_down_id, &operation_id);
+                    let _ = helpers::supabase_call(
+                        "db.update",
+                        json!({
+                            "table": "operations",
+                            "body": { "status": "awaiting_approval", "spans": recorder.spans() },
+                            "filters": [{ "column": "id", "op": "eq", "value": operation_id }],
+                            "access_token": access_token
+                        }),
+                    );
+                    emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": "Awaiting the Don's approval."}), access_token);
+                    return Ok(json!({
+                        "content": plan::render_plan(&drafted),
+                        "message_id": plan_message_id,
+                        "operation_id": operation_id,
+                        "awaiting_approval": true,
+                        "answered_by": helpers::answered_by_json(&answered_by)
+                    })
+                    .to_string());
+                }
+                plan = Some(drafted);
+            }
+            // Without a plan there is nothing for the Don to approve, so don't proceed
+            Err(e) if plan_mode == "approval" => planning_error = Some(format!("Planning failed: {e}")),
+            Err(_) => {
+                emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": "Couldn't draft a plan — proceeding without one."}), access_token);
+            }
+        }
+    }
+    if let Some(p) = &plan {
+        enriched_system = build_enriched_system(system, &crew_info, member_name, Some(p));
+        crew_info["run"]["plan_message_id"] = p.get("message_id").cloned().unwrap_or(Value::Null);
+    }
 
-    // 5. Run agentic loop
-    let loop_result = run_agentic_loop(
-        catalyst_ref, model, &enriched_system, &conversation, &tools_for_llm, max_turns,
-        sit_down_id, member_id, member_name, access_token,
-        &crew_info, owner_id,
-    );
+    // 5. Build tool definitions (raw, provider-formatting happens in request builder)
+    let has_table = crew_info["table"].as_array().is_some_and(|t| !t.is_empty());
+    let tools_for_llm = tools::build_tool_definitions(plan.is_some(), !events_sid.is_empty(), has_table);
+
+    // 6. Run agentic loop over the model chain (primary, then fallbacks)
+    let loop_result = match planning_error {
+        Some(e) => Err(e),
+        None => run_agentic_loop(
+            &chain, &enriched_system, &conversation, &tools_for_llm, max_turns,
+            events_sid, member_id, member_name, access_token,
+            &mut crew_info, owner_id, &mut recorder,
+        ),
+    };
 
     match loop_result {
         Ok(result) => {
@@ -155,8 +300,15 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
             let turns = result.get("turns").and_then(|v| v.as_u64()).unwrap_or(0);
             let tool_calls_log = result.get("tool_calls").cloned().unwrap_or(json!([]));
             let usage = result.get("usage").cloned().unwrap_or(json!({}));
+            let answered_by = result.get("answered_by").cloned().unwrap_or(Value::Null);
+            let answered_ref = answered_by.get("catalyst_ref").and_then(|v| v.as_str()).unwrap_or(catalyst_ref);
+            let answered_model = answered_by.get("model").and_then(|v| v.as_str()).unwrap_or(model);
 
-            // 6. Update operation record
+            let mut operation_usage = usage.clone();
+            operation_usage["answered_by"] = answered_by.clone();
+
+            // 7. Record per-model usage, then update the operation record (priced from those rows)
+            helpers::record_catalyst_usage(&billed_to, member_id, sit_down_id, &operation_id);
             let _ = helpers::supabase_call(
                 "db.update",
                 json!({
@@ -166,7 +318,8 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
                         "result_content": content,
                         "turns_used": turns,
                         "tool_calls": tool_calls_log,
-                        "usage": usage,
+                        "usage": operation_usage,
+                        "spans": recorder.spans(),
                         "completed_at": "now()"
                     },
                     "filters": [
@@ -176,8 +329,20 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
                 }),
             );
 
-            // 7. Insert report message
-            emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": "Compiling report..."}), access_token);
+            // Nested runs hand their report straight back to the parent caporegime
+            if nested {
+                return Ok(json!({
+                    "content": content,
+                    "operation_id": operation_id,
+                    "turns": turns,
+                    "usage": usage,
+                    "answered_by": answered_by
+                })
+                .to_string());
+            }
+
+            // 8. Insert report message
+            emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": "Compiling report..."}), access_token);
 
             let report_content = if content.is_empty() {
                 "Operation completed.".to_string()
@@ -186,33 +351,48 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
             };
 
             let mut report_metadata = json!({
-                "provider": catalyst_ref,
-                "model": model,
+                "provider": answered_ref,
+                "model": answered_model,
                 "operation_id": operation_id,
                 "turns": turns
             });
+            if answered_ref != catalyst_ref || answered_model != model {
+                report_metadata["fallback_from"] = json!({ "provider": catalyst_ref, "model": model });
+            }
+            if let Some(routing) = parsed.get("routing").filter(|v| v.is_object()) {
+                report_metadata["routing"] = routing.clone();
+            }
             if let Some(rid) = reply_to_id {
                 report_metadata["reply_to_id"] = json!(rid);
             }
+            if let Some(plan_message_id) = plan.as_ref().and_then(|p| p.get("message_id")).filter(|v| !v.is_null()) {
+                report_metadata["plan_message_id"] = plan_message_id.clone();
+            }
+            // The report supersedes interim updates; the client folds them under it
+            if let Some(progress_ids) = crew_info["run"].get("progress_message_ids").filter(|v| v.is_array()) {
+                report_metadata["progress_message_ids"] = progress_ids.clone();
+            }
 
             let report_message_id = helpers::insert_ai_message(
                 sit_down_id, member_id, &report_content, &report_metadata, access_token,
             ).unwrap_or_default();
 
-            emit_event(sit_down_id, member_id, member_name, json!({"kind": "message_inserted", "message_id": report_message_id}), access_token);
+            emit_event(events_sid, member_id, member_name, json!({"kind": "message_inserted", "message_id": report_message_id}), access_token);
 
             Ok(json!({
                 "content": content,
                 "message_id": report_message_id,
                 "operation_id": operation_id,
                 "turns": turns,
-                "usage": usage
+                "usage": usage,
+                "answered_by": answered_by
             })
             .to_string())
         }
         Err(e) => {
-            emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": "Operation failed."}), access_token);
+            emit_event(events_sid, member_id, member_name, json!({"kind": "status", "text": "Operation failed."}), access_token);
 
+            helpers::record_catalyst_usage(&billed_to, member_id, sit_down_id, &operation_id);
             let _ = helpers::supabase_call(
                 "db.update",
                 json!({
@@ -220,6 +400,7 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
                     "body": {
                         "status": "failed",
                         "result_content": e,
+                        "spans": recorder.spans(),
                         "completed_at": "now()"
                     },
                     "filters": [
@@ -229,14 +410,21 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
                 }),
             );
 
+            if nested {
+                return Err(e);
+            }
+
             let fail_content = format!("Operation failed: {e}");
-            let fail_metadata = json!({
+            let mut fail_metadata = json!({
                 "provider": catalyst_ref,
                 "model": model,
                 "type": "caporegime_report",
                 "operation_id": operation_id,
                 "status": "failed"
             });
+            if let Some(progress_ids) = crew_info["run"].get("progress_message_ids").filter(|v| v.is_array()) {
+                fail_metadata["progress_message_ids"] = progress_ids.clone();
+            }
 
             let _ = helpers::insert_ai_message(
                 sit_down_id, member_id, &fail_content, &fail_metadata, access_token,
@@ -247,6 +435,46 @@ fn handle_respond(parsed: &Value) -> Result<String, String> {
     }
 }
 
+/// A tool call's span, plus a soldier span under it for delegations. Calls ran as
+/// one parallel batch, so each gets the batch window.
+fn record_tool_span(
+    recorder: &mut trace::Recorder,
+    turn_span: u64,
+    window: (u64, u64),
+    (id, name): (&str, &str),
+    calls: &[(String, String, Value)],
+    result: &Value,
+) {
+    let error = result.get("error").and_then(|v| v.as_str());
+    let mut attributes = json!({ "tool_call_id": id, "cyfr.parallel_calls": calls.len() });
+    if let Some(answered_by) = result.get("answered_by").filter(|v| v.is_object()) {
+        attributes["gen_ai.system"] = answered_by.get("catalyst_ref").cloned().unwrap_or(Value::Null);
+        attributes["gen_ai.response.model"] = answered_by.get("model").cloned().unwrap_or(Value::Null);
+    }
+    if let Some(usage) = result.get("usage") {
+        attributes["gen_ai.usage.input_tokens"] = usage.get("input_tokens").cloned().unwrap_or(Value::Null);
+        attributes["gen_ai.usage.output_tokens"] = usage.get("output_tokens").cloned().unwrap_or(Value::Null);
+    }
+    let tool_span = recorder.record(Some(turn_span), "tool_call", name, window, attributes.clone(), error);
+
+    if name != "delegate" {
+        return;
+    }
+    let soldier = calls
+        .iter()
+        .find(|(call_id, _, _)| call_id == id)
+        .and_then(|(_, _, args)| args.get("soldier_name").and_then(|v| v.as_str()))
+        .unwrap_or("soldier");
+    if let Some(child_op) = result.get("operation_id").filter(|v| v.is_string()) {
+        attributes["child_operation_id"] = child_op.clone();
+    }
+    if let Some(loan_id) = result.get("loan_id").filter(|v| !v.is_null()) {
+        attributes["loan_id"] = loan_id.clone();
+        attributes["lent_by"] = result.get("lent_by").cloned().unwrap_or(Value::Null);
+    }
+    recorder.record(Some(tool_span), "soldier", soldier, window, attributes, error);
+}
+
 fn truncate_json(val: &Value, max: usize) -> String {
     let s = val.to_string();
     if s.len() <= max { s } else { format!("{}…", &s[..max]) }
@@ -257,8 +485,7 @@ fn truncate_json(val: &Value, max: usize) -> String {
 // ---------------------------------------------------------------------------
 
 fn run_agentic_loop(
-    catalyst_ref: &str,
-    model: &str,
+    chain: &[helpers::ModelRef],
     system: &str,
     initial_conversation: &[Value],
     tools_for_llm: &[Value],
@@ -267,8 +494,9 @@ fn run_agentic_loop(
     member_id: &str,
     member_name: &str,
     access_token: &str,
-    crew_info: &Value,
+    crew_info: &mut Value,
     owner_id: &str,
+    recorder: &mut trace::Recorder,
 ) -> Result<Value, String> {
     let mut conversation = initial_conversation.to_vec();
     let mut turns: u64 = 0;
@@ -276,6 +504,7 @@ fn run_agentic_loop(
     let mut total_input_tokens: u64 = 0;
     let mut total_output_tokens: u64 = 0;
     let mut tool_calls_log: Vec<Value> = Vec::new();
+    let mut active = 0;
 
     loop {
         turns += 1;
@@ -284,15 +513,58 @@ fn run_agentic_loop(
             break;
         }
 
-        let provider_label = extract_provider_label(catalyst_ref);
-        emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": format!("Turn {}: Calling {}...", turns, provider_label)}), access_token);
         emit_event(sit_down_id, member_id, member_name, json!({"kind": "turn_start", "turn": turns}), access_token);
+        let turn_span = recorder.start(None, "turn", &format!("turn {turns}"), json!({ "turn": turns }));
+        // Sub-caporegimes delegated this turn inherit what's left of the turn budget
+        crew_info["run"]["remaining_turns"] = json!(max_turns - turns as usize);
+        crew_info["run"]["turn"] = json!(turns);
+
+        let data = loop {
+            let (catalyst_ref, model) = &chain[active];
+            let provider_label = extract_provider_label(catalyst_ref);
+            emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": format!("Turn {}: Calling {}...", turns, provider_label)}), access_token);
+
+            let catalyst_input = tools::build_provider_request_with_tools(
+                catalyst_ref, model, &conversation, system, tools_for_llm, DEFAULT_MAX_TOKENS,
+            );
 
-        let catalyst_input = tools::build_provider_request_with_tools(
-            catalyst_ref, model, &conversation, system, tools_for_llm, DEFAULT_MAX_TOKENS,
-        );
-
-        let data = helpers::invoke_catalyst(catalyst_ref, &catalyst_input)?;
+            let catalyst_span = recorder.start(Some(turn_span), "catalyst", "chat", json!({
+                "gen_ai.system": provider_label,
+                "gen_ai.request.model": model,
+                "cyfr.catalyst_ref": catalyst_ref
+            }));
+            let called = helpers::invoke_catalyst(catalyst_ref, &catalyst_input);
+            let catalyst_attributes = match &called {
+                Ok(data) => json!({
+                    "gen_ai.usage.input_tokens": data.pointer("/usage/input_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+                    "gen_ai.usage.output_tokens": data.pointer("/usage/output_tokens").and_then(|v| v.as_u64()).unwrap_or(0)
+                }),
+                Err(_) => json!({}),
+            };
+            recorder.end(catalyst_span, catalyst_attributes, called.as_ref().err().map(|e| e.as_str()));
+
+            match called {
+                Ok(data) => break data,
+                Err(e) if provider_errors::is_retryable_provider_error(&e) => {
+                    let failed = active;
+                    active = helpers::next_fallback(chain, active, turns > 1).ok_or(e.clone())?;
+                    tool_calls_log.push(json!({
+                        "name": "model_fallback",
+                        "arguments": {
+                            "from": helpers::answered_by_json(&chain[failed]),
+                            "to": helpers::answered_by_json(&chain[active]),
+                            "error": truncate_str(&e, 300)
+                        },
+                        "turn": turns
+                    }));
+                }
+                Err(e) => {
+                    recorder.end(turn_span, json!({}), Some(&e));
+                    return Err(e);
+                }
+            }
+        };
+        let catalyst_ref = chain[active].0.as_str();
 
         if let Some(usage) = data.get("usage") {
             let inp = usage.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
@@ -328,6 +600,7 @@ fn run_agentic_loop(
                 tool_calls_log.push(json!({
                     "name": tc.name,
                     "arguments": tc.arguments,
+                    "tool_call_id": tc.id,
                     "turn": turns
                 }));
             }
@@ -337,11 +610,59 @@ fn run_agentic_loop(
                 .map(|tc| (tc.id.clone(), tc.name.clone(), tc.arguments.clone()))
                 .collect();
 
+            let batch_start = trace::now_ms();
             let results = tools::execute_tools_parallel(
                 &call_tuples, crew_info, member_id, owner_id, access_token,
             );
+            let batch_window = (batch_start, trace::now_ms());
 
             for (id, name, result_str) in &results {
+                let parsed_result = serde_json::from_str::<Value>(result_str).unwrap_or(Value::Null);
+
+                // Sub-caporegime runs count against this run's token usage
+                if let Some(usage) = parsed_result.get("usage") {
+                    total_input_tokens += usage.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+                    total_output_tokens += usage.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+                }
+                // Keep the consultation itself in the operation log, not just the question
+                if name == "consult_member" {
+                    if let Some(answer) = parsed_result.get("answer").and_then(|v| v.as_str()) {
+                        if let Some(entry) = tool_calls_log.iter_mut().rev().find(|e| e.get("tool_call_id").and_then(|v| v.as_str()) == Some(id)) {
+                            entry["answer"] = json!(truncate_str(answer, 2000));
+                        }
+                    }
+                }
+                if name == "post_update" {
+                    if let Some(message_id) = parsed_result.get("message_id").and_then(|v| v.as_str()) {
+                        emit_event(sit_down_id, member_id, member_name, json!({"kind": "message_inserted", "message_id": message_id}), access_token);
+                    }
+                }
+                if let Some(child_op) = parsed_result.get("operation_id").and_then(|v| v.as_str()) {
+                    if let Some(entry) = tool_calls_log.iter_mut().rev().find(|e| e.get("tool_call_id").and_then(|v| v.as_str()) == Some(id)) {
+                        entry["child_operation_id"] = json!(child_op);
+                    }
+                }
+
+                if let Some(loan_id) = parsed_result.get("loan_id").filter(|v| !v.is_null()) {
+                    if let Some(entry) = tool_calls_log.iter_mut().rev().find(|e| e.get("tool_call_id").and_then(|v| v.as_str()) == Some(id)) {
+                        entry["loan_id"] = loan_id.clone();
+                        entry["lent_by"] = parsed_result.get("lent_by").cloned().unwrap_or(Value::Null);
+                    }
+                }
+
+                // Record which model a delegated soldier actually answered with
+                let answered_by = parsed_result
+                    .get("answered_by")
+                    .cloned()
+                    .filter(|a| !a.is_null());
+                if let Some(answered_by) = answered_by {
+                    if let Some(entry) = tool_calls_log.iter_mut().rev().find(|e| e.get("tool_call_id").and_then(|v| v.as_str()) == Some(id)) {
+                        entry["answered_by"] = answered_by;
+                    }
+                }
+
+                record_tool_span(recorder, turn_span, batch_window, (id, name), &call_tuples, &parsed_result);
+
                 let preview = if result_str.len() > 300 { &result_str[..300] } else { result_str };
                 emit_event(sit_down_id, member_id, member_name, json!({
                     "kind": "tool_result", "turn": turns,
@@ -365,11 +686,13 @@ fn run_agentic_loop(
                 conversation.push(tool_results_msg);
             }
 
+            recorder.end(turn_span, json!({ "cyfr.tool_calls": results.len() }), None);
             continue;
         }
 
         let assistant_msg = tools::build_assistant_message(&data, catalyst_ref);
         conversation.push(assistant_msg);
+        recorder.end(turn_span, json!({}), None);
         break;
     }
 
@@ -380,7 +703,8 @@ fn run_agentic_loop(
         "usage": {
             "input_tokens": total_input_tokens,
             "output_tokens": total_output_tokens
-        }
+        },
+        "answered_by": helpers::answered_by_json(&chain[active])
     }))
 }
 
@@ -401,10 +725,82 @@ fn handle_invoke_soldier(parsed: &Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .unwrap_or("");
 
-    let content = helpers::invoke_soldier(soldier, task, access_token)?;
+    if let Some(schema) = parsed.get("output_schema").filter(|s| s.is_object()) {
+        let (output, answered_by) = helpers::invoke_soldier_structured(soldier, task, schema, access_token)?;
+        return Ok(json!({
+            "content": output.to_string(),
+            "output": output,
+            "answered_by": answered_by.as_ref().map(helpers::answered_by_json),
+            "catalyst_usage": helpers::take_catalyst_usage()
+        })
+        .to_string());
+    }
+
+    let (content, answered_by) = helpers::invoke_soldier(soldier, task, access_token)?;
 
     Ok(json!({
-        "content": content
+        "content": content,
+        "answered_by": answered_by.as_ref().map(helpers::answered_by_json),
+        "catalyst_usage": helpers::take_catalyst_usage()
+    })
+    .to_string())
+}
+
+// ===========================================================================
+// export_trace — an operation tree as OTLP/JSON
+// ===========================================================================
+
+fn handle_export_trace(parsed: &Value) -> Result<String, String> {
+    let operation_id = parsed
+        .get("operation_id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'operation_id'")?;
+    let access_token = parsed
+        .get("access_token")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'access_token'")?;
+
+    trace::export_otlp(operation_id, access_token).map(|otlp| otlp.to_string())
+}
+
+// ===========================================================================
+// save_plan_as_job — turn an operation's plan into a reusable job
+// ===========================================================================
+
+fn handle_save_plan_as_job(parsed: &Value) -> Result<String, String> {
+    let operation_id = parsed
+        .get("operation_id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'operation_id'")?;
+    let access_token = parsed
+        .get("access_token")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'access_token'")?;
+
+    let operation = plan::fetch_operation_plan(operation_id, access_token)?;
+    let op_plan = operation.get("plan").filter(|p| p.is_object()).ok_or("This operation has no plan")?;
+    let member_id = operation.get("member_id").and_then(|v| v.as_str()).unwrap_or("");
+    let owner_id = operation.get("owner_id").and_then(|v| v.as_str()).unwrap_or("");
+    let sit_down_id = operation.get("sit_down_id").and_then(|v| v.as_str());
+
+    let (steps, skipped) = plan::plan_to_job_steps(op_plan);
+    if steps.is_empty() {
+        return Err("None of the plan's steps are assigned to a soldier, so it can't run as a job".to_string());
+    }
+
+    let summary = op_plan.get("summary").and_then(|v| v.as_str()).unwrap_or("");
+    let name = parsed
+        .get("name")
+        .and_then(|v| v.as_str())
+        .filter(|n| !n.trim().is_empty())
+        .map(|n| n.trim().to_string())
+        .unwrap_or_else(|| truncate(summary, 80));
+
+    let job = helpers::job_create(member_id, owner_id, &name, Some(summary), &json!(steps), None, sit_down_id, access_token)?;
+
+    Ok(json!({
+        "job": job,
+        "skipped_steps": skipped
     })
     .to_string())
 }
@@ -429,6 +825,14 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
 
     let sit_down_id = parsed.get("sit_down_id").and_then(|v| v.as_str()).unwrap_or("");
 
+    // Usage is written with the service role, so it's billed to the user the token
+    // belongs to, never to the owner_id the request names. The quota checked below
+    // and the jobs RLS lets this token read are that same user's.
+    let billed_to = helpers::caller_id(access_token)?;
+    if owner_id != billed_to {
+        return Err("'owner_id' does not match the caller".to_string());
+    }
+
     // Load job definition: from job_id or inline steps
     let (job_name, steps, job_sit_down_id) = if let Some(job_id) = parsed.get("job_id").and_then(|v| v.as_str()) {
         let job = helpers::job_get(job_id, caporegime_id, access_token)?;
@@ -453,7 +857,7 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
     let steps_arr = steps.as_array().ok_or("'steps' must be an array")?;
 
     // Fetch crew info for soldier lookups
-    let crew_info = fetch_crew_info(caporegime_id, owner_id, access_token);
+    let crew_info = fetch_crew_info(caporegime_id, owner_id, &effective_sid, access_token);
 
     // Fetch caporegime member name for events
     let member_name_result = helpers::supabase_call(
@@ -475,6 +879,15 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .unwrap_or("Caporegime");
 
+    // Jobs run without a Don watching, so the owner's quota is checked before any soldier is invoked
+    if let Some(exceeded) = helpers::check_quota(access_token)? {
+        let reason = helpers::quota_message(&exceeded);
+        emit_event(&effective_sid, caporegime_id, member_name, json!({
+            "kind": "error", "code": "quota_exceeded", "message": reason
+        }), access_token);
+        return Err(reason);
+    }
+
     // Create operation record
     let operation = helpers::supabase_call(
         "db.insert",
@@ -500,6 +913,7 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
+    set_run_id(&operation_id);
 
     if !effective_sid.is_empty() {
         emit_event(&effective_sid, caporegime_id, member_name, json!({
@@ -510,6 +924,7 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
     // Execute steps
     let mut step_results: HashMap<String, Value> = HashMap::new();
     let mut tool_calls_log: Vec<Value> = Vec::new();
+    let mut recorder = trace::Recorder::default();
     let mut last_output = String::new();
 
     for step in steps_arr {
@@ -522,17 +937,19 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
             }), access_token);
         }
 
+        let step_span = recorder.start(None, "step", step_id, json!({ "cyfr.step.type": step_type }));
         let result = match step_type {
             "for_each" => execute_for_each_step(
-                step, &crew_info, &step_results, caporegime_id, owner_id, access_token,
-                &operation_id, &mut tool_calls_log,
+                step, &crew_info, &step_results, owner_id, access_token,
+                (&mut recorder, step_span), &mut tool_calls_log,
             ),
             "delegate" => execute_delegate_step(
                 step, &crew_info, &step_results, access_token,
-                &operation_id, &mut tool_calls_log,
+                (&mut recorder, step_span), &mut tool_calls_log,
             ),
             _ => Err(format!("Unknown step type: {step_type}")),
         };
+        recorder.end(step_span, json!({}), result.as_ref().err().map(|e| e.as_str()));
 
         match result {
             Ok(step_output) => {
@@ -554,7 +971,7 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
                     "db.update",
                     json!({
                         "table": "operations",
-                        "body": { "tool_calls": tool_calls_log },
+                        "body": { "tool_calls": tool_calls_log, "spans": recorder.spans() },
                         "filters": [{"column": "id", "op": "eq", "value": operation_id}],
                         "access_token": access_token
                     }),
@@ -570,12 +987,14 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
                             "status": "failed",
                             "result_content": format!("Step '{}' failed: {}", step_id, e),
                             "tool_calls": tool_calls_log,
+                            "spans": recorder.spans(),
                             "completed_at": "now()"
                         },
                         "filters": [{"column": "id", "op": "eq", "value": operation_id}],
                         "access_token": access_token
                     }),
                 );
+                helpers::record_catalyst_usage(&billed_to, caporegime_id, &effective_sid, &operation_id);
                 return Err(format!("Step '{}' failed: {}", step_id, e));
             }
         }
@@ -596,6 +1015,7 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
                 "status": "completed",
                 "result_content": summary,
                 "tool_calls": tool_calls_log,
+                "spans": recorder.spans(),
                 "completed_at": "now()"
             },
             "filters": [{"column": "id", "op": "eq", "value": operation_id}],
@@ -603,6 +1023,9 @@ fn handle_execute_job(parsed: &Value) -> Result<String, String> {
         }),
     );
 
+    // No sit-down records a job's usage, so the job writes it for its owner
+    helpers::record_catalyst_usage(&billed_to, caporegime_id, &effective_sid, &operation_id);
+
     // Post summary to sit-down if configured
     if !effective_sid.is_empty() {
         let report = format!("**Job completed: {}**\n\n{}", job_name, summary);
@@ -636,19 +1059,16 @@ fn execute_for_each_step(
     step: &Value,
     crew_info: &Value,
     step_results: &HashMap<String, Value>,
-    _caporegime_id: &str,
     owner_id: &str,
     access_token: &str,
-    _operation_id: &str,
+    (recorder, step_span): (&mut trace::Recorder, u64),
     tool_calls_log: &mut Vec<Value>,
 ) -> Result<Value, String> {
     let step_id = step.get("id").and_then(|v| v.as_str()).unwrap_or("");
-    let soldier_name = step.get("soldier").and_then(|v| v.as_str()).unwrap_or("");
     let prompt_template = step.get("prompt").and_then(|v| v.as_str()).unwrap_or("");
     let parallel = step.get("parallel").and_then(|v| v.as_bool()).unwrap_or(true);
 
-    let soldier = find_soldier(crew_info, soldier_name)
-        .ok_or_else(|| format!("Soldier '{}' not found", soldier_name))?;
+    let (worker_label, worker) = resolve_step_worker(step, crew_info)?;
 
     // Resolve items: inline array or bookkeeper source
     let items = resolve_items(step, crew_info, owner_id, access_token)?;
@@ -663,49 +1083,71 @@ fn execute_for_each_step(
         (item.clone(), prompt)
     }).collect();
 
-    let results = if parallel && prompts.len() > 1 {
-        // Parallel: spawn all, await all
-        let task_ids: Vec<String> = prompts.iter().map(|(_, prompt)| {
-            helpers::spawn_soldier(soldier, prompt, access_token)
-        }).collect();
-
-        let awaited = helpers::await_all_tasks(&task_ids);
-
-        prompts.iter().zip(awaited.into_iter()).map(|((item, prompt), result)| {
-            let output = result.unwrap_or_else(|e| format!("Error: {e}"));
-            let item_label = item.get("title").and_then(|v| v.as_str())
-                .or_else(|| item.as_str())
-                .unwrap_or("item");
-            tool_calls_log.push(json!({
-                "step_id": step_id,
-                "soldier": soldier_name,
-                "item": item_label,
-                "input": truncate_str(prompt, 500),
-                "output": truncate_str(&output, 2000),
-                "status": "completed"
-            }));
-            json!({"item": item, "result": output})
-        }).collect::<Vec<Value>>()
-    } else {
+    // Each item's (start_ms, end_ms): its own call when sequential, the whole batch when parallel
+    let mut windows: Vec<(u64, u64)> = Vec::new();
+    let outputs: Vec<(Value, Value)> = match &worker {
+        // Parallel: spawn all, await all (reagents are cheap and deterministic — always sequential)
+        StepWorker::Soldier { soldier, schema } if parallel && prompts.len() > 1 => {
+            let batch_start = trace::now_ms();
+            let task_ids: Vec<String> = prompts.iter().map(|(_, prompt)| {
+                helpers::spawn_soldier(soldier, prompt, schema.as_ref(), access_token)
+            }).collect();
+
+            let awaited = helpers::await_all_tasks(&task_ids);
+            windows = vec![(batch_start, trace::now_ms()); awaited.len()];
+            awaited
+                .into_iter()
+                .map(|result| match result {
+                    Ok(task_result) => {
+                        if let Some(usage) = task_result.get("catalyst_usage") {
+                            helpers::merge_catalyst_usage(usage);
+                        }
+                        let answered_by = task_result.get("answered_by").cloned().unwrap_or(Value::Null);
+                        // Structured tasks return their validated JSON as `output`
+                        let output = match task_result.get("output") {
+                            Some(output) if schema.is_some() => output.clone(),
+                            _ => task_result.get("content").cloned().unwrap_or(task_result),
+                        };
+                        (output, answered_by)
+                    }
+                    Err(e) => (json!(format!("Error: {e}")), Value::Null),
+                })
+                .collect()
+        }
         // Sequential: one at a time
-        prompts.iter().map(|(item, prompt)| {
-            let output = helpers::invoke_soldier(soldier, prompt, access_token)
-                .unwrap_or_else(|e| format!("Error: {e}"));
-            let item_label = item.get("title").and_then(|v| v.as_str())
-                .or_else(|| item.as_str())
-                .unwrap_or("item");
-            tool_calls_log.push(json!({
-                "step_id": step_id,
-                "soldier": soldier_name,
-                "item": item_label,
-                "input": truncate_str(prompt, 500),
-                "output": truncate_str(&output, 2000),
-                "status": "completed"
-            }));
-            json!({"item": item, "result": output})
-        }).collect::<Vec<Value>>()
+        _ => prompts.iter().map(|(item, prompt)| {
+            let started = trace::now_ms();
+            let outcome = match run_step_worker(&worker, prompt, Some(item), access_token) {
+                Ok((output, answered_by)) => (output, answered_by.as_ref().map(helpers::answered_by_json).unwrap_or(Value::Null)),
+                Err(e) => (json!(format!("Error: {e}")), Value::Null),
+            };
+            windows.push((started, trace::now_ms()));
+            outcome
+        }).collect(),
     };
 
+    let results = prompts.iter().zip(outputs).enumerate().map(|(i, ((item, prompt), (output, answered_by)))| {
+        let item_label = item.get("title").and_then(|v| v.as_str())
+            .or_else(|| item.as_str())
+            .unwrap_or("item");
+        let error = output.as_str().filter(|s| s.starts_with("Error: "));
+        let window = windows.get(i).copied().unwrap_or_else(|| (trace::now_ms(), trace::now_ms()));
+        recorder.record(Some(step_span), "soldier", &worker_label, window, json!({
+            "item": item_label,
+            "gen_ai.response.model": answered_by.get("model").cloned().unwrap_or(Value::Null)
+        }), error);
+        tool_calls_log.push(json!({
+            "step_id": step_id,
+            "soldier": worker_label,
+            "item": item_label,
+            "input": truncate_str(prompt, 500),
+            "output": truncate_str(&helpers::reagent_output_to_string(&output), 2000),
+            "answered_by": answered_by,
+            "status": "completed"
+        }));
+        json!({"item": item, "result": output})
+    }).collect::<Vec<Value>>();
+
     Ok(json!(results))
 }
 
@@ -714,30 +1156,108 @@ fn execute_delegate_step(
     crew_info: &Value,
     step_results: &HashMap<String, Value>,
     access_token: &str,
-    _operation_id: &str,
+    (recorder, step_span): (&mut trace::Recorder, u64),
     tool_calls_log: &mut Vec<Value>,
 ) -> Result<Value, String> {
     let step_id = step.get("id").and_then(|v| v.as_str()).unwrap_or("");
-    let soldier_name = step.get("soldier").and_then(|v| v.as_str()).unwrap_or("");
     let prompt_template = step.get("prompt").and_then(|v| v.as_str()).unwrap_or("");
 
-    let soldier = find_soldier(crew_info, soldier_name)
-        .ok_or_else(|| format!("Soldier '{}' not found", soldier_name))?;
+    let (worker_label, worker) = resolve_step_worker(step, crew_info)?;
 
     // Resolve template variables (no item context for delegate)
     let prompt = resolve_template_no_item(prompt_template, step_results);
 
-    let output = helpers::invoke_soldier(soldier, &prompt, access_token)?;
+    let soldier_span = recorder.start(Some(step_span), "soldier", &worker_label, json!({}));
+    let ran = run_step_worker(&worker, &prompt, None, access_token);
+    let soldier_attributes = match &ran {
+        Ok((_, Some(answered_by))) => json!({ "gen_ai.system": answered_by.0, "gen_ai.response.model": answered_by.1 }),
+        _ => json!({}),
+    };
+    recorder.end(soldier_span, soldier_attributes, ran.as_ref().err().map(|e| e.as_str()));
+    let (output, answered_by) = ran?;
 
     tool_calls_log.push(json!({
         "step_id": step_id,
-        "soldier": soldier_name,
+        "soldier": worker_label,
         "input": truncate_str(&prompt, 500),
-        "output": truncate_str(&output, 2000),
+        "output": truncate_str(&helpers::reagent_output_to_string(&output), 2000),
+        "answered_by": answered_by.as_ref().map(helpers::answered_by_json),
         "status": "completed"
     }));
 
-    Ok(json!(output))
+    Ok(output)
+}
+
+// ---------------------------------------------------------------------------
+// Step workers (crew soldier or reagent)
+// ---------------------------------------------------------------------------
+
+/// What a job step runs: an LLM soldier from the crew, or a reagent — either
+/// referenced directly by the step (`reagent`) or via a reagent-type soldier.
+/// `schema` is the step's `output_schema`, falling back to the soldier's own.
+enum StepWorker<'a> {
+    Soldier { soldier: &'a Value, schema: Option<Value> },
+    Reagent { reference: String, config: Option<Value>, schema: Option<Value> },
+}
+
+fn resolve_step_worker<'a>(step: &Value, crew_info: &'a Value) -> Result<(String, StepWorker<'a>), String> {
+    let step_schema = step.get("output_schema").filter(|s| s.is_object()).cloned();
+
+    if let Some(reagent_ref) = step.get("reagent").and_then(|v| v.as_str()) {
+        let worker = StepWorker::Reagent {
+            reference: reagent_ref.to_string(),
+            config: step.get("reagent_config").cloned(),
+            schema: step_schema,
+        };
+        return Ok((reagent_ref.to_string(), worker));
+    }
+
+    let soldier_name = step.get("soldier").and_then(|v| v.as_str()).unwrap_or("");
+    let soldier = find_soldier(crew_info, soldier_name)
+        .ok_or_else(|| format!("Soldier '{}' not found", soldier_name))?;
+    let schema = step_schema.or_else(|| helpers::soldier_output_schema(soldier));
+
+    let soldier_type = soldier.get("soldier_type").and_then(|v| v.as_str()).unwrap_or("default");
+    if soldier_type == "reagent" {
+        let config = soldier.get("soldier_config").cloned().unwrap_or(json!({}));
+        let worker = StepWorker::Reagent {
+            reference: config.get("reagent_ref").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            config: config.get("config").cloned(),
+            schema,
+        };
+        return Ok((soldier_name.to_string(), worker));
+    }
+
+    Ok((soldier_name.to_string(), StepWorker::Soldier { soldier, schema }))
+}
+
+/// Run a step worker. Soldiers return text unless an output schema applies;
+/// reagents return their structured output as-is (validated when a schema applies).
+/// Also returns the model that answered, for the operation log (`None` for reagents).
+fn run_step_worker(
+    worker: &StepWorker,
+    prompt: &str,
+    item: Option<&Value>,
+    access_token: &str,
+) -> Result<(Value, Option<helpers::ModelRef>), String> {
+    match worker {
+        StepWorker::Soldier { soldier, schema: Some(schema) } => {
+            helpers::invoke_soldier_structured(soldier, prompt, schema, access_token)
+        }
+        StepWorker::Soldier { soldier, schema: None } => {
+            helpers::invoke_soldier(soldier, prompt, access_token).map(|(s, m)| (json!(s), m))
+        }
+        StepWorker::Reagent { reference, config, schema } => {
+            let input = helpers::build_reagent_input(prompt, item, config.as_ref());
+            let output = helpers::invoke_reagent(reference, &input)?;
+            if let Some(schema) = schema {
+                schema::validate(&output, schema).map_err(|errors| {
+                    format!("Reagent output does not match output_schema: {}", errors.join("; "))
+                })?;
+            }
+            Ok((output, None))
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -798,27 +1318,17 @@ fn resolve_template(template: &str, item: &Value, step_results: &HashMap<String,
         result = result.replace("{{item}}", &serde_json::to_string_pretty(item).unwrap_or_default());
     }
 
-    // Replace {{item.field}} patterns
-    if let Some(obj) = item.as_object() {
-        for (key, val) in obj {
-            let placeholder = format!("{{{{item.{}}}}}", key);
-            let replacement = val.as_str().map(|s| s.to_string())
-                .unwrap_or_else(|| val.to_string());
-            result = result.replace(&placeholder, &replacement);
+    // Replace {{item.field}} / {{item.field[0].sub}} paths
+    result = replace_placeholders(&result, |expr| {
+        let path = expr.strip_prefix("item")?;
+        if !path.starts_with('.') && !path.starts_with('[') {
+            return None;
         }
-    }
-
-    // Replace {{today}}
-    result = result.replace("{{today}}", &today_date());
-
-    // Replace {{step_id.results}} patterns
-    for (step_id, step_output) in step_results {
-        let placeholder = format!("{{{{{}.results}}}}", step_id);
-        let replacement = serde_json::to_string_pretty(step_output).unwrap_or_default();
-        result = result.replace(&placeholder, &replacement);
-    }
+        schema::resolve_path(item, path)
+            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+    });
 
-    result
+    resolve_template_no_item(&result, step_results)
 }
 
 fn resolve_template_no_item(template: &str, step_results: &HashMap<String, Value>) -> String {
@@ -827,14 +1337,41 @@ fn resolve_template_no_item(template: &str, step_results: &HashMap<String, Value
     // Replace {{today}}
     result = result.replace("{{today}}", &today_date());
 
-    // Replace {{step_id.results}} patterns
-    for (step_id, step_output) in step_results {
-        let placeholder = format!("{{{{{}.results}}}}", step_id);
-        let replacement = serde_json::to_string_pretty(step_output).unwrap_or_default();
-        result = result.replace(&placeholder, &replacement);
+    // Replace {{step_id.results}} and {{step_id.results[0].field}} paths
+    replace_placeholders(&result, |expr| {
+        let (step_id, path) = expr.split_once(".results")?;
+        let step_output = step_results.get(step_id)?;
+        if path.is_empty() {
+            return Some(serde_json::to_string_pretty(step_output).unwrap_or_default());
+        }
+        schema::resolve_path(step_output, path).map(placeholder_text)
+    })
+}
+
+/// Replace every `{{expr}}` for which `resolve` returns a value; others are left untouched.
+fn replace_placeholders(template: &str, resolve: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else { break };
+        let expr = &rest[start + 2..start + 2 + len];
+        out.push_str(&rest[..start]);
+        match resolve(expr.trim()) {
+            Some(replacement) => out.push_str(&replacement),
+            None => out.push_str(&rest[start..start + 2 + len + 2]),
+        }
+        rest = &rest[start + 2 + len + 2..];
     }
 
-    result
+    out.push_str(rest);
+    out
+}
+
+/// Strings are spliced raw; anything else as pretty JSON (matching `{{step.results}}`).
+fn placeholder_text(value: &Value) -> String {
+    value.as_str().map(|s| s.to_string())
+        .unwrap_or_else(|| serde_json::to_string_pretty(value).unwrap_or_default())
 }
 
 fn today_date() -> String {
@@ -847,12 +1384,12 @@ fn today_date() -> String {
 // Crew helpers
 // ---------------------------------------------------------------------------
 
-fn fetch_crew_info(caporegime_id: &str, owner_id: &str, access_token: &str) -> Value {
+fn fetch_crew_info(caporegime_id: &str, owner_id: &str, sit_down_id: &str, access_token: &str) -> Value {
     let soldiers = helpers::supabase_call(
         "db.select",
         json!({
             "table": "members",
-            "select": "id,name,system_prompt,soldier_type,soldier_config,catalog_model:model_catalog(provider,model,alias)",
+            "select": "id,name,system_prompt,soldier_type,soldier_config,fallback_model_ids,catalog_model:model_catalog(id,provider,model,alias)",
             "filters": [
                 { "column": "caporegime_id", "op": "eq", "value": caporegime_id },
                 { "column": "member_type", "op": "eq", "value": "soldier" }
@@ -860,6 +1397,38 @@ fn fetch_crew_info(caporegime_id: &str, owner_id: &str, access_token: &str) -> V
             "access_token": access_token
         }),
     ).unwrap_or(json!([]));
+    let mut soldiers = route_catalog_aliases(attach_fallback_models(soldiers, access_token), access_token);
+
+    // Soldiers allied Dons have lent to this caporegime (active, unexpired, in scope)
+    let sit_down_scope = if sit_down_id.is_empty() { Value::Null } else { json!(sit_down_id) };
+    let borrowed = helpers::supabase_call(
+        "db.rpc",
+        json!({
+            "function": "list_borrowed_soldiers",
+            "body": { "p_caporegime_id": caporegime_id, "p_sit_down_id": sit_down_scope },
+            "access_token": access_token
+        }),
+    ).unwrap_or(json!([]));
+    if let (Some(own), Value::Array(borrowed)) = (
+        soldiers.as_array_mut(),
+        route_catalog_aliases(attach_fallback_models(borrowed, access_token), access_token),
+    ) {
+        own.extend(borrowed);
+    }
+
+    let captains = helpers::supabase_call(
+        "db.select",
+        json!({
+            "table": "members",
+            "select": "id,name,owner_id,member_type,system_prompt,fallback_model_ids,catalog_model:model_catalog(id,provider,model,alias)",
+            "filters": [
+                { "column": "caporegime_id", "op": "eq", "value": caporegime_id },
+                { "column": "member_type", "op": "eq", "value": "caporegime" }
+            ],
+            "access_token": access_token
+        }),
+    ).unwrap_or(json!([]));
+    let captains = route_catalog_aliases(attach_fallback_models(captains, access_token), access_token);
 
     let bookkeepers = helpers::supabase_call(
         "db.select",
@@ -876,10 +1445,147 @@ fn fetch_crew_info(caporegime_id: &str, owner_id: &str, access_token: &str) -> V
 
     json!({
         "soldiers": soldiers,
+        "captains": captains,
         "bookkeepers": bookkeepers
     })
 }
 
+/// Consuls and bookkeepers in this sit-down that the caporegime may consult.
+/// Only the Don's own members — an ally's consul answers to its own Don.
+fn table_members(context: &Value, member_id: &str, owner_id: &str, access_token: &str) -> Value {
+    let members: Vec<Value> = context
+        .get("participants")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p.get("member").filter(|m| m.is_object()))
+        .filter(|m| {
+            let member_type = m.get("member_type").and_then(|v| v.as_str()).unwrap_or("");
+            (member_type == "consul" || member_type == "bookkeeper")
+                && m.get("owner_id").and_then(|v| v.as_str()) == Some(owner_id)
+                && m.get("id").and_then(|v| v.as_str()) != Some(member_id)
+        })
+        .cloned()
+        .collect();
+    attach_fallback_models(json!(members), access_token)
+}
+
+/// Capability a crew member's model must not lack: tool calling for
+/// caporegimes, bookkeepers and external soldiers, native web search for
+/// consuls and default soldiers. Reagent soldiers have no model.
+fn required_capability(member: &Value) -> Option<&'static str> {
+    match (
+        member.get("member_type").and_then(|v| v.as_str()),
+        member.get("soldier_type").and_then(|v| v.as_str()),
+    ) {
+        (Some("caporegime" | "bookkeeper"), _) | (_, Some("external")) => Some("tools"),
+        (_, Some("reagent")) => None,
+        _ => Some("web_search"),
+    }
+}
+
+/// Resolve each member's `fallback_model_ids` into ordered `fallback_models`
+/// (`{provider, model, alias}`) with one catalog lookup for the whole crew.
+/// Inactive or removed catalog entries are dropped from the chain, as are
+/// models whose `capabilities` rule out what the member's role needs.
+fn attach_fallback_models(soldiers: Value, access_token: &str) -> Value {
+    let Value::Array(mut soldiers) = soldiers else { return soldiers };
+
+    let mut ids: Vec<String> = Vec::new();
+    for soldier in &soldiers {
+        let soldier_ids = soldier.get("fallback_model_ids").and_then(|v| v.as_array());
+        for id in soldier_ids.into_iter().flatten().filter_map(|v| v.as_str()) {
+            if !ids.iter().any(|i| i == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    if ids.is_empty() {
+        return Value::Array(soldiers);
+    }
+
+    let catalog = helpers::supabase_call(
+        "db.select",
+        json!({
+            "table": "model_catalog",
+            "select": "id,provider,model,alias,capabilities",
+            "filters": [
+                { "column": "id", "op": "in", "value": format!("({})", ids.join(",")) },
+                { "column": "is_active", "op": "eq", "value": "true" }
+            ],
+            "access_token": access_token
+        }),
+    ).unwrap_or(json!([]));
+    let catalog_arr = catalog.as_array().cloned().unwrap_or_default();
+
+    for soldier in soldiers.iter_mut() {
+        let required = required_capability(soldier);
+        let fallback_models: Vec<Value> = soldier
+            .get("fallback_model_ids")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|id| {
+                catalog_arr.iter().find(|m| m.get("id") == Some(id)).cloned()
+            })
+            .filter(|m| {
+                required.and_then(|cap| m.get("capabilities")?.get(cap)?.as_bool()) != Some(false)
+            })
+            .map(|mut m| {
+                if let Some(obj) = m.as_object_mut() {
+                    obj.remove("capabilities");
+                }
+                m
+            })
+            .collect();
+        soldier["fallback_models"] = json!(fallback_models);
+    }
+
+    Value::Array(soldiers)
+}
+
+/// Weighted aliases: swap each member's catalog model for the target its
+/// alias routes to (sticky per member when the entry asks for it) and keep
+/// the pick under `routing`. Plain entries, and failed picks, are left as is.
+fn route_catalog_aliases(members: Value, access_token: &str) -> Value {
+    let Value::Array(mut members) = members else { return members };
+
+    for member in members.iter_mut() {
+        let Some(catalog_id) = member
+            .get("catalog_model")
+            .and_then(|c| c.get("id"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+        else {
+            continue;
+        };
+        let member_id = member.get("id").cloned().unwrap_or(Value::Null);
+        let pick = helpers::supabase_call(
+            "db.rpc",
+            json!({
+                "function": "pick_catalog_target",
+                "body": { "p_catalog_id": catalog_id, "p_member_id": member_id },
+                "access_token": access_token
+            }),
+        ).unwrap_or(Value::Null);
+        if pick.get("routed").and_then(|v| v.as_bool()) != Some(true) {
+            continue;
+        }
+
+        member["catalog_model"]["provider"] = pick.get("provider").cloned().unwrap_or(Value::Null);
+        member["catalog_model"]["model"] = pick.get("model").cloned().unwrap_or(Value::Null);
+        member["routing"] = json!({
+            "catalog_id": pick.get("catalog_id"),
+            "alias": pick.get("alias"),
+            "target_catalog_id": pick.get("target_catalog_id"),
+            "target_alias": pick.get("target_alias"),
+            "sticky": pick.get("sticky")
+        });
+    }
+
+    Value::Array(members)
+}
+
 /// Find a member by name: tries exact (case-insensitive), then substring match.
 fn fuzzy_find<'a>(members: &'a [Value], name: &str) -> Option<&'a Value> {
     let needle = name.to_lowercase();
@@ -912,7 +1618,7 @@ fn find_bookkeeper<'a>(crew_info: &'a Value, name: &str) -> Option<&'a Value> {
     fuzzy_find(bookkeepers, name)
 }
 
-fn build_enriched_system(base_system: &str, crew_info: &Value, member_name: &str) -> String {
+fn build_enriched_system(base_system: &str, crew_info: &Value, member_name: &str, plan: Option<&Value>) -> String {
     let mut enriched = format!(
         "You are {member_name}, a Caporegime in the Family. You are an orchestrator — you receive \
          orders from the Don, work through them using your tools, and report back with results.\n\n\
@@ -929,10 +1635,36 @@ fn build_enriched_system(base_system: &str, crew_info: &Value, member_name: &str
             let model_info = soldier.get("catalog_model")
                 .and_then(|cm| cm.get("alias"))
                 .and_then(|v| v.as_str())
+                .or_else(|| soldier.get("soldier_config").and_then(|c| c.get("reagent_ref")).and_then(|v| v.as_str()))
                 .unwrap_or("unknown model");
             let soldier_type = soldier.get("soldier_type").and_then(|v| v.as_str()).unwrap_or("default");
-            let type_tag = if soldier_type == "external" { " [API-connected]" } else { "" };
-            enriched.push_str(&format!("- {name} ({model_info}){type_tag}: {}\n", truncate(prompt, 100)));
+            let type_tag = match soldier_type {
+                "external" => " [API-connected]",
+                "reagent" => " [deterministic reagent — pass raw data as the task]",
+                _ => "",
+            };
+            let loan_tag = soldier
+                .get("lender_name")
+                .and_then(|v| v.as_str())
+                .map(|lender| format!(" [on loan from Don {lender}]"))
+                .unwrap_or_default();
+            // Lent soldiers arrive without their prompt
+            if prompt.is_empty() {
+                enriched.push_str(&format!("- {name} ({model_info}){type_tag}{loan_tag}\n"));
+            } else {
+                enriched.push_str(&format!("- {name} ({model_info}){type_tag}{loan_tag}: {}\n", truncate(prompt, 100)));
+            }
+        }
+        enriched.push('\n');
+    }
+
+    let captains = crew_info.get("captains").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    if !captains.is_empty() {
+        enriched.push_str("SUB-CAPOREGIMES (run their own crews — `delegate` multi-step work to them by name):\n");
+        for captain in &captains {
+            let name = captain.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown");
+            let prompt = captain.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
+            enriched.push_str(&format!("- {name} [caporegime]: {}\n", truncate(prompt, 100)));
         }
         enriched.push('\n');
     }
@@ -947,12 +1679,28 @@ fn build_enriched_system(base_system: &str, crew_info: &Value, member_name: &str
         enriched.push('\n');
     }
 
+    let table = crew_info.get("table").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    if !table.is_empty() {
+        enriched.push_str("AT THE TABLE (use `consult_member` to ask for an opinion or a records check):\n");
+        for m in &table {
+            let name = m.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown");
+            let member_type = m.get("member_type").and_then(|v| v.as_str()).unwrap_or("consul");
+            let prompt = m.get("system_prompt").and_then(|v| v.as_str()).unwrap_or("");
+            enriched.push_str(&format!("- {name} [{member_type}]: {}\n", truncate(prompt, 100)));
+        }
+        enriched.push('\n');
+    }
+
     enriched.push_str("WORKFLOW TOOLS:\n\
         - `read_journal`: Review past operations and their results\n\
         - `create_job`: Save a reusable workflow with optional cron schedule\n\
         - `list_jobs`: View saved jobs\n\
         - `run_job`: Execute a saved job immediately\n\n");
 
+    if let Some(plan) = plan {
+        enriched.push_str(&plan::plan_section(plan));
+    }
+
     enriched.push_str("---\n\n");
     enriched.push_str(base_system);
 
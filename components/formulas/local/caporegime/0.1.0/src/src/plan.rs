@@ -0,0 +1,203 @@
+use serde_json::{json, Value};
+
+use crate::helpers::{self, ModelRef};
+
+// ---------------------------------------------------------------------------
+// Plan-then-execute (optional planning phase for Brain mode)
+// ---------------------------------------------------------------------------
+//
+// A plan is stored on `operations.plan` as
+// `{summary, steps: [{id, description, soldier?, expected_output?, status, note?}], message_id?}`.
+// Step status moves pending → in_progress → done | skipped | failed as the
+// agentic loop reports progress through the `update_plan_item` tool.
+
+const MAX_PLAN_STEPS: u64 = 12;
+const STEP_STATUSES: [&str; 5] = ["pending", "in_progress", "done", "skipped", "failed"];
+
+const PLANNING_INSTRUCTIONS: &str = "PLANNING PHASE: Do not start the work yet. Draft a plan for the Don's \
+    latest orders: a one-sentence summary, then the ordered steps needed to carry them out. For each step \
+    give a short id (s1, s2, ...), what will be done, which soldier or sub-caporegime will do it (omit if you \
+    will handle it yourself with your own tools) and the output you expect back. Keep it to the steps that \
+    are actually needed.";
+
+fn plan_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["summary", "steps"],
+        "properties": {
+            "summary": { "type": "string" },
+            "steps": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": MAX_PLAN_STEPS,
+                "items": {
+                    "type": "object",
+                    "required": ["id", "description"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "description": { "type": "string" },
+                        "soldier": { "type": "string" },
+                        "expected_output": { "type": "string" }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Ask the model chain for a structured plan. Steps come back with unique ids
+/// and a `pending` status.
+pub fn generate_plan(chain: &[ModelRef], system: &str, conversation: &[Value]) -> Result<(Value, ModelRef), String> {
+    let planning_system = format!("{system}\n\n---\n\n{PLANNING_INSTRUCTIONS}");
+    let schema = plan_schema();
+    let messages = conversation.to_vec();
+
+    let (mut plan, answered_by) = helpers::with_fallback(chain, |catalyst_ref, model| {
+        helpers::request_structured(catalyst_ref, model, &planning_system, messages.clone(), &schema)
+    })?;
+
+    let mut seen: Vec<String> = Vec::new();
+    if let Some(steps) = plan.get_mut("steps").and_then(|v| v.as_array_mut()) {
+        for (i, step) in steps.iter_mut().enumerate() {
+            let id = step.get("id").and_then(|v| v.as_str()).unwrap_or("").trim().to_string();
+            let id = if id.is_empty() || seen.contains(&id) { format!("s{}", i + 1) } else { id };
+            seen.push(id.clone());
+            step["id"] = json!(id);
+            step["status"] = json!("pending");
+        }
+    }
+
+    Ok((plan, answered_by))
+}
+
+/// Markdown rendering of a plan for the sit-down message.
+pub fn render_plan(plan: &Value) -> String {
+    let summary = plan.get("summary").and_then(|v| v.as_str()).unwrap_or("");
+    let mut out = format!("**Plan:** {summary}\n");
+    for (i, step) in plan_steps(plan).iter().enumerate() {
+        let description = step.get("description").and_then(|v| v.as_str()).unwrap_or("");
+        out.push_str(&format!("\n{}. {description}", i + 1));
+        if let Some(soldier) = step.get("soldier").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            out.push_str(&format!(" — *{soldier}*"));
+        }
+        if let Some(expected) = step.get("expected_output").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            out.push_str(&format!("\n   → {expected}"));
+        }
+    }
+    out
+}
+
+/// System prompt section that pins the execution phase to the plan.
+pub fn plan_section(plan: &Value) -> String {
+    let mut section = String::from(
+        "APPROVED PLAN (work through it in order; call `update_plan_item` when you start and finish each step, \
+         and mark steps you drop as skipped with a reason):\n",
+    );
+    for step in plan_steps(plan) {
+        let id = step.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        let description = step.get("description").and_then(|v| v.as_str()).unwrap_or("");
+        let status = step.get("status").and_then(|v| v.as_str()).unwrap_or("pending");
+        section.push_str(&format!("- [{id}] ({status}) {description}"));
+        if let Some(soldier) = step.get("soldier").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            section.push_str(&format!(" — assigned to {soldier}"));
+        }
+        if let Some(expected) = step.get("expected_output").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            section.push_str(&format!(" — expect: {expected}"));
+        }
+        section.push('\n');
+    }
+    section.push('\n');
+    section
+}
+
+fn plan_steps(plan: &Value) -> Vec<Value> {
+    plan.get("steps").and_then(|v| v.as_array()).cloned().unwrap_or_default()
+}
+
+pub fn fetch_operation_plan(operation_id: &str, access_token: &str) -> Result<Value, String> {
+    let rows = helpers::supabase_call(
+        "db.select",
+        json!({
+            "table": "operations",
+            "select": "id,member_id,owner_id,sit_down_id,status,plan,spans",
+            "filters": [{ "column": "id", "op": "eq", "value": operation_id }],
+            "access_token": access_token
+        }),
+    )?;
+    rows.as_array()
+        .and_then(|a| a.first())
+        .cloned()
+        .ok_or_else(|| "Operation not found".to_string())
+}
+
+/// Store the plan on the operation and mirror it onto the plan message, if one was posted.
+pub fn save_plan(operation_id: &str, plan: &Value, access_token: &str) -> Result<(), String> {
+    helpers::supabase_call(
+        "db.update",
+        json!({
+            "table": "operations",
+            "body": { "plan": plan },
+            "filters": [{ "column": "id", "op": "eq", "value": operation_id }],
+            "access_token": access_token
+        }),
+    )?;
+    if let Some(message_id) = plan.get("message_id").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+        helpers::update_message_metadata(message_id, &json!({ "plan": plan }), access_token)?;
+    }
+    Ok(())
+}
+
+/// Set one step's status (and optional note). Returns the updated step.
+pub fn update_item(operation_id: &str, item_id: &str, status: &str, note: Option<&str>, access_token: &str) -> Result<Value, String> {
+    if !STEP_STATUSES.contains(&status) {
+        return Err(format!("Invalid status '{status}'. Use one of: {}", STEP_STATUSES.join(", ")));
+    }
+
+    let operation = fetch_operation_plan(operation_id, access_token)?;
+    let mut plan = operation.get("plan").cloned().filter(|p| p.is_object()).ok_or("This operation has no plan")?;
+
+    let steps = plan.get_mut("steps").and_then(|v| v.as_array_mut()).ok_or("This operation has no plan")?;
+    let step = steps
+        .iter_mut()
+        .find(|s| s.get("id").and_then(|v| v.as_str()) == Some(item_id))
+        .ok_or_else(|| format!("Plan item '{item_id}' not found"))?;
+    step["status"] = json!(status);
+    if let Some(note) = note.filter(|n| !n.is_empty()) {
+        step["note"] = json!(note);
+    }
+    let updated = step.clone();
+
+    save_plan(operation_id, &plan, access_token)?;
+    Ok(updated)
+}
+
+/// Convert plan steps into mechanical job steps. Only steps assigned to a
+/// soldier can run without an orchestration LLM; the rest are reported as skipped.
+pub fn plan_to_job_steps(plan: &Value) -> (Vec<Value>, Vec<String>) {
+    let mut job_steps: Vec<Value> = Vec::new();
+    let mut skipped = Vec::new();
+    for step in plan_steps(plan) {
+        let id = step.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let description = step.get("description").and_then(|v| v.as_str()).unwrap_or("");
+        match step.get("soldier").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+            Some(soldier) => {
+                let mut prompt = description.to_string();
+                if let Some(expected) = step.get("expected_output").and_then(|v| v.as_str()).filter(|s| !s.is_empty()) {
+                    prompt.push_str(&format!("\n\nReturn: {expected}"));
+                }
+                // Each step builds on the one before it, as it did in the original run
+                if let Some(prev_id) = job_steps.last().and_then(|s| s.get("id")).and_then(|v| v.as_str()) {
+                    prompt.push_str(&format!("\n\nPrevious step results:\n{{{{{prev_id}.results}}}}"));
+                }
+                job_steps.push(json!({
+                    "id": id,
+                    "type": "delegate",
+                    "soldier": soldier,
+                    "prompt": prompt
+                }));
+            }
+            None => skipped.push(id),
+        }
+    }
+    (job_steps, skipped)
+}
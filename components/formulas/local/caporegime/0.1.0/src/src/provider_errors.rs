@@ -0,0 +1,54 @@
+//! Provider error classification for model fallback chains.
+//!
+//! caporegime, bookkeeper and consul each carry this file as
+//! `src/provider_errors.rs`, byte for byte the same. Change all three together.
+
+use serde_json::Value;
+
+/// Provider error types (Anthropic, OpenAI and Gemini spellings) that mean
+/// an outage, overload or rate limit rather than a bad request.
+const RETRYABLE_ERROR_TYPES: [&str; 9] = [
+    "rate_limit_error", "rate_limit_exceeded", "overloaded_error", "api_error", "server_error",
+    "timeout", "resource_exhausted", "unavailable", "deadline_exceeded",
+];
+
+/// Prefix a provider error with its HTTP status and error type, e.g.
+/// `Provider error (429, rate_limit_error): ...`, so fallbacks are decided on
+/// those rather than on the message text.
+pub fn provider_error(catalyst_result: &Value, err: &Value, message: &str) -> String {
+    let inner = err.get("error").unwrap_or(err);
+    let status = [catalyst_result.get("status"), catalyst_result.get("status_code"), err.get("status"), err.get("code"), inner.get("code")]
+        .into_iter()
+        .flatten()
+        .find_map(|v| v.as_u64());
+    let kind = [inner.get("type"), inner.get("status"), err.get("type")]
+        .into_iter()
+        .flatten()
+        .find_map(|v| v.as_str());
+    match (status, kind) {
+        (Some(s), Some(k)) => format!("Provider error ({s}, {k}): {message}"),
+        (Some(s), None) => format!("Provider error ({s}): {message}"),
+        (None, Some(k)) => format!("Provider error ({k}): {message}"),
+        (None, None) => message.to_string(),
+    }
+}
+
+/// Provider outages, overload and rate limits are worth retrying on another model;
+/// bad requests and auth failures are not. Classified by the status and type
+/// `provider_error` put in front of the message, never by the message itself.
+pub fn is_retryable_provider_error(err: &str) -> bool {
+    // The executor couldn't run the catalyst at all (task timeout, crash)
+    if err.starts_with("Catalyst invoke error") {
+        return true;
+    }
+    let Some((tags, _)) = err
+        .strip_prefix("Provider error (")
+        .and_then(|rest| rest.split_once("): "))
+    else {
+        return false;
+    };
+    tags.split(", ").any(|tag| match tag.parse::<u16>() {
+        Ok(status) => status == 408 || status == 429 || status >= 500,
+        Err(_) => RETRYABLE_ERROR_TYPES.contains(&tag.to_lowercase().as_str()),
+    })
+}
@@ -0,0 +1,170 @@
+use serde_json::Value;
+
+// ---------------------------------------------------------------------------
+// Minimal JSON Schema validation for structured soldier outputs
+// ---------------------------------------------------------------------------
+//
+// Supports the subset soldiers and job steps actually declare: `type`
+// (including type arrays), `properties`, `required`, `items`, `enum`,
+// `minItems`/`maxItems` and `additionalProperties: false`. Unknown keywords
+// are ignored rather than rejected.
+
+/// Validate `value` against `schema`. Returns every mismatch found, each
+/// prefixed with its JSON path (`$.items[0].title`).
+pub fn validate(value: &Value, schema: &Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at(value, schema, "$", &mut errors);
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema_obj) = schema.as_object() else { return };
+
+    if let Some(expected) = schema_obj.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(arr) => arr.iter().filter_map(|t| t.as_str()).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(value, t)) {
+            errors.push(format!("{path}: expected {}, got {}", types.join(" | "), type_name(value)));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema_obj.get("enum").and_then(|v| v.as_array()) {
+        if !allowed.contains(value) {
+            errors.push(format!("{path}: value {} is not one of {}", value, Value::Array(allowed.clone())));
+        }
+    }
+
+    if let Some(obj) = value.as_object() {
+        if let Some(required) = schema_obj.get("required").and_then(|v| v.as_array()) {
+            for key in required.iter().filter_map(|k| k.as_str()) {
+                if !obj.contains_key(key) {
+                    errors.push(format!("{path}: missing required property '{key}'"));
+                }
+            }
+        }
+
+        let properties = schema_obj.get("properties").and_then(|v| v.as_object());
+        if let Some(props) = properties {
+            for (key, prop_schema) in props {
+                if let Some(prop_value) = obj.get(key) {
+                    validate_at(prop_value, prop_schema, &format!("{path}.{key}"), errors);
+                }
+            }
+        }
+
+        if schema_obj.get("additionalProperties") == Some(&Value::Bool(false)) {
+            for key in obj.keys() {
+                if !properties.map(|p| p.contains_key(key)).unwrap_or(false) {
+                    errors.push(format!("{path}: unexpected property '{key}'"));
+                }
+            }
+        }
+    }
+
+    if let Some(arr) = value.as_array() {
+        if let Some(min) = schema_obj.get("minItems").and_then(|v| v.as_u64()) {
+            if (arr.len() as u64) < min {
+                errors.push(format!("{path}: expected at least {min} items, got {}", arr.len()));
+            }
+        }
+        if let Some(max) = schema_obj.get("maxItems").and_then(|v| v.as_u64()) {
+            if (arr.len() as u64) > max {
+                errors.push(format!("{path}: expected at most {max} items, got {}", arr.len()));
+            }
+        }
+        if let Some(item_schema) = schema_obj.get("items") {
+            for (i, item) in arr.iter().enumerate() {
+                validate_at(item, item_schema, &format!("{path}[{i}]"), errors);
+            }
+        }
+    }
+}
+
+fn matches_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some()
+            || value.as_f64().map(|f| f.fract() == 0.0).unwrap_or(false),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Parse model text as JSON, tolerating a surrounding ```json fence or prose
+/// before/after the outermost object or array.
+pub fn parse_json_output(text: &str) -> Result<Value, String> {
+    let trimmed = text.trim();
+    if let Ok(v) = serde_json::from_str::<Value>(trimmed) {
+        return Ok(v);
+    }
+
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .map(|s| s.trim());
+    if let Some(inner) = unfenced {
+        if let Ok(v) = serde_json::from_str::<Value>(inner) {
+            return Ok(v);
+        }
+    }
+
+    let start = trimmed.find(['{', '[']);
+    let end = trimmed.rfind(['}', ']']);
+    if let (Some(start), Some(end)) = (start, end) {
+        if end > start {
+            if let Ok(v) = serde_json::from_str::<Value>(&trimmed[start..=end]) {
+                return Ok(v);
+            }
+        }
+    }
+
+    Err("Output is not valid JSON".to_string())
+}
+
+/// Resolve a path like `[0].field.sub` or `.field[2]` against a value.
+/// An empty path returns the value itself.
+pub fn resolve_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            let key = &after[..end];
+            if key.is_empty() {
+                return None;
+            }
+            current = current.get(key)?;
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let close = after.find(']')?;
+            let index: usize = after[..close].trim().parse().ok()?;
+            current = current.get(index)?;
+            rest = &after[close + 1..];
+        } else {
+            return None;
+        }
+    }
+
+    Some(current)
+}
@@ -3,6 +3,11 @@ use serde_json::{json, Value};
 use crate::bindings::cyfr::formula::invoke;
 use crate::helpers;
 
+/// Progress updates a single operation may post to the sit-down.
+const MAX_PROGRESS_UPDATES: usize = 5;
+/// Turns that must pass between two progress updates.
+const PROGRESS_UPDATE_TURN_GAP: u64 = 2;
+const MAX_PROGRESS_UPDATE_CHARS: usize = 600;
 
 // ---------------------------------------------------------------------------
 // Hardcoded tool definitions (replaces MCP discovery)
@@ -12,14 +17,14 @@ fn caporegime_tools() -> Vec<Value> {
     vec![
         json!({
             "name": "delegate",
-            "description": "Delegate a task to a named soldier. The soldier will use their own LLM to complete the task and return the result.",
+            "description": "Delegate a task to a named soldier or sub-caporegime. A soldier uses their own LLM to complete the task; a sub-caporegime runs it with their own crew and reports back. Either way the result is returned.",
             "input_schema": {
                 "type": "object",
                 "required": ["soldier_name", "task"],
                 "properties": {
                     "soldier_name": {
                         "type": "string",
-                        "description": "Name of the soldier to delegate to (must match a soldier in your crew)"
+                        "description": "Name of the soldier or sub-caporegime to delegate to (must match a member of your crew)"
                     },
                     "task": {
                         "type": "string",
@@ -125,7 +130,7 @@ fn caporegime_tools() -> Vec<Value> {
                     },
                     "steps": {
                         "type": "array",
-                        "description": "Array of step definitions. Each step has: id (string), type ('delegate' or 'for_each'), soldier (soldier name), prompt (template string with {{item}}, {{step_id.results}}, {{today}}). for_each steps also have: items (string[] or {bookkeeper: name, tag_filter?: string}), parallel (boolean, default true).",
+                        "description": "Array of step definitions. Each step has: id (string), type ('delegate' or 'for_each'), soldier (soldier name) or reagent (a CYFR reagent reference like 'reagent:local.csv-parse' for deterministic transforms, with optional reagent_config object), prompt (template string with {{item}}, {{item.field}}, {{step_id.results}}, {{step_id.results[0].field}}, {{today}}), optional output_schema (JSON Schema — the result is validated structured JSON instead of free text). for_each steps also have: items (string[] or {bookkeeper: name, tag_filter?: string}), parallel (boolean, default true).",
                         "items": { "type": "object" }
                     },
                     "schedule": {
@@ -161,6 +166,65 @@ fn caporegime_tools() -> Vec<Value> {
                 }
             }
         }),
+        json!({
+            "name": "consult_member",
+            "description": "Ask a consul or bookkeeper sitting at this sit-down for an opinion or a check of their records. They answer you directly; the Don sees the exchange in the operation log.",
+            "input_schema": {
+                "type": "object",
+                "required": ["member_name", "question"],
+                "properties": {
+                    "member_name": {
+                        "type": "string",
+                        "description": "Name of the consul or bookkeeper at the table"
+                    },
+                    "question": {
+                        "type": "string",
+                        "description": "The question, with any context they need to answer it"
+                    }
+                }
+            }
+        }),
+        json!({
+            "name": "post_update",
+            "description": format!(
+                "Post a short progress update to the sit-down so the Don can follow a long operation. \
+                 Use it for real milestones (a phase finished, a soldier came back with something important), \
+                 not for every step. At most {MAX_PROGRESS_UPDATES} per operation, and not within {PROGRESS_UPDATE_TURN_GAP} turns of the last one."
+            ),
+            "input_schema": {
+                "type": "object",
+                "required": ["message"],
+                "properties": {
+                    "message": {
+                        "type": "string",
+                        "description": "One or two sentences on what has been done and what comes next"
+                    }
+                }
+            }
+        }),
+        json!({
+            "name": "update_plan_item",
+            "description": "Report progress on a step of your approved plan. Mark it in_progress when you start, then done, skipped or failed with a short note.",
+            "input_schema": {
+                "type": "object",
+                "required": ["item_id", "status"],
+                "properties": {
+                    "item_id": {
+                        "type": "string",
+                        "description": "ID of the plan step (e.g. 's1')"
+                    },
+                    "status": {
+                        "type": "string",
+                        "enum": ["in_progress", "done", "skipped", "failed"],
+                        "description": "New status for the step"
+                    },
+                    "note": {
+                        "type": "string",
+                        "description": "Optional one-line outcome or reason"
+                    }
+                }
+            }
+        }),
     ]
 }
 
@@ -168,8 +232,19 @@ fn caporegime_tools() -> Vec<Value> {
 // Build tool definitions for provider
 // ---------------------------------------------------------------------------
 
-pub fn build_tool_definitions() -> Vec<Value> {
+/// `update_plan_item` is only offered when the run is executing against a plan,
+/// `post_update` only when the run reports to a sit-down, and `consult_member`
+/// only when a consul or bookkeeper is at the table.
+pub fn build_tool_definitions(has_plan: bool, can_post_updates: bool, has_table: bool) -> Vec<Value> {
     caporegime_tools()
+        .into_iter()
+        .filter(|t| match t.get("name").and_then(|v| v.as_str()) {
+            Some("update_plan_item") => has_plan,
+            Some("post_update") => can_post_updates,
+            Some("consult_member") => has_table,
+            _ => true,
+        })
+        .collect()
 }
 
 pub fn format_tools_for_provider(tools: &[Value], catalyst_ref: &str) -> Value {
@@ -397,13 +472,13 @@ pub fn build_tool_results_message(results: &[(String, String, String)], catalyst
 pub fn dispatch_caporegime_tool(
     tool_name: &str,
     args: &Value,
-    crew_info: &Value,
+    crew_info: &mut Value,
     member_id: &str,
     owner_id: &str,
     access_token: &str,
 ) -> String {
     match tool_name {
-        "delegate" => dispatch_delegate(args, crew_info, access_token),
+        "delegate" => dispatch_delegate(args, crew_info, owner_id, access_token),
         "search_bookkeeper" => dispatch_search_bookkeeper(args, crew_info, owner_id, access_token),
         "list_bookkeeper_entries" => dispatch_list_bookkeeper_entries(args, crew_info, owner_id, access_token),
         "store_in_bookkeeper" => dispatch_store_in_bookkeeper(args, crew_info, owner_id, access_token),
@@ -411,6 +486,9 @@ pub fn dispatch_caporegime_tool(
         "create_job" => dispatch_create_job(args, member_id, owner_id, access_token),
         "list_jobs" => dispatch_list_jobs(member_id, access_token),
         "run_job" => dispatch_run_job(args, member_id, owner_id, access_token),
+        "update_plan_item" => dispatch_update_plan_item(args, crew_info, access_token),
+        "post_update" => dispatch_post_update(args, crew_info, member_id, access_token),
+        "consult_member" => dispatch_consult_member(args, crew_info, access_token),
         _ => json!({"error": format!("Unknown tool: {}", tool_name)}).to_string(),
     }
 }
@@ -442,12 +520,22 @@ fn find_soldier<'a>(crew_info: &'a Value, name: &str) -> Option<&'a Value> {
     fuzzy_find(soldiers, name)
 }
 
+fn find_captain<'a>(crew_info: &'a Value, name: &str) -> Option<&'a Value> {
+    let captains = crew_info.get("captains").and_then(|v| v.as_array())?;
+    fuzzy_find(captains, name)
+}
+
 fn find_bookkeeper<'a>(crew_info: &'a Value, name: &str) -> Option<&'a Value> {
     let bookkeepers = crew_info.get("bookkeepers").and_then(|v| v.as_array())?;
     fuzzy_find(bookkeepers, name)
 }
 
-fn dispatch_delegate(args: &Value, crew_info: &Value, access_token: &str) -> String {
+fn find_table_member<'a>(crew_info: &'a Value, name: &str) -> Option<&'a Value> {
+    let table = crew_info.get("table").and_then(|v| v.as_array())?;
+    fuzzy_find(table, name)
+}
+
+fn dispatch_delegate(args: &Value, crew_info: &Value, owner_id: &str, access_token: &str) -> String {
     let soldier_name = args.get("soldier_name").and_then(|v| v.as_str()).unwrap_or("");
     let task = args.get("task").and_then(|v| v.as_str()).unwrap_or("");
 
@@ -457,15 +545,92 @@ fn dispatch_delegate(args: &Value, crew_info: &Value, access_token: &str) -> Str
 
     let soldier = match find_soldier(crew_info, soldier_name) {
         Some(s) => s,
-        None => return json!({"error": format!("Soldier '{}' not found in your crew", soldier_name)}).to_string(),
+        None => return match find_captain(crew_info, soldier_name) {
+            Some(captain) => dispatch_delegate_to_captain(captain, task, crew_info, owner_id, access_token),
+            None => json!({"error": format!("Soldier '{}' not found in your crew", soldier_name)}).to_string(),
+        },
     };
 
-    match helpers::invoke_soldier(soldier, task, access_token) {
-        Ok(content) => json!({"result": content}).to_string(),
+    let result = if let Some(schema) = helpers::soldier_output_schema(soldier) {
+        helpers::invoke_soldier_structured(soldier, task, &schema, access_token)
+            .map(|(output, answered_by)| json!({
+                "result": output,
+                "answered_by": answered_by.as_ref().map(helpers::answered_by_json)
+            }))
+    } else {
+        helpers::invoke_soldier(soldier, task, access_token)
+            .map(|(content, answered_by)| json!({
+                "result": content,
+                "answered_by": answered_by.as_ref().map(helpers::answered_by_json)
+            }))
+    };
+
+    match result {
+        Ok(mut out) => {
+            // Borrowed soldiers: the operation log records whose loan did the work
+            if let Some(loan_id) = soldier.get("loan_id").filter(|v| !v.is_null()) {
+                out["loan_id"] = loan_id.clone();
+                out["lent_by"] = soldier.get("lender_id").cloned().unwrap_or(Value::Null);
+            }
+            out.to_string()
+        }
+        Err(e) => json!({"error": format!("Delegation failed: {}", e)}).to_string(),
+    }
+}
+
+/// Hand a task to a sub-caporegime. Their run is a child operation; its token
+/// usage is returned so the parent run can account for it.
+fn dispatch_delegate_to_captain(captain: &Value, task: &str, crew_info: &Value, owner_id: &str, access_token: &str) -> String {
+    let run = crew_info.get("run").cloned().unwrap_or(json!({}));
+    match helpers::invoke_captain(captain, task, &run, owner_id, access_token) {
+        Ok(report) => json!({
+            "result": report.get("content").cloned().unwrap_or(Value::Null),
+            "operation_id": report.get("operation_id").cloned().unwrap_or(Value::Null),
+            "usage": report.get("usage").cloned().unwrap_or(Value::Null),
+            "answered_by": report.get("answered_by").cloned().unwrap_or(Value::Null)
+        }).to_string(),
         Err(e) => json!({"error": format!("Delegation failed: {}", e)}).to_string(),
     }
 }
 
+fn dispatch_consult_member(args: &Value, crew_info: &Value, access_token: &str) -> String {
+    let name = args.get("member_name").and_then(|v| v.as_str()).unwrap_or("");
+    let question = args.get("question").and_then(|v| v.as_str()).unwrap_or("");
+
+    if name.is_empty() || question.is_empty() {
+        return json!({"error": "Missing required 'member_name' or 'question'"}).to_string();
+    }
+
+    let member = match find_table_member(crew_info, name) {
+        Some(m) => m,
+        None => return json!({"error": format!("No consul or bookkeeper named '{}' at this table", name)}).to_string(),
+    };
+    let member_name = member.get("name").and_then(|v| v.as_str()).unwrap_or(name);
+    let run = crew_info.get("run").cloned().unwrap_or(Value::Null);
+
+    match helpers::invoke_table_member(member, question, &run, access_token) {
+        Ok(data) => {
+            // The member's formula records nothing when a caporegime consults it
+            let answered_by = data.get("answered_by").cloned().unwrap_or(Value::Null);
+            let usage = data.get("usage").cloned().unwrap_or(Value::Null);
+            helpers::merge_catalyst_usage(&json!([{
+                "catalyst_ref": answered_by.get("catalyst_ref"),
+                "model": answered_by.get("model"),
+                "input_tokens": usage.get("input_tokens"),
+                "output_tokens": usage.get("output_tokens")
+            }]));
+            json!({
+                "member": member_name,
+                "answer": data.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+                "usage": usage,
+                "answered_by": answered_by
+            })
+            .to_string()
+        }
+        Err(e) => json!({"error": format!("Consulting {} failed: {}", member_name, e)}).to_string(),
+    }
+}
+
 fn dispatch_search_bookkeeper(args: &Value, crew_info: &Value, owner_id: &str, access_token: &str) -> String {
     let bk_name = args.get("bookkeeper_name").and_then(|v| v.as_str()).unwrap_or("");
     let query = args.get("query").and_then(|v| v.as_str()).unwrap_or("");
@@ -598,6 +763,67 @@ fn dispatch_create_job(args: &Value, member_id: &str, owner_id: &str, access_tok
     json!({"job": job}).to_string()
 }
 
+fn dispatch_update_plan_item(args: &Value, crew_info: &Value, access_token: &str) -> String {
+    let item_id = args.get("item_id").and_then(|v| v.as_str()).unwrap_or("");
+    let status = args.get("status").and_then(|v| v.as_str()).unwrap_or("");
+    let note = args.get("note").and_then(|v| v.as_str());
+    let operation_id = crew_info.get("run").and_then(|r| r.get("operation_id")).and_then(|v| v.as_str()).unwrap_or("");
+
+    if item_id.is_empty() || status.is_empty() {
+        return json!({"error": "Missing required 'item_id' or 'status'"}).to_string();
+    }
+
+    match crate::plan::update_item(operation_id, item_id, status, note, access_token) {
+        Ok(step) => json!({"updated": step}).to_string(),
+        Err(e) => json!({"error": format!("Plan update failed: {}", e)}).to_string(),
+    }
+}
+
+/// Persist a progress message in the sit-down. The run's `progress_message_ids` and
+/// `last_update_turn` are updated here so calls later in the same turn see the limit.
+fn dispatch_post_update(args: &Value, crew_info: &mut Value, member_id: &str, access_token: &str) -> String {
+    let message = args.get("message").and_then(|v| v.as_str()).unwrap_or("").trim();
+    if message.is_empty() {
+        return json!({"error": "Missing required 'message'"}).to_string();
+    }
+
+    let run = crew_info.get("run").cloned().unwrap_or(Value::Null);
+    let sit_down_id = run.get("sit_down_id").and_then(|v| v.as_str()).unwrap_or("");
+    let operation_id = run.get("operation_id").and_then(|v| v.as_str()).unwrap_or("");
+    let turn = run.get("turn").and_then(|v| v.as_u64()).unwrap_or(0);
+    let posted: Vec<Value> = run.get("progress_message_ids").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+    if sit_down_id.is_empty() {
+        return json!({"error": "This operation has no sit-down to post to"}).to_string();
+    }
+    if posted.len() >= MAX_PROGRESS_UPDATES {
+        return json!({"error": format!("Update limit reached ({MAX_PROGRESS_UPDATES} per operation). Save it for the final report.")}).to_string();
+    }
+    if let Some(last) = run.get("last_update_turn").and_then(|v| v.as_u64()) {
+        if turn < last + PROGRESS_UPDATE_TURN_GAP {
+            return json!({"error": format!("Too soon — the last update was on turn {last}. Post again from turn {} on.", last + PROGRESS_UPDATE_TURN_GAP)}).to_string();
+        }
+    }
+
+    let content: String = message.chars().take(MAX_PROGRESS_UPDATE_CHARS).collect();
+    let metadata = json!({
+        "type": "progress",
+        "operation_id": operation_id,
+        "turn": turn
+    });
+
+    match helpers::insert_ai_message(sit_down_id, member_id, &content, &metadata, access_token) {
+        Ok(message_id) => {
+            let mut posted = posted;
+            posted.push(json!(message_id));
+            crew_info["run"]["progress_message_ids"] = json!(posted);
+            crew_info["run"]["last_update_turn"] = json!(turn);
+            json!({"posted": true, "message_id": message_id, "updates_left": MAX_PROGRESS_UPDATES - posted.len()}).to_string()
+        }
+        Err(e) => json!({"error": format!("Failed to post update: {}", e)}).to_string(),
+    }
+}
+
 fn create_cyfr_schedule(job_id: &str, cron_expression: &str, member_id: &str, owner_id: &str, access_token: &str) -> Result<String, String> {
     let request = json!({
         "tool": "schedule",
@@ -687,7 +913,7 @@ fn dispatch_run_job(args: &Value, member_id: &str, owner_id: &str, access_token:
 
 pub fn execute_tools_parallel(
     tool_calls: &[(String, String, Value)],
-    crew_info: &Value,
+    crew_info: &mut Value,
     member_id: &str,
     owner_id: &str,
     access_token: &str,
@@ -893,6 +1119,115 @@ fn build_provider_request_impl(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Structured output (JSON mode / tool-forced output per provider)
+// ---------------------------------------------------------------------------
+
+pub const STRUCTURED_OUTPUT_TOOL: &str = "submit_output";
+
+/// Providers require an object at the root of a response schema. Non-object
+/// schemas are wrapped as `{ "result": <schema> }` and unwrapped on extraction.
+fn structured_root_schema(schema: &Value) -> (Value, bool) {
+    if schema.get("type").and_then(|v| v.as_str()) == Some("object") {
+        (schema.clone(), false)
+    } else {
+        (json!({
+            "type": "object",
+            "properties": { "result": schema },
+            "required": ["result"]
+        }), true)
+    }
+}
+
+/// Gemini's responseSchema is an OpenAPI subset — strip `additionalProperties` everywhere.
+fn strip_additional_properties(schema: &Value) -> Value {
+    match schema {
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .filter(|(k, _)| k.as_str() != "additionalProperties")
+                .map(|(k, v)| (k.clone(), strip_additional_properties(v)))
+                .collect(),
+        ),
+        Value::Array(arr) => Value::Array(arr.iter().map(strip_additional_properties).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Build a provider request that forces output matching `schema`:
+/// Claude gets a forced `submit_output` tool, OpenAI/Grok a `json_schema` text format,
+/// OpenRouter a `json_schema` response_format, Gemini a JSON mime type + responseSchema.
+/// Native web search is not offered — forced output and search tools don't mix.
+pub fn build_structured_request(
+    catalyst_ref: &str,
+    model: &str,
+    messages: &[Value],
+    system: &str,
+    schema: &Value,
+    max_tokens: u64,
+) -> Value {
+    let lower = catalyst_ref.to_lowercase();
+    let (root_schema, _) = structured_root_schema(schema);
+
+    if lower.contains("claude") {
+        let tool = json!({
+            "name": STRUCTURED_OUTPUT_TOOL,
+            "description": "Submit your final result. The input must match the required output schema exactly.",
+            "input_schema": root_schema
+        });
+        let mut request = build_provider_request_impl(catalyst_ref, model, messages, system, &[tool], max_tokens, false);
+        request["params"]["tool_choice"] = json!({"type": "tool", "name": STRUCTURED_OUTPUT_TOOL});
+        return request;
+    }
+
+    let mut request = build_provider_request_impl(catalyst_ref, model, messages, system, &[], max_tokens, false);
+    if lower.contains("openrouter") {
+        request["params"]["response_format"] = json!({
+            "type": "json_schema",
+            "json_schema": { "name": "soldier_output", "schema": root_schema }
+        });
+    } else if lower.contains("openai") || lower.contains("grok") {
+        request["params"]["text"] = json!({
+            "format": {
+                "type": "json_schema",
+                "name": "soldier_output",
+                "schema": root_schema,
+                "strict": false
+            }
+        });
+    } else if lower.contains("gemini") {
+        request["params"]["generationConfig"]["responseMimeType"] = json!("application/json");
+        request["params"]["generationConfig"]["responseSchema"] = strip_additional_properties(&root_schema);
+    }
+    request
+}
+
+/// Pull the structured value out of a `build_structured_request` response.
+/// Does not validate — callers check the result against the schema.
+pub fn extract_structured_output(data: &Value, catalyst_ref: &str, schema: &Value) -> Result<Value, String> {
+    let lower = catalyst_ref.to_lowercase();
+    let (_, wrapped) = structured_root_schema(schema);
+
+    let forced_tool_input = if lower.contains("claude") {
+        extract_tool_calls(data, catalyst_ref)
+            .into_iter()
+            .find(|tc| tc.name == STRUCTURED_OUTPUT_TOOL)
+            .map(|tc| tc.arguments)
+    } else {
+        None
+    };
+
+    let output = match forced_tool_input {
+        Some(v) => v,
+        None => crate::schema::parse_json_output(&extract_text(data, catalyst_ref))?,
+    };
+
+    if wrapped {
+        output.get("result").cloned().ok_or_else(|| "Output is missing the 'result' wrapper".to_string())
+    } else {
+        Ok(output)
+    }
+}
+
 /// Extract text from response data (multi-provider, handles tool_use turns too)
 pub fn extract_text(data: &Value, catalyst_ref: &str) -> String {
     let lower = catalyst_ref.to_lowercase();
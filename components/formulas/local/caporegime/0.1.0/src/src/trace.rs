@@ -0,0 +1,404 @@
+use serde_json::{json, Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::helpers;
+use crate::MAX_CREW_DEPTH;
+
+// ---------------------------------------------------------------------------
+// Operation traces
+// ---------------------------------------------------------------------------
+//
+// While an operation runs it records spans into `operations.spans` as
+// `{id, parent_id, kind, name, start_ms, end_ms, attributes, error?}`.
+// `parent_id: null` hangs the span off the operation itself. Kinds:
+//   turn      — one Brain-mode loop iteration
+//   catalyst  — one provider call (planning, or a turn's model call/fallback)
+//   tool_call — one tool the model called (parallel calls share the batch window)
+//   soldier   — the soldier or sub-caporegime behind a delegation or job step item
+//   step      — one Hands-mode job step
+// A sub-caporegime's own operation is linked through the `child_operation_id`
+// attribute on the soldier span that delegated to it.
+
+const SERVICE_NAME: &str = "cyfr.caporegime";
+const SPAN_KIND_INTERNAL: u64 = 1;
+const SPAN_KIND_CLIENT: u64 = 3;
+const STATUS_OK: u64 = 1;
+const STATUS_ERROR: u64 = 2;
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[derive(Default)]
+pub struct Recorder {
+    spans: Vec<Value>,
+}
+
+impl Recorder {
+    /// Continue an operation's existing spans (an approved plan resuming its run).
+    pub fn from_existing(spans: Option<&Value>) -> Self {
+        Recorder {
+            spans: spans.and_then(|v| v.as_array()).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Open a span now. Close it with `end`.
+    pub fn start(&mut self, parent: Option<u64>, kind: &str, name: &str, attributes: Value) -> u64 {
+        let start = now_ms();
+        self.record(parent, kind, name, (start, start), attributes, None)
+    }
+
+    /// Close a span, merging in attributes learned along the way.
+    pub fn end(&mut self, id: u64, attributes: Value, error: Option<&str>) {
+        let Some(span) = self.spans.iter_mut().find(|s| s.get("id").and_then(|v| v.as_u64()) == Some(id)) else {
+            return;
+        };
+        span["end_ms"] = json!(now_ms());
+        if let (Some(existing), Some(extra)) = (span["attributes"].as_object_mut(), attributes.as_object()) {
+            for (k, v) in extra {
+                existing.insert(k.clone(), v.clone());
+            }
+        }
+        if let Some(e) = error {
+            span["error"] = json!(crate::truncate_str(e, 500));
+        }
+    }
+
+    /// Add a span whose `(start_ms, end_ms)` window is already known (calls that ran in a parallel batch).
+    pub fn record(
+        &mut self,
+        parent: Option<u64>,
+        kind: &str,
+        name: &str,
+        (start_ms, end_ms): (u64, u64),
+        attributes: Value,
+        error: Option<&str>,
+    ) -> u64 {
+        let id = self.spans.iter().filter_map(|s| s.get("id").and_then(|v| v.as_u64())).max().unwrap_or(0) + 1;
+        let mut span = json!({
+            "id": id,
+            "parent_id": parent,
+            "kind": kind,
+            "name": name,
+            "start_ms": start_ms,
+            "end_ms": end_ms,
+            "attributes": if attributes.is_object() { attributes } else { json!({}) }
+        });
+        if let Some(e) = error {
+            span["error"] = json!(crate::truncate_str(e, 500));
+        }
+        self.spans.push(span);
+        id
+    }
+
+    pub fn spans(&self) -> Value {
+        json!(self.spans)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OTLP/JSON export
+// ---------------------------------------------------------------------------
+
+const OPERATION_SELECT: &str =
+    "id,member_id,parent_operation_id,depth,status,task_summary,result_content,turns_used,tool_calls,usage,spans,started_at,completed_at,member:members(name)";
+
+/// Export an operation and every sub-operation it delegated to as one OTLP
+/// trace (`{resourceSpans: [...]}`), ready for Jaeger or any OTLP/JSON viewer.
+pub fn export_otlp(operation_id: &str, access_token: &str) -> Result<Value, String> {
+    let root = fetch_operations("id", &format!("({operation_id})"), access_token)?
+        .into_iter()
+        .next()
+        .ok_or("Operation not found")?;
+
+    // Sub-caporegime runs, level by level
+    let mut operations = vec![root.clone()];
+    let mut frontier = vec![operation_id.to_string()];
+    for _ in 1..MAX_CREW_DEPTH {
+        if frontier.is_empty() {
+            break;
+        }
+        let children = fetch_operations("parent_operation_id", &format!("({})", frontier.join(",")), access_token)?;
+        frontier = children.iter().filter_map(|c| c.get("id").and_then(|v| v.as_str()).map(String::from)).collect();
+        operations.extend(children);
+    }
+
+    let trace_id = hex_id(operation_id);
+    let mut otlp_spans = Vec::new();
+    for op in &operations {
+        let op_id = op.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        let parent_span_id = op
+            .get("parent_operation_id")
+            .and_then(|v| v.as_str())
+            .filter(|_| op_id != operation_id)
+            .map(|parent_id| {
+                let parent = operations.iter().find(|o| o.get("id").and_then(|v| v.as_str()) == Some(parent_id));
+                let linked = parent.and_then(|p| {
+                    operation_spans(p).into_iter().find(|s| {
+                        s.pointer("/attributes/child_operation_id").and_then(|v| v.as_str()) == Some(op_id)
+                    })
+                });
+                span_id(parent_id, linked.and_then(|s| s.get("id").and_then(|v| v.as_u64())).unwrap_or(0))
+            });
+        otlp_spans.extend(operation_to_otlp(op, &trace_id, parent_span_id));
+    }
+
+    Ok(json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    attribute("service.name", &json!(SERVICE_NAME)),
+                    attribute("cyfr.root_operation_id", &json!(operation_id))
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "caporegime", "version": env!("CARGO_PKG_VERSION") },
+                "spans": otlp_spans
+            }]
+        }]
+    }))
+}
+
+fn fetch_operations(column: &str, ids: &str, access_token: &str) -> Result<Vec<Value>, String> {
+    let rows = helpers::supabase_call(
+        "db.select",
+        json!({
+            "table": "operations",
+            "select": OPERATION_SELECT,
+            "filters": [{ "column": column, "op": "in", "value": ids }],
+            "order": [{ "column": "started_at", "direction": "asc" }],
+            "access_token": access_token
+        }),
+    )?;
+    Ok(rows.as_array().cloned().unwrap_or_default())
+}
+
+/// Recorded spans, or — for operations that predate span recording — spans
+/// rebuilt from the `tool_calls` log without timings.
+fn operation_spans(op: &Value) -> Vec<Value> {
+    let recorded = op.get("spans").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    if !recorded.is_empty() {
+        return recorded;
+    }
+    legacy_spans(op)
+}
+
+fn legacy_spans(op: &Value) -> Vec<Value> {
+    let mut rec = Recorder::default();
+    let (start, end) = operation_window(op, &[]);
+    let approximate = json!({ "cyfr.timing": "approximate" });
+    let mut groups: Vec<(String, u64)> = Vec::new();
+
+    for entry in op.get("tool_calls").and_then(|v| v.as_array()).cloned().unwrap_or_default() {
+        // Brain mode: {name, arguments, turn, ...}; Hands mode: {step_id, soldier, item, input, output, ...}
+        let (group_kind, group_name) = match (entry.get("turn").and_then(|v| v.as_u64()), entry.get("step_id").and_then(|v| v.as_str())) {
+            (Some(turn), _) => ("turn", format!("turn {turn}")),
+            (None, Some(step_id)) => ("step", step_id.to_string()),
+            _ => ("turn", "turn ?".to_string()),
+        };
+        let group = match groups.iter().find(|(name, _)| *name == group_name) {
+            Some((_, id)) => *id,
+            None => {
+                let id = rec.record(None, group_kind, &group_name, (start, end), approximate.clone(), None);
+                groups.push((group_name, id));
+                id
+            }
+        };
+
+        let mut attributes = approximate.clone();
+        for key in ["tool_call_id", "item", "child_operation_id", "loan_id", "lent_by", "status"] {
+            if let Some(v) = entry.get(key).filter(|v| !v.is_null()) {
+                attributes[key] = v.clone();
+            }
+        }
+        if let Some(answered_by) = entry.get("answered_by").filter(|v| v.is_object()) {
+            attributes["gen_ai.response.model"] = answered_by.get("model").cloned().unwrap_or(Value::Null);
+        }
+
+        if let Some(soldier) = entry.get("soldier").and_then(|v| v.as_str()) {
+            rec.record(Some(group), "soldier", soldier, (start, end), attributes, None);
+            continue;
+        }
+        let name = entry.get("name").and_then(|v| v.as_str()).unwrap_or("unknown");
+        let error = entry.pointer("/arguments/error").and_then(|v| v.as_str()).filter(|_| name == "model_fallback");
+        let kind = if name == "model_fallback" { "catalyst" } else { "tool_call" };
+        let tool = rec.record(Some(group), kind, name, (start, end), attributes.clone(), error);
+        if name == "delegate" {
+            let soldier = entry.pointer("/arguments/soldier_name").and_then(|v| v.as_str()).unwrap_or("soldier");
+            rec.record(Some(tool), "soldier", soldier, (start, end), attributes, None);
+        }
+    }
+
+    rec.spans
+}
+
+fn operation_to_otlp(op: &Value, trace_id: &str, parent_span_id: Option<String>) -> Vec<Value> {
+    let op_id = op.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    let spans = operation_spans(op);
+    let (start, end) = operation_window(op, &spans);
+    let status = op.get("status").and_then(|v| v.as_str()).unwrap_or("running");
+
+    let mut root_attributes = json!({
+        "cyfr.operation.id": op_id,
+        "cyfr.operation.status": status,
+        "cyfr.member.id": op.get("member_id").cloned().unwrap_or(Value::Null),
+        "cyfr.member.name": op.pointer("/member/name").cloned().unwrap_or(Value::Null),
+        "cyfr.depth": op.get("depth").cloned().unwrap_or(json!(0)),
+        "cyfr.task_summary": op.get("task_summary").and_then(|v| v.as_str()).map(|s| crate::truncate_str(s, 500)),
+        "cyfr.turns_used": op.get("turns_used").cloned().unwrap_or(Value::Null)
+    });
+    if let Some(usage) = op.get("usage") {
+        root_attributes["gen_ai.usage.input_tokens"] = usage.get("input_tokens").cloned().unwrap_or(Value::Null);
+        root_attributes["gen_ai.usage.output_tokens"] = usage.get("output_tokens").cloned().unwrap_or(Value::Null);
+        root_attributes["gen_ai.response.model"] = usage.pointer("/answered_by/model").cloned().unwrap_or(Value::Null);
+    }
+    let root_error = (status == "failed").then(|| op.get("result_content").and_then(|v| v.as_str()).unwrap_or("Operation failed"));
+
+    let mut out = vec![otlp_span(
+        trace_id,
+        (&span_id(op_id, 0), parent_span_id.as_deref()),
+        "operation",
+        SPAN_KIND_INTERNAL,
+        (start, end),
+        &root_attributes,
+        root_error,
+    )];
+
+    for span in &spans {
+        let id = span.get("id").and_then(|v| v.as_u64()).unwrap_or(0);
+        let parent = span.get("parent_id").and_then(|v| v.as_u64()).unwrap_or(0);
+        let kind = span.get("kind").and_then(|v| v.as_str()).unwrap_or("internal");
+        let name = span.get("name").and_then(|v| v.as_str()).unwrap_or(kind);
+        let span_start = span.get("start_ms").and_then(|v| v.as_u64()).unwrap_or(start);
+        // Spans left open by a crash end with their operation
+        let span_end = span.get("end_ms").and_then(|v| v.as_u64()).filter(|e| *e >= span_start).unwrap_or(end);
+        let mut attributes = span.get("attributes").cloned().unwrap_or(json!({}));
+        attributes["cyfr.span.kind"] = json!(kind);
+        out.push(otlp_span(
+            trace_id,
+            (&span_id(op_id, id), Some(&span_id(op_id, parent))),
+            &format!("{kind} {name}"),
+            if kind == "catalyst" { SPAN_KIND_CLIENT } else { SPAN_KIND_INTERNAL },
+            (span_start, span_end),
+            &attributes,
+            span.get("error").and_then(|v| v.as_str()),
+        ));
+    }
+    out
+}
+
+fn otlp_span(
+    trace_id: &str,
+    (span_id, parent_span_id): (&str, Option<&str>),
+    name: &str,
+    kind: u64,
+    (start_ms, end_ms): (u64, u64),
+    attributes: &Value,
+    error: Option<&str>,
+) -> Value {
+    let attributes: Vec<Value> = attributes
+        .as_object()
+        .map(|obj| obj.iter().filter(|(_, v)| !v.is_null()).map(|(k, v)| attribute(k, v)).collect())
+        .unwrap_or_default();
+    let status = match error {
+        Some(message) => json!({ "code": STATUS_ERROR, "message": message }),
+        None => json!({ "code": STATUS_OK }),
+    };
+    let mut span = json!({
+        "traceId": trace_id,
+        "spanId": span_id,
+        "name": name,
+        "kind": kind,
+        "startTimeUnixNano": (start_ms * 1_000_000).to_string(),
+        "endTimeUnixNano": (end_ms * 1_000_000).to_string(),
+        "attributes": attributes,
+        "status": status
+    });
+    if let Some(parent) = parent_span_id {
+        span["parentSpanId"] = json!(parent);
+    }
+    span
+}
+
+/// OTLP KeyValue. Integers are strings in OTLP/JSON; objects and arrays are flattened to JSON text.
+fn attribute(key: &str, value: &Value) -> Value {
+    let any_value = match value {
+        Value::String(s) => json!({ "stringValue": s }),
+        Value::Bool(b) => json!({ "boolValue": b }),
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({ "intValue": n.to_string() }),
+        Value::Number(n) => json!({ "doubleValue": n.as_f64().unwrap_or(0.0) }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+    let mut kv = Map::new();
+    kv.insert("key".into(), json!(key));
+    kv.insert("value".into(), any_value);
+    Value::Object(kv)
+}
+
+/// The operation's own window, stretched to cover every recorded span.
+fn operation_window(op: &Value, spans: &[Value]) -> (u64, u64) {
+    let started = op.get("started_at").and_then(|v| v.as_str()).and_then(parse_timestamp_ms);
+    let completed = op.get("completed_at").and_then(|v| v.as_str()).and_then(parse_timestamp_ms);
+    let span_start = spans.iter().filter_map(|s| s.get("start_ms").and_then(|v| v.as_u64())).min();
+    let span_end = spans.iter().filter_map(|s| s.get("end_ms").and_then(|v| v.as_u64())).max();
+
+    let start = [started, span_start].into_iter().flatten().min().unwrap_or(0);
+    let end = [completed, span_end, Some(start)].into_iter().flatten().max().unwrap_or(start);
+    (start, end)
+}
+
+/// 32 hex chars from a uuid (OTLP trace ids are 16 bytes).
+fn hex_id(uuid: &str) -> String {
+    let hex: String = uuid.chars().filter(|c| c.is_ascii_hexdigit()).collect::<String>().to_lowercase();
+    format!("{hex:0<32}").chars().take(32).collect()
+}
+
+/// 16 hex chars: the operation's first 8 hex digits plus the span's index (0 = the operation).
+fn span_id(operation_id: &str, index: u64) -> String {
+    let prefix: String = hex_id(operation_id).chars().take(8).collect();
+    format!("{prefix}{:08x}", index)
+}
+
+/// Parse a Postgres timestamptz (`2026-10-18T09:30:12.345678+00:00`) to unix milliseconds.
+fn parse_timestamp_ms(ts: &str) -> Option<u64> {
+    let ts = ts.trim().replace(' ', "T");
+    let (date, rest) = ts.split_once('T')?;
+    let mut date_parts = date.split('-').map(|p| p.parse::<i64>());
+    let (year, month, day) = (date_parts.next()?.ok()?, date_parts.next()?.ok()?, date_parts.next()?.ok()?);
+
+    // Split off the zone: Z, +hh:mm, +hh or -hh:mm
+    let (time, offset_secs) = match rest.find(['Z', 'z', '+', '-']) {
+        Some(i) => {
+            let (time, zone) = rest.split_at(i);
+            let sign = if zone.starts_with('-') { -1 } else { 1 };
+            let digits: String = zone.chars().filter(|c| c.is_ascii_digit()).collect();
+            let hours = digits.get(0..2).and_then(|h| h.parse::<i64>().ok()).unwrap_or(0);
+            let minutes = digits.get(2..4).and_then(|m| m.parse::<i64>().ok()).unwrap_or(0);
+            (time, sign * (hours * 3600 + minutes * 60))
+        }
+        None => (rest, 0),
+    };
+
+    let mut time_parts = time.split(':');
+    let hour = time_parts.next()?.parse::<i64>().ok()?;
+    let minute = time_parts.next()?.parse::<i64>().ok()?;
+    let seconds = time_parts.next().unwrap_or("0");
+    let (whole, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+    let second = whole.parse::<i64>().ok()?;
+    let millis = format!("{fraction:0<3}").get(0..3).and_then(|m| m.parse::<i64>().ok()).unwrap_or(0);
+
+    // Days from civil (Howard Hinnant)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    u64::try_from(secs * 1000 + millis).ok()
+}
@@ -71,6 +71,25 @@ fn handle_request(input: &str) -> Result<String, String> {
                 .ok_or("Missing required 'contact_user_id'")?;
             remove(access_token, contact_user_id)
         }
+        "lend_soldier" => lend_soldier(access_token, &parsed),
+        "respond_loan" => {
+            let loan_id = parsed
+                .get("loan_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'loan_id'")?;
+            let accept = parsed
+                .get("accept")
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing required 'accept'")?;
+            respond_loan(access_token, loan_id, accept)
+        }
+        "revoke_loan" => {
+            let loan_id = parsed
+                .get("loan_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'loan_id'")?;
+            revoke_loan(access_token, loan_id)
+        }
         _ => Err(format!("Unknown action: {action}")),
     }
 }
@@ -154,12 +173,25 @@ fn get_state(access_token: &str) -> Result<String, String> {
     )
     .unwrap_or(Value::Array(vec![]));
 
+    // Open soldier loans in either direction (offered or active). Borrowers
+    // can't read the lent soldier's row, so the RPC embeds its name and avatar
+    let soldier_loans = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "list_soldier_loans",
+            "body": {},
+            "access_token": access_token
+        }),
+    )
+    .unwrap_or(Value::Array(vec![]));
+
     Ok(json!({
         "contacts": contacts,
         "pending_invites": pending_invites,
         "sent_invites": sent_invites,
         "commission_sit_downs": commission_sit_downs,
-        "back_room_sit_downs": back_room_sit_downs
+        "back_room_sit_downs": back_room_sit_downs,
+        "soldier_loans": soldier_loans
     })
     .to_string())
 }
@@ -216,6 +248,69 @@ fn remove(access_token: &str, contact_user_id: &str) -> Result<String, String> {
     Ok(json!({ "removed": true }).to_string())
 }
 
+// ---------------------------------------------------------------------------
+// Soldier loans
+// ---------------------------------------------------------------------------
+
+/// Offer a soldier to an allied Don. Consent, scope and expiry are checked by the RPC.
+fn lend_soldier(access_token: &str, parsed: &Value) -> Result<String, String> {
+    let soldier_id = parsed
+        .get("soldier_id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'soldier_id'")?;
+    let borrower_id = parsed
+        .get("borrower_id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'borrower_id'")?;
+    let expires_at = parsed
+        .get("expires_at")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing required 'expires_at'")?;
+
+    let loan = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "lend_soldier",
+            "body": {
+                "p_soldier_id": soldier_id,
+                "p_borrower_id": borrower_id,
+                "p_expires_at": expires_at,
+                "p_caporegime_id": parsed.get("caporegime_id").cloned().unwrap_or(Value::Null),
+                "p_sit_down_id": parsed.get("sit_down_id").cloned().unwrap_or(Value::Null)
+            },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "loan": loan }).to_string())
+}
+
+fn respond_loan(access_token: &str, loan_id: &str, accept: bool) -> Result<String, String> {
+    let loan = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "respond_soldier_loan",
+            "body": { "p_loan_id": loan_id, "p_accept": accept },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "loan": loan }).to_string())
+}
+
+fn revoke_loan(access_token: &str, loan_id: &str) -> Result<String, String> {
+    let loan = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "revoke_soldier_loan",
+            "body": { "p_loan_id": loan_id },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "loan": loan }).to_string())
+}
+
 // ---------------------------------------------------------------------------
 // Sub-component invocations
 // ---------------------------------------------------------------------------
@@ -1,8 +1,12 @@
 #[allow(warnings)]
 mod bindings;
+mod events;
+mod provider_errors;
 
 use bindings::exports::cyfr::formula::run::Guest;
 use bindings::cyfr::formula::invoke;
+use events::{emit_event, flush_events, set_run_id};
+use provider_errors::{is_retryable_provider_error, provider_error};
 
 use serde_json::{json, Value};
 
@@ -10,7 +14,9 @@ struct Component;
 
 impl Guest for Component {
     fn run(input: String) -> String {
-        match handle_request(&input) {
+        let result = handle_request(&input);
+        flush_events();
+        match result {
             Ok(output) => output,
             Err(e) => json!({
                 "error": {
@@ -55,13 +61,37 @@ fn handle_request(input: &str) -> Result<String, String> {
     let access_token = parsed.get("access_token").and_then(|v| v.as_str()).unwrap_or("");
     let member = parsed.get("member").cloned().unwrap_or(Value::Null);
     let member_name = member.get("name").and_then(|v| v.as_str()).unwrap_or("Consul");
+    set_run_id(parsed.get("run_id").and_then(|v| v.as_str()).unwrap_or(""));
 
-    let provider_label = extract_provider_label(catalyst_ref);
-    emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": format!("Calling {}...", provider_label)}), access_token);
     emit_event(sit_down_id, member_id, member_name, json!({"kind": "turn_start", "turn": 1}), access_token);
 
-    let catalyst_input = build_provider_request(catalyst_ref, model, &conversation, system);
-    let data = invoke_catalyst(catalyst_ref, &catalyst_input)?;
+    // Try the primary model, then each fallback in order on provider/rate-limit errors
+    let chain = model_chain(&parsed, catalyst_ref, model);
+    let mut answered: Option<(String, String, Value)> = None;
+    let mut last_err = String::new();
+
+    for (i, (chain_ref, chain_model)) in chain.iter().enumerate() {
+        let provider_label = extract_provider_label(chain_ref);
+        let status = if i == 0 {
+            format!("Calling {}...", provider_label)
+        } else {
+            format!("Falling back to {} ({})...", provider_label, chain_model)
+        };
+        emit_event(sit_down_id, member_id, member_name, json!({"kind": "status", "text": status}), access_token);
+
+        let catalyst_input = build_provider_request(chain_ref, chain_model, &conversation, system);
+        match invoke_catalyst(chain_ref, &catalyst_input) {
+            Ok(data) => {
+                answered = Some((chain_ref.clone(), chain_model.clone(), data));
+                break;
+            }
+            Err(e) if is_retryable_provider_error(&e) => last_err = e,
+            Err(e) => return Err(e),
+        }
+    }
+
+    let (catalyst_ref, model, data) = answered.ok_or(last_err)?;
+    let catalyst_ref = catalyst_ref.as_str();
     emit_native_tool_events(&data, catalyst_ref, sit_down_id, member_id, member_name, access_token);
     let content = extract_content(&data, catalyst_ref);
 
@@ -76,25 +106,52 @@ fn handle_request(input: &str) -> Result<String, String> {
 
     Ok(json!({
         "content": content,
-        "usage": usage
+        "usage": usage,
+        "answered_by": { "catalyst_ref": catalyst_ref, "model": model }
     })
     .to_string())
 }
 
 // ---------------------------------------------------------------------------
-// Emit helper
+// Supabase (event persistence only, see events.rs)
 // ---------------------------------------------------------------------------
 
-// Fire-and-forget: broadcast failure is acceptable, message delivery is DB-backed
-fn emit_event(sit_down_id: &str, member_id: &str, member_name: &str, event: Value, _access_token: &str) {
-    let mut payload = event;
-    if let Some(obj) = payload.as_object_mut() {
-        obj.insert("member_id".into(), json!(member_id));
-        obj.insert("member_name".into(), json!(member_name));
-        obj.insert("sit_down_id".into(), json!(sit_down_id));
+const SUPABASE_REF: &str = "catalyst:moonmoon69.supabase";
+
+fn supabase_call(operation: &str, params: Value) -> Result<Value, String> {
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": SUPABASE_REF,
+            "input": {
+                "operation": operation,
+                "params": params
+            },
+            "type": "catalyst"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse Supabase response: {e}"))?;
+
+    if let Some(err) = response.get("error") {
+        return Err(format!("Supabase invoke error: {err}"));
+    }
+
+    let envelope = response.get("output").cloned().unwrap_or(Value::Null);
+    let raw_result = envelope.get("result").cloned().unwrap_or(Value::Null);
+    let result = match &raw_result {
+        Value::String(s) => serde_json::from_str::<Value>(s).unwrap_or(raw_result.clone()),
+        _ => raw_result,
+    };
+
+    if let Some(err) = result.get("error") {
+        return Err(format!("Supabase error: {err}"));
     }
 
-    let _ = invoke::emit(&payload.to_string());
+    Ok(result.get("data").cloned().unwrap_or(Value::Null))
 }
 
 // ---------------------------------------------------------------------------
@@ -137,12 +194,30 @@ fn invoke_catalyst(catalyst_ref: &str, catalyst_input: &Value) -> Result<Value,
             .or_else(|| err.get("error").and_then(|e| e.get("message")))
             .and_then(|v| v.as_str())
             .unwrap_or(&fallback);
-        return Err(err_msg.to_string());
+        return Err(provider_error(&catalyst_result, err, err_msg));
     }
 
     Ok(catalyst_result.get("data").cloned().unwrap_or(Value::Null))
 }
 
+/// Primary `(catalyst_ref, model)` followed by the `fallback_models` sit-down resolved
+/// from the member's fallback chain.
+fn model_chain(parsed: &Value, catalyst_ref: &str, model: &str) -> Vec<(String, String)> {
+    let mut chain = vec![(catalyst_ref.to_string(), model.to_string())];
+    if let Some(fallbacks) = parsed.get("fallback_models").and_then(|v| v.as_array()) {
+        for fb in fallbacks {
+            let fb_ref = fb.get("catalyst_ref").and_then(|v| v.as_str());
+            let fb_model = fb.get("model").and_then(|v| v.as_str());
+            if let (Some(r), Some(m)) = (fb_ref, fb_model) {
+                if !chain.iter().any(|(cr, cm)| cr == r && cm == m) {
+                    chain.push((r.to_string(), m.to_string()));
+                }
+            }
+        }
+    }
+    chain
+}
+
 fn truncate_json(val: &Value, max: usize) -> String {
     let s = val.to_string();
     if s.len() <= max { s } else { format!("{}…", &s[..max]) }
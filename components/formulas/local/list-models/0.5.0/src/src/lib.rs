@@ -15,6 +15,7 @@ impl Guest for Component {
             Err(e) => json!({
                 "models": {},
                 "refs": {},
+                "cached": [],
                 "errors": {"formula": e}
             }).to_string(),
         }
@@ -32,6 +33,10 @@ struct Provider {
     registry_ref: &'static str,
 }
 
+const SUPABASE_REF: &str = "catalyst:moonmoon69.supabase";
+/// How long a provider's cached list is served before it is fetched again.
+const CACHE_TTL_SECONDS: u64 = 6 * 60 * 60;
+
 const ALL_PROVIDERS: &[Provider] = &[
     Provider { key: "claude",     registry_ref: "catalyst:moonmoon69.claude"     },
     Provider { key: "openai",     registry_ref: "catalyst:moonmoon69.openai"     },
@@ -69,44 +74,64 @@ fn handle_request(input: &str) -> Result<String, String> {
         None => ALL_PROVIDERS.iter().collect(),
     };
 
-    if providers.len() == 1 {
-        // Single provider: use direct invoke::call (no async overhead)
-        return invoke_single(&providers);
-    }
-
-    // Multiple providers: spawn all, then await-all
-    invoke_parallel(&providers)
-}
-
-// ---------------------------------------------------------------------------
-// Single-provider path (no async overhead)
-// ---------------------------------------------------------------------------
+    let refresh = parsed.get("refresh").and_then(|v| v.as_bool()).unwrap_or(false);
 
-fn invoke_single(providers: &[&Provider]) -> Result<String, String> {
-    let provider = providers[0];
     let mut models = json!({});
     let mut errors = json!({});
     let mut refs = json!({});
+    let mut cached: Vec<&str> = Vec::new();
 
-    refs[provider.key] = Value::String(provider.registry_ref.to_string());
+    for provider in &providers {
+        refs[provider.key] = Value::String(provider.registry_ref.to_string());
+    }
 
-    match invoke_models_list(provider) {
-        Ok(data) => {
-            models[provider.key] = data;
+    // Serve fresh cached lists unless asked to refresh; a cache failure just
+    // means every provider is fetched live
+    if !refresh {
+        for row in read_cache(&providers) {
+            let Some(key) = row.get("provider").and_then(|v| v.as_str()) else { continue };
+            if let Some(provider) = providers.iter().find(|p| p.key == key) {
+                models[provider.key] = row.get("models").cloned().unwrap_or(json!([]));
+                cached.push(provider.key);
+            }
         }
-        Err(e) => {
-            errors[provider.key] = Value::String(e);
+    }
+
+    let stale: Vec<&Provider> = providers
+        .iter()
+        .filter(|p| !cached.contains(&p.key))
+        .copied()
+        .collect();
+    let fetched = match stale.len() {
+        0 => Vec::new(),
+        // Single provider: use direct invoke::call (no async overhead)
+        1 => vec![invoke_models_list(stale[0])],
+        // Multiple providers: spawn all, then await-all
+        _ => invoke_parallel(&stale)?,
+    };
+
+    for (provider, result) in stale.iter().zip(fetched) {
+        match result {
+            Ok(data) => {
+                let normalized = normalize_models(provider.key, &data);
+                write_cache(provider.key, &normalized);
+                models[provider.key] = normalized;
+            }
+            Err(e) => {
+                errors[provider.key] = Value::String(e);
+            }
         }
     }
 
-    Ok(json!({ "models": models, "refs": refs, "errors": errors }).to_string())
+    Ok(json!({ "models": models, "refs": refs, "cached": cached, "errors": errors }).to_string())
 }
 
 // ---------------------------------------------------------------------------
 // Multi-provider parallel invocation via spawn + await-all
 // ---------------------------------------------------------------------------
 
-fn invoke_parallel(providers: &[&Provider]) -> Result<String, String> {
+/// Raw `models.list` data (or an error) per provider, in `providers` order.
+fn invoke_parallel(providers: &[&Provider]) -> Result<Vec<Result<Value, String>>, String> {
     // Spawn all invocations
     let mut task_ids: Vec<String> = Vec::new();
 
@@ -154,32 +179,18 @@ fn invoke_parallel(providers: &[&Provider]) -> Result<String, String> {
         .cloned()
         .unwrap_or_default();
 
-    // Map results back to provider keys (results are in spawn order)
-    let mut models = json!({});
-    let mut errors = json!({});
-    let mut refs = json!({});
-
-    for (i, provider) in providers.iter().enumerate() {
-        refs[provider.key] = Value::String(provider.registry_ref.to_string());
-        let result = results.get(i).cloned().unwrap_or(json!({"status": "error", "error": {"message": "Missing result"}}));
-        let status = result.get("status").and_then(|v| v.as_str()).unwrap_or("error");
-
-        if status == "completed" {
-            match parse_catalyst_output(&result) {
-                Ok(data) => {
-                    models[provider.key] = data;
-                }
-                Err(e) => {
-                    errors[provider.key] = Value::String(e);
-                }
+    // Results are in spawn order
+    Ok((0..providers.len())
+        .map(|i| {
+            let result = results.get(i).cloned().unwrap_or(json!({"status": "error", "error": {"message": "Missing result"}}));
+            let status = result.get("status").and_then(|v| v.as_str()).unwrap_or("error");
+            if status == "completed" {
+                parse_catalyst_output(&result)
+            } else {
+                Err(result.get("error").map(|e| e.to_string()).unwrap_or_else(|| format!("status: {status}")))
             }
-        } else {
-            let err_msg = result.get("error").map(|e| e.to_string()).unwrap_or_else(|| format!("status: {status}"));
-            errors[provider.key] = Value::String(err_msg);
-        }
-    }
-
-    Ok(json!({ "models": models, "refs": refs, "errors": errors }).to_string())
+        })
+        .collect())
 }
 
 // ---------------------------------------------------------------------------
@@ -233,3 +244,200 @@ fn invoke_models_list(provider: &Provider) -> Result<Value, String> {
 
     parse_catalyst_output(&response)
 }
+
+// ---------------------------------------------------------------------------
+// Normalization
+// ---------------------------------------------------------------------------
+
+/// Input tokens from which a model counts as `long_context`.
+const LONG_CONTEXT_TOKENS: u64 = 200_000;
+
+/// One provider's raw `models.list` data as a list sorted by id of
+/// `{id, display_name, context_window, max_output, input_modalities,
+/// output_modalities, tools, pricing, capabilities}`. Whatever the listing
+/// doesn't say is null; `pricing` is USD per million tokens.
+fn normalize_models(provider_key: &str, data: &Value) -> Value {
+    let list = if provider_key == "gemini" { data.get("models") } else { data.get("data") };
+
+    let mut models: Vec<Value> = list
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|raw| match provider_key {
+            "gemini" => normalize_gemini(raw),
+            "openrouter" => normalize_openrouter(raw),
+            _ => normalize_basic(raw),
+        })
+        .map(|mut model| {
+            model["capabilities"] = detect_capabilities(provider_key, &model);
+            model
+        })
+        .collect();
+
+    models.sort_by(|a, b| {
+        let a = a.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        let b = b.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        a.cmp(b)
+    });
+    Value::Array(models)
+}
+
+/// Claude, OpenAI and Grok list little more than ids; Claude adds a display
+/// name and, on newer API versions, token limits.
+fn normalize_basic(raw: &Value) -> Option<Value> {
+    let id = raw.get("id").and_then(|v| v.as_str())?;
+    Some(json!({
+        "id": id,
+        "display_name": raw.get("display_name").and_then(|v| v.as_str()).unwrap_or(id),
+        "context_window": raw.get("max_input_tokens").filter(|v| v.is_u64()),
+        "max_output": raw.get("max_tokens").filter(|v| v.is_u64()),
+        "input_modalities": null,
+        "output_modalities": null,
+        "tools": null,
+        "pricing": null
+    }))
+}
+
+fn normalize_gemini(raw: &Value) -> Option<Value> {
+    let id = raw.get("name").and_then(|v| v.as_str())?.trim_start_matches("models/");
+    Some(json!({
+        "id": id,
+        "display_name": raw.get("displayName").and_then(|v| v.as_str()).unwrap_or(id),
+        "context_window": raw.get("inputTokenLimit").filter(|v| v.is_u64()),
+        "max_output": raw.get("outputTokenLimit").filter(|v| v.is_u64()),
+        "input_modalities": null,
+        "output_modalities": null,
+        "tools": null,
+        "pricing": null
+    }))
+}
+
+fn normalize_openrouter(raw: &Value) -> Option<Value> {
+    let id = raw.get("id").and_then(|v| v.as_str())?;
+    let architecture = raw.get("architecture");
+    let pricing = raw.get("pricing");
+    Some(json!({
+        "id": id,
+        "display_name": raw.get("name").and_then(|v| v.as_str()).unwrap_or(id),
+        "context_window": raw.get("context_length").filter(|v| v.is_u64()),
+        "max_output": raw
+            .get("top_provider")
+            .and_then(|t| t.get("max_completion_tokens"))
+            .filter(|v| v.is_u64()),
+        "input_modalities": architecture.and_then(|a| a.get("input_modalities")).filter(|v| v.is_array()),
+        "output_modalities": architecture.and_then(|a| a.get("output_modalities")).filter(|v| v.is_array()),
+        "tools": raw
+            .get("supported_parameters")
+            .and_then(|v| v.as_array())
+            .map(|params| params.iter().any(|p| p.as_str() == Some("tools"))),
+        "pricing": match (per_million(pricing, "prompt"), per_million(pricing, "completion")) {
+            (Some(input), Some(output)) => json!({ "input_per_million": input, "output_per_million": output }),
+            _ => Value::Null,
+        }
+    }))
+}
+
+/// OpenRouter prices are USD per token, as strings; negative means variable
+/// (e.g. `openrouter/auto`) and is treated as unknown.
+fn per_million(pricing: Option<&Value>, field: &str) -> Option<f64> {
+    let price = pricing?.get(field)?;
+    let per_token = price
+        .as_str()
+        .and_then(|s| s.parse::<f64>().ok())
+        .or_else(|| price.as_f64())?;
+    if per_token < 0.0 || !per_token.is_finite() {
+        return None;
+    }
+    // Round away float noise from the per-token representation
+    Some((per_token * 1_000_000.0 * 1_000_000.0).round() / 1_000_000.0)
+}
+
+/// `{tools, vision, web_search, long_context}` for what a normalized model
+/// reveals. Keys nothing is known about are left out (unknown).
+fn detect_capabilities(provider_key: &str, model: &Value) -> Value {
+    let mut caps = json!({});
+    if provider_key == "openrouter" {
+        // OpenRouter's web plugin works with every model
+        caps["web_search"] = json!(true);
+    }
+    if let Some(tools) = model.get("tools").and_then(|v| v.as_bool()) {
+        caps["tools"] = json!(tools);
+    }
+    if let Some(modalities) = model.get("input_modalities").and_then(|v| v.as_array()) {
+        caps["vision"] = json!(modalities.iter().any(|m| m.as_str() == Some("image")));
+    }
+    if let Some(context) = model.get("context_window").and_then(|v| v.as_u64()) {
+        caps["long_context"] = json!(context >= LONG_CONTEXT_TOKENS);
+    }
+    caps
+}
+
+// ---------------------------------------------------------------------------
+// Cache (model_list_cache, migration 038)
+// ---------------------------------------------------------------------------
+
+/// Cached `{provider, models}` rows younger than the TTL. Empty on any failure.
+fn read_cache(providers: &[&Provider]) -> Vec<Value> {
+    let keys: Vec<&str> = providers.iter().map(|p| p.key).collect();
+    supabase_call(
+        "db.rpc",
+        json!({
+            "function": "fresh_model_lists",
+            "body": { "p_providers": keys, "p_max_age_seconds": CACHE_TTL_SECONDS },
+            "service_role": true
+        }),
+    )
+    .ok()
+    .and_then(|rows| rows.as_array().cloned())
+    .unwrap_or_default()
+}
+
+/// Best effort: a failed write only means the next call fetches live again.
+fn write_cache(provider_key: &str, models: &Value) {
+    let _ = supabase_call(
+        "db.upsert",
+        json!({
+            "table": "model_list_cache",
+            "body": { "provider": provider_key, "models": models, "fetched_at": "now()" },
+            "on_conflict": "provider",
+            "service_role": true
+        }),
+    );
+}
+
+fn supabase_call(operation: &str, params: Value) -> Result<Value, String> {
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": SUPABASE_REF,
+            "input": {
+                "operation": operation,
+                "params": params
+            },
+            "type": "catalyst"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse Supabase response: {e}"))?;
+
+    if let Some(err) = response.get("error") {
+        return Err(format!("Supabase invoke error: {err}"));
+    }
+
+    let envelope = response.get("output").cloned().unwrap_or(Value::Null);
+    let raw_result = envelope.get("result").cloned().unwrap_or(Value::Null);
+    let result = match &raw_result {
+        Value::String(s) => serde_json::from_str::<Value>(s).unwrap_or(raw_result.clone()),
+        _ => raw_result,
+    };
+
+    if let Some(err) = result.get("error") {
+        return Err(format!("Supabase error: {err}"));
+    }
+
+    Ok(result.get("data").cloned().unwrap_or(Value::Null))
+}
@@ -26,6 +26,9 @@ impl Guest for Component {
 bindings::export!(Component with_types_in bindings);
 
 const SUPABASE_REF: &str = "catalyst:moonmoon69.supabase";
+const MAX_FALLBACK_MODELS: usize = 5;
+/// Levels in a crew hierarchy, counting the top caporegime (must match the caporegime formula).
+const MAX_CREW_DEPTH: usize = 3;
 
 fn handle_request(input: &str) -> Result<String, String> {
     let parsed: Value =
@@ -99,6 +102,14 @@ fn handle_request(input: &str) -> Result<String, String> {
                 .ok_or("Missing required 'member_id'")?;
             regenerate_token(access_token, member_id)
         }
+        "list_notifications" => list_notifications(access_token),
+        "dismiss_notification" => {
+            let notification_id = parsed
+                .get("notification_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'notification_id'")?;
+            dismiss_notification(access_token, notification_id)
+        }
         _ => Err(format!("Unknown action: {action}")),
     }
 }
@@ -191,54 +202,32 @@ fn create_member(access_token: &str, member: &Value) -> Result<String, String> {
         }
     }
 
+    // A caporegime may report to another caporegime and serve in its crew
+    if member_type == "caporegime" {
+        if let Some(parent_id) = caporegime_id {
+            validate_parent_caporegime(access_token, user_id, parent_id, None)?;
+        }
+    }
+
     let catalog_model_id = member.get("catalog_model_id").and_then(|v| v.as_str());
 
-    if catalog_model_id.is_none() || catalog_model_id == Some("") {
+    // Reagent soldiers run deterministic WASM compute — no model involved
+    let is_reagent_soldier = member_type == "soldier"
+        && member.get("soldier_type").and_then(|v| v.as_str()) == Some("reagent");
+
+    if !is_reagent_soldier && (catalog_model_id.is_none() || catalog_model_id == Some("")) {
         return Err(format!("Missing required 'member.catalog_model_id' for {member_type}"));
     }
 
+    let caller_tier = fetch_caller_tier(access_token, user_id)?;
+    enforce_member_limits(access_token, user_id, &caller_tier, member_type)?;
+
+    let required = required_capabilities(member_type, member.get("soldier_type").and_then(|v| v.as_str()));
+
     // Validate catalog model if provided
-    let mut caller_tier = "associate";
     if let Some(model_id) = catalog_model_id {
         if !model_id.is_empty() {
-            let caller_profile = supabase_call(
-                "db.select",
-                json!({
-                    "table": "profiles",
-                    "select": "id,tier",
-                    "filters": [
-                        { "column": "id", "op": "eq", "value": user_id }
-                    ],
-                    "access_token": access_token
-                }),
-            )?;
-
-            let profile = caller_profile
-                .as_array()
-                .and_then(|arr| arr.first())
-                .ok_or("Profile not found")?;
-
-            caller_tier = profile
-                .get("tier")
-                .and_then(|v| v.as_str())
-                .unwrap_or("associate");
-
-            let catalog_models = supabase_call(
-                "db.select",
-                json!({
-                    "table": "model_catalog",
-                    "select": "id,min_tier,is_active",
-                    "filters": [
-                        { "column": "id", "op": "eq", "value": model_id }
-                    ],
-                    "access_token": access_token
-                }),
-            )?;
-
-            let catalog_model = catalog_models
-                .as_array()
-                .and_then(|arr| arr.first())
-                .ok_or("Catalog model not found")?;
+            let catalog_model = fetch_catalog_model(access_token, model_id)?;
 
             let is_active = catalog_model
                 .get("is_active")
@@ -249,19 +238,16 @@ fn create_member(access_token: &str, member: &Value) -> Result<String, String> {
                 return Err("Selected model is not available".to_string());
             }
 
-            let min_tier = catalog_model
-                .get("min_tier")
-                .and_then(|v| v.as_str())
-                .unwrap_or("associate");
-
-            if !tier_has_access(caller_tier, min_tier) {
-                return Err(format!(
-                    "Your tier ({caller_tier}) does not have access to this model (requires {min_tier})"
-                ));
-            }
+            check_model_access(&caller_tier, &catalog_model, "this model")?;
+            check_model_capabilities(&catalog_model, required, member_type, "This model")?;
         }
     }
 
+    let fallback_model_ids = match member.get("fallback_model_ids") {
+        Some(ids) if !is_reagent_soldier => validate_fallback_models(access_token, user_id, ids, required, member_type)?,
+        _ => vec![],
+    };
+
     // Build insert body
     let mut body = json!({
         "owner_id": user_id,
@@ -276,25 +262,42 @@ fn create_member(access_token: &str, member: &Value) -> Result<String, String> {
         }
     }
 
+    if !fallback_model_ids.is_empty() {
+        body["fallback_model_ids"] = json!(fallback_model_ids);
+    }
+
     if let Some(cap_id) = caporegime_id {
-        body["caporegime_id"] = json!(cap_id);
+        if member_type == "soldier" || member_type == "caporegime" {
+            body["caporegime_id"] = json!(cap_id);
+        }
     }
 
     if let Some(avatar_url) = member.get("avatar_url").and_then(|v| v.as_str()) {
         body["avatar_url"] = json!(avatar_url);
     }
 
+    // Caporegime-specific: plan-then-execute mode
+    if member_type == "caporegime" {
+        if let Some(plan_mode) = member.get("plan_mode") {
+            body["plan_mode"] = json!(validate_plan_mode(plan_mode)?);
+        }
+    }
+
     // Soldier-specific fields
     if member_type == "soldier" {
         if let Some(soldier_type) = member.get("soldier_type").and_then(|v| v.as_str()) {
             match soldier_type {
-                "default" | "external" => {
+                "default" | "external" | "reagent" => {
                     body["soldier_type"] = json!(soldier_type);
                 }
-                _ => return Err(format!("Invalid soldier_type: {soldier_type}. Must be 'default' or 'external'")),
+                _ => return Err(format!("Invalid soldier_type: {soldier_type}. Must be 'default', 'external' or 'reagent'")),
             }
         }
+        if is_reagent_soldier {
+            validate_reagent_config(member.get("soldier_config"))?;
+        }
         if let Some(soldier_config) = member.get("soldier_config") {
+            validate_output_schema(soldier_config)?;
             body["soldier_config"] = soldier_config.clone();
         }
     }
@@ -326,7 +329,7 @@ fn update_member(access_token: &str, member_id: &str, updates: &Value) -> Result
 
     // Build the update body from allowed fields only
     let mut body = json!({});
-    let allowed_fields = ["name", "catalog_model_id", "system_prompt", "avatar_url", "soldier_type", "soldier_config"];
+    let allowed_fields = ["name", "catalog_model_id", "fallback_model_ids", "caporegime_id", "system_prompt", "avatar_url", "soldier_type", "soldier_config", "plan_mode"];
 
     for field in &allowed_fields {
         if let Some(val) = updates.get(*field) {
@@ -338,10 +341,19 @@ fn update_member(access_token: &str, member_id: &str, updates: &Value) -> Result
     if let Some(soldier_type) = updates.get("soldier_type").and_then(|v| v.as_str()) {
         match soldier_type {
             "default" | "external" => {}
-            _ => return Err(format!("Invalid soldier_type: {soldier_type}. Must be 'default' or 'external'")),
+            "reagent" => validate_reagent_config(updates.get("soldier_config"))?,
+            _ => return Err(format!("Invalid soldier_type: {soldier_type}. Must be 'default', 'external' or 'reagent'")),
         }
     }
 
+    if let Some(soldier_config) = updates.get("soldier_config") {
+        validate_output_schema(soldier_config)?;
+    }
+
+    if let Some(plan_mode) = updates.get("plan_mode") {
+        validate_plan_mode(plan_mode)?;
+    }
+
     if body.as_object().map(|o| o.is_empty()).unwrap_or(true) {
         return Err("No valid fields to update".to_string());
     }
@@ -358,46 +370,51 @@ fn update_member(access_token: &str, member_id: &str, updates: &Value) -> Result
         return Err("catalog_model_id cannot be null — please select a valid model".to_string());
     }
 
-    // If catalog_model_id is being changed, validate tier access
-    if let Some(new_model_id) = updates.get("catalog_model_id").and_then(|v| v.as_str()) {
-        let caller_profile = supabase_call(
+    // Model changes (or a soldier_type change) are checked against what the member's role needs
+    let model_change = ["catalog_model_id", "fallback_model_ids", "soldier_type"]
+        .iter()
+        .any(|f| updates.get(*f).is_some());
+    let (member_type, required) = if model_change {
+        let current = supabase_call(
             "db.select",
             json!({
-                "table": "profiles",
-                "select": "id,tier",
+                "table": "members",
+                "select": "member_type,soldier_type,catalog_model_id",
                 "filters": [
-                    { "column": "id", "op": "eq", "value": user_id }
+                    { "column": "id", "op": "eq", "value": member_id },
+                    { "column": "owner_id", "op": "eq", "value": user_id }
                 ],
                 "access_token": access_token
             }),
         )?;
-
-        let profile = caller_profile
+        let current = current
             .as_array()
             .and_then(|arr| arr.first())
-            .ok_or("Profile not found")?;
-
-        let caller_tier = profile
-            .get("tier")
-            .and_then(|v| v.as_str())
-            .unwrap_or("associate");
-
-        let catalog_models = supabase_call(
-            "db.select",
-            json!({
-                "table": "model_catalog",
-                "select": "id,min_tier,is_active",
-                "filters": [
-                    { "column": "id", "op": "eq", "value": new_model_id }
-                ],
-                "access_token": access_token
-            }),
-        )?;
+            .cloned()
+            .ok_or("Member not found or not owned by you")?;
+        let member_type = current.get("member_type").and_then(|v| v.as_str()).unwrap_or("consul").to_string();
+        let soldier_type = updates
+            .get("soldier_type")
+            .or_else(|| current.get("soldier_type"))
+            .and_then(|v| v.as_str());
+        let required = required_capabilities(&member_type, soldier_type);
+
+        // A new soldier_type may need more than the current model offers
+        if updates.get("catalog_model_id").is_none() && updates.get("soldier_type").is_some() {
+            if let Some(current_model_id) = current.get("catalog_model_id").and_then(|v| v.as_str()) {
+                let catalog_model = fetch_catalog_model(access_token, current_model_id)?;
+                check_model_capabilities(&catalog_model, required, &member_type, "The current model")?;
+            }
+        }
+        (member_type, required)
+    } else {
+        (String::new(), &[][..])
+    };
 
-        let catalog_model = catalog_models
-            .as_array()
-            .and_then(|arr| arr.first())
-            .ok_or("Catalog model not found")?;
+    // If catalog_model_id is being changed, validate tier access
+    if let Some(new_model_id) = updates.get("catalog_model_id").and_then(|v| v.as_str()) {
+        let caller_tier = fetch_caller_tier(access_token, user_id)?;
+        let catalog_model = fetch_catalog_model(access_token, new_model_id)?;
 
         let is_active = catalog_model
             .get("is_active")
@@ -408,18 +425,45 @@ fn update_member(access_token: &str, member_id: &str, updates: &Value) -> Result
             return Err("Selected model is not available".to_string());
         }
 
-        let min_tier = catalog_model
-            .get("min_tier")
+        check_model_access(&caller_tier, &catalog_model, "this model")?;
+        check_model_capabilities(&catalog_model, required, &member_type, "This model")?;
+    }
+
+    // Reassigning a caporegime under another caporegime (null detaches it)
+    if let Some(parent) = updates.get("caporegime_id") {
+        let target = supabase_call(
+            "db.select",
+            json!({
+                "table": "members",
+                "select": "id,member_type",
+                "filters": [
+                    { "column": "id", "op": "eq", "value": member_id },
+                    { "column": "owner_id", "op": "eq", "value": user_id }
+                ],
+                "access_token": access_token
+            }),
+        )?;
+        let target_type = target
+            .as_array()
+            .and_then(|arr| arr.first())
+            .and_then(|m| m.get("member_type"))
             .and_then(|v| v.as_str())
-            .unwrap_or("associate");
+            .ok_or("Member not found or not owned by you")?;
+        if target_type != "caporegime" {
+            return Err("Only caporegimes can be placed under another caporegime".to_string());
+        }
 
-        if !tier_has_access(caller_tier, min_tier) {
-            return Err(format!(
-                "Your tier ({caller_tier}) does not have access to this model (requires {min_tier})"
-            ));
+        match parent.as_str() {
+            Some(parent_id) => validate_parent_caporegime(access_token, user_id, parent_id, Some(member_id))?,
+            None if parent.is_null() => {}
+            None => return Err("'caporegime_id' must be a caporegime id or null".to_string()),
         }
     }
 
+    if let Some(ids) = updates.get("fallback_model_ids") {
+        body["fallback_model_ids"] = json!(validate_fallback_models(access_token, user_id, ids, required, &member_type)?);
+    }
+
     let updated = supabase_call(
         "db.update",
         json!({
@@ -498,7 +542,21 @@ fn list_crew(access_token: &str, caporegime_id: &str) -> Result<String, String>
         }),
     )?;
 
-    Ok(json!({ "soldiers": soldiers }).to_string())
+    let captains = supabase_call(
+        "db.select",
+        json!({
+            "table": "members",
+            "select": "*,catalog_model:model_catalog(*)",
+            "filters": [
+                { "column": "caporegime_id", "op": "eq", "value": caporegime_id },
+                { "column": "member_type", "op": "eq", "value": "caporegime" }
+            ],
+            "order": [{ "column": "created_at", "direction": "desc" }],
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "soldiers": soldiers, "captains": captains }).to_string())
 }
 
 fn generate_token() -> Result<(String, String, String), String> {
@@ -667,18 +725,408 @@ fn regenerate_token(access_token: &str, member_id: &str) -> Result<String, Strin
     Ok(json!({ "token": token, "token_prefix": prefix }).to_string())
 }
 
-/// Check if a user tier has access to a model requiring min_tier.
-/// Hierarchy: godfather > boss > associate
-fn tier_has_access(user_tier: &str, min_tier: &str) -> bool {
-    let tier_level = |t: &str| -> u8 {
-        match t {
-            "godfather" => 3,
-            "boss" => 2,
-            "associate" => 1,
-            _ => 0,
-        }
+/// Caporegime planning phase: 'off', 'auto' (plan, then execute) or 'approval' (wait for the Don).
+fn validate_plan_mode(plan_mode: &Value) -> Result<&str, String> {
+    match plan_mode.as_str() {
+        Some(mode @ ("off" | "auto" | "approval")) => Ok(mode),
+        _ => Err("Invalid plan_mode. Must be 'off', 'auto' or 'approval'".to_string()),
+    }
+}
+
+/// Reagent soldiers must point at a CYFR reagent via `soldier_config.reagent_ref`.
+fn validate_reagent_config(soldier_config: Option<&Value>) -> Result<(), String> {
+    let reagent_ref = soldier_config
+        .and_then(|c| c.get("reagent_ref"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    if !reagent_ref.starts_with("reagent:") || reagent_ref.len() <= "reagent:".len() {
+        return Err("Reagent soldiers require 'soldier_config.reagent_ref' (e.g. 'reagent:local.csv-parse')".to_string());
+    }
+
+    Ok(())
+}
+
+/// A soldier's optional `output_schema` must be a JSON Schema object with a `type`.
+fn validate_output_schema(soldier_config: &Value) -> Result<(), String> {
+    match soldier_config.get("output_schema") {
+        None | Some(Value::Null) => Ok(()),
+        Some(schema) if schema.get("type").and_then(|v| v.as_str()).is_some() => Ok(()),
+        Some(_) => Err("'soldier_config.output_schema' must be a JSON Schema object with a 'type'".to_string()),
+    }
+}
+
+/// A parent caporegime must be owned by the caller, must not sit below `member_id`
+/// (no cycles), and the resulting chain must stay within `MAX_CREW_DEPTH` levels,
+/// counting the sub-caporegimes `member_id` brings along.
+fn validate_parent_caporegime(
+    access_token: &str,
+    user_id: &str,
+    parent_id: &str,
+    member_id: Option<&str>,
+) -> Result<(), String> {
+    if Some(parent_id) == member_id {
+        return Err("A caporegime cannot report to itself".to_string());
+    }
+
+    let subtree_height = match member_id {
+        Some(id) => crew_subtree_height(access_token, user_id, id)?,
+        None => 0,
     };
-    tier_level(user_tier) >= tier_level(min_tier)
+    let too_deep = || Err(format!("Crews can be at most {MAX_CREW_DEPTH} levels deep"));
+    if 1 + subtree_height >= MAX_CREW_DEPTH {
+        return too_deep();
+    }
+
+    // Walk up from the parent: each hop is one level above the new position
+    let mut current = parent_id.to_string();
+    let mut levels = 1;
+    loop {
+        let rows = supabase_call(
+            "db.select",
+            json!({
+                "table": "members",
+                "select": "id,caporegime_id",
+                "filters": [
+                    { "column": "id", "op": "eq", "value": current },
+                    { "column": "owner_id", "op": "eq", "value": user_id },
+                    { "column": "member_type", "op": "eq", "value": "caporegime" }
+                ],
+                "access_token": access_token
+            }),
+        )?;
+        let row = rows
+            .as_array()
+            .and_then(|arr| arr.first())
+            .ok_or("Parent caporegime not found or not owned by you")?;
+
+        match row.get("caporegime_id").and_then(|v| v.as_str()) {
+            Some(next) if Some(next) == member_id => {
+                return Err("That would make the caporegime report to its own crew".to_string());
+            }
+            Some(next) => {
+                levels += 1;
+                if levels + subtree_height >= MAX_CREW_DEPTH {
+                    return too_deep();
+                }
+                current = next.to_string();
+            }
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Levels of sub-caporegimes below `member_id` (0 when it leads none), capped
+/// at `MAX_CREW_DEPTH` since anything that deep can't move anywhere.
+fn crew_subtree_height(access_token: &str, user_id: &str, member_id: &str) -> Result<usize, String> {
+    let mut frontier = vec![member_id.to_string()];
+    let mut height = 0;
+    while height < MAX_CREW_DEPTH {
+        let rows = supabase_call(
+            "db.select",
+            json!({
+                "table": "members",
+                "select": "id",
+                "filters": [
+                    { "column": "caporegime_id", "op": "in", "value": format!("({})", frontier.join(",")) },
+                    { "column": "owner_id", "op": "eq", "value": user_id },
+                    { "column": "member_type", "op": "eq", "value": "caporegime" }
+                ],
+                "access_token": access_token
+            }),
+        )?;
+        frontier = rows
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|m| m.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if frontier.is_empty() {
+            break;
+        }
+        height += 1;
+    }
+    Ok(height)
+}
+
+/// Fallback chains are an ordered list of distinct catalog model ids, each of
+/// which must be active, available at the caller's tier and not lacking a
+/// capability the member's role needs. Null clears the chain.
+fn validate_fallback_models(
+    access_token: &str,
+    user_id: &str,
+    ids: &Value,
+    required: &[&str],
+    member_type: &str,
+) -> Result<Vec<String>, String> {
+    if ids.is_null() {
+        return Ok(vec![]);
+    }
+
+    let arr = ids
+        .as_array()
+        .ok_or("'fallback_model_ids' must be an array of catalog model ids")?;
+
+    let mut model_ids: Vec<String> = Vec::new();
+    for id in arr {
+        let id = id
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .ok_or("'fallback_model_ids' must contain only catalog model ids")?;
+        if !model_ids.iter().any(|m| m == id) {
+            model_ids.push(id.to_string());
+        }
+    }
+
+    if model_ids.len() > MAX_FALLBACK_MODELS {
+        return Err(format!("At most {MAX_FALLBACK_MODELS} fallback models are allowed"));
+    }
+    if model_ids.is_empty() {
+        return Ok(model_ids);
+    }
+
+    let caller_tier = fetch_caller_tier(access_token, user_id)?;
+
+    let catalog_models = supabase_call(
+        "db.select",
+        json!({
+            "table": "model_catalog",
+            "select": CATALOG_ACCESS_SELECT,
+            "filters": [
+                { "column": "id", "op": "in", "value": format!("({})", model_ids.join(",")) }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+    let catalog_arr = catalog_models.as_array().cloned().unwrap_or_default();
+
+    for id in &model_ids {
+        let entry = catalog_arr
+            .iter()
+            .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(id.as_str()))
+            .ok_or_else(|| format!("Fallback model '{id}' not found"))?;
+        let alias = entry.get("alias").and_then(|v| v.as_str()).unwrap_or(id);
+
+        if !entry.get("is_active").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Err(format!("Fallback model '{alias}' is not available"));
+        }
+
+        check_model_access(&caller_tier, entry, &format!("fallback model '{alias}'"))?;
+        check_model_capabilities(entry, required, member_type, &format!("Fallback model '{alias}'"))?;
+    }
+
+    Ok(model_ids)
+}
+
+// ---------------------------------------------------------------------------
+// Notifications (e.g. a member moved to another model by the Godfather)
+// ---------------------------------------------------------------------------
+
+/// Unread notifications for the caller, newest first.
+fn list_notifications(access_token: &str) -> Result<String, String> {
+    let user = fetch_user(access_token)?;
+    let user_id = user
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or("Could not determine user ID from token")?;
+
+    let notifications = supabase_call(
+        "db.select",
+        json!({
+            "table": "notifications",
+            "select": "id,kind,title,body,data,created_at",
+            "filters": [
+                { "column": "user_id", "op": "eq", "value": user_id },
+                { "column": "read_at", "op": "is", "value": null }
+            ],
+            "order": [{ "column": "created_at", "direction": "desc" }],
+            "limit": 50,
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "notifications": notifications }).to_string())
+}
+
+fn dismiss_notification(access_token: &str, notification_id: &str) -> Result<String, String> {
+    let user = fetch_user(access_token)?;
+    let user_id = user
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or("Could not determine user ID from token")?;
+
+    supabase_call(
+        "db.update",
+        json!({
+            "table": "notifications",
+            "body": { "read_at": "now()" },
+            "filters": [
+                { "column": "id", "op": "eq", "value": notification_id },
+                { "column": "user_id", "op": "eq", "value": user_id }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "dismissed": true }).to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tiers (rank, limits and allowed providers live in the `tiers` table)
+// ---------------------------------------------------------------------------
+
+/// Catalog columns needed to check a tier's access to a model.
+const CATALOG_ACCESS_SELECT: &str = "id,alias,provider,min_tier,is_active,capabilities,min_tier_info:tiers(display_name,rank)";
+
+/// The caller's `tiers` row, joined through their profile.
+fn fetch_caller_tier(access_token: &str, user_id: &str) -> Result<Value, String> {
+    let profiles = supabase_call(
+        "db.select",
+        json!({
+            "table": "profiles",
+            "select": "id,tier_info:tiers(key,display_name,rank,max_members,max_caporegimes,allowed_providers)",
+            "filters": [
+                { "column": "id", "op": "eq", "value": user_id }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    profiles
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|p| p.get("tier_info"))
+        .filter(|t| t.is_object())
+        .cloned()
+        .ok_or_else(|| "Profile not found".to_string())
+}
+
+fn fetch_catalog_model(access_token: &str, model_id: &str) -> Result<Value, String> {
+    let catalog_models = supabase_call(
+        "db.select",
+        json!({
+            "table": "model_catalog",
+            "select": CATALOG_ACCESS_SELECT,
+            "filters": [
+                { "column": "id", "op": "eq", "value": model_id }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+
+    catalog_models
+        .as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .ok_or_else(|| "Catalog model not found".to_string())
+}
+
+/// A tier may use a catalog model when its rank meets the model's `min_tier`
+/// and the model's provider is in the tier's `allowed_providers` (null = all).
+fn check_model_access(caller_tier: &Value, catalog_model: &Value, label: &str) -> Result<(), String> {
+    let tier_name = caller_tier.get("display_name").and_then(|v| v.as_str()).unwrap_or("unknown");
+    let rank = caller_tier.get("rank").and_then(|v| v.as_i64()).unwrap_or(0);
+    let min_tier = catalog_model.get("min_tier_info").cloned().unwrap_or(Value::Null);
+    let min_rank = min_tier.get("rank").and_then(|v| v.as_i64()).unwrap_or(i64::MAX);
+
+    if rank < min_rank {
+        let required = min_tier
+            .get("display_name")
+            .and_then(|v| v.as_str())
+            .or_else(|| catalog_model.get("min_tier").and_then(|v| v.as_str()))
+            .unwrap_or("unknown");
+        return Err(format!(
+            "Your tier ({tier_name}) does not have access to {label} (requires {required})"
+        ));
+    }
+
+    if let Some(allowed) = caller_tier.get("allowed_providers").and_then(|v| v.as_array()) {
+        let provider = catalog_model.get("provider").and_then(|v| v.as_str()).unwrap_or("");
+        if !allowed.iter().any(|p| p.as_str() == Some(provider)) {
+            return Err(format!("Your tier ({tier_name}) cannot use {provider} models"));
+        }
+    }
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Model capabilities
+// ---------------------------------------------------------------------------
+
+/// Capabilities a member's role relies on. Caporegimes, bookkeepers and
+/// external soldiers drive tool loops; consuls and default soldiers attach the
+/// provider's native web search. Reagent soldiers have no model.
+fn required_capabilities(member_type: &str, soldier_type: Option<&str>) -> &'static [&'static str] {
+    match (member_type, soldier_type) {
+        ("caporegime" | "bookkeeper", _) => &["tools"],
+        ("soldier", Some("external")) => &["tools"],
+        ("soldier", Some("reagent")) => &[],
+        ("soldier", _) | ("consul", _) => &["web_search"],
+        _ => &[],
+    }
+}
+
+/// Catalog `capabilities` maps a capability to true/false; a missing key is
+/// unknown and allowed, so only an explicit false refuses the model.
+fn check_model_capabilities(catalog_model: &Value, required: &[&str], member_type: &str, label: &str) -> Result<(), String> {
+    let capabilities = catalog_model.get("capabilities");
+    for capability in required {
+        if capabilities.and_then(|c| c.get(*capability)).and_then(|v| v.as_bool()) == Some(false) {
+            let what = match *capability {
+                "tools" => "tool calling",
+                "web_search" => "native web search",
+                other => other,
+            };
+            return Err(format!("{label} does not support {what}, which a {member_type} needs"));
+        }
+    }
+    Ok(())
+}
+
+/// Refuse to create a member past the tier's `max_members` (every member the
+/// Don owns) or, for caporegimes, `max_caporegimes`. Null limits are unlimited.
+fn enforce_member_limits(access_token: &str, user_id: &str, caller_tier: &Value, member_type: &str) -> Result<(), String> {
+    let max_members = caller_tier.get("max_members").and_then(|v| v.as_u64());
+    let max_caporegimes = caller_tier.get("max_caporegimes").and_then(|v| v.as_u64());
+    if max_members.is_none() && (member_type != "caporegime" || max_caporegimes.is_none()) {
+        return Ok(());
+    }
+
+    let owned = supabase_call(
+        "db.select",
+        json!({
+            "table": "members",
+            "select": "id,member_type",
+            "filters": [
+                { "column": "owner_id", "op": "eq", "value": user_id }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+    let owned = owned.as_array().cloned().unwrap_or_default();
+    let tier_name = caller_tier.get("display_name").and_then(|v| v.as_str()).unwrap_or("unknown");
+
+    if let Some(max) = max_members {
+        if owned.len() as u64 >= max {
+            return Err(format!("Your tier ({tier_name}) allows at most {max} members"));
+        }
+    }
+    if member_type == "caporegime" {
+        if let Some(max) = max_caporegimes {
+            let caporegimes = owned
+                .iter()
+                .filter(|m| m.get("member_type").and_then(|v| v.as_str()) == Some("caporegime"))
+                .count() as u64;
+            if caporegimes >= max {
+                return Err(format!("Your tier ({tier_name}) allows at most {max} caporegimes"));
+            }
+        }
+    }
+
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -36,7 +36,16 @@ const CONSUL_REF: &str = "formula:local.consul";
 const CAPOREGIME_REF: &str = "formula:local.caporegime";
 const BOOKKEEPER_REF: &str = "formula:local.bookkeeper";
 const MAX_ALL_MENTIONS: usize = 5;
-const MAX_CONTEXT_MESSAGES: usize = 25;
+/// Most messages ever loaded for AI context; the token budget trims from there
+const CONTEXT_FETCH_LIMIT: usize = 200;
+/// History token budget for members whose model has `long_context`
+const LONG_CONTEXT_TOKEN_BUDGET: u64 = 120_000;
+/// History token budget for every other model, including unknown ones
+const DEFAULT_CONTEXT_TOKEN_BUDGET: u64 = 24_000;
+/// Per-message allowance for role and name framing
+const MESSAGE_TOKEN_OVERHEAD: u64 = 4;
+/// Message columns AI context needs
+const CONTEXT_MESSAGE_SELECT: &str = "id,content,sender_type,sender_user_id,sender_member_id,created_at,thread_id,profile:profiles(display_name),member:members(id,name,owner_id)";
 
 // ---------------------------------------------------------------------------
 // 1. Action routing
@@ -67,6 +76,24 @@ fn handle_request(input: &str) -> Result<String, String> {
             let description = parsed.get("description").and_then(|v| v.as_str());
             create_sit_down(access_token, name, description)
         }
+        "fork_sit_down" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let message_id = parsed
+                .get("message_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'message_id'")?;
+            let name = parsed.get("name").and_then(|v| v.as_str());
+            fork_sit_down(access_token, sit_down_id, message_id, name)
+        }
+        "set_context_budget" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            // null (or absent) goes back to each member's model default
+            let budget = match parsed.get("context_token_budget") {
+                None | Some(Value::Null) => None,
+                Some(v) => Some(v.as_u64().ok_or("'context_token_budget' must be a positive integer or null")?),
+            };
+            set_context_budget(access_token, sit_down_id, budget)
+        }
         "delete" | "delete_commission" | "leave_commission" | "leave" => {
             let sit_down_id = require_sit_down_id(&parsed)?;
             leave_sit_down(access_token, sit_down_id)
@@ -159,6 +186,21 @@ fn handle_request(input: &str) -> Result<String, String> {
             let limit = parsed.get("limit").and_then(|v| v.as_u64()).unwrap_or(50);
             list_messages(access_token, sit_down_id, before, limit)
         }
+        "list_thread" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let message_id = parsed
+                .get("message_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'message_id'")?;
+            let limit = parsed.get("limit").and_then(|v| v.as_u64()).unwrap_or(200).min(1000);
+            list_thread(access_token, sit_down_id, message_id, limit)
+        }
+        "list_events" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let since_sequence = parsed.get("since_sequence").and_then(|v| v.as_i64());
+            let limit = parsed.get("limit").and_then(|v| v.as_u64()).unwrap_or(200).min(1000);
+            list_events(access_token, sit_down_id, since_sequence, limit)
+        }
         "send_message" => {
             let sit_down_id = require_sit_down_id(&parsed)?;
             let content = parsed
@@ -170,6 +212,47 @@ fn handle_request(input: &str) -> Result<String, String> {
             let client_messages = parsed.get("messages").cloned();
             send_message(access_token, sit_down_id, content, reply_to_id, client_participants, client_messages)
         }
+        "edit_message" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let message_id = parsed
+                .get("message_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'message_id'")?;
+            let content = parsed
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'content'")?;
+            let rerun_mentions = parsed.get("rerun_mentions").and_then(|v| v.as_bool()).unwrap_or(false);
+            edit_message(access_token, sit_down_id, message_id, content, rerun_mentions)
+        }
+        "regenerate" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let message_id = parsed
+                .get("message_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'message_id'")?;
+            regenerate(access_token, sit_down_id, message_id)
+        }
+        "select_version" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let message_id = parsed
+                .get("message_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'message_id'")?;
+            let version = parsed
+                .get("version")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing required 'version'")?;
+            select_version(access_token, sit_down_id, message_id, version)
+        }
+        "delete_message" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let message_id = parsed
+                .get("message_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'message_id'")?;
+            delete_message(access_token, sit_down_id, message_id)
+        }
 
         // Back room: create or get direct 1-1 sitdown
         "create_or_get_back_room" => {
@@ -190,7 +273,40 @@ fn handle_request(input: &str) -> Result<String, String> {
                 .get("context")
                 .ok_or("Missing required 'context'")?;
             let reply_to_id = parsed.get("reply_to_id").and_then(|v| v.as_str());
-            respond_member(access_token, member_id, context, reply_to_id)
+            respond_member(access_token, member_id, context, reply_to_id, None, None)
+        }
+
+        // Caporegime plans (plan-then-execute)
+        "review_plan" => {
+            let sit_down_id = require_sit_down_id(&parsed)?;
+            let operation_id = parsed
+                .get("operation_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'operation_id'")?;
+            let approved = parsed
+                .get("approved")
+                .and_then(|v| v.as_bool())
+                .ok_or("Missing required 'approved'")?;
+            review_plan(access_token, sit_down_id, operation_id, approved)
+        }
+        "save_plan_as_job" => {
+            let operation_id = parsed
+                .get("operation_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'operation_id'")?;
+            let name = parsed.get("name").and_then(|v| v.as_str());
+            save_plan_as_job(access_token, operation_id, name)
+        }
+        "export_trace" => {
+            let operation_id = parsed
+                .get("operation_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing required 'operation_id'")?;
+            call_caporegime(json!({
+                "action": "export_trace",
+                "operation_id": operation_id,
+                "access_token": access_token
+            }))
         }
 
         _ => Err(format!("Unknown action: {action}")),
@@ -247,6 +363,58 @@ fn create_sit_down(
     Ok(json!({ "sit_down": sit_down }).to_string())
 }
 
+/// New sit-down with the same participants and the history up to and
+/// including `message_id` (see `fork_sit_down()` in migration 041). The name
+/// defaults to "<original> (fork)".
+fn fork_sit_down(
+    access_token: &str,
+    sit_down_id: &str,
+    message_id: &str,
+    name: Option<&str>,
+) -> Result<String, String> {
+    let sit_down = supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "fork_sit_down",
+            "body": {
+                "p_sit_down_id": sit_down_id,
+                "p_message_id": message_id,
+                "p_name": name
+            },
+            "access_token": access_token
+        }),
+    )?;
+
+    Ok(json!({ "sit_down": sit_down }).to_string())
+}
+
+/// Override how many tokens of history members get in this sit-down, or clear
+/// the override with `None`. Creator or admins only (enforced by the RPC).
+fn set_context_budget(access_token: &str, sit_down_id: &str, budget: Option<u64>) -> Result<String, String> {
+    if let Some(b) = budget {
+        if !(1_000..=1_000_000).contains(&b) {
+            return Err("Context budget must be between 1,000 and 1,000,000 tokens".to_string());
+        }
+    }
+    let sit_down = supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "set_context_token_budget",
+            "body": {
+                "p_sit_down_id": sit_down_id,
+                "p_budget": budget
+            },
+            "access_token": access_token
+        }),
+    )?;
+    let sit_down = match sit_down {
+        Value::Array(arr) => arr.into_iter().next().unwrap_or(Value::Null),
+        row => row,
+    };
+
+    Ok(json!({ "sit_down": sit_down }).to_string())
+}
+
 fn leave_sit_down(access_token: &str, sit_down_id: &str) -> Result<String, String> {
     let user = fetch_user(access_token)?;
     let user_id = user
@@ -754,7 +922,7 @@ fn mark_read(access_token: &str, sit_down_id: &str) -> Result<String, String> {
 }
 
 // ---------------------------------------------------------------------------
-// 4. Messaging: list_messages + unified send_message
+// 4. Messaging: list_messages + list_thread + unified send_message + edit/delete
 // ---------------------------------------------------------------------------
 
 // ---------------------------------------------------------------------------
@@ -804,6 +972,101 @@ fn list_messages(access_token: &str, sit_down_id: &str, before: Option<&str>, li
     Ok(json!({ "messages": reversed, "has_more": has_more }).to_string())
 }
 
+/// The thread `message_id` belongs to (or starts), oldest first: its root,
+/// then up to `limit` replies.
+fn list_thread(access_token: &str, sit_down_id: &str, message_id: &str, limit: u64) -> Result<String, String> {
+    let select = "*,profile:profiles(*),member:members(*,catalog_model:model_catalog(*))";
+
+    let rows = supabase_call(
+        "db.select",
+        json!({
+            "table": "messages",
+            "select": select,
+            "filters": [
+                { "column": "id", "op": "eq", "value": message_id },
+                { "column": "sit_down_id", "op": "eq", "value": sit_down_id }
+            ],
+            "limit": 1,
+            "access_token": access_token
+        }),
+    )?;
+    let message = rows
+        .as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .ok_or("Message not found")?;
+
+    let root = match message.get("thread_id").and_then(|v| v.as_str()) {
+        Some(root_id) => {
+            let roots = supabase_call(
+                "db.select",
+                json!({
+                    "table": "messages",
+                    "select": select,
+                    "filters": [{ "column": "id", "op": "eq", "value": root_id }],
+                    "limit": 1,
+                    "access_token": access_token
+                }),
+            )?;
+            roots.as_array().and_then(|arr| arr.first()).cloned().ok_or("Thread not found")?
+        }
+        None => message,
+    };
+    let root_id = root.get("id").and_then(|v| v.as_str()).unwrap_or(message_id).to_string();
+
+    let replies = supabase_call(
+        "db.select",
+        json!({
+            "table": "messages",
+            "select": select,
+            "filters": [{ "column": "thread_id", "op": "eq", "value": root_id }],
+            "order": [{ "column": "created_at", "direction": "asc" }],
+            "limit": limit,
+            "access_token": access_token
+        }),
+    )?;
+    let replies = replies.as_array().cloned().unwrap_or_default();
+    let has_more = replies.len() as u64 == limit;
+
+    let mut messages = vec![root];
+    messages.extend(replies);
+    Ok(json!({ "root_id": root_id, "messages": messages, "has_more": has_more }).to_string())
+}
+
+/// Persisted member events, oldest first. With `since_sequence`, everything after
+/// that cursor (for reconnects); without it, the most recent `limit` events (for a
+/// fresh page load). `has_more` tells a reconnecting client to page again.
+fn list_events(access_token: &str, sit_down_id: &str, since_sequence: Option<i64>, limit: u64) -> Result<String, String> {
+    let mut filters = vec![
+        json!({ "column": "sit_down_id", "op": "eq", "value": sit_down_id }),
+    ];
+    if let Some(since) = since_sequence {
+        filters.push(json!({ "column": "sequence", "op": "gt", "value": since }));
+    }
+
+    let direction = if since_sequence.is_some() { "asc" } else { "desc" };
+    let rows = supabase_call(
+        "db.select",
+        json!({
+            "table": "member_events",
+            "select": "sequence,member_id,run_id,kind,payload,created_at",
+            "filters": filters,
+            "order": [{ "column": "sequence", "direction": direction }],
+            "limit": limit,
+            "access_token": access_token
+        }),
+    )?;
+
+    let mut events = rows.as_array().cloned().unwrap_or_default();
+    if since_sequence.is_none() {
+        events.reverse();
+    }
+    let has_more = since_sequence.is_some() && events.len() as u64 == limit;
+    let last_sequence = events.last().and_then(|e| e.get("sequence")).cloned().unwrap_or(json!(since_sequence));
+
+    Ok(json!({ "events": events, "last_sequence": last_sequence, "has_more": has_more }).to_string())
+}
+
 fn send_message(
     access_token: &str,
     sit_down_id: &str,
@@ -816,20 +1079,9 @@ fn send_message(
     let user_id = user_id_from_jwt(access_token)?;
 
     // 2. Fetch sit-down participants (use client-provided data if available, else DB fetch)
-    let participants = if let Some(cp) = client_participants {
-        cp
-    } else {
-        supabase_call(
-            "db.select",
-            json!({
-                "table": "sit_down_participants",
-                "select": "id,user_id,member_id,profile:profiles(id,display_name),member:members(id,name,member_type,owner_id,system_prompt,catalog_model:model_catalog(id,provider,model,alias)),sit_down:sit_downs(id,name,is_commission)",
-                "filters": [
-                    { "column": "sit_down_id", "op": "eq", "value": sit_down_id }
-                ],
-                "access_token": access_token
-            }),
-        )?
+    let participants = match client_participants {
+        Some(cp) => cp,
+        None => fetch_participants(access_token, sit_down_id)?,
     };
     let participants_arr = participants
         .as_array()
@@ -843,45 +1095,8 @@ fn send_message(
         return Err("Not a participant of this sit-down".to_string());
     }
 
-    // Extract member list for mention parsing (exclude informants and soldiers — not mentionable)
-    let members: Vec<Value> = participants_arr
-        .iter()
-        .filter_map(|p| {
-            let m = p.get("member")?;
-            if m.is_null() {
-                return None;
-            }
-            let member_type = m.get("member_type").and_then(|v| v.as_str()).unwrap_or("consul");
-            if member_type == "informant" || member_type == "soldier" {
-                return None;
-            }
-            Some(json!({
-                "id": m.get("id")?,
-                "name": m.get("name")?,
-                "owner_id": m.get("owner_id")?
-            }))
-        })
-        .collect();
-
-    // Extract Don list for disambiguation
-    let dons: Vec<Value> = participants_arr
-        .iter()
-        .filter_map(|p| {
-            let uid = p.get("user_id").and_then(|v| v.as_str())?;
-            let profile = p.get("profile")?;
-            if profile.is_null() {
-                return None;
-            }
-            let display_name = profile.get("display_name").and_then(|v| v.as_str())?;
-            Some(json!({
-                "user_id": uid,
-                "display_name": display_name
-            }))
-        })
-        .collect();
-
     // 3. Parse mentions server-side via the mention-parser reagent
-    let mention_result = parse_mentions(content, &members, &dons)?;
+    let mention_result = parse_participant_mentions(content, participants_arr)?;
 
     // Check for mention parsing errors (e.g., @all exceeds limit)
     if let Some(err) = mention_result.get("error") {
@@ -928,6 +1143,12 @@ fn send_message(
         .unwrap_or("")
         .to_string();
 
+    let inserted_row = inserted
+        .as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .unwrap_or(Value::Null);
+
     // 5. If no mentions, return immediately
     if mentioned_ids.is_empty() {
         return Ok(json!({
@@ -938,23 +1159,16 @@ fn send_message(
         .to_string());
     }
 
-    // 6. Extract sit_down from participants (joined in step 2) + fetch messages for AI context
-    let sit_down_obj = participants_arr
-        .first()
-        .and_then(|p| p.get("sit_down"))
-        .cloned()
-        .unwrap_or(Value::Null);
-
-    // Use client-provided messages if available, appending the just-inserted user message
-    let inserted_row = inserted
-        .as_array()
-        .and_then(|arr| arr.first())
-        .cloned()
-        .unwrap_or(Value::Null);
-
-    let mut messages = if let Some(mut cm) = client_messages {
+    // 6. Context messages. A reply's context is its thread, which the client's
+    // timeline window may not hold; otherwise client-provided if available,
+    // appending the just-inserted user message
+    let thread_id = inserted_row.get("thread_id").and_then(|v| v.as_str());
+    let messages = if let Some(root) = thread_id {
+        fetch_context_messages(access_token, sit_down_id, Some(root), None)?
+    } else if let Some(mut cm) = client_messages {
         // Append the newly inserted message so AI sees it in context
         if let Some(arr) = cm.as_array_mut() {
+            arr.retain(|m| m.get("deleted_at").map(|v| v.is_null()).unwrap_or(true));
             let don_profile = participants_arr.iter()
                 .find(|p| p.get("user_id").and_then(|v| v.as_str()) == Some(&user_id))
                 .and_then(|p| p.get("profile").cloned())
@@ -966,86 +1180,202 @@ fn send_message(
                 "sender_user_id": user_id,
                 "sender_member_id": null,
                 "created_at": inserted_row.get("created_at").cloned().unwrap_or(Value::Null),
+                "thread_id": null,
                 "profile": don_profile,
             }));
-            // Cap to the most recent N messages (client may send more than the window)
+            // Cap to the most recent N messages; each member's token budget trims further
             let len = arr.len();
-            if len > MAX_CONTEXT_MESSAGES {
-                *arr = arr.split_off(len - MAX_CONTEXT_MESSAGES);
+            if len > CONTEXT_FETCH_LIMIT {
+                *arr = arr.split_off(len - CONTEXT_FETCH_LIMIT);
             }
         }
         cm
     } else {
-        supabase_call(
-            "db.select",
-            json!({
-                "table": "messages",
-                "select": "id,content,sender_type,sender_user_id,sender_member_id,created_at,profile:profiles(display_name),member:members(id,name,owner_id)",
-                "filters": [
-                    { "column": "sit_down_id", "op": "eq", "value": sit_down_id }
-                ],
-                "order": [{ "column": "created_at", "direction": "desc" }],
-                "limit": MAX_CONTEXT_MESSAGES,
-                "access_token": access_token
-            }),
-        )?
+        fetch_context_messages(access_token, sit_down_id, None, None)?
     };
 
-    // Augment last message with reply context so the AI knows what's being replied to
-    if let Some(rid) = reply_to_id {
-        let reply_quote = messages.as_array().and_then(|arr| {
-            let replied = arr.iter().find(|m| m.get("id").and_then(|v| v.as_str()) == Some(rid))?;
-            let sender_type = replied.get("sender_type").and_then(|v| v.as_str()).unwrap_or("");
-            let sender_name = if sender_type == "don" {
-                let dn = replied.get("profile")
-                    .and_then(|v| v.get("display_name"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Don");
-                format!("Don {dn}")
-            } else {
-                replied.get("member")
-                    .and_then(|v| v.get("name"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown")
-                    .to_string()
-            };
-            let rc = replied.get("content").and_then(|v| v.as_str()).unwrap_or("");
-            // Truncate long replies to avoid bloating context
-            let truncated = if rc.len() > 300 { format!("{}...", &rc[..300]) } else { rc.to_string() };
-            Some(format!("[replying to {sender_name}: \"{truncated}\"]"))
-        });
+    let outcome = dispatch_mentions(
+        access_token, sit_down_id, &message_id, reply_to_id, &mentioned_ids, &participants, messages,
+    )?;
+    Ok(outcome.to_string())
+}
 
-        if let Some(quote) = reply_quote {
-            if let Some(arr) = messages.as_array_mut() {
-                if let Some(last) = arr.last_mut() {
-                    let orig = last.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                    last["content"] = json!(format!("{quote}\n{orig}"));
-                }
+/// Parse `content` for mentions of the sit-down's mentionable members, with
+/// the participating Dons for disambiguation.
+fn parse_participant_mentions(content: &str, participants_arr: &[Value]) -> Result<Value, String> {
+    // Extract member list for mention parsing (exclude informants and soldiers — not mentionable)
+    let members: Vec<Value> = participants_arr
+        .iter()
+        .filter_map(|p| {
+            let m = p.get("member")?;
+            if m.is_null() {
+                return None;
             }
-        }
-    }
+            let member_type = m.get("member_type").and_then(|v| v.as_str()).unwrap_or("consul");
+            if member_type == "informant" || member_type == "soldier" {
+                return None;
+            }
+            Some(json!({
+                "id": m.get("id")?,
+                "name": m.get("name")?,
+                "owner_id": m.get("owner_id")?
+            }))
+        })
+        .collect();
 
-    // Pre-fetch context to pass to each spawned _respond_member
-    let context = json!({
-        "sit_down_id": sit_down_id,
-        "sit_down": sit_down_obj,
-        "participants": participants,
-        "messages": messages,
-        "user_id": user_id
-    });
+    // Extract Don list for disambiguation
+    let dons: Vec<Value> = participants_arr
+        .iter()
+        .filter_map(|p| {
+            let uid = p.get("user_id").and_then(|v| v.as_str())?;
+            let profile = p.get("profile")?;
+            if profile.is_null() {
+                return None;
+            }
+            let display_name = profile.get("display_name").and_then(|v| v.as_str())?;
+            Some(json!({
+                "user_id": uid,
+                "display_name": display_name
+            }))
+        })
+        .collect();
 
-    // 7. Spawn one _respond_member per mentioned member
-    let mut task_ids: Vec<String> = Vec::new();
-    for mid in &mentioned_ids {
-        let mut spawn_input = json!({
-            "action": "_respond_member",
-            "access_token": access_token,
-            "member_id": mid,
-            "context": context
-        });
-        if let Some(rid) = reply_to_id {
-            spawn_input["reply_to_id"] = json!(rid);
-        }
+    parse_mentions(content, &members, &dons)
+}
+
+/// Prefix the Don's message `message_id` with a quote of the message it
+/// replies to, so the AI knows what's being replied to.
+fn quote_reply(messages: &mut Value, message_id: &str, rid: &str) {
+    let reply_quote = messages.as_array().and_then(|arr| {
+        let replied = arr.iter().find(|m| m.get("id").and_then(|v| v.as_str()) == Some(rid))?;
+        let sender_type = replied.get("sender_type").and_then(|v| v.as_str()).unwrap_or("");
+        let sender_name = if sender_type == "don" {
+            let dn = replied.get("profile")
+                .and_then(|v| v.get("display_name"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("Don");
+            format!("Don {dn}")
+        } else {
+            replied.get("member")
+                .and_then(|v| v.get("name"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown")
+                .to_string()
+        };
+        let rc = replied.get("content").and_then(|v| v.as_str()).unwrap_or("");
+        // Truncate long replies to avoid bloating context
+        let truncated = if rc.len() > 300 { format!("{}...", &rc[..300]) } else { rc.to_string() };
+        Some(format!("[replying to {sender_name}: \"{truncated}\"]"))
+    });
+
+    if let Some(quote) = reply_quote {
+        let target = messages
+            .as_array_mut()
+            .and_then(|arr| arr.iter_mut().find(|m| m.get("id").and_then(|v| v.as_str()) == Some(message_id)));
+        if let Some(target) = target {
+            let orig = target.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            target["content"] = json!(format!("{quote}\n{orig}"));
+        }
+    }
+}
+
+/// Run each mentioned member on the Don message `message_id` and collect the
+/// outcomes as `{message_id, mentioned_member_ids, results}` (plus
+/// `quota_exceeded` when the Don's quota refused them all). Shared by
+/// send_message and edit_message's mention re-run.
+fn dispatch_mentions(
+    access_token: &str,
+    sit_down_id: &str,
+    message_id: &str,
+    reply_to_id: Option<&str>,
+    mentioned_ids: &[String],
+    participants: &Value,
+    mut messages: Value,
+) -> Result<Value, String> {
+    let user_id = user_id_from_jwt(access_token)?;
+    let participants_arr = participants
+        .as_array()
+        .ok_or("Expected participants array")?;
+    // Refuse before any member is invoked once the Don's quota is used up
+    if let Some(exceeded) = check_quota(access_token)? {
+        let reason = quota_message(&exceeded);
+        let results: Vec<Value> = mentioned_ids
+            .iter()
+            .map(|mid| {
+                let member_name = lookup_member_name(participants_arr, mid);
+                emit_sit_down_event(sit_down_id, mid, &member_name, message_id, access_token,
+                    json!({"kind": "error", "code": "quota_exceeded", "message": reason}));
+                json!({ "member_id": mid, "status": "error", "error": reason })
+            })
+            .collect();
+        return Ok(json!({
+            "message_id": message_id,
+            "mentioned_member_ids": mentioned_ids,
+            "results": results,
+            "quota_exceeded": exceeded
+        }));
+    }
+
+    // Extract sit_down from participants (joined by fetch_participants);
+    // client-provided participants don't carry it
+    let mut sit_down_obj = participants_arr
+        .first()
+        .and_then(|p| p.get("sit_down"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    if sit_down_obj.is_null() {
+        sit_down_obj = supabase_call(
+            "db.select",
+            json!({
+                "table": "sit_downs",
+                "select": "id,name,is_commission,context_token_budget",
+                "filters": [{ "column": "id", "op": "eq", "value": sit_down_id }],
+                "limit": 1,
+                "access_token": access_token
+            }),
+        )?
+        .as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .unwrap_or(Value::Null);
+    }
+
+    if let Some(rid) = reply_to_id {
+        quote_reply(&mut messages, message_id, rid);
+    }
+
+    // A message in a thread is answered from the thread alone
+    let thread_id = messages
+        .as_array()
+        .and_then(|arr| arr.iter().find(|m| m.get("id").and_then(|v| v.as_str()) == Some(message_id)))
+        .and_then(|m| m.get("thread_id"))
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    // Pre-fetch context to pass to each spawned _respond_member.
+    // The Don's message id doubles as the run id that groups persisted member events.
+    let context = json!({
+        "sit_down_id": sit_down_id,
+        "sit_down": sit_down_obj,
+        "participants": participants,
+        "messages": messages,
+        "user_id": user_id,
+        "run_id": message_id,
+        "thread_id": thread_id
+    });
+
+    // 7. Spawn one _respond_member per mentioned member
+    let mut task_ids: Vec<String> = Vec::new();
+    for mid in mentioned_ids {
+        let mut spawn_input = json!({
+            "action": "_respond_member",
+            "access_token": access_token,
+            "member_id": mid,
+            "context": context
+        });
+        if let Some(rid) = reply_to_id {
+            spawn_input["reply_to_id"] = json!(rid);
+        }
 
         let spawn_request = json!({
             "tool": "execution",
@@ -1105,7 +1435,7 @@ fn send_message(
 
             // Emit error event from parent so the client's SSE stream sees it
             let member_name = lookup_member_name(participants_arr, mid);
-            emit_sit_down_event(sit_down_id, mid, &member_name,
+            emit_sit_down_event(sit_down_id, mid, &member_name, message_id, access_token,
                 json!({"kind": "error", "message": clean_error_message(err_msg)}));
 
             results.push(json!({
@@ -1129,7 +1459,7 @@ fn send_message(
                 .unwrap_or("Formula error");
 
             let member_name = lookup_member_name(participants_arr, mid);
-            emit_sit_down_event(sit_down_id, mid, &member_name,
+            emit_sit_down_event(sit_down_id, mid, &member_name, message_id, access_token,
                 json!({"kind": "error", "message": clean_error_message(err_msg)}));
 
             results.push(json!({
@@ -1157,8 +1487,317 @@ fn send_message(
         "message_id": message_id,
         "mentioned_member_ids": mentioned_ids,
         "results": results
-    })
-    .to_string())
+    }))
+}
+
+/// Edit a message's content (sender or sit-down admin; enforced by the
+/// `edit_message` RPC, which keeps the previous version in metadata.edits).
+/// Don messages have their mentions re-parsed; with `rerun_mentions` every
+/// member mentioned in the new text answers again, seeing the conversation
+/// only up to the edited message.
+fn edit_message(
+    access_token: &str,
+    sit_down_id: &str,
+    message_id: &str,
+    content: &str,
+    rerun_mentions: bool,
+) -> Result<String, String> {
+    let rows = supabase_call(
+        "db.select",
+        json!({
+            "table": "messages",
+            "select": "id,sender_type,created_at,metadata,thread_id",
+            "filters": [
+                { "column": "id", "op": "eq", "value": message_id },
+                { "column": "sit_down_id", "op": "eq", "value": sit_down_id }
+            ],
+            "limit": 1,
+            "access_token": access_token
+        }),
+    )?;
+    let original = rows
+        .as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .ok_or("Message not found")?;
+    let is_don_message = original.get("sender_type").and_then(|v| v.as_str()) == Some("don");
+
+    // Member replies keep their stored mentions; Don messages are re-parsed
+    let mut participants = Value::Null;
+    let mut mentioned_ids: Vec<String> = Vec::new();
+    if is_don_message {
+        participants = fetch_participants(access_token, sit_down_id)?;
+        let participants_arr = participants
+            .as_array()
+            .ok_or("Expected participants array")?;
+        let mention_result = parse_participant_mentions(content, participants_arr)?;
+        if let Some(err) = mention_result.get("error") {
+            return Ok(json!({ "error": err }).to_string());
+        }
+        mentioned_ids = mention_result
+            .get("mentioned_member_ids")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    let updated = supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "edit_message",
+            "body": {
+                "p_message_id": message_id,
+                "p_sit_down_id": sit_down_id,
+                "p_content": content,
+                "p_mentions": if is_don_message { json!(mentioned_ids) } else { Value::Null }
+            },
+            "access_token": access_token
+        }),
+    )?;
+    let updated = match updated {
+        Value::Array(arr) => arr.into_iter().next().unwrap_or(Value::Null),
+        row => row,
+    };
+
+    if !rerun_mentions || !is_don_message || mentioned_ids.is_empty() {
+        return Ok(json!({
+            "message": updated,
+            "message_id": message_id,
+            "mentioned_member_ids": mentioned_ids,
+            "results": []
+        })
+        .to_string());
+    }
+
+    let created_at = original.get("created_at").and_then(|v| v.as_str());
+    let thread_id = original.get("thread_id").and_then(|v| v.as_str());
+    let reply_to_id = original
+        .get("metadata")
+        .and_then(|m| m.get("reply_to_id"))
+        .and_then(|v| v.as_str());
+    let messages = fetch_context_messages(access_token, sit_down_id, thread_id, created_at)?;
+
+    let mut outcome = dispatch_mentions(
+        access_token, sit_down_id, message_id, reply_to_id, &mentioned_ids, &participants, messages,
+    )?;
+    outcome["message"] = updated;
+    Ok(outcome.to_string())
+}
+
+/// Soft-delete a message (sender or sit-down admin). The row stays as a
+/// placeholder so replies to it still resolve; its content is cleared.
+fn delete_message(access_token: &str, sit_down_id: &str, message_id: &str) -> Result<String, String> {
+    let deleted = supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "delete_message",
+            "body": {
+                "p_message_id": message_id,
+                "p_sit_down_id": sit_down_id
+            },
+            "access_token": access_token
+        }),
+    )?;
+    let deleted = match deleted {
+        Value::Array(arr) => arr.into_iter().next().unwrap_or(Value::Null),
+        row => row,
+    };
+    Ok(json!({ "message": deleted }).to_string())
+}
+
+/// Re-run the member that wrote `message_id` on the context it answered:
+/// the conversation up to that message. The new answer is stored as another
+/// version of the same message and becomes canonical; `select_version` picks
+/// a different one. Caporegime reports come from an operation rather than a
+/// single answer, so they can't be regenerated — mention the caporegime again.
+fn regenerate(access_token: &str, sit_down_id: &str, message_id: &str) -> Result<String, String> {
+    let user_id = user_id_from_jwt(access_token)?;
+
+    let rows = supabase_call(
+        "db.select",
+        json!({
+            "table": "messages",
+            "select": "id,sender_type,sender_member_id,created_at,metadata,deleted_at,thread_id,member:members(member_type,owner_id)",
+            "filters": [
+                { "column": "id", "op": "eq", "value": message_id },
+                { "column": "sit_down_id", "op": "eq", "value": sit_down_id }
+            ],
+            "limit": 1,
+            "access_token": access_token
+        }),
+    )?;
+    let original = rows
+        .as_array()
+        .and_then(|arr| arr.first())
+        .cloned()
+        .ok_or("Message not found")?;
+
+    if original.get("sender_type").and_then(|v| v.as_str()) != Some("member") {
+        return Err("Only member answers can be regenerated".to_string());
+    }
+    if original.get("deleted_at").is_some_and(|v| !v.is_null()) {
+        return Err("Message was deleted".to_string());
+    }
+    let member_id = original
+        .get("sender_member_id")
+        .and_then(|v| v.as_str())
+        .ok_or("Message has no member")?
+        .to_string();
+    let metadata = original.get("metadata").cloned().unwrap_or(json!({}));
+    let member_type = original
+        .get("member")
+        .and_then(|m| m.get("member_type"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("consul");
+    if member_type == "caporegime" || metadata.get("operation_id").is_some() {
+        return Err("Caporegime reports can't be regenerated. Mention the caporegime again to rerun the operation.".to_string());
+    }
+
+    // Checked again by add_message_version, but fail before spending tokens
+    let participants = fetch_participants(access_token, sit_down_id)?;
+    let participants_arr = participants
+        .as_array()
+        .ok_or("Expected participants array")?;
+    let is_owner = original
+        .get("member")
+        .and_then(|m| m.get("owner_id"))
+        .and_then(|v| v.as_str())
+        == Some(user_id.as_str());
+    let is_admin = participants_arr.iter().any(|p| {
+        p.get("user_id").and_then(|v| v.as_str()) == Some(user_id.as_str())
+            && p.get("is_admin").and_then(|v| v.as_bool()).unwrap_or(false)
+    });
+    if !is_owner && !is_admin {
+        return Err("Only the member's owner or a sit-down admin can regenerate this message".to_string());
+    }
+    let member_in_sit_down = participants_arr.iter().any(|p| {
+        p.get("member").and_then(|m| m.get("id")).and_then(|v| v.as_str()) == Some(member_id.as_str())
+    });
+    if !member_in_sit_down {
+        return Err("This member has left the sit-down".to_string());
+    }
+
+    if let Some(exceeded) = check_quota(access_token)? {
+        return Err(quota_message(&exceeded));
+    }
+
+    // The same window the original answer saw: everything before it
+    let created_at = original.get("created_at").and_then(|v| v.as_str());
+    let thread_id = original.get("thread_id").and_then(|v| v.as_str());
+    let mut messages = fetch_context_messages(access_token, sit_down_id, thread_id, created_at)?;
+    if let Some(arr) = messages.as_array_mut() {
+        arr.retain(|m| m.get("id").and_then(|v| v.as_str()) != Some(message_id));
+    }
+
+    let sit_down_obj = participants_arr
+        .first()
+        .and_then(|p| p.get("sit_down"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    let context = json!({
+        "sit_down_id": sit_down_id,
+        "sit_down": sit_down_obj,
+        "participants": participants,
+        "messages": messages,
+        "user_id": user_id,
+        "run_id": message_id,
+        "thread_id": thread_id
+    });
+    let reply_to_id = metadata.get("reply_to_id").and_then(|v| v.as_str());
+
+    respond_member(access_token, &member_id, &context, reply_to_id, None, Some(message_id))
+}
+
+/// Make one of a regenerated message's versions canonical again.
+fn select_version(access_token: &str, sit_down_id: &str, message_id: &str, version: u64) -> Result<String, String> {
+    let selected = supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "select_message_version",
+            "body": {
+                "p_message_id": message_id,
+                "p_sit_down_id": sit_down_id,
+                "p_version": version
+            },
+            "access_token": access_token
+        }),
+    )?;
+    let selected = match selected {
+        Value::Array(arr) => arr.into_iter().next().unwrap_or(Value::Null),
+        row => row,
+    };
+    Ok(json!({ "message": selected }).to_string())
+}
+
+/// Participants with joined profile, member and sit-down, as the AI response path needs them.
+fn fetch_participants(access_token: &str, sit_down_id: &str) -> Result<Value, String> {
+    supabase_call(
+        "db.select",
+        json!({
+            "table": "sit_down_participants",
+            "select": "id,user_id,member_id,is_admin,profile:profiles(id,display_name),member:members(id,name,member_type,owner_id,system_prompt,fallback_model_ids,plan_mode,catalog_model:model_catalog(id,provider,model,alias,capabilities)),sit_down:sit_downs(id,name,is_commission,context_token_budget)",
+            "filters": [
+                { "column": "sit_down_id", "op": "eq", "value": sit_down_id }
+            ],
+            "access_token": access_token
+        }),
+    )
+}
+
+/// The most recent messages, newest first, for AI conversation context: the
+/// main timeline, or with `thread_id` the thread's root and its replies.
+fn fetch_context_messages(
+    access_token: &str,
+    sit_down_id: &str,
+    thread_id: Option<&str>,
+    until: Option<&str>,
+) -> Result<Value, String> {
+    // Deleted messages stay as placeholders in the chat but never reach a model
+    let mut filters = vec![
+        json!({ "column": "sit_down_id", "op": "eq", "value": sit_down_id }),
+        json!({ "column": "deleted_at", "op": "is", "value": null }),
+        json!({ "column": "thread_id", "op": if thread_id.is_some() { "eq" } else { "is" }, "value": thread_id }),
+    ];
+    // An edited message re-runs against the conversation as it stood when it was sent
+    if let Some(ts) = until {
+        filters.push(json!({ "column": "created_at", "op": "lte", "value": ts }));
+    }
+    let mut messages = supabase_call(
+        "db.select",
+        json!({
+            "table": "messages",
+            "select": CONTEXT_MESSAGE_SELECT,
+            "filters": filters,
+            "order": [{ "column": "created_at", "direction": "desc" }],
+            "limit": CONTEXT_FETCH_LIMIT,
+            "access_token": access_token
+        }),
+    )?;
+
+    if let Some(root_id) = thread_id {
+        let root = supabase_call(
+            "db.select",
+            json!({
+                "table": "messages",
+                "select": CONTEXT_MESSAGE_SELECT,
+                "filters": [
+                    { "column": "id", "op": "eq", "value": root_id },
+                    { "column": "deleted_at", "op": "is", "value": null }
+                ],
+                "limit": 1,
+                "access_token": access_token
+            }),
+        )?;
+        if let (Some(arr), Some(root)) = (messages.as_array_mut(), root.as_array().and_then(|r| r.first())) {
+            arr.push(root.clone());
+        }
+    }
+
+    Ok(messages)
 }
 
 // ---------------------------------------------------------------------------
@@ -1170,6 +1809,8 @@ fn respond_member(
     member_id: &str,
     context: &Value,
     reply_to_id: Option<&str>,
+    approved_operation_id: Option<&str>,
+    regenerate_message_id: Option<&str>,
 ) -> Result<String, String> {
     let sit_down_id = context
         .get("sit_down_id")
@@ -1188,6 +1829,7 @@ fn respond_member(
         .get("user_id")
         .and_then(|v| v.as_str())
         .ok_or("context missing user_id")?;
+    let run_id = context.get("run_id").and_then(|v| v.as_str()).unwrap_or("");
 
     // 1. Look up member from pre-fetched participants context (no Supabase call)
     let member = participants
@@ -1223,27 +1865,53 @@ fn respond_member(
         }).to_string());
     }
 
-    let catalog_model = member
-        .get("catalog_model")
-        .filter(|v| !v.is_null())
-        .ok_or_else(|| format!("{member_name}'s model has been removed. Please assign a new model."))?;
-
-    let provider = catalog_model
-        .get("provider")
-        .and_then(|v| v.as_str())
-        .ok_or("catalog_model missing 'provider'")?;
+    // 2. Resolve the model chain: primary catalog model, then ordered fallbacks.
+    // If the primary was removed from the catalog, the first fallback takes its place.
+    let mut fallback_models = resolve_fallback_models(&member, access_token);
+    let mut routing = Value::Null;
 
-    let model = catalog_model
-        .get("model")
-        .and_then(|v| v.as_str())
-        .ok_or("catalog_model missing 'model'")?;
+    let (provider, model) = match member.get("catalog_model").filter(|v| !v.is_null()) {
+        Some(catalog_model) => {
+            let provider = catalog_model
+                .get("provider")
+                .and_then(|v| v.as_str())
+                .ok_or("catalog_model missing 'provider'")?;
+            let model = catalog_model
+                .get("model")
+                .and_then(|v| v.as_str())
+                .ok_or("catalog_model missing 'model'")?;
+            // A weighted alias hands this response to one of its target models
+            let pick = catalog_model
+                .get("id")
+                .and_then(|v| v.as_str())
+                .and_then(|catalog_id| pick_catalog_target(catalog_id, member_id, access_token));
+            match pick {
+                Some((target_provider, target_model, route)) => {
+                    routing = route;
+                    (target_provider, target_model)
+                }
+                None => (provider.to_string(), model.to_string()),
+            }
+        }
+        None if !fallback_models.is_empty() => {
+            let first = fallback_models.remove(0);
+            (
+                first.get("provider").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                first.get("model").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            )
+        }
+        None => {
+            return Err(format!("{member_name}'s model has been removed. Please assign a new model."));
+        }
+    };
 
-    // 2. Resolve catalyst reference from provider
-    let (catalyst_ref, _) = resolve_provider_ref(provider)?;
+    let (catalyst_ref, _) = resolve_provider_ref(&provider)?;
 
     // 3. Build system prompt + conversation history
     let system_prompt = build_system_prompt(&member, sit_down, participants, user_id);
-    let conversation = build_conversation_history(member_id, messages, sit_down, participants);
+    let thread_id = context.get("thread_id").and_then(|v| v.as_str());
+    let token_budget = context_token_budget(&member, sit_down).saturating_sub(estimate_tokens(&system_prompt));
+    let conversation = build_conversation_history(member_id, messages, sit_down, participants, thread_id, token_budget);
 
     // 5. Resolve the formula ref based on member_type
     let formula_ref = match member_type {
@@ -1270,6 +1938,18 @@ fn respond_member(
     if let Some(rid) = reply_to_id {
         fm_input["reply_to_id"] = json!(rid);
     }
+    if let Some(op_id) = approved_operation_id {
+        fm_input["approved_operation_id"] = json!(op_id);
+    }
+    if !run_id.is_empty() {
+        fm_input["run_id"] = json!(run_id);
+    }
+    if !fallback_models.is_empty() {
+        fm_input["fallback_models"] = json!(fallback_models);
+    }
+    if !routing.is_null() {
+        fm_input["routing"] = routing.clone();
+    }
 
     let fm_request = json!({
         "tool": "execution",
@@ -1310,6 +1990,20 @@ fn respond_member(
         return Err(format!("Empty response from {member_name}"));
     }
 
+    // The formula reports which model in the chain actually answered
+    let answered_ref = fm_result
+        .get("answered_by")
+        .and_then(|a| a.get("catalyst_ref"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(&catalyst_ref)
+        .to_string();
+    let answered_model = fm_result
+        .get("answered_by")
+        .and_then(|a| a.get("model"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(&model)
+        .to_string();
+
     // Check if the formula already inserted the message (caporegime)
     let has_message_id = fm_result
         .get("message_id")
@@ -1327,9 +2021,15 @@ fn respond_member(
     } else {
         // Sit-down inserts (consul, bookkeeper)
         let mut metadata = json!({
-            "provider": catalyst_ref,
-            "model": model
+            "provider": answered_ref,
+            "model": answered_model
         });
+        if answered_ref != catalyst_ref || answered_model != model {
+            metadata["fallback_from"] = json!({ "provider": catalyst_ref, "model": model });
+        }
+        if !routing.is_null() {
+            metadata["routing"] = routing.clone();
+        }
         if let Some(rid) = reply_to_id {
             metadata["reply_to_id"] = json!(rid);
         }
@@ -1338,25 +2038,365 @@ fn respond_member(
             metadata["operation_id"] = json!(op_id);
         }
 
-        let mid = insert_ai_message(sit_down_id, member_id, &content, &metadata, access_token)
-            .unwrap_or_default();
+        let mid = match regenerate_message_id {
+            // A regenerated answer becomes the newest version of the original message
+            Some(original_id) => {
+                add_message_version(original_id, &content, &metadata, access_token)?;
+                original_id.to_string()
+            }
+            None => insert_ai_message(sit_down_id, member_id, &content, &metadata, access_token)
+                .unwrap_or_default(),
+        };
 
         // Emit message_inserted event
-        emit_sit_down_event(sit_down_id, member_id, member_name,
+        emit_sit_down_event(sit_down_id, member_id, member_name, run_id, access_token,
             json!({"kind": "message_inserted", "message_id": mid}));
 
         mid
     };
 
+    // Usage is recorded only for an answer that reached the sit-down. A caporegime
+    // records its own, one row per model its run called.
+    if let Some(usage) = fm_result.get("usage").filter(|_| !message_id.is_empty() && !has_message_id) {
+        record_usage(
+            &json!({
+                "member_id": member_id,
+                "sit_down_id": sit_down_id,
+                "operation_id": fm_result.get("operation_id").and_then(|v| v.as_str()),
+                "message_id": Some(message_id.as_str()).filter(|s| !s.is_empty()),
+                "provider": provider_name(&answered_ref),
+                "model": answered_model
+            }),
+            usage,
+            access_token,
+        );
+    }
+
     Ok(json!({
         "message_id": message_id,
         "content": content,
-        "provider": provider,
-        "model": model
+        "provider": answered_ref,
+        "model": answered_model
     })
     .to_string())
 }
 
+/// Write one response's token usage to `usage_ledger`. Best-effort: a failed
+/// write never costs the Don their answer. Written with the service role, as
+/// clients can't record usage, and billed to the Don Supabase Auth verifies
+/// the token for (user_id_from_jwt doesn't check the signature).
+fn record_usage(entry: &Value, usage: &Value, access_token: &str) {
+    let input_tokens = usage.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+    let output_tokens = usage.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0);
+    if input_tokens == 0 && output_tokens == 0 {
+        return;
+    }
+    let Ok(user) = fetch_user(access_token) else { return };
+    let Some(user_id) = user.get("id").and_then(|v| v.as_str()) else { return };
+
+    let mut body = entry.clone();
+    body["user_id"] = json!(user_id);
+    body["input_tokens"] = json!(input_tokens);
+    body["output_tokens"] = json!(output_tokens);
+    let _ = supabase_call_once(
+        "db.insert",
+        json!({
+            "table": "usage_ledger",
+            "body": body,
+            "service_role": true
+        }),
+    );
+}
+
+/// The caller's first used-up quota (see `check_quota()` in migration 032),
+/// or None when they are within every limit.
+fn check_quota(access_token: &str) -> Result<Option<Value>, String> {
+    let status = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "check_quota",
+            "body": {},
+            "access_token": access_token
+        }),
+    )?;
+    Ok(status.get("exceeded").filter(|v| v.is_object()).cloned())
+}
+
+/// "Quota exceeded: 1000000 of 1000000 tokens used today (tier quota). Resets at ..."
+fn quota_message(exceeded: &Value) -> String {
+    let period = match exceeded.get("period").and_then(|v| v.as_str()) {
+        Some("month") => "this month",
+        _ => "today",
+    };
+    let source = exceeded.get("source").and_then(|v| v.as_str()).unwrap_or("tier");
+    let resets_at = exceeded.get("resets_at").and_then(|v| v.as_str()).unwrap_or("the next period");
+    let limit = exceeded.get("limit").cloned().unwrap_or(Value::Null);
+    let used = exceeded.get("used").cloned().unwrap_or(Value::Null);
+    let amount = if exceeded.get("metric").and_then(|v| v.as_str()) == Some("cost") {
+        format!(
+            "${:.2} of ${:.2} spent",
+            used.as_f64().unwrap_or(0.0),
+            limit.as_f64().unwrap_or(0.0)
+        )
+    } else {
+        format!("{used} of {limit} tokens used")
+    };
+    format!("Quota exceeded: {amount} {period} ({source} quota). Resets at {resets_at}.")
+}
+
+/// `catalyst:moonmoon69.claude` → `claude`
+fn provider_name(catalyst_ref: &str) -> &str {
+    catalyst_ref.rsplit('.').next().unwrap_or(catalyst_ref)
+}
+
+/// When the member's catalog entry is a weighted alias, the `(provider, model)`
+/// picked for this response plus the routing record kept in message metadata
+/// (`catalog_id`, `alias`, `target_catalog_id`, `target_alias`, `sticky`).
+/// None for plain entries, or if the pick fails: the entry's own model answers.
+fn pick_catalog_target(catalog_id: &str, member_id: &str, access_token: &str) -> Option<(String, String, Value)> {
+    let pick = supabase_call(
+        "db.rpc",
+        json!({
+            "function": "pick_catalog_target",
+            "body": { "p_catalog_id": catalog_id, "p_member_id": member_id },
+            "access_token": access_token
+        }),
+    )
+    .ok()?;
+
+    if pick.get("routed").and_then(|v| v.as_bool()) != Some(true) {
+        return None;
+    }
+    let provider = pick.get("provider").and_then(|v| v.as_str())?.to_string();
+    let model = pick.get("model").and_then(|v| v.as_str())?.to_string();
+    let route = json!({
+        "catalog_id": pick.get("catalog_id"),
+        "alias": pick.get("alias"),
+        "target_catalog_id": pick.get("target_catalog_id"),
+        "target_alias": pick.get("target_alias"),
+        "sticky": pick.get("sticky")
+    });
+    Some((provider, model, route))
+}
+
+/// Resolve a member's `fallback_model_ids` into ordered `{provider, catalyst_ref,
+/// model, alias}` entries. Inactive, removed or unsupported entries are skipped
+/// so a stale chain degrades to fewer fallbacks rather than an error, as are
+/// models whose catalog `capabilities` rule out what the member's role needs.
+fn resolve_fallback_models(member: &Value, access_token: &str) -> Vec<Value> {
+    let ids: Vec<&str> = member
+        .get("fallback_model_ids")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    if ids.is_empty() {
+        return vec![];
+    }
+
+    let catalog = supabase_call(
+        "db.select",
+        json!({
+            "table": "model_catalog",
+            "select": "id,provider,model,alias,capabilities",
+            "filters": [
+                { "column": "id", "op": "in", "value": format!("({})", ids.join(",")) },
+                { "column": "is_active", "op": "eq", "value": "true" }
+            ],
+            "access_token": access_token
+        }),
+    )
+    .unwrap_or(json!([]));
+    let catalog_arr = catalog.as_array().cloned().unwrap_or_default();
+
+    // Caporegimes and bookkeepers run tool loops; consuls attach native web search
+    let required = match member.get("member_type").and_then(|v| v.as_str()) {
+        Some("caporegime" | "bookkeeper") => "tools",
+        _ => "web_search",
+    };
+
+    ids.iter()
+        .filter_map(|id| {
+            let entry = catalog_arr
+                .iter()
+                .find(|m| m.get("id").and_then(|v| v.as_str()) == Some(*id))?;
+            let lacks_required = entry
+                .get("capabilities")
+                .and_then(|c| c.get(required))
+                .and_then(|v| v.as_bool())
+                == Some(false);
+            if lacks_required {
+                return None;
+            }
+            let provider = entry.get("provider").and_then(|v| v.as_str())?;
+            let (catalyst_ref, _) = resolve_provider_ref(provider).ok()?;
+            Some(json!({
+                "provider": provider,
+                "catalyst_ref": catalyst_ref,
+                "model": entry.get("model")?,
+                "alias": entry.get("alias").cloned().unwrap_or(Value::Null)
+            }))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// 5a. Caporegime plans: review (approve/reject) + save as job
+// ---------------------------------------------------------------------------
+
+fn review_plan(access_token: &str, sit_down_id: &str, operation_id: &str, approved: bool) -> Result<String, String> {
+    let user_id = user_id_from_jwt(access_token)?;
+
+    // RLS scopes operations to their owner, so only the caporegime's Don can review its plan
+    let operation = supabase_call(
+        "db.select",
+        json!({
+            "table": "operations",
+            "select": "id,member_id,sit_down_id,status,plan",
+            "filters": [
+                { "column": "id", "op": "eq", "value": operation_id }
+            ],
+            "access_token": access_token
+        }),
+    )?
+    .as_array()
+    .and_then(|arr| arr.first())
+    .cloned()
+    .ok_or("Operation not found")?;
+
+    if operation.get("sit_down_id").and_then(|v| v.as_str()) != Some(sit_down_id) {
+        return Err("Operation does not belong to this sit-down".to_string());
+    }
+
+    let member_id = operation.get("member_id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let plan_message_id = operation
+        .get("plan")
+        .and_then(|p| p.get("message_id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    // Claim the paused operation atomically so a double tap can't run it twice
+    let body = if approved {
+        json!({ "status": "running" })
+    } else {
+        json!({ "status": "cancelled", "result_content": "Plan rejected by the Don.", "completed_at": "now()" })
+    };
+    let claimed = supabase_call(
+        "db.update",
+        json!({
+            "table": "operations",
+            "body": body,
+            "filters": [
+                { "column": "id", "op": "eq", "value": operation_id },
+                { "column": "status", "op": "eq", "value": "awaiting_approval" }
+            ],
+            "access_token": access_token
+        }),
+    )?;
+    if claimed.as_array().map(|a| a.is_empty()).unwrap_or(true) {
+        return Err("This plan is no longer awaiting approval".to_string());
+    }
+
+    let plan_status = if approved { "approved" } else { "rejected" };
+    if !plan_message_id.is_empty() {
+        let _ = update_ai_message_metadata(&plan_message_id, &json!({ "plan_status": plan_status }), access_token);
+    }
+
+    if !approved {
+        return Ok(json!({ "operation_id": operation_id, "status": "cancelled" }).to_string());
+    }
+
+    // If the resume never gets going, hand the plan back to the Don rather than
+    // leaving the operation stuck in running
+    resume_plan(access_token, sit_down_id, operation_id, &member_id, &user_id).inspect_err(|_| {
+        let _ = supabase_call(
+            "db.update",
+            json!({
+                "table": "operations",
+                "body": { "status": "awaiting_approval" },
+                "filters": [
+                    { "column": "id", "op": "eq", "value": operation_id },
+                    { "column": "status", "op": "eq", "value": "running" }
+                ],
+                "access_token": access_token
+            }),
+        );
+        if !plan_message_id.is_empty() {
+            let _ = update_ai_message_metadata(&plan_message_id, &json!({ "plan_status": "awaiting_approval" }), access_token);
+        }
+    })
+}
+
+/// Resume the caporegime against its approved plan with fresh sit-down context.
+fn resume_plan(access_token: &str, sit_down_id: &str, operation_id: &str, member_id: &str, user_id: &str) -> Result<String, String> {
+    let participants = fetch_participants(access_token, sit_down_id)?;
+    let sit_down_obj = participants
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|p| p.get("sit_down"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    let messages = fetch_context_messages(access_token, sit_down_id, None, None)?;
+    let context = json!({
+        "sit_down_id": sit_down_id,
+        "sit_down": sit_down_obj,
+        "participants": participants,
+        "messages": messages,
+        "user_id": user_id,
+        "run_id": operation_id
+    });
+
+    respond_member(access_token, member_id, &context, None, Some(operation_id), None)
+}
+
+fn save_plan_as_job(access_token: &str, operation_id: &str, name: Option<&str>) -> Result<String, String> {
+    let mut input = json!({
+        "action": "save_plan_as_job",
+        "operation_id": operation_id,
+        "access_token": access_token
+    });
+    if let Some(n) = name {
+        input["name"] = json!(n);
+    }
+    call_caporegime(input)
+}
+
+/// Run a caporegime action that works on a stored operation (no agentic run).
+fn call_caporegime(input: Value) -> Result<String, String> {
+    let request = json!({
+        "tool": "execution",
+        "action": "run",
+        "args": {
+            "reference": CAPOREGIME_REF,
+            "input": input,
+            "type": "formula"
+        }
+    });
+
+    let response_str = invoke::call(&request.to_string());
+    let response: Value = serde_json::from_str(&response_str)
+        .map_err(|e| format!("Failed to parse caporegime response: {e}"))?;
+
+    if let Some(err) = response.get("error") {
+        return Err(format!("caporegime invoke error: {err}"));
+    }
+
+    let output = response.get("output").cloned().unwrap_or(Value::Null);
+    let raw = output.get("result").cloned().unwrap_or(Value::Null);
+    let result = match &raw {
+        Value::String(s) => serde_json::from_str::<Value>(s).unwrap_or(raw.clone()),
+        _ => raw,
+    };
+
+    if let Some(err) = result.get("error") {
+        let msg = err.get("message").and_then(|v| v.as_str()).unwrap_or("Formula error");
+        return Err(msg.to_string());
+    }
+
+    Ok(result.to_string())
+}
+
 // ---------------------------------------------------------------------------
 // 6. AI provider helpers (from sit-down-response)
 // ---------------------------------------------------------------------------
@@ -1472,6 +2512,8 @@ fn build_conversation_history(
     messages: &Value,
     sit_down: &Value,
     participants: &Value,
+    thread_id: Option<&str>,
+    token_budget: u64,
 ) -> Vec<Value> {
     let msgs = match messages.as_array() {
         Some(arr) => arr,
@@ -1517,14 +2559,24 @@ fn build_conversation_history(
         })
         .unwrap_or_default();
 
-    let mut sorted: Vec<&Value> = msgs.iter().collect();
+    // A thread reply sees only its thread; the main timeline leaves threads out
+    let mut sorted: Vec<&Value> = msgs
+        .iter()
+        .filter(|m| {
+            let msg_thread = m.get("thread_id").and_then(|v| v.as_str());
+            match thread_id {
+                Some(root) => m.get("id").and_then(|v| v.as_str()) == Some(root) || msg_thread == Some(root),
+                None => msg_thread.is_none(),
+            }
+        })
+        .collect();
     sorted.sort_by(|a, b| {
         let a_ts = a.get("created_at").and_then(|v| v.as_str()).unwrap_or("");
         let b_ts = b.get("created_at").and_then(|v| v.as_str()).unwrap_or("");
         a_ts.cmp(b_ts)
     });
 
-    let mut history: Vec<Value> = sorted
+    let history: Vec<Value> = sorted
         .iter()
         .map(|msg| {
             let sender_type = msg.get("sender_type").and_then(|v| v.as_str()).unwrap_or("");
@@ -1563,6 +2615,9 @@ fn build_conversation_history(
         })
         .collect();
 
+    // A thread keeps its root however far back it is
+    let mut history = fit_to_token_budget(history, token_budget, thread_id.is_some());
+
     if let Some(last) = history.last() {
         if last.get("role").and_then(|v| v.as_str()) == Some("assistant") {
             let content = last.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string();
@@ -1574,6 +2629,78 @@ fn build_conversation_history(
     history
 }
 
+/// Tokens available for a member's conversation history: the sit-down's
+/// override if set, otherwise what its catalog model's context allows.
+fn context_token_budget(member: &Value, sit_down: &Value) -> u64 {
+    if let Some(budget) = sit_down.get("context_token_budget").and_then(|v| v.as_u64()) {
+        return budget;
+    }
+    let long_context = member
+        .get("catalog_model")
+        .and_then(|m| m.get("capabilities"))
+        .and_then(|c| c.get("long_context"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if long_context {
+        LONG_CONTEXT_TOKEN_BUDGET
+    } else {
+        DEFAULT_CONTEXT_TOKEN_BUDGET
+    }
+}
+
+/// Rough token count: about four bytes per token, which overestimates
+/// non-Latin text rather than letting it overflow the budget.
+fn estimate_tokens(text: &str) -> u64 {
+    (text.len() as u64).div_ceil(4) + MESSAGE_TOKEN_OVERHEAD
+}
+
+/// Keep the newest messages of `history` (oldest first) that fit in `budget`
+/// tokens. The newest message is always kept whole. Older messages larger than
+/// a quarter of the budget are truncated, so one pasted report can't crowd out
+/// the rest. With `pin_first` the oldest message is kept as well.
+fn fit_to_token_budget(mut history: Vec<Value>, budget: u64, pin_first: bool) -> Vec<Value> {
+    let Some(newest) = history.pop() else {
+        return history;
+    };
+    let per_message_cap = budget / 4;
+    let truncate = |mut msg: Value| -> (Value, u64) {
+        let content = msg.get("content").and_then(|v| v.as_str()).unwrap_or("");
+        if estimate_tokens(content) > per_message_cap {
+            let mut end = (per_message_cap.saturating_sub(MESSAGE_TOKEN_OVERHEAD) * 4) as usize;
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
+            msg["content"] = json!(format!("{} [... truncated]", &content[..end]));
+        }
+        let tokens = estimate_tokens(msg.get("content").and_then(|v| v.as_str()).unwrap_or(""));
+        (msg, tokens)
+    };
+
+    let mut used = estimate_tokens(newest.get("content").and_then(|v| v.as_str()).unwrap_or(""));
+    let pinned = if pin_first && !history.is_empty() {
+        let (first, tokens) = truncate(history.remove(0));
+        used += tokens;
+        Some(first)
+    } else {
+        None
+    };
+
+    let mut kept: Vec<Value> = Vec::new();
+    while let Some(msg) = history.pop() {
+        let (msg, tokens) = truncate(msg);
+        if used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        kept.push(msg);
+    }
+
+    kept.extend(pinned);
+    kept.reverse();
+    kept.push(newest);
+    kept
+}
+
 fn lookup_member_name(participants: &[Value], member_id: &str) -> String {
     participants
         .iter()
@@ -1842,17 +2969,87 @@ fn insert_ai_message(
     Ok(message_id)
 }
 
+/// Store `content` as a new, canonical version of a member message (see
+/// migration 040). Owner- or admin-checked by the RPC.
+fn add_message_version(message_id: &str, content: &str, metadata: &Value, access_token: &str) -> Result<(), String> {
+    supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "add_message_version",
+            "body": {
+                "p_message_id": message_id,
+                "p_content": content,
+                "p_metadata": metadata
+            },
+            "access_token": access_token
+        }),
+    )?;
+    Ok(())
+}
+
+/// Merge `patch` into a member message's metadata via RPC (owner-checked, bypasses RLS).
+fn update_ai_message_metadata(message_id: &str, patch: &Value, access_token: &str) -> Result<(), String> {
+    supabase_call(
+        "db.rpc",
+        json!({
+            "function": "update_ai_message_metadata",
+            "body": {
+                "p_message_id": message_id,
+                "p_metadata": patch
+            },
+            "access_token": access_token
+        }),
+    )?;
+    Ok(())
+}
+
 // Fire-and-forget event emission for sit-down orchestration
-fn emit_sit_down_event(sit_down_id: &str, member_id: &str, member_name: &str, event: Value) {
+/// Persist an event to `member_events` and emit it on the live stream.
+/// Persistence is best-effort: a failed insert still emits, just without a sequence.
+fn emit_sit_down_event(sit_down_id: &str, member_id: &str, member_name: &str, run_id: &str, access_token: &str, event: Value) {
     let mut payload = event;
     if let Some(obj) = payload.as_object_mut() {
         obj.insert("member_id".into(), json!(member_id));
         obj.insert("member_name".into(), json!(member_name));
         obj.insert("sit_down_id".into(), json!(sit_down_id));
+        obj.insert("run_id".into(), json!(run_id));
+    }
+    if let Some(sequence) = persist_member_event(sit_down_id, member_id, run_id, &payload, access_token) {
+        payload["sequence"] = json!(sequence);
     }
     let _ = invoke::emit(&payload.to_string());
 }
 
+/// Written through `record_member_events`, which checks that the Don and the
+/// member both sit at this table.
+fn persist_member_event(sit_down_id: &str, member_id: &str, run_id: &str, payload: &Value, access_token: &str) -> Option<i64> {
+    if run_id.is_empty() {
+        return None;
+    }
+    let kind = payload.get("kind").and_then(|v| v.as_str()).unwrap_or("status");
+    let recorded = supabase_call_once(
+        "db.rpc",
+        json!({
+            "function": "record_member_events",
+            "body": {
+                "p_events": [{
+                    "sit_down_id": sit_down_id,
+                    "member_id": member_id,
+                    "run_id": run_id,
+                    "kind": kind,
+                    "payload": payload
+                }]
+            },
+            "access_token": access_token
+        }),
+    )
+    .ok()?;
+    recorded
+        .as_array()
+        .and_then(|arr| arr.first())
+        .and_then(|v| v.as_i64())
+}
+
 fn parse_mentions(text: &str, members: &[Value], dons: &[Value]) -> Result<Value, String> {
     let request = json!({
         "tool": "execution",
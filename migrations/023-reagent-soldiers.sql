@@ -0,0 +1,8 @@
+-- 023-reagent-soldiers.sql
+-- Reagent soldiers: deterministic WASM compute in place of an LLM call.
+-- The reagent reference lives in soldier_config.reagent_ref (e.g. "reagent:local.csv-parse").
+-- Reagent soldiers have no model, so catalog_model_id stays null for them.
+
+ALTER TABLE public.members DROP CONSTRAINT IF EXISTS members_soldier_type_check;
+ALTER TABLE public.members ADD CONSTRAINT members_soldier_type_check
+  CHECK (soldier_type IN ('default', 'external', 'reagent'));
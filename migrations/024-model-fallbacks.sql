@@ -0,0 +1,7 @@
+-- 024-model-fallbacks.sql
+-- Ordered fallback chain of catalog models for members and soldiers.
+-- When the primary model fails with a provider or rate-limit error, each entry
+-- is tried in order before giving up. Arrays can't carry a foreign key, so
+-- removed catalog entries are skipped at resolve time instead of cascading.
+
+ALTER TABLE public.members ADD COLUMN fallback_model_ids uuid[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,17 @@
+-- 025-crew-hierarchies.sql
+-- Crew hierarchies: a caporegime can report to another caporegime and serve in
+-- its crew. members.caporegime_id already links soldiers to their caporegime;
+-- it now does the same for sub-caporegimes. Nesting depth and cycles are
+-- enforced by members-api and again at runtime by the caporegime formula.
+--
+-- Delegated runs record their parent so nested operations can be traced.
+
+ALTER TABLE public.operations ADD COLUMN parent_operation_id uuid
+  REFERENCES public.operations(id) ON DELETE SET NULL;
+ALTER TABLE public.operations ADD COLUMN depth int NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_operations_parent ON public.operations (parent_operation_id)
+  WHERE parent_operation_id IS NOT NULL;
+
+CREATE INDEX idx_members_caporegime ON public.members (caporegime_id)
+  WHERE caporegime_id IS NOT NULL;
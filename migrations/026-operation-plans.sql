@@ -0,0 +1,47 @@
+-- 026-operation-plans.sql
+-- Plan-then-execute for caporegime Brain runs. A caporegime with plan_mode set
+-- first drafts a structured plan (steps, soldiers, expected outputs), stores it
+-- on the operation and posts it to the sit-down. In 'approval' mode the run
+-- pauses until the Don approves or rejects the plan.
+
+ALTER TABLE public.members ADD COLUMN plan_mode text NOT NULL DEFAULT 'off'
+  CHECK (plan_mode IN ('off','auto','approval'));
+
+ALTER TABLE public.operations ADD COLUMN plan jsonb;
+
+ALTER TABLE public.operations DROP CONSTRAINT operations_status_check;
+ALTER TABLE public.operations ADD CONSTRAINT operations_status_check
+  CHECK (status IN ('running','awaiting_approval','completed','failed','cancelled'));
+
+-- ============================================
+-- RPC: Merge into an AI message's metadata (bypasses RLS safely)
+-- Used to reflect plan progress on the plan message as items complete.
+-- ============================================
+create or replace function public.update_ai_message_metadata(
+  p_message_id uuid,
+  p_metadata jsonb
+) returns public.messages as $$
+declare
+  v_message public.messages;
+begin
+  select * into v_message from public.messages where id = p_message_id;
+  if v_message.id is null or v_message.sender_type <> 'member' then
+    raise exception 'Message not found';
+  end if;
+
+  -- Only the owner of the sending member may annotate its messages
+  if not exists (
+    select 1 from public.members
+    where id = v_message.sender_member_id and owner_id = auth.uid()
+  ) then
+    raise exception 'Not the owner of this member';
+  end if;
+
+  update public.messages
+  set metadata = coalesce(metadata, '{}'::jsonb) || p_metadata
+  where id = p_message_id
+  returning * into v_message;
+
+  return v_message;
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,223 @@
+-- 027-soldier-loans.sql
+-- Borrowing allied soldiers across the Commission. A Don offers one of their
+-- soldiers to an allied Don (an accepted commission contact), optionally scoped
+-- to one of the borrower's caporegimes and/or one shared commission sit-down,
+-- and always with an expiry. The borrower must accept before the soldier joins
+-- their caporegimes' crews. Either side can revoke at any time.
+--
+-- A borrowed soldier runs with its owner's model and soldier_config. Each
+-- delegation is counted on the loan so the lender can see what it was used for.
+
+create table public.soldier_loans (
+  id uuid primary key default gen_random_uuid(),
+  soldier_id uuid not null references public.members(id) on delete cascade,
+  lender_id uuid not null references public.profiles(id) on delete cascade,
+  borrower_id uuid not null references public.profiles(id) on delete cascade,
+  caporegime_id uuid references public.members(id) on delete cascade,
+  sit_down_id uuid references public.sit_downs(id) on delete cascade,
+  status text not null default 'offered'
+    check (status in ('offered', 'active', 'declined', 'revoked')),
+  expires_at timestamptz not null,
+  invocations int not null default 0,
+  last_used_at timestamptz,
+  created_at timestamptz not null default now(),
+  responded_at timestamptz,
+  check (lender_id != borrower_id)
+);
+
+-- One open offer or active loan per soldier and borrower
+create unique index idx_soldier_loans_open on public.soldier_loans (soldier_id, borrower_id)
+  where status in ('offered', 'active');
+create index idx_soldier_loans_borrower on public.soldier_loans (borrower_id, status);
+create index idx_soldier_loans_lender on public.soldier_loans (lender_id, status);
+
+alter table public.soldier_loans enable row level security;
+
+-- Both sides can see the loan; all writes go through the RPCs below
+create policy "Lenders and borrowers can view loans"
+  on public.soldier_loans for select
+  using (lender_id = (select auth.uid()) or borrower_id = (select auth.uid()));
+
+-- Borrowers can read soldiers currently lent to them, even outside a shared sit-down
+create policy "Borrowers can view lent soldiers"
+  on public.members for select
+  using (
+    exists (
+      select 1 from public.soldier_loans sl
+      where sl.soldier_id = members.id
+        and sl.borrower_id = (select auth.uid())
+        and sl.status = 'active'
+        and sl.expires_at > now()
+    )
+  );
+
+-- ============================================
+-- RPC: Offer a soldier to an allied Don
+-- ============================================
+create or replace function public.lend_soldier(
+  p_soldier_id uuid,
+  p_borrower_id uuid,
+  p_expires_at timestamptz,
+  p_caporegime_id uuid default null,
+  p_sit_down_id uuid default null
+) returns public.soldier_loans as $$
+declare
+  v_loan public.soldier_loans;
+begin
+  if not exists (
+    select 1 from public.members
+    where id = p_soldier_id and owner_id = auth.uid() and member_type = 'soldier'
+  ) then
+    raise exception 'SOLDIER_NOT_FOUND';
+  end if;
+
+  if not exists (
+    select 1 from public.commission_contacts
+    where user_id = auth.uid() and contact_user_id = p_borrower_id and status = 'accepted'
+  ) then
+    raise exception 'NOT_A_COMMISSION_CONTACT';
+  end if;
+
+  if p_expires_at <= now() or p_expires_at > now() + interval '90 days' then
+    raise exception 'INVALID_EXPIRY';
+  end if;
+
+  if p_caporegime_id is not null and not exists (
+    select 1 from public.members
+    where id = p_caporegime_id and owner_id = p_borrower_id and member_type = 'caporegime'
+  ) then
+    raise exception 'CAPOREGIME_NOT_FOUND';
+  end if;
+
+  -- Sit-down scope must be a commission both Dons sit in
+  if p_sit_down_id is not null and not exists (
+    select 1 from public.sit_downs sd
+    where sd.id = p_sit_down_id
+      and sd.is_commission = true
+      and exists (select 1 from public.sit_down_participants where sit_down_id = sd.id and user_id = auth.uid())
+      and exists (select 1 from public.sit_down_participants where sit_down_id = sd.id and user_id = p_borrower_id)
+  ) then
+    raise exception 'SIT_DOWN_NOT_SHARED';
+  end if;
+
+  insert into public.soldier_loans (soldier_id, lender_id, borrower_id, caporegime_id, sit_down_id, expires_at)
+  values (p_soldier_id, auth.uid(), p_borrower_id, p_caporegime_id, p_sit_down_id, p_expires_at)
+  returning * into v_loan;
+
+  return v_loan;
+exception
+  when unique_violation then
+    raise exception 'LOAN_ALREADY_OPEN';
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Borrower accepts or declines an offer
+-- ============================================
+create or replace function public.respond_soldier_loan(p_loan_id uuid, p_accept boolean)
+returns public.soldier_loans as $$
+declare
+  v_loan public.soldier_loans;
+begin
+  update public.soldier_loans
+  set status = case when p_accept then 'active' else 'declined' end,
+      responded_at = now()
+  where id = p_loan_id
+    and borrower_id = auth.uid()
+    and status = 'offered'
+    and expires_at > now()
+  returning * into v_loan;
+
+  if v_loan.id is null then
+    raise exception 'LOAN_NOT_FOUND';
+  end if;
+
+  return v_loan;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Either side ends a loan
+-- ============================================
+create or replace function public.revoke_soldier_loan(p_loan_id uuid)
+returns public.soldier_loans as $$
+declare
+  v_loan public.soldier_loans;
+begin
+  update public.soldier_loans
+  set status = 'revoked', responded_at = coalesce(responded_at, now())
+  where id = p_loan_id
+    and (lender_id = auth.uid() or borrower_id = auth.uid())
+    and status in ('offered', 'active')
+  returning * into v_loan;
+
+  if v_loan.id is null then
+    raise exception 'LOAN_NOT_FOUND';
+  end if;
+
+  return v_loan;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Soldiers lent to the caller that a caporegime may use right now
+-- Returns the soldier rows the caporegime formula needs, tagged with the loan.
+-- ============================================
+create or replace function public.list_borrowed_soldiers(
+  p_caporegime_id uuid,
+  p_sit_down_id uuid default null
+) returns jsonb as $$
+begin
+  if not exists (
+    select 1 from public.members
+    where id = p_caporegime_id and owner_id = auth.uid() and member_type = 'caporegime'
+  ) then
+    raise exception 'CAPOREGIME_NOT_FOUND';
+  end if;
+
+  return coalesce((
+    select jsonb_agg(
+      jsonb_build_object(
+        'id', m.id,
+        'name', m.name,
+        'system_prompt', m.system_prompt,
+        'soldier_type', m.soldier_type,
+        'soldier_config', m.soldier_config,
+        'fallback_model_ids', m.fallback_model_ids,
+        'catalog_model', (
+          select jsonb_build_object('provider', mc.provider, 'model', mc.model, 'alias', mc.alias)
+          from public.model_catalog mc where mc.id = m.catalog_model_id
+        ),
+        'loan_id', sl.id,
+        'lender_id', sl.lender_id,
+        'lender_name', p.display_name,
+        'loan_expires_at', sl.expires_at
+      )
+      order by m.name
+    )
+    from public.soldier_loans sl
+    join public.members m on m.id = sl.soldier_id
+    join public.profiles p on p.id = sl.lender_id
+    where sl.borrower_id = auth.uid()
+      and sl.status = 'active'
+      and sl.expires_at > now()
+      and (sl.caporegime_id is null or sl.caporegime_id = p_caporegime_id)
+      and (sl.sit_down_id is null or sl.sit_down_id = p_sit_down_id)
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Count a delegation against a loan (called by the borrower's caporegime)
+-- ============================================
+create or replace function public.record_soldier_loan_usage(p_loan_id uuid)
+returns void as $$
+begin
+  update public.soldier_loans
+  set invocations = invocations + 1, last_used_at = now()
+  where id = p_loan_id
+    and borrower_id = auth.uid()
+    and status = 'active'
+    and expires_at > now();
+end;
+$$ language plpgsql security definer set search_path = '';
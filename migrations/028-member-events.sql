@@ -0,0 +1,43 @@
+-- 028-member-events.sql
+-- Persisted member activity events. Every status, tool_use, tool_result,
+-- text_delta, usage and message_inserted event a member emits is also written
+-- here, so a client that connects late or reconnects can rebuild live progress
+-- with sit-down's list_events action instead of losing it.
+--
+-- `run_id` groups the events of one member response: the Don's message that
+-- triggered it, or the operation id for job runs and approved plans.
+-- `sequence` is a global, strictly increasing cursor for `since_sequence`.
+
+create table public.member_events (
+  sequence bigint generated always as identity primary key,
+  sit_down_id uuid not null references public.sit_downs(id) on delete cascade,
+  member_id uuid not null references public.members(id) on delete cascade,
+  run_id text not null,
+  kind text not null,
+  payload jsonb not null default '{}',
+  created_at timestamptz not null default now()
+);
+
+create index idx_member_events_sit_down on public.member_events (sit_down_id, sequence);
+create index idx_member_events_created on public.member_events (created_at);
+
+alter table public.member_events enable row level security;
+
+create policy "Participants can view member events"
+  on public.member_events for select
+  using (public.is_sit_down_participant(sit_down_id));
+
+-- Formulas write events with the acting Don's token
+create policy "Participants can insert member events"
+  on public.member_events for insert
+  with check (public.is_sit_down_participant(sit_down_id));
+
+-- ============================================
+-- Retention: events are only needed to rebuild recent progress.
+-- Schedule with pg_cron, e.g. select cron.schedule('prune-member-events', '0 * * * *',
+--   $$select public.prune_member_events()$$);
+-- ============================================
+create or replace function public.prune_member_events(p_older_than interval default interval '1 day')
+returns void as $$
+  delete from public.member_events where created_at < now() - p_older_than;
+$$ language sql security definer set search_path = '';
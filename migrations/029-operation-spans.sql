@@ -0,0 +1,10 @@
+-- 029-operation-spans.sql
+-- Timed spans for caporegime operations, so a run can be exported as an
+-- OpenTelemetry trace (caporegime's export_trace action, via sit-down).
+--
+-- Each element is {id, parent_id, kind, name, start_ms, end_ms, attributes, error?}
+-- with kind one of turn, catalyst, tool_call, soldier, step. `tool_calls` stays
+-- as the human-readable log; operations recorded before this migration are
+-- exported from it with approximate timings.
+
+ALTER TABLE public.operations ADD COLUMN spans jsonb NOT NULL DEFAULT '[]';
@@ -0,0 +1,90 @@
+-- 030-usage-ledger.sql
+-- One row per AI member response: who asked (the Don), which member answered,
+-- on which model, and how many tokens it took. Written by sit-down after every
+-- consul, bookkeeper and caporegime response. A caporegime row covers its whole
+-- operation, including soldiers, sub-caporegimes and members it consulted.
+
+create table public.usage_ledger (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid not null references public.profiles(id) on delete cascade,
+  member_id uuid references public.members(id) on delete set null,
+  sit_down_id uuid references public.sit_downs(id) on delete set null,
+  operation_id uuid references public.operations(id) on delete set null,
+  message_id uuid references public.messages(id) on delete set null,
+  provider text not null,
+  model text not null,
+  input_tokens bigint not null default 0,
+  output_tokens bigint not null default 0,
+  created_at timestamptz not null default now()
+);
+
+create index idx_usage_ledger_user on public.usage_ledger (user_id, created_at);
+create index idx_usage_ledger_created on public.usage_ledger (created_at);
+
+alter table public.usage_ledger enable row level security;
+
+create policy "Users can view own usage"
+  on public.usage_ledger for select
+  using (user_id = (select auth.uid()));
+
+create policy "Godfathers can view all usage"
+  on public.usage_ledger for select
+  using (exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather'));
+
+create policy "Users can record own usage"
+  on public.usage_ledger for insert
+  with check (user_id = (select auth.uid()));
+
+-- ============================================
+-- RPC: Usage totals over the last p_days, grouped by don, member, model or day
+-- Godfathers see every Don; everyone else sees only their own usage.
+-- ============================================
+create or replace function public.usage_report(
+  p_group_by text,
+  p_days int default 30
+) returns jsonb as $$
+declare
+  v_is_godfather boolean;
+begin
+  if p_group_by not in ('don', 'member', 'model', 'day') then
+    raise exception 'INVALID_GROUP_BY';
+  end if;
+  if p_days < 1 or p_days > 366 then
+    raise exception 'INVALID_PERIOD';
+  end if;
+
+  select exists (select 1 from public.profiles where id = auth.uid() and tier = 'godfather')
+  into v_is_godfather;
+
+  return coalesce((
+    select jsonb_agg(
+      to_jsonb(r)
+      order by case when p_group_by = 'day' then r.key end, r.input_tokens + r.output_tokens desc
+    )
+    from (
+      select
+        case p_group_by
+          when 'don' then ul.user_id::text
+          when 'member' then coalesce(ul.member_id::text, 'removed')
+          when 'model' then ul.provider || '/' || ul.model
+          else to_char(date_trunc('day', ul.created_at), 'YYYY-MM-DD')
+        end as key,
+        case p_group_by
+          when 'don' then max(p.display_name)
+          when 'member' then coalesce(max(m.name), 'Removed member')
+          when 'model' then max(ul.model)
+          else min(to_char(date_trunc('day', ul.created_at), 'YYYY-MM-DD'))
+        end as label,
+        count(*) as responses,
+        sum(ul.input_tokens) as input_tokens,
+        sum(ul.output_tokens) as output_tokens
+      from public.usage_ledger ul
+      left join public.profiles p on p.id = ul.user_id
+      left join public.members m on m.id = ul.member_id
+      where ul.created_at >= now() - make_interval(days => p_days)
+        and (v_is_godfather or ul.user_id = auth.uid())
+      group by 1
+    ) r
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,135 @@
+-- 031-model-pricing.sql
+-- Per-model pricing in the catalog, and dollar-cost estimates wherever token
+-- usage is recorded. Prices are USD per million tokens, set by the Godfather
+-- through admin-api's catalog_add / catalog_update. A model without prices
+-- costs null (unknown), not zero.
+--
+-- Costs are estimated in the database, so every writer gets them for free:
+--   usage_ledger.cost_usd        — every consul, bookkeeper and caporegime response
+--   operations.usage->'cost_usd' — caporegime operations, from usage.answered_by
+-- An estimate prices all of a response's tokens at the model that answered it.
+
+alter table public.model_catalog
+  add column input_price_per_million numeric(12,4) check (input_price_per_million >= 0),
+  add column output_price_per_million numeric(12,4) check (output_price_per_million >= 0);
+
+alter table public.usage_ledger add column cost_usd numeric(14,6);
+
+-- ============================================
+-- Cost of a number of tokens on a provider/model, or null when unpriced
+-- ============================================
+create or replace function public.estimate_cost(
+  p_provider text,
+  p_model text,
+  p_input_tokens bigint,
+  p_output_tokens bigint
+) returns numeric as $$
+  select round(
+    (coalesce(p_input_tokens, 0) * mc.input_price_per_million
+      + coalesce(p_output_tokens, 0) * mc.output_price_per_million) / 1000000.0,
+    6
+  )
+  from public.model_catalog mc
+  where mc.provider = p_provider
+    and mc.model = p_model
+    and mc.input_price_per_million is not null
+    and mc.output_price_per_million is not null
+  order by mc.is_active desc, mc.created_at
+  limit 1;
+$$ language sql stable security definer set search_path = '';
+
+create or replace function public.usage_ledger_set_cost()
+returns trigger as $$
+begin
+  if new.cost_usd is null then
+    new.cost_usd := public.estimate_cost(new.provider, new.model, new.input_tokens, new.output_tokens);
+  end if;
+  return new;
+end;
+$$ language plpgsql set search_path = '';
+
+create trigger usage_ledger_set_cost
+  before insert on public.usage_ledger
+  for each row execute function public.usage_ledger_set_cost();
+
+-- Operations carry usage as jsonb: {input_tokens, output_tokens, answered_by: {catalyst_ref, model}}
+create or replace function public.operations_set_cost()
+returns trigger as $$
+declare
+  v_cost numeric;
+begin
+  if new.usage is null or new.usage->'answered_by' is null or new.usage ? 'cost_usd' then
+    return new;
+  end if;
+
+  v_cost := public.estimate_cost(
+    regexp_replace(new.usage->'answered_by'->>'catalyst_ref', '^.*\.', ''),
+    new.usage->'answered_by'->>'model',
+    (new.usage->>'input_tokens')::bigint,
+    (new.usage->>'output_tokens')::bigint
+  );
+  if v_cost is not null then
+    new.usage := new.usage || jsonb_build_object('cost_usd', v_cost);
+  end if;
+  return new;
+end;
+$$ language plpgsql set search_path = '';
+
+create trigger operations_set_cost
+  before insert or update of usage on public.operations
+  for each row execute function public.operations_set_cost();
+
+-- ============================================
+-- usage_report gains cost totals (null when nothing in the group was priced)
+-- ============================================
+create or replace function public.usage_report(
+  p_group_by text,
+  p_days int default 30
+) returns jsonb as $$
+declare
+  v_is_godfather boolean;
+begin
+  if p_group_by not in ('don', 'member', 'model', 'day') then
+    raise exception 'INVALID_GROUP_BY';
+  end if;
+  if p_days < 1 or p_days > 366 then
+    raise exception 'INVALID_PERIOD';
+  end if;
+
+  select exists (select 1 from public.profiles where id = auth.uid() and tier = 'godfather')
+  into v_is_godfather;
+
+  return coalesce((
+    select jsonb_agg(
+      to_jsonb(r)
+      order by case when p_group_by = 'day' then r.key end, r.input_tokens + r.output_tokens desc
+    )
+    from (
+      select
+        case p_group_by
+          when 'don' then ul.user_id::text
+          when 'member' then coalesce(ul.member_id::text, 'removed')
+          when 'model' then ul.provider || '/' || ul.model
+          else to_char(date_trunc('day', ul.created_at), 'YYYY-MM-DD')
+        end as key,
+        case p_group_by
+          when 'don' then max(p.display_name)
+          when 'member' then coalesce(max(m.name), 'Removed member')
+          when 'model' then max(ul.model)
+          else min(to_char(date_trunc('day', ul.created_at), 'YYYY-MM-DD'))
+        end as label,
+        count(*) as responses,
+        sum(ul.input_tokens) as input_tokens,
+        sum(ul.output_tokens) as output_tokens,
+        sum(ul.cost_usd) as cost_usd,
+        count(*) filter (where ul.cost_usd is null) as unpriced_responses
+      from public.usage_ledger ul
+      left join public.profiles p on p.id = ul.user_id
+      left join public.members m on m.id = ul.member_id
+      where ul.created_at >= now() - make_interval(days => p_days)
+        and (v_is_godfather or ul.user_id = auth.uid())
+      group by 1
+    ) r
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
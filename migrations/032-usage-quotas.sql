@@ -0,0 +1,117 @@
+-- 032-usage-quotas.sql
+-- Daily or monthly token and cost quotas, set per tier with per-user
+-- overrides. A user's override for a period replaces their tier's quota for
+-- that period; no row means no limit. Usage is measured from usage_ledger
+-- (tokens, and cost_usd from 031), so a quota covers every consul,
+-- bookkeeper and caporegime response the user asked for.
+--
+-- Enforced before members are invoked: sit-down send_message and caporegime
+-- execute_job call check_quota() and refuse with a "quota exceeded" event.
+
+create table public.usage_quotas (
+  id uuid primary key default gen_random_uuid(),
+  tier text check (tier in ('godfather', 'boss', 'associate')),
+  user_id uuid references public.profiles(id) on delete cascade,
+  period text not null check (period in ('day', 'month')),
+  max_tokens bigint check (max_tokens >= 0),
+  max_cost_usd numeric(14,4) check (max_cost_usd >= 0),
+  updated_by uuid references auth.users(id),
+  updated_at timestamptz not null default now(),
+  check ((tier is null) <> (user_id is null)),
+  check (max_tokens is not null or max_cost_usd is not null),
+  unique (tier, period),
+  unique (user_id, period)
+);
+
+alter table public.usage_quotas enable row level security;
+
+-- Everyone can see tier quotas and their own overrides; the Godfather sees all
+create policy "Users can view tier quotas and their own overrides"
+  on public.usage_quotas for select
+  using (
+    tier is not null
+    or user_id = (select auth.uid())
+    or exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather')
+  );
+
+create policy "Godfather can manage quotas"
+  on public.usage_quotas for all
+  using (exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather'))
+  with check (exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather'));
+
+-- ============================================
+-- Quota status for the calling user
+-- ============================================
+-- Returns {allowed, exceeded, limits}. Each limit is
+-- {period, source, max_tokens, max_cost_usd, used_tokens, used_cost_usd, resets_at};
+-- `exceeded` is the first limit that is used up, or null.
+create or replace function public.check_quota()
+returns jsonb as $$
+declare
+  v_user_id uuid := auth.uid();
+  v_limits jsonb;
+  v_exceeded jsonb;
+begin
+  if v_user_id is null then
+    raise exception 'NOT_AUTHENTICATED';
+  end if;
+
+  with effective as (
+    select distinct on (q.period)
+      q.period,
+      case when q.user_id is not null then 'user' else 'tier' end as source,
+      q.max_tokens,
+      q.max_cost_usd
+    from public.usage_quotas q
+    join public.profiles p on p.id = v_user_id
+    where q.user_id = v_user_id or q.tier = p.tier
+    order by q.period, (q.user_id is not null) desc
+  ),
+  measured as (
+    select
+      e.*,
+      date_trunc(e.period, now()) as period_start,
+      date_trunc(e.period, now()) + ('1 ' || e.period)::interval as resets_at,
+      coalesce(u.tokens, 0) as used_tokens,
+      coalesce(u.cost, 0) as used_cost_usd
+    from effective e
+    left join lateral (
+      select
+        sum(ul.input_tokens + ul.output_tokens) as tokens,
+        sum(ul.cost_usd) as cost
+      from public.usage_ledger ul
+      where ul.user_id = v_user_id
+        and ul.created_at >= date_trunc(e.period, now())
+    ) u on true
+  )
+  select
+    coalesce(jsonb_agg(jsonb_build_object(
+      'period', m.period,
+      'source', m.source,
+      'max_tokens', m.max_tokens,
+      'max_cost_usd', m.max_cost_usd,
+      'used_tokens', m.used_tokens,
+      'used_cost_usd', m.used_cost_usd,
+      'resets_at', m.resets_at
+    ) order by m.period), '[]'::jsonb),
+    (array_agg(jsonb_build_object(
+      'period', m.period,
+      'source', m.source,
+      'metric', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then 'tokens' else 'cost' end,
+      'limit', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then m.max_tokens else m.max_cost_usd end,
+      'used', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then m.used_tokens else m.used_cost_usd end,
+      'resets_at', m.resets_at
+    ) order by m.period) filter (
+      where (m.max_tokens is not null and m.used_tokens >= m.max_tokens)
+         or (m.max_cost_usd is not null and m.used_cost_usd >= m.max_cost_usd)
+    ))[1]
+  into v_limits, v_exceeded
+  from measured m;
+
+  return jsonb_build_object(
+    'allowed', v_exceeded is null,
+    'exceeded', v_exceeded,
+    'limits', v_limits
+  );
+end;
+$$ language plpgsql stable security definer set search_path = '';
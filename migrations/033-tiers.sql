@@ -0,0 +1,95 @@
+-- 033-tiers.sql
+-- Configurable tiers. Tiers used to be three hardcoded strings (godfather >
+-- boss > associate). Now each is a row with a display name, a rank (higher
+-- outranks lower) and attached limits. admin-api and members-api read every
+-- tier check from this table. Quotas attach through usage_quotas.tier (032).
+--
+-- Two keys are reserved:
+--   godfather — the admin tier; RLS policies and admin-api check for it by key
+--   associate — the default tier for new signups
+-- Both can be renamed (display_name) and re-limited, but never deleted.
+
+create table public.tiers (
+  key text primary key check (key ~ '^[a-z][a-z0-9_]{0,31}$'),
+  display_name text not null check (length(trim(display_name)) > 0),
+  rank int not null unique,
+  max_members int check (max_members >= 0),             -- null = unlimited
+  max_caporegimes int check (max_caporegimes >= 0),     -- null = unlimited
+  allowed_providers text[],                             -- null = every provider
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+insert into public.tiers (key, display_name, rank) values
+  ('godfather', 'Godfather', 300),
+  ('boss', 'Boss', 200),
+  ('associate', 'Associate', 100);
+
+alter table public.tiers enable row level security;
+
+create policy "Anyone signed in can view tiers"
+  on public.tiers for select
+  using ((select auth.uid()) is not null);
+
+create policy "Godfather can manage tiers"
+  on public.tiers for all
+  using (exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather'))
+  with check (exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather'));
+
+create or replace function public.protect_reserved_tiers()
+returns trigger as $$
+begin
+  if old.key in ('godfather', 'associate') then
+    raise exception 'RESERVED_TIER';
+  end if;
+  return old;
+end;
+$$ language plpgsql set search_path = '';
+
+create trigger protect_reserved_tiers
+  before delete on public.tiers
+  for each row execute function public.protect_reserved_tiers();
+
+-- ============================================
+-- Point every tier column at the table instead of a fixed list
+-- ============================================
+-- Renaming a key is not supported (keys are referenced by RLS), and a tier
+-- still assigned to users, catalog entries or quotas cannot be deleted.
+alter table public.profiles drop constraint profiles_tier_check;
+alter table public.profiles
+  add constraint profiles_tier_fkey foreign key (tier) references public.tiers(key);
+
+alter table public.model_catalog drop constraint model_catalog_min_tier_check;
+alter table public.model_catalog
+  add constraint model_catalog_min_tier_fkey foreign key (min_tier) references public.tiers(key);
+
+alter table public.usage_quotas drop constraint usage_quotas_tier_check;
+alter table public.usage_quotas
+  add constraint usage_quotas_tier_fkey foreign key (tier) references public.tiers(key) on delete cascade;
+
+-- ============================================
+-- Catalog visibility by rank and allowed providers
+-- ============================================
+create or replace function public.tier_can_use_model(p_tier text, p_min_tier text, p_provider text)
+returns boolean as $$
+  select exists (
+    select 1
+    from public.tiers t
+    join public.tiers m on m.key = p_min_tier
+    where t.key = p_tier
+      and t.rank >= m.rank
+      and (t.allowed_providers is null or p_provider = any(t.allowed_providers))
+  );
+$$ language sql stable security definer set search_path = '';
+
+drop policy "Users can view catalog for their tier" on public.model_catalog;
+
+create policy "Users can view catalog for their tier"
+  on public.model_catalog for select
+  using (
+    is_active = true and exists (
+      select 1 from public.profiles p
+      where p.id = (select auth.uid())
+        and public.tier_can_use_model(p.tier, min_tier, provider)
+    )
+  );
@@ -0,0 +1,47 @@
+-- 034-admin-audit-log.sql
+-- Append-only record of every admin-api mutation: tier changes, catalog
+-- edits, tier definitions and quotas. admin-api writes one row after each
+-- change, with the row's state before and after (null for creates and
+-- deletes respectively). Godfathers can read the log and append to it as
+-- themselves. Nobody can edit or delete a row.
+
+create table public.admin_audit_log (
+  id bigint generated always as identity primary key,
+  -- No foreign key: the log outlives the accounts it mentions
+  actor_id uuid not null,
+  action text not null,
+  target_type text not null check (target_type in ('profile', 'catalog', 'tier', 'quota')),
+  target_id text not null,
+  before jsonb,
+  after jsonb,
+  created_at timestamptz not null default now()
+);
+
+create index idx_admin_audit_log_created on public.admin_audit_log (created_at desc);
+create index idx_admin_audit_log_target on public.admin_audit_log (target_type, target_id, created_at desc);
+create index idx_admin_audit_log_actor on public.admin_audit_log (actor_id, created_at desc);
+
+alter table public.admin_audit_log enable row level security;
+
+create policy "Godfathers can view the audit log"
+  on public.admin_audit_log for select
+  using (exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather'));
+
+create policy "Godfathers can append to the audit log as themselves"
+  on public.admin_audit_log for insert
+  with check (
+    actor_id = (select auth.uid())
+    and exists (select 1 from public.profiles where id = (select auth.uid()) and tier = 'godfather')
+  );
+
+-- No update or delete policies, and a trigger so even privileged roles cannot rewrite history
+create or replace function public.admin_audit_log_append_only()
+returns trigger as $$
+begin
+  raise exception 'admin_audit_log is append-only';
+end;
+$$ language plpgsql set search_path = '';
+
+create trigger admin_audit_log_append_only
+  before update or delete on public.admin_audit_log
+  for each row execute function public.admin_audit_log_append_only();
@@ -0,0 +1,201 @@
+-- 035-catalog-migration.sql
+-- Move every member and soldier off a catalog entry in one transaction, so a
+-- retired model can be deleted without leaving members that fail with
+-- "{name}'s model has been removed". Members belong to their Dons and are
+-- hidden from the Godfather by RLS, hence the security definer RPC.
+--
+-- Affected Dons get a row in `notifications`, which is also pushed to their
+-- devices through the same Expo endpoint as new-message notifications.
+
+-- ============================================
+-- Notifications
+-- ============================================
+create table public.notifications (
+  id uuid primary key default gen_random_uuid(),
+  user_id uuid not null references auth.users(id) on delete cascade,
+  kind text not null,
+  title text not null,
+  body text not null,
+  data jsonb not null default '{}',
+  read_at timestamptz,
+  created_at timestamptz not null default now()
+);
+
+create index idx_notifications_user on public.notifications (user_id, created_at desc);
+
+alter table public.notifications enable row level security;
+
+create policy "Users can view own notifications"
+  on public.notifications for select
+  using (user_id = (select auth.uid()));
+
+create policy "Users can mark own notifications read"
+  on public.notifications for update
+  using (user_id = (select auth.uid()))
+  with check (user_id = (select auth.uid()));
+
+create or replace function public.push_notification()
+returns trigger as $$
+declare
+  tokens text[];
+begin
+  select array_agg(pt.token) into tokens
+  from public.push_tokens pt
+  where pt.user_id = new.user_id;
+
+  if tokens is null then
+    return new;
+  end if;
+
+  perform net.http_post(
+    url     := 'https://exp.host/--/api/v2/push/send',
+    headers := jsonb_build_object(
+      'Accept',       'application/json',
+      'Content-Type', 'application/json'
+    ),
+    body    := (
+      select jsonb_agg(jsonb_build_object(
+        'to', t,
+        'sound', 'default',
+        'title', new.title,
+        'body', new.body,
+        'data', new.data || jsonb_build_object('notificationId', new.id)
+      ))
+      from unnest(tokens) as t
+    )
+  );
+
+  return new;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+create trigger notifications_push
+  after insert on public.notifications
+  for each row execute function public.push_notification();
+
+-- ============================================
+-- Catalog migration
+-- ============================================
+-- Members whose primary model is p_from switch to p_to; p_from is replaced by
+-- p_to inside fallback chains (duplicates and a fallback equal to the primary
+-- are dropped). With p_check_tier, members whose Don's tier cannot use p_to
+-- are left untouched and reported as skipped. With p_dry_run nothing is
+-- written and the report shows what would happen.
+create or replace function public.migrate_catalog_members(
+  p_from uuid,
+  p_to uuid,
+  p_dry_run boolean default true,
+  p_check_tier boolean default true
+)
+returns jsonb as $$
+declare
+  v_from public.model_catalog%rowtype;
+  v_to public.model_catalog%rowtype;
+  v_report jsonb;
+  v_migrated int := 0;
+  v_skipped int := 0;
+  v_notified int := 0;
+begin
+  if not exists (
+    select 1 from public.profiles
+    where id = (select auth.uid()) and tier = 'godfather'
+  ) then
+    raise exception 'Access denied: godfather tier required';
+  end if;
+
+  if p_from = p_to then
+    raise exception 'Source and target catalog entries are the same';
+  end if;
+
+  select * into v_from from public.model_catalog where id = p_from;
+  if not found then
+    raise exception 'Source catalog entry not found';
+  end if;
+
+  select * into v_to from public.model_catalog where id = p_to;
+  if not found then
+    raise exception 'Target catalog entry not found';
+  end if;
+  if not v_to.is_active then
+    raise exception 'Target catalog entry is inactive';
+  end if;
+
+  create temp table _catalog_migration on commit drop as
+  select
+    m.id,
+    m.name,
+    m.member_type,
+    m.owner_id,
+    p.display_name as owner_name,
+    p.tier as owner_tier,
+    m.catalog_model_id = p_from as uses_primary,
+    p_from = any(m.fallback_model_ids) as uses_fallback,
+    p_check_tier and not public.tier_can_use_model(p.tier, v_to.min_tier, v_to.provider) as skipped
+  from public.members m
+  join public.profiles p on p.id = m.owner_id
+  where m.catalog_model_id = p_from or p_from = any(m.fallback_model_ids);
+
+  select
+    coalesce(jsonb_agg(jsonb_build_object(
+      'id', id,
+      'name', name,
+      'member_type', member_type,
+      'owner_id', owner_id,
+      'owner_name', owner_name,
+      'owner_tier', owner_tier,
+      'uses_primary', uses_primary,
+      'uses_fallback', uses_fallback,
+      'skipped', skipped
+    ) order by owner_name, name), '[]'::jsonb),
+    count(*) filter (where not skipped),
+    count(*) filter (where skipped)
+  into v_report, v_migrated, v_skipped
+  from _catalog_migration;
+
+  if not p_dry_run then
+    update public.members m
+    set
+      catalog_model_id = case when m.catalog_model_id = p_from then p_to else m.catalog_model_id end,
+      fallback_model_ids = coalesce((
+        select array_agg(f.model_id order by f.ord)
+        from (
+          select u.model_id, min(u.ord) as ord
+          from unnest(array_replace(m.fallback_model_ids, p_from, p_to)) with ordinality as u(model_id, ord)
+          group by u.model_id
+        ) f
+        where f.model_id is distinct from
+          (case when m.catalog_model_id = p_from then p_to else m.catalog_model_id end)
+      ), '{}')
+    from _catalog_migration c
+    where c.id = m.id and not c.skipped;
+
+    insert into public.notifications (user_id, kind, title, body, data)
+    select
+      c.owner_id,
+      'model_migrated',
+      'Model switched',
+      v_from.alias || ' is being retired and was replaced by ' || v_to.alias
+        || ' for ' || string_agg(c.name, ', ' order by c.name) || '.',
+      jsonb_build_object(
+        'from_catalog_id', p_from,
+        'to_catalog_id', p_to,
+        'member_ids', jsonb_agg(c.id)
+      )
+    from _catalog_migration c
+    where not c.skipped
+    group by c.owner_id;
+
+    get diagnostics v_notified = row_count;
+  end if;
+
+  return jsonb_build_object(
+    'dry_run', p_dry_run,
+    'from', jsonb_build_object('id', v_from.id, 'alias', v_from.alias, 'provider', v_from.provider, 'model', v_from.model),
+    'to', jsonb_build_object('id', v_to.id, 'alias', v_to.alias, 'provider', v_to.provider, 'model', v_to.model),
+    'members', v_report,
+    'migrated_count', v_migrated,
+    'skipped_count', v_skipped,
+    'notified_dons', v_notified
+  );
+end;
+$$ language plpgsql security definer set search_path = '';
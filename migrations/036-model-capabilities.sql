@@ -0,0 +1,11 @@
+-- 036-model-capabilities.sql
+-- What a catalog model can do: `tools` (function calling), `vision`,
+-- `web_search` (the provider's native search tool) and `long_context`
+-- (200k+ input tokens). Each key is true or false; a missing key is unknown.
+-- members-api refuses a model that explicitly lacks what the member's role
+-- needs, and sit-down skips such models in fallback chains. Unknown counts as
+-- supported, so existing entries keep working until the Godfather fills them in.
+
+alter table public.model_catalog
+  add column capabilities jsonb not null default '{}'
+    check (jsonb_typeof(capabilities) = 'object');
@@ -0,0 +1,161 @@
+-- 037-weighted-aliases.sql
+-- One catalog alias routed across several models. An entry with rows in
+-- `model_catalog_targets` no longer answers with its own provider/model:
+-- each response picks one of its target catalog entries by weight. Targets are
+-- catalog entries themselves, so they keep their own prices (cost estimates
+-- stay per model) and may be on another provider. With `sticky_routing` a
+-- member always lands on the same target (hashed on member id) until the
+-- targets change; otherwise every response draws again.
+--
+-- An entry without targets, or whose targets are all inactive, answers with
+-- its own provider/model as before. Routing is one level deep: a target's own
+-- targets are ignored.
+
+alter table public.model_catalog
+  add column sticky_routing boolean not null default false;
+
+create table public.model_catalog_targets (
+  catalog_id uuid not null references public.model_catalog(id) on delete cascade,
+  target_catalog_id uuid not null references public.model_catalog(id) on delete cascade,
+  weight int not null default 1 check (weight > 0),
+  created_at timestamptz not null default now(),
+  primary key (catalog_id, target_catalog_id),
+  check (catalog_id <> target_catalog_id)
+);
+
+create index idx_model_catalog_targets_target on public.model_catalog_targets (target_catalog_id);
+
+alter table public.model_catalog_targets enable row level security;
+
+create policy "Signed-in users can view catalog targets"
+  on public.model_catalog_targets for select
+  using ((select auth.uid()) is not null);
+
+create policy "Godfather can manage catalog targets"
+  on public.model_catalog_targets for all
+  using (
+    exists (
+      select 1 from public.profiles
+      where id = (select auth.uid()) and tier = 'godfather'
+    )
+  )
+  with check (
+    exists (
+      select 1 from public.profiles
+      where id = (select auth.uid()) and tier = 'godfather'
+    )
+  );
+
+-- ============================================
+-- Pick the model that answers for a catalog entry
+-- ============================================
+-- Returns {catalog_id, alias, provider, model, routed} and, when routed,
+-- {target_catalog_id, target_alias, sticky}. Security definer because target
+-- entries may sit above the Don's tier or be hidden from the catalog listing.
+create or replace function public.pick_catalog_target(p_catalog_id uuid, p_member_id uuid default null)
+returns jsonb as $$
+declare
+  v_entry public.model_catalog%rowtype;
+  v_total int;
+  v_point int;
+  v_target record;
+begin
+  select * into v_entry from public.model_catalog where id = p_catalog_id;
+  if not found then
+    return null;
+  end if;
+
+  select coalesce(sum(t.weight), 0) into v_total
+  from public.model_catalog_targets t
+  join public.model_catalog mc on mc.id = t.target_catalog_id
+  where t.catalog_id = p_catalog_id and mc.is_active;
+
+  if v_total = 0 then
+    return jsonb_build_object(
+      'catalog_id', v_entry.id,
+      'alias', v_entry.alias,
+      'provider', v_entry.provider,
+      'model', v_entry.model,
+      'routed', false
+    );
+  end if;
+
+  if v_entry.sticky_routing and p_member_id is not null then
+    v_point := mod(abs(hashtext(p_member_id::text || ':' || p_catalog_id::text)::bigint), v_total)::int;
+  else
+    v_point := floor(random() * v_total)::int;
+  end if;
+
+  select mc.id, mc.alias, mc.provider, mc.model into v_target
+  from (
+    select t.target_catalog_id,
+           sum(t.weight) over (order by t.target_catalog_id) as upper_bound
+    from public.model_catalog_targets t
+    join public.model_catalog mc on mc.id = t.target_catalog_id
+    where t.catalog_id = p_catalog_id and mc.is_active
+  ) w
+  join public.model_catalog mc on mc.id = w.target_catalog_id
+  where w.upper_bound > v_point
+  order by w.upper_bound
+  limit 1;
+
+  return jsonb_build_object(
+    'catalog_id', v_entry.id,
+    'alias', v_entry.alias,
+    'provider', v_target.provider,
+    'model', v_target.model,
+    'routed', true,
+    'target_catalog_id', v_target.id,
+    'target_alias', v_target.alias,
+    'sticky', v_entry.sticky_routing and p_member_id is not null
+  );
+end;
+$$ language plpgsql volatile security definer set search_path = '';
+
+-- ============================================
+-- Borrowed soldiers carry their catalog entry id, so the borrower's
+-- caporegime can route them through pick_catalog_target too
+-- ============================================
+create or replace function public.list_borrowed_soldiers(
+  p_caporegime_id uuid,
+  p_sit_down_id uuid default null
+) returns jsonb as $$
+begin
+  if not exists (
+    select 1 from public.members
+    where id = p_caporegime_id and owner_id = auth.uid() and member_type = 'caporegime'
+  ) then
+    raise exception 'CAPOREGIME_NOT_FOUND';
+  end if;
+
+  return coalesce((
+    select jsonb_agg(
+      jsonb_build_object(
+        'id', m.id,
+        'name', m.name,
+        'system_prompt', m.system_prompt,
+        'soldier_type', m.soldier_type,
+        'soldier_config', m.soldier_config,
+        'fallback_model_ids', m.fallback_model_ids,
+        'catalog_model', (
+          select jsonb_build_object('id', mc.id, 'provider', mc.provider, 'model', mc.model, 'alias', mc.alias)
+          from public.model_catalog mc where mc.id = m.catalog_model_id
+        ),
+        'loan_id', sl.id,
+        'lender_id', sl.lender_id,
+        'lender_name', p.display_name,
+        'loan_expires_at', sl.expires_at
+      )
+      order by m.name
+    )
+    from public.soldier_loans sl
+    join public.members m on m.id = sl.soldier_id
+    join public.profiles p on p.id = sl.lender_id
+    where sl.borrower_id = auth.uid()
+      and sl.status = 'active'
+      and sl.expires_at > now()
+      and (sl.caporegime_id is null or sl.caporegime_id = p_caporegime_id)
+      and (sl.sit_down_id is null or sl.sit_down_id = p_sit_down_id)
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
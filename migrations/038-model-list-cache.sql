@@ -0,0 +1,27 @@
+-- 038-model-list-cache.sql
+-- Cache of list-models' normalized per-provider model lists, so loading the
+-- Admin page doesn't call five providers every time. list-models reads and
+-- writes it with the service role; nobody else needs it, so RLS is on with
+-- no policies. Failed provider lists are never cached.
+
+create table public.model_list_cache (
+  provider text primary key,
+  models jsonb not null default '[]',
+  fetched_at timestamptz not null default now(),
+  constraint model_list_cache_models_is_array check (jsonb_typeof(models) = 'array')
+);
+
+alter table public.model_list_cache enable row level security;
+
+-- ============================================
+-- Cached lists younger than p_max_age_seconds
+-- ============================================
+-- The formula has no clock of its own, so freshness is decided here. Runs
+-- with the caller's rights, so only the service role sees any rows.
+create or replace function public.fresh_model_lists(p_providers text[], p_max_age_seconds int)
+returns table (provider text, models jsonb, fetched_at timestamptz) as $$
+  select c.provider, c.models, c.fetched_at
+  from public.model_list_cache c
+  where c.provider = any(p_providers)
+    and c.fetched_at > now() - make_interval(secs => p_max_age_seconds);
+$$ language sql stable set search_path = '';
@@ -0,0 +1,116 @@
+-- 039-message-edits.sql
+-- Edit and delete messages. Messages have no update/delete policies, so both
+-- go through security definer RPCs that allow the sender (the Don who sent it,
+-- or the owner of the member that sent it) or a sit-down admin.
+--
+-- Edits keep every previous version in metadata.edits as
+-- [{content, mentions, edited_at, edited_by}], oldest first. Deletes are soft:
+-- the row stays so replies and caporegime reports that reference it still
+-- resolve, but its content, mentions and edit history are cleared.
+
+alter table public.messages add column edited_at timestamptz;
+alter table public.messages add column deleted_at timestamptz;
+
+-- ============================================
+-- Who may edit or delete a message
+-- ============================================
+create or replace function public.can_manage_message(p_message public.messages)
+returns boolean as $$
+  select
+    (p_message.sender_type = 'don' and p_message.sender_user_id = (select auth.uid()))
+    or (p_message.sender_type = 'member' and exists (
+      select 1 from public.members
+      where id = p_message.sender_member_id and owner_id = (select auth.uid())
+    ))
+    or exists (
+      select 1 from public.sit_down_participants
+      where sit_down_id = p_message.sit_down_id
+        and user_id = (select auth.uid())
+        and is_admin
+    );
+$$ language sql stable security definer set search_path = '';
+
+-- ============================================
+-- RPC: Edit a message
+-- ============================================
+-- p_mentions replaces the stored mentions when given (sit-down re-parses them
+-- for Don messages); null keeps the current ones.
+create or replace function public.edit_message(
+  p_message_id uuid,
+  p_sit_down_id uuid,
+  p_content text,
+  p_mentions uuid[] default null
+) returns public.messages as $$
+declare
+  v_message public.messages;
+begin
+  select * into v_message from public.messages
+  where id = p_message_id and sit_down_id = p_sit_down_id;
+  if v_message.id is null then
+    raise exception 'Message not found';
+  end if;
+  if v_message.deleted_at is not null then
+    raise exception 'Message was deleted';
+  end if;
+  if not public.can_manage_message(v_message) then
+    raise exception 'Only the sender or a sit-down admin can edit this message';
+  end if;
+  if coalesce(trim(p_content), '') = '' then
+    raise exception 'Message cannot be empty';
+  end if;
+
+  update public.messages
+  set
+    content = p_content,
+    mentions = coalesce(p_mentions, mentions),
+    edited_at = now(),
+    metadata = jsonb_set(
+      coalesce(metadata, '{}'::jsonb),
+      '{edits}',
+      coalesce(metadata->'edits', '[]'::jsonb) || jsonb_build_array(jsonb_build_object(
+        'content', v_message.content,
+        'mentions', to_jsonb(v_message.mentions),
+        'edited_at', now(),
+        'edited_by', (select auth.uid())
+      ))
+    )
+  where id = p_message_id
+  returning * into v_message;
+
+  return v_message;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Delete a message (soft)
+-- ============================================
+create or replace function public.delete_message(p_message_id uuid, p_sit_down_id uuid)
+returns public.messages as $$
+declare
+  v_message public.messages;
+begin
+  select * into v_message from public.messages
+  where id = p_message_id and sit_down_id = p_sit_down_id;
+  if v_message.id is null then
+    raise exception 'Message not found';
+  end if;
+  if v_message.deleted_at is not null then
+    return v_message;
+  end if;
+  if not public.can_manage_message(v_message) then
+    raise exception 'Only the sender or a sit-down admin can delete this message';
+  end if;
+
+  update public.messages
+  set
+    content = '',
+    mentions = '{}',
+    deleted_at = now(),
+    metadata = (coalesce(metadata, '{}'::jsonb) - 'edits')
+      || jsonb_build_object('deleted_by', (select auth.uid()))
+  where id = p_message_id
+  returning * into v_message;
+
+  return v_message;
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,152 @@
+-- 040-message-versions.sql
+-- Regenerated member answers. sit-down's `regenerate` re-runs a member on the
+-- context its message was written in and stores the new answer as another
+-- version of the same message rather than a new message.
+--
+-- Versions live in metadata.versions as [{content, metadata, created_at}],
+-- oldest first, with metadata.selected_version pointing at the canonical one.
+-- The message's own content (and provider/model/fallback_from/routing) always
+-- mirror the selected version, so everything that reads messages — member
+-- context, the chat, caporegime reports — keeps seeing a single answer. The
+-- first regeneration seeds the list with the original answer.
+
+-- ============================================
+-- The metadata that belongs to one version of an answer
+-- ============================================
+create or replace function public.message_version_fields(p_metadata jsonb)
+returns jsonb as $$
+  select jsonb_strip_nulls(jsonb_build_object(
+    'provider', p_metadata->'provider',
+    'model', p_metadata->'model',
+    'fallback_from', p_metadata->'fallback_from',
+    'routing', p_metadata->'routing'
+  ));
+$$ language sql immutable set search_path = '';
+
+-- ============================================
+-- RPC: Add a regenerated version and make it canonical
+-- ============================================
+create or replace function public.add_message_version(
+  p_message_id uuid,
+  p_content text,
+  p_metadata jsonb
+) returns public.messages as $$
+declare
+  v_message public.messages;
+  v_versions jsonb;
+begin
+  select * into v_message from public.messages where id = p_message_id;
+  if v_message.id is null or v_message.sender_type <> 'member' then
+    raise exception 'Message not found';
+  end if;
+  if v_message.deleted_at is not null then
+    raise exception 'Message was deleted';
+  end if;
+  if not public.can_manage_message(v_message) then
+    raise exception 'Only the member''s owner or a sit-down admin can regenerate this message';
+  end if;
+
+  v_versions := coalesce(
+    v_message.metadata->'versions',
+    jsonb_build_array(jsonb_build_object(
+      'content', v_message.content,
+      'metadata', public.message_version_fields(coalesce(v_message.metadata, '{}'::jsonb)),
+      'created_at', v_message.created_at
+    ))
+  ) || jsonb_build_array(jsonb_build_object(
+    'content', p_content,
+    'metadata', public.message_version_fields(p_metadata),
+    'created_at', now()
+  ));
+
+  update public.messages
+  set
+    content = p_content,
+    metadata = (coalesce(metadata, '{}'::jsonb) - array['provider', 'model', 'fallback_from', 'routing'])
+      || public.message_version_fields(p_metadata)
+      || jsonb_build_object(
+        'versions', v_versions,
+        'selected_version', jsonb_array_length(v_versions) - 1
+      )
+  where id = p_message_id
+  returning * into v_message;
+
+  return v_message;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Pick which version is canonical
+-- ============================================
+create or replace function public.select_message_version(
+  p_message_id uuid,
+  p_sit_down_id uuid,
+  p_version int
+) returns public.messages as $$
+declare
+  v_message public.messages;
+  v_version jsonb;
+begin
+  select * into v_message from public.messages
+  where id = p_message_id and sit_down_id = p_sit_down_id;
+  if v_message.id is null then
+    raise exception 'Message not found';
+  end if;
+  if v_message.deleted_at is not null then
+    raise exception 'Message was deleted';
+  end if;
+  if not public.can_manage_message(v_message) then
+    raise exception 'Only the member''s owner or a sit-down admin can pick a version';
+  end if;
+
+  v_version := v_message.metadata->'versions'->p_version;
+  if p_version < 0 or v_version is null then
+    raise exception 'Version not found';
+  end if;
+
+  update public.messages
+  set
+    content = v_version->>'content',
+    metadata = (coalesce(metadata, '{}'::jsonb) - array['provider', 'model', 'fallback_from', 'routing'])
+      || coalesce(v_version->'metadata', '{}'::jsonb)
+      || jsonb_build_object('selected_version', p_version)
+  where id = p_message_id
+  returning * into v_message;
+
+  return v_message;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- Deleting a message also drops its other versions
+-- ============================================
+create or replace function public.delete_message(p_message_id uuid, p_sit_down_id uuid)
+returns public.messages as $$
+declare
+  v_message public.messages;
+begin
+  select * into v_message from public.messages
+  where id = p_message_id and sit_down_id = p_sit_down_id;
+  if v_message.id is null then
+    raise exception 'Message not found';
+  end if;
+  if v_message.deleted_at is not null then
+    return v_message;
+  end if;
+  if not public.can_manage_message(v_message) then
+    raise exception 'Only the sender or a sit-down admin can delete this message';
+  end if;
+
+  update public.messages
+  set
+    content = '',
+    mentions = '{}',
+    deleted_at = now(),
+    metadata = (coalesce(metadata, '{}'::jsonb) - array['edits', 'versions', 'selected_version'])
+      || jsonb_build_object('deleted_by', (select auth.uid()))
+  where id = p_message_id
+  returning * into v_message;
+
+  return v_message;
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,100 @@
+-- 041-fork-sit-down.sql
+-- Fork a sit-down from any message: a new sit-down with the same Dons and
+-- members and a copy of the history up to and including that message, so a
+-- different line of questioning doesn't pollute the original table. The fork
+-- records where it came from in forked_from_sit_down_id/forked_from_message_id.
+--
+-- Copies are new rows with new ids. Message references inside metadata
+-- (reply_to_id, progress_message_ids) are remapped to the copies; edit
+-- history, versions and operation ids are carried over as they are. A fork
+-- of a Back Room is an ordinary commission sit-down, since a Back Room is
+-- unique per pair of Dons.
+
+alter table public.sit_downs
+  add column forked_from_sit_down_id uuid references public.sit_downs(id) on delete set null,
+  add column forked_from_message_id uuid references public.messages(id) on delete set null;
+
+-- ============================================
+-- RPC: Fork a sit-down
+-- ============================================
+create or replace function public.fork_sit_down(
+  p_sit_down_id uuid,
+  p_message_id uuid,
+  p_name text default null
+) returns public.sit_downs as $$
+declare
+  v_source public.sit_downs;
+  v_until timestamptz;
+  v_fork public.sit_downs;
+begin
+  if not public.is_sit_down_participant(p_sit_down_id) then
+    raise exception 'Not a participant of this sit-down';
+  end if;
+
+  select * into v_source from public.sit_downs where id = p_sit_down_id;
+
+  select created_at into v_until from public.messages
+  where id = p_message_id and sit_down_id = p_sit_down_id;
+  if v_until is null then
+    raise exception 'Message not found';
+  end if;
+
+  insert into public.sit_downs (
+    name, description, created_by, is_commission, forked_from_sit_down_id, forked_from_message_id
+  ) values (
+    coalesce(nullif(trim(p_name), ''), v_source.name || ' (fork)'),
+    v_source.description,
+    auth.uid(),
+    v_source.is_commission,
+    v_source.id,
+    p_message_id
+  )
+  returning * into v_fork;
+
+  -- History goes in before any participant, so notify_new_message finds
+  -- nobody to push the copies to
+  with source as (
+    select m.*, gen_random_uuid() as new_id
+    from public.messages m
+    where m.sit_down_id = p_sit_down_id and m.created_at <= v_until
+  ),
+  id_map as (
+    select coalesce(jsonb_object_agg(id::text, new_id), '{}'::jsonb) as ids from source
+  )
+  insert into public.messages (
+    id, sit_down_id, sender_type, sender_user_id, sender_member_id,
+    content, mentions, metadata, created_at, edited_at, deleted_at
+  )
+  select
+    s.new_id, v_fork.id, s.sender_type, s.sender_user_id, s.sender_member_id,
+    s.content, s.mentions,
+    coalesce(s.metadata, '{}'::jsonb)
+      || case when s.metadata ? 'reply_to_id'
+           then jsonb_build_object('reply_to_id', coalesce(id_map.ids -> (s.metadata->>'reply_to_id'), 'null'::jsonb))
+           else '{}'::jsonb end
+      || case when jsonb_typeof(s.metadata->'progress_message_ids') = 'array'
+           then jsonb_build_object('progress_message_ids', (
+             select coalesce(jsonb_agg(id_map.ids -> x), '[]'::jsonb)
+             from jsonb_array_elements_text(s.metadata->'progress_message_ids') x
+             where id_map.ids ? x
+           ))
+           else '{}'::jsonb end,
+    s.created_at, s.edited_at, s.deleted_at
+  from source s cross join id_map;
+
+  insert into public.sit_down_participants (sit_down_id, user_id, member_id, added_by, is_admin)
+  select
+    v_fork.id, sp.user_id, sp.member_id, auth.uid(),
+    sp.is_admin or (v_source.is_commission and sp.user_id = auth.uid())
+  from public.sit_down_participants sp
+  where sp.sit_down_id = p_sit_down_id;
+
+  -- The copied history has already been read
+  insert into public.sit_down_read_receipts (sit_down_id, user_id, last_read_at)
+  select v_fork.id, sp.user_id, now()
+  from public.sit_down_participants sp
+  where sp.sit_down_id = v_fork.id and sp.user_id is not null;
+
+  return v_fork;
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,184 @@
+-- 042-message-threads.sql
+-- Threads built on reply_to_id. A reply joins the thread of the message it
+-- replies to: thread_id is that thread's root (the first message that isn't
+-- a reply), set on insert by assign_message_thread(). The root keeps
+-- thread_reply_count and thread_last_reply_at up to date for the chat.
+--
+-- Member answers to a reply carry the same reply_to_id, so they land in the
+-- thread too. sit-down builds a thread message's context from the thread
+-- alone, and main-timeline context leaves thread replies out.
+
+alter table public.messages
+  add column thread_id uuid references public.messages(id) on delete set null,
+  add column thread_reply_count int not null default 0,
+  add column thread_last_reply_at timestamptz;
+
+create index idx_messages_thread on public.messages (thread_id, created_at)
+  where thread_id is not null;
+
+-- ============================================
+-- Backfill existing reply chains
+-- ============================================
+with recursive chain as (
+  select id, id as root
+  from public.messages
+  where metadata->>'reply_to_id' is null
+  union all
+  select m.id, c.root
+  from public.messages m
+  join chain c on m.metadata->>'reply_to_id' = c.id::text
+)
+update public.messages m
+set thread_id = c.root
+from chain c
+where m.id = c.id and c.root <> m.id;
+
+-- ============================================
+-- Recount a sit-down's threads
+-- ============================================
+create or replace function public.refresh_thread_counts(p_sit_down_id uuid)
+returns void as $$
+  update public.messages r
+  set
+    thread_reply_count = coalesce(t.replies, 0),
+    thread_last_reply_at = t.last_reply_at
+  from public.messages m
+  left join (
+    select thread_id, count(*)::int as replies, max(created_at) as last_reply_at
+    from public.messages
+    where sit_down_id = p_sit_down_id and thread_id is not null
+    group by thread_id
+  ) t on t.thread_id = m.id
+  where r.id = m.id
+    and m.sit_down_id = p_sit_down_id
+    and (m.thread_reply_count > 0 or t.thread_id is not null);
+$$ language sql volatile security definer set search_path = '';
+
+select public.refresh_thread_counts(id) from public.sit_downs;
+
+-- ============================================
+-- Replies join their parent's thread
+-- ============================================
+create or replace function public.assign_message_thread()
+returns trigger as $$
+begin
+  if new.thread_id is null and new.metadata->>'reply_to_id' is not null then
+    select coalesce(p.thread_id, p.id) into new.thread_id
+    from public.messages p
+    where p.id::text = new.metadata->>'reply_to_id' and p.sit_down_id = new.sit_down_id;
+  end if;
+  return new;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+create trigger trg_assign_message_thread
+  before insert on public.messages
+  for each row
+  execute function public.assign_message_thread();
+
+create or replace function public.count_thread_reply()
+returns trigger as $$
+begin
+  update public.messages
+  set
+    thread_reply_count = thread_reply_count + 1,
+    thread_last_reply_at = greatest(thread_last_reply_at, new.created_at)
+  where id = new.thread_id;
+  return new;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+create trigger trg_count_thread_reply
+  after insert on public.messages
+  for each row
+  when (new.thread_id is not null)
+  execute function public.count_thread_reply();
+
+-- ============================================
+-- Forks keep their threads
+-- ============================================
+create or replace function public.fork_sit_down(
+  p_sit_down_id uuid,
+  p_message_id uuid,
+  p_name text default null
+) returns public.sit_downs as $$
+declare
+  v_source public.sit_downs;
+  v_until timestamptz;
+  v_fork public.sit_downs;
+begin
+  if not public.is_sit_down_participant(p_sit_down_id) then
+    raise exception 'Not a participant of this sit-down';
+  end if;
+
+  select * into v_source from public.sit_downs where id = p_sit_down_id;
+
+  select created_at into v_until from public.messages
+  where id = p_message_id and sit_down_id = p_sit_down_id;
+  if v_until is null then
+    raise exception 'Message not found';
+  end if;
+
+  insert into public.sit_downs (
+    name, description, created_by, is_commission, forked_from_sit_down_id, forked_from_message_id
+  ) values (
+    coalesce(nullif(trim(p_name), ''), v_source.name || ' (fork)'),
+    v_source.description,
+    auth.uid(),
+    v_source.is_commission,
+    v_source.id,
+    p_message_id
+  )
+  returning * into v_fork;
+
+  -- History goes in before any participant, so notify_new_message finds
+  -- nobody to push the copies to
+  with source as (
+    select m.*, gen_random_uuid() as new_id
+    from public.messages m
+    where m.sit_down_id = p_sit_down_id and m.created_at <= v_until
+  ),
+  id_map as (
+    select coalesce(jsonb_object_agg(id::text, new_id), '{}'::jsonb) as ids from source
+  )
+  insert into public.messages (
+    id, sit_down_id, sender_type, sender_user_id, sender_member_id,
+    content, mentions, metadata, created_at, edited_at, deleted_at, thread_id
+  )
+  select
+    s.new_id, v_fork.id, s.sender_type, s.sender_user_id, s.sender_member_id,
+    s.content, s.mentions,
+    coalesce(s.metadata, '{}'::jsonb)
+      || case when s.metadata ? 'reply_to_id'
+           then jsonb_build_object('reply_to_id', coalesce(id_map.ids -> (s.metadata->>'reply_to_id'), 'null'::jsonb))
+           else '{}'::jsonb end
+      || case when jsonb_typeof(s.metadata->'progress_message_ids') = 'array'
+           then jsonb_build_object('progress_message_ids', (
+             select coalesce(jsonb_agg(id_map.ids -> x), '[]'::jsonb)
+             from jsonb_array_elements_text(s.metadata->'progress_message_ids') x
+             where id_map.ids ? x
+           ))
+           else '{}'::jsonb end,
+    s.created_at, s.edited_at, s.deleted_at,
+    (id_map.ids ->> s.thread_id::text)::uuid
+  from source s cross join id_map;
+
+  -- Copies arrive in no particular order, so count the fork's threads afresh
+  perform public.refresh_thread_counts(v_fork.id);
+
+  insert into public.sit_down_participants (sit_down_id, user_id, member_id, added_by, is_admin)
+  select
+    v_fork.id, sp.user_id, sp.member_id, auth.uid(),
+    sp.is_admin or (v_source.is_commission and sp.user_id = auth.uid())
+  from public.sit_down_participants sp
+  where sp.sit_down_id = p_sit_down_id;
+
+  -- The copied history has already been read
+  insert into public.sit_down_read_receipts (sit_down_id, user_id, last_read_at)
+  select v_fork.id, sp.user_id, now()
+  from public.sit_down_participants sp
+  where sp.sit_down_id = v_fork.id and sp.user_id is not null;
+
+  return v_fork;
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,41 @@
+-- 043-context-token-budget.sql
+-- Member context is sized in tokens rather than messages. sit-down estimates
+-- each message's tokens and keeps the newest ones that fit the answering
+-- member's budget, which follows its catalog model's `long_context`
+-- capability. context_token_budget overrides that for every member of one
+-- sit-down; null leaves it to the models.
+
+alter table public.sit_downs
+  add column context_token_budget int
+    check (context_token_budget is null or context_token_budget between 1000 and 1000000);
+
+-- ============================================
+-- RPC: Set or clear a sit-down's context budget
+-- ============================================
+create or replace function public.set_context_token_budget(
+  p_sit_down_id uuid,
+  p_budget int
+) returns public.sit_downs as $$
+declare
+  v_sit_down public.sit_downs;
+begin
+  select * into v_sit_down from public.sit_downs where id = p_sit_down_id;
+  if v_sit_down.id is null then
+    raise exception 'Sit-down not found';
+  end if;
+
+  if v_sit_down.created_by is distinct from auth.uid() and not exists (
+    select 1 from public.sit_down_participants
+    where sit_down_id = p_sit_down_id and user_id = auth.uid() and is_admin
+  ) then
+    raise exception 'Only the creator or a sit-down admin can change the context budget';
+  end if;
+
+  update public.sit_downs
+  set context_token_budget = p_budget
+  where id = p_sit_down_id
+  returning * into v_sit_down;
+
+  return v_sit_down;
+end;
+$$ language plpgsql security definer set search_path = '';
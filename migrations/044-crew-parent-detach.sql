@@ -0,0 +1,22 @@
+-- 044-crew-parent-detach.sql
+-- members.caporegime_id cascades on delete (014), which is right for soldiers
+-- but meant deleting a caporegime silently deleted every sub-caporegime below
+-- it, with their crews, jobs and operations. Sub-caporegimes are now detached
+-- (they become top-level caporegimes) before their parent row goes; soldiers
+-- still go with their caporegime.
+
+create or replace function public.detach_sub_caporegimes()
+returns trigger as $$
+begin
+  if old.member_type = 'caporegime' then
+    update public.members
+    set caporegime_id = null
+    where caporegime_id = old.id and member_type = 'caporegime';
+  end if;
+  return old;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+create trigger detach_sub_caporegimes
+  before delete on public.members
+  for each row execute function public.detach_sub_caporegimes();
@@ -0,0 +1,173 @@
+-- 045-soldier-loan-secrets.sql
+-- Borrowers could read the lender's API secrets. "Borrowers can view lent
+-- soldiers" (027) granted a full-row select on members, and
+-- list_borrowed_soldiers (027, 037) returned soldier_config, where external
+-- soldiers keep secrets[].value.
+--
+-- Borrowers now never read a lent soldier's row. What the borrower's
+-- caporegime needs to run the soldier comes from list_borrowed_soldiers as a
+-- fixed set of fields: output_schema, plus reagent_ref and config for reagent
+-- soldiers. The loans list comes from list_soldier_loans with the soldier's
+-- name and avatar only.
+--
+-- External soldiers can't be lent. They call third-party APIs with the
+-- lender's credentials, and a loan runs inside the borrower's caporegime,
+-- where those credentials would be exposed. Open loans of external soldiers are
+-- revoked, and a soldier switched to external after it was lent stops being
+-- offered to the borrower.
+
+drop policy "Borrowers can view lent soldiers" on public.members;
+
+update public.soldier_loans sl
+set status = 'revoked', responded_at = coalesce(sl.responded_at, now())
+from public.members m
+where m.id = sl.soldier_id
+  and m.soldier_type = 'external'
+  and sl.status in ('offered', 'active');
+
+-- ============================================
+-- The soldier_config fields a borrower's caporegime may see
+-- ============================================
+create or replace function public.lendable_soldier_config(p_soldier_type text, p_config jsonb)
+returns jsonb as $$
+  select jsonb_strip_nulls(jsonb_build_object(
+    'output_schema', p_config->'output_schema',
+    'reagent_ref', case when p_soldier_type = 'reagent' then p_config->'reagent_ref' end,
+    'config', case when p_soldier_type = 'reagent' then p_config->'config' end
+  ));
+$$ language sql immutable set search_path = '';
+
+-- ============================================
+-- RPC: Offer a soldier to an allied Don (external soldiers refused)
+-- ============================================
+create or replace function public.lend_soldier(
+  p_soldier_id uuid,
+  p_borrower_id uuid,
+  p_expires_at timestamptz,
+  p_caporegime_id uuid default null,
+  p_sit_down_id uuid default null
+) returns public.soldier_loans as $$
+declare
+  v_loan public.soldier_loans;
+  v_soldier_type text;
+begin
+  select soldier_type into v_soldier_type from public.members
+  where id = p_soldier_id and owner_id = auth.uid() and member_type = 'soldier';
+  if v_soldier_type is null then
+    raise exception 'SOLDIER_NOT_FOUND';
+  end if;
+  if v_soldier_type = 'external' then
+    raise exception 'SOLDIER_NOT_LENDABLE';
+  end if;
+
+  if not exists (
+    select 1 from public.commission_contacts
+    where user_id = auth.uid() and contact_user_id = p_borrower_id and status = 'accepted'
+  ) then
+    raise exception 'NOT_A_COMMISSION_CONTACT';
+  end if;
+
+  if p_expires_at <= now() or p_expires_at > now() + interval '90 days' then
+    raise exception 'INVALID_EXPIRY';
+  end if;
+
+  if p_caporegime_id is not null and not exists (
+    select 1 from public.members
+    where id = p_caporegime_id and owner_id = p_borrower_id and member_type = 'caporegime'
+  ) then
+    raise exception 'CAPOREGIME_NOT_FOUND';
+  end if;
+
+  -- Sit-down scope must be a commission both Dons sit in
+  if p_sit_down_id is not null and not exists (
+    select 1 from public.sit_downs sd
+    where sd.id = p_sit_down_id
+      and sd.is_commission = true
+      and exists (select 1 from public.sit_down_participants where sit_down_id = sd.id and user_id = auth.uid())
+      and exists (select 1 from public.sit_down_participants where sit_down_id = sd.id and user_id = p_borrower_id)
+  ) then
+    raise exception 'SIT_DOWN_NOT_SHARED';
+  end if;
+
+  insert into public.soldier_loans (soldier_id, lender_id, borrower_id, caporegime_id, sit_down_id, expires_at)
+  values (p_soldier_id, auth.uid(), p_borrower_id, p_caporegime_id, p_sit_down_id, p_expires_at)
+  returning * into v_loan;
+
+  return v_loan;
+exception
+  when unique_violation then
+    raise exception 'LOAN_ALREADY_OPEN';
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: Soldiers lent to the caller that a caporegime may use right now
+-- ============================================
+create or replace function public.list_borrowed_soldiers(
+  p_caporegime_id uuid,
+  p_sit_down_id uuid default null
+) returns jsonb as $$
+begin
+  if not exists (
+    select 1 from public.members
+    where id = p_caporegime_id and owner_id = auth.uid() and member_type = 'caporegime'
+  ) then
+    raise exception 'CAPOREGIME_NOT_FOUND';
+  end if;
+
+  return coalesce((
+    select jsonb_agg(
+      jsonb_build_object(
+        'id', m.id,
+        'name', m.name,
+        'system_prompt', m.system_prompt,
+        'soldier_type', m.soldier_type,
+        'soldier_config', public.lendable_soldier_config(m.soldier_type, coalesce(m.soldier_config, '{}'::jsonb)),
+        'fallback_model_ids', m.fallback_model_ids,
+        'catalog_model', (
+          select jsonb_build_object('id', mc.id, 'provider', mc.provider, 'model', mc.model, 'alias', mc.alias)
+          from public.model_catalog mc where mc.id = m.catalog_model_id
+        ),
+        'loan_id', sl.id,
+        'lender_id', sl.lender_id,
+        'lender_name', p.display_name,
+        'loan_expires_at', sl.expires_at
+      )
+      order by m.name
+    )
+    from public.soldier_loans sl
+    join public.members m on m.id = sl.soldier_id
+    join public.profiles p on p.id = sl.lender_id
+    where sl.borrower_id = auth.uid()
+      and sl.status = 'active'
+      and sl.expires_at > now()
+      and m.soldier_type <> 'external'
+      and (sl.caporegime_id is null or sl.caporegime_id = p_caporegime_id)
+      and (sl.sit_down_id is null or sl.sit_down_id = p_sit_down_id)
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: The caller's open loans in either direction, newest first
+-- Soldiers are embedded with their name and avatar only.
+-- ============================================
+create or replace function public.list_soldier_loans()
+returns jsonb as $$
+  select coalesce(jsonb_agg(
+    to_jsonb(sl) || jsonb_build_object(
+      'soldier', jsonb_build_object('id', s.id, 'name', s.name, 'avatar_url', s.avatar_url),
+      'caporegime', case when c.id is not null then jsonb_build_object('id', c.id, 'name', c.name) end,
+      'lender', jsonb_build_object('id', lp.id, 'display_name', lp.display_name),
+      'borrower', jsonb_build_object('id', bp.id, 'display_name', bp.display_name)
+    )
+    order by sl.created_at desc
+  ), '[]'::jsonb)
+  from public.soldier_loans sl
+  join public.members s on s.id = sl.soldier_id
+  left join public.members c on c.id = sl.caporegime_id
+  join public.profiles lp on lp.id = sl.lender_id
+  join public.profiles bp on bp.id = sl.borrower_id
+  where (sl.lender_id = (select auth.uid()) or sl.borrower_id = (select auth.uid()))
+    and sl.status in ('offered', 'active');
+$$ language sql stable security definer set search_path = '';
@@ -0,0 +1,8 @@
+-- 046-member-events-server-only.sql
+-- Any participant could insert member events with any member_id, so a Don
+-- could write progress, text or errors as someone else's member. Events are now
+-- written by the formulas with the service role only: sit-down after it has
+-- written to the sit-down with the Don's token, and the member formulas after
+-- the Don's token has shown they sit at that table.
+
+drop policy "Participants can insert member events" on public.member_events;
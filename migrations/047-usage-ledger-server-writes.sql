@@ -0,0 +1,15 @@
+-- 047-usage-ledger-server-writes.sql
+-- Clients could write their own usage_ledger rows, including negative token
+-- counts that lowered their totals. Usage is now written by the formulas with
+-- the service role only: sit-down for every member response, billed to the Don
+-- whose token ran it, and caporegime for every job run, billed to the job's
+-- owner. Token counts can no longer be negative.
+
+drop policy "Users can record own usage" on public.usage_ledger;
+
+-- Rows already written with negative counts could only have come from a client
+delete from public.usage_ledger where input_tokens < 0 or output_tokens < 0;
+
+alter table public.usage_ledger
+  add constraint usage_ledger_input_tokens_check check (input_tokens >= 0),
+  add constraint usage_ledger_output_tokens_check check (output_tokens >= 0);
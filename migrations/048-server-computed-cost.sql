@@ -0,0 +1,46 @@
+-- 048-server-computed-cost.sql
+-- usage_ledger_set_cost (031) kept any cost_usd the writer supplied, so a row
+-- could carry a made-up cost. Cost is now always computed from the catalog
+-- prices, on insert and whenever the model or token counts change. The same
+-- goes for the cost_usd in operations.usage.
+
+create or replace function public.usage_ledger_set_cost()
+returns trigger as $$
+begin
+  new.cost_usd := public.estimate_cost(new.provider, new.model, new.input_tokens, new.output_tokens);
+  return new;
+end;
+$$ language plpgsql set search_path = '';
+
+drop trigger usage_ledger_set_cost on public.usage_ledger;
+
+create trigger usage_ledger_set_cost
+  before insert or update of provider, model, input_tokens, output_tokens, cost_usd on public.usage_ledger
+  for each row execute function public.usage_ledger_set_cost();
+
+create or replace function public.operations_set_cost()
+returns trigger as $$
+declare
+  v_cost numeric;
+begin
+  if new.usage is null then
+    return new;
+  end if;
+
+  new.usage := new.usage - 'cost_usd';
+  if new.usage->'answered_by' is null then
+    return new;
+  end if;
+
+  v_cost := public.estimate_cost(
+    regexp_replace(new.usage->'answered_by'->>'catalyst_ref', '^.*\.', ''),
+    new.usage->'answered_by'->>'model',
+    (new.usage->>'input_tokens')::bigint,
+    (new.usage->>'output_tokens')::bigint
+  );
+  if v_cost is not null then
+    new.usage := new.usage || jsonb_build_object('cost_usd', v_cost);
+  end if;
+  return new;
+end;
+$$ language plpgsql set search_path = '';
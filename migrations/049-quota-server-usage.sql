@@ -0,0 +1,104 @@
+-- 049-quota-server-usage.sql
+-- check_quota() (032) summed every usage_ledger row, including rows a client
+-- wrote for itself. Rows now record whether the server wrote them, and quotas
+-- count only those. server_recorded is set by trigger from the role doing the
+-- insert, so no writer can claim it: true for the service role (the formulas,
+-- since 047) and database functions, false for anon or authenticated clients.
+--
+-- Rows from before this migration are kept as server-recorded. Clients could
+-- only add rows under their own user_id, and the negative ones that lowered a
+-- total were removed in 047, so what's left can only count against the Don
+-- who wrote it.
+
+alter table public.usage_ledger add column server_recorded boolean not null default true;
+alter table public.usage_ledger alter column server_recorded set default false;
+
+create or replace function public.usage_ledger_set_server_recorded()
+returns trigger as $$
+begin
+  new.server_recorded := current_user not in ('anon', 'authenticated');
+  return new;
+end;
+$$ language plpgsql set search_path = '';
+
+create trigger usage_ledger_set_server_recorded
+  before insert or update of server_recorded on public.usage_ledger
+  for each row execute function public.usage_ledger_set_server_recorded();
+
+-- ============================================
+-- Quota status for the calling user, from server-recorded usage only
+-- ============================================
+-- Returns {allowed, exceeded, limits}. Each limit is
+-- {period, source, max_tokens, max_cost_usd, used_tokens, used_cost_usd, resets_at};
+-- `exceeded` is the first limit that is used up, or null.
+create or replace function public.check_quota()
+returns jsonb as $$
+declare
+  v_user_id uuid := auth.uid();
+  v_limits jsonb;
+  v_exceeded jsonb;
+begin
+  if v_user_id is null then
+    raise exception 'NOT_AUTHENTICATED';
+  end if;
+
+  with effective as (
+    select distinct on (q.period)
+      q.period,
+      case when q.user_id is not null then 'user' else 'tier' end as source,
+      q.max_tokens,
+      q.max_cost_usd
+    from public.usage_quotas q
+    join public.profiles p on p.id = v_user_id
+    where q.user_id = v_user_id or q.tier = p.tier
+    order by q.period, (q.user_id is not null) desc
+  ),
+  measured as (
+    select
+      e.*,
+      date_trunc(e.period, now()) as period_start,
+      date_trunc(e.period, now()) + ('1 ' || e.period)::interval as resets_at,
+      coalesce(u.tokens, 0) as used_tokens,
+      coalesce(u.cost, 0) as used_cost_usd
+    from effective e
+    left join lateral (
+      select
+        sum(ul.input_tokens + ul.output_tokens) as tokens,
+        sum(ul.cost_usd) as cost
+      from public.usage_ledger ul
+      where ul.user_id = v_user_id
+        and ul.server_recorded
+        and ul.created_at >= date_trunc(e.period, now())
+    ) u on true
+  )
+  select
+    coalesce(jsonb_agg(jsonb_build_object(
+      'period', m.period,
+      'source', m.source,
+      'max_tokens', m.max_tokens,
+      'max_cost_usd', m.max_cost_usd,
+      'used_tokens', m.used_tokens,
+      'used_cost_usd', m.used_cost_usd,
+      'resets_at', m.resets_at
+    ) order by m.period), '[]'::jsonb),
+    (array_agg(jsonb_build_object(
+      'period', m.period,
+      'source', m.source,
+      'metric', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then 'tokens' else 'cost' end,
+      'limit', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then m.max_tokens else m.max_cost_usd end,
+      'used', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then m.used_tokens else m.used_cost_usd end,
+      'resets_at', m.resets_at
+    ) order by m.period) filter (
+      where (m.max_tokens is not null and m.used_tokens >= m.max_tokens)
+         or (m.max_cost_usd is not null and m.used_cost_usd >= m.max_cost_usd)
+    ))[1]
+  into v_limits, v_exceeded
+  from measured m;
+
+  return jsonb_build_object(
+    'allowed', v_exceeded is null,
+    'exceeded', v_exceeded,
+    'limits', v_limits
+  );
+end;
+$$ language plpgsql stable security definer set search_path = '';
@@ -0,0 +1,228 @@
+-- 050-admin-audit-triggers.sql
+-- admin-api wrote each admin_audit_log row itself, after the change had
+-- already been committed, so a failed write left a change unaudited and a
+-- Godfather writing to the tables directly through PostgREST bypassed the log.
+-- Audit rows are now written by AFTER triggers on the audited tables, in the
+-- same transaction as the change, whoever makes it. Nobody can insert into the
+-- log directly any more.
+--
+-- actor_id is auth.uid() of the request that made the change. It is null for
+-- changes made without a user, such as the service role or the SQL editor.
+--
+-- Actions keep admin-api's names: update_tier; tier_create, tier_update and
+-- tier_delete; catalog_add, catalog_update (catalog_toggle when only
+-- is_active changed, which now also covers catalog sync's deactivations) and
+-- catalog_delete; catalog_set_targets, one row per alias target added,
+-- reweighted or removed; quota_set and quota_delete; and catalog_migrate,
+-- written by migrate_catalog_members.
+
+drop policy "Godfathers can append to the audit log as themselves" on public.admin_audit_log;
+
+alter table public.admin_audit_log alter column actor_id drop not null;
+
+-- ============================================
+-- Trigger: one audit row per changed row
+-- ============================================
+-- TG_ARGV[0] is the target_type, TG_ARGV[1] the column holding the target id.
+create or replace function public.audit_admin_change()
+returns trigger as $$
+declare
+  v_before jsonb := case when tg_op <> 'INSERT' then to_jsonb(old) end;
+  v_after jsonb := case when tg_op <> 'DELETE' then to_jsonb(new) end;
+  v_action text;
+begin
+  if v_before is not distinct from v_after then
+    return null;
+  end if;
+
+  v_action := case tg_argv[0]
+    when 'profile' then 'update_tier'
+    when 'tier' then
+      case tg_op when 'INSERT' then 'tier_create' when 'UPDATE' then 'tier_update' else 'tier_delete' end
+    when 'quota' then
+      case tg_op when 'DELETE' then 'quota_delete' else 'quota_set' end
+    when 'catalog' then
+      case
+        when tg_table_name = 'model_catalog_targets' then 'catalog_set_targets'
+        when tg_op = 'INSERT' then 'catalog_add'
+        when tg_op = 'DELETE' then 'catalog_delete'
+        when (v_before - 'is_active') = (v_after - 'is_active') then 'catalog_toggle'
+        else 'catalog_update'
+      end
+  end;
+
+  insert into public.admin_audit_log (actor_id, action, target_type, target_id, before, after)
+  values (
+    auth.uid(),
+    v_action,
+    tg_argv[0],
+    coalesce(v_after, v_before)->>tg_argv[1],
+    v_before,
+    v_after
+  );
+
+  return null;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+create trigger profiles_audit_tier
+  after update of tier on public.profiles
+  for each row when (old.tier is distinct from new.tier)
+  execute function public.audit_admin_change('profile', 'id');
+
+create trigger tiers_audit
+  after insert or update or delete on public.tiers
+  for each row execute function public.audit_admin_change('tier', 'key');
+
+create trigger model_catalog_audit
+  after insert or update or delete on public.model_catalog
+  for each row execute function public.audit_admin_change('catalog', 'id');
+
+create trigger model_catalog_targets_audit
+  after insert or update or delete on public.model_catalog_targets
+  for each row execute function public.audit_admin_change('catalog', 'catalog_id');
+
+create trigger usage_quotas_audit
+  after insert or update or delete on public.usage_quotas
+  for each row execute function public.audit_admin_change('quota', 'id');
+
+-- ============================================
+-- Catalog migration, now audited in its own transaction
+-- ============================================
+-- Members whose primary model is p_from switch to p_to; p_from is replaced by
+-- p_to inside fallback chains (duplicates and a fallback equal to the primary
+-- are dropped). With p_check_tier, members whose Don's tier cannot use p_to
+-- are left untouched and reported as skipped. With p_dry_run nothing is
+-- written and the report shows what would happen. A real run is audited in
+-- the same transaction, as the member updates it makes aren't admin tables.
+create or replace function public.migrate_catalog_members(
+  p_from uuid,
+  p_to uuid,
+  p_dry_run boolean default true,
+  p_check_tier boolean default true
+)
+returns jsonb as $$
+declare
+  v_from public.model_catalog%rowtype;
+  v_to public.model_catalog%rowtype;
+  v_report jsonb;
+  v_migrated int := 0;
+  v_skipped int := 0;
+  v_notified int := 0;
+begin
+  if not exists (
+    select 1 from public.profiles
+    where id = (select auth.uid()) and tier = 'godfather'
+  ) then
+    raise exception 'Access denied: godfather tier required';
+  end if;
+
+  if p_from = p_to then
+    raise exception 'Source and target catalog entries are the same';
+  end if;
+
+  select * into v_from from public.model_catalog where id = p_from;
+  if not found then
+    raise exception 'Source catalog entry not found';
+  end if;
+
+  select * into v_to from public.model_catalog where id = p_to;
+  if not found then
+    raise exception 'Target catalog entry not found';
+  end if;
+  if not v_to.is_active then
+    raise exception 'Target catalog entry is inactive';
+  end if;
+
+  create temp table _catalog_migration on commit drop as
+  select
+    m.id,
+    m.name,
+    m.member_type,
+    m.owner_id,
+    p.display_name as owner_name,
+    p.tier as owner_tier,
+    m.catalog_model_id = p_from as uses_primary,
+    p_from = any(m.fallback_model_ids) as uses_fallback,
+    p_check_tier and not public.tier_can_use_model(p.tier, v_to.min_tier, v_to.provider) as skipped
+  from public.members m
+  join public.profiles p on p.id = m.owner_id
+  where m.catalog_model_id = p_from or p_from = any(m.fallback_model_ids);
+
+  select
+    coalesce(jsonb_agg(jsonb_build_object(
+      'id', id,
+      'name', name,
+      'member_type', member_type,
+      'owner_id', owner_id,
+      'owner_name', owner_name,
+      'owner_tier', owner_tier,
+      'uses_primary', uses_primary,
+      'uses_fallback', uses_fallback,
+      'skipped', skipped
+    ) order by owner_name, name), '[]'::jsonb),
+    count(*) filter (where not skipped),
+    count(*) filter (where skipped)
+  into v_report, v_migrated, v_skipped
+  from _catalog_migration;
+
+  if not p_dry_run then
+    update public.members m
+    set
+      catalog_model_id = case when m.catalog_model_id = p_from then p_to else m.catalog_model_id end,
+      fallback_model_ids = coalesce((
+        select array_agg(f.model_id order by f.ord)
+        from (
+          select u.model_id, min(u.ord) as ord
+          from unnest(array_replace(m.fallback_model_ids, p_from, p_to)) with ordinality as u(model_id, ord)
+          group by u.model_id
+        ) f
+        where f.model_id is distinct from
+          (case when m.catalog_model_id = p_from then p_to else m.catalog_model_id end)
+      ), '{}')
+    from _catalog_migration c
+    where c.id = m.id and not c.skipped;
+
+    insert into public.notifications (user_id, kind, title, body, data)
+    select
+      c.owner_id,
+      'model_migrated',
+      'Model switched',
+      v_from.alias || ' is being retired and was replaced by ' || v_to.alias
+        || ' for ' || string_agg(c.name, ', ' order by c.name) || '.',
+      jsonb_build_object(
+        'from_catalog_id', p_from,
+        'to_catalog_id', p_to,
+        'member_ids', jsonb_agg(c.id)
+      )
+    from _catalog_migration c
+    where not c.skipped
+    group by c.owner_id;
+
+    get diagnostics v_notified = row_count;
+
+    if v_migrated > 0 then
+      insert into public.admin_audit_log (actor_id, action, target_type, target_id, before, after)
+      select
+        auth.uid(),
+        'catalog_migrate',
+        'catalog',
+        p_from::text,
+        jsonb_build_object('catalog_model_id', p_from, 'member_ids', jsonb_agg(c.id order by c.id)),
+        jsonb_build_object('catalog_model_id', p_to, 'member_ids', jsonb_agg(c.id order by c.id))
+      from _catalog_migration c
+      where not c.skipped;
+    end if;
+  end if;
+
+  return jsonb_build_object(
+    'dry_run', p_dry_run,
+    'from', jsonb_build_object('id', v_from.id, 'alias', v_from.alias, 'provider', v_from.provider, 'model', v_from.model),
+    'to', jsonb_build_object('id', v_to.id, 'alias', v_to.alias, 'provider', v_to.provider, 'model', v_to.model),
+    'members', v_report,
+    'migrated_count', v_migrated,
+    'skipped_count', v_skipped,
+    'notified_dons', v_notified
+  );
+end;
+$$ language plpgsql security definer set search_path = '';
@@ -0,0 +1,190 @@
+-- 051-catalog-migration-capabilities.sql
+-- migrate_catalog_members (035) only checked tiers, so it could move a member
+-- onto a model that lacks what its role needs, which members-api would have
+-- refused: a caporegime onto a model without tool calling, say. Such members
+-- are now always skipped and reported with the capabilities the target lacks.
+--
+-- The role requirements mirror members-api's required_capabilities and are
+-- shared with the alias target checks.
+
+-- ============================================
+-- Capabilities a member's role needs from its models
+-- ============================================
+create or replace function public.member_required_capabilities(p_member_type text, p_soldier_type text)
+returns text[] as $$
+  select case
+    when p_member_type in ('caporegime', 'bookkeeper') then array['tools']
+    when p_member_type = 'soldier' and p_soldier_type = 'external' then array['tools']
+    when p_member_type = 'soldier' and p_soldier_type = 'reagent' then array[]::text[]
+    when p_member_type in ('soldier', 'consul') then array['web_search']
+    else array[]::text[]
+  end;
+$$ language sql immutable set search_path = '';
+
+-- ============================================
+-- Which of p_required a model's capabilities explicitly lack
+-- ============================================
+-- A missing key is unknown and counts as supported, as in members-api.
+create or replace function public.missing_capabilities(p_capabilities jsonb, p_required text[])
+returns text[] as $$
+  select coalesce(array_agg(r order by r), array[]::text[])
+  from unnest(p_required) as r
+  where p_capabilities->r = 'false'::jsonb;
+$$ language sql immutable set search_path = '';
+
+-- ============================================
+-- Catalog migration, skipping members the target can't serve
+-- ============================================
+-- Members whose primary model is p_from switch to p_to; p_from is replaced by
+-- p_to inside fallback chains (duplicates and a fallback equal to the primary
+-- are dropped). Members whose role needs a capability p_to explicitly lacks
+-- are left untouched and reported as skipped with skip_reason 'capabilities'
+-- and the missing_capabilities. With p_check_tier, members whose Don's tier
+-- cannot use p_to are skipped the same way with skip_reason 'tier'. With
+-- p_dry_run nothing is written and the report shows what would happen. A real run is audited in
+-- the same transaction, as the member updates it makes aren't admin tables.
+create or replace function public.migrate_catalog_members(
+  p_from uuid,
+  p_to uuid,
+  p_dry_run boolean default true,
+  p_check_tier boolean default true
+)
+returns jsonb as $$
+declare
+  v_from public.model_catalog%rowtype;
+  v_to public.model_catalog%rowtype;
+  v_report jsonb;
+  v_migrated int := 0;
+  v_skipped int := 0;
+  v_notified int := 0;
+begin
+  if not exists (
+    select 1 from public.profiles
+    where id = (select auth.uid()) and tier = 'godfather'
+  ) then
+    raise exception 'Access denied: godfather tier required';
+  end if;
+
+  if p_from = p_to then
+    raise exception 'Source and target catalog entries are the same';
+  end if;
+
+  select * into v_from from public.model_catalog where id = p_from;
+  if not found then
+    raise exception 'Source catalog entry not found';
+  end if;
+
+  select * into v_to from public.model_catalog where id = p_to;
+  if not found then
+    raise exception 'Target catalog entry not found';
+  end if;
+  if not v_to.is_active then
+    raise exception 'Target catalog entry is inactive';
+  end if;
+
+  create temp table _catalog_migration on commit drop as
+  select c.*, s.skip_reason, s.skip_reason is not null as skipped
+  from (
+    select
+      m.id,
+      m.name,
+      m.member_type,
+      m.owner_id,
+      p.display_name as owner_name,
+      p.tier as owner_tier,
+      m.catalog_model_id = p_from as uses_primary,
+      p_from = any(m.fallback_model_ids) as uses_fallback,
+      public.missing_capabilities(
+        v_to.capabilities,
+        public.member_required_capabilities(m.member_type, m.soldier_type)
+      ) as missing_capabilities
+    from public.members m
+    join public.profiles p on p.id = m.owner_id
+    where m.catalog_model_id = p_from or p_from = any(m.fallback_model_ids)
+  ) c
+  cross join lateral (
+    select case
+      when cardinality(c.missing_capabilities) > 0 then 'capabilities'
+      when p_check_tier and not public.tier_can_use_model(c.owner_tier, v_to.min_tier, v_to.provider) then 'tier'
+    end as skip_reason
+  ) s;
+
+  select
+    coalesce(jsonb_agg(jsonb_build_object(
+      'id', id,
+      'name', name,
+      'member_type', member_type,
+      'owner_id', owner_id,
+      'owner_name', owner_name,
+      'owner_tier', owner_tier,
+      'uses_primary', uses_primary,
+      'uses_fallback', uses_fallback,
+      'skipped', skipped,
+      'skip_reason', skip_reason,
+      'missing_capabilities', to_jsonb(missing_capabilities)
+    ) order by owner_name, name), '[]'::jsonb),
+    count(*) filter (where not skipped),
+    count(*) filter (where skipped)
+  into v_report, v_migrated, v_skipped
+  from _catalog_migration;
+
+  if not p_dry_run then
+    update public.members m
+    set
+      catalog_model_id = case when m.catalog_model_id = p_from then p_to else m.catalog_model_id end,
+      fallback_model_ids = coalesce((
+        select array_agg(f.model_id order by f.ord)
+        from (
+          select u.model_id, min(u.ord) as ord
+          from unnest(array_replace(m.fallback_model_ids, p_from, p_to)) with ordinality as u(model_id, ord)
+          group by u.model_id
+        ) f
+        where f.model_id is distinct from
+          (case when m.catalog_model_id = p_from then p_to else m.catalog_model_id end)
+      ), '{}')
+    from _catalog_migration c
+    where c.id = m.id and not c.skipped;
+
+    insert into public.notifications (user_id, kind, title, body, data)
+    select
+      c.owner_id,
+      'model_migrated',
+      'Model switched',
+      v_from.alias || ' is being retired and was replaced by ' || v_to.alias
+        || ' for ' || string_agg(c.name, ', ' order by c.name) || '.',
+      jsonb_build_object(
+        'from_catalog_id', p_from,
+        'to_catalog_id', p_to,
+        'member_ids', jsonb_agg(c.id)
+      )
+    from _catalog_migration c
+    where not c.skipped
+    group by c.owner_id;
+
+    get diagnostics v_notified = row_count;
+
+    if v_migrated > 0 then
+      insert into public.admin_audit_log (actor_id, action, target_type, target_id, before, after)
+      select
+        auth.uid(),
+        'catalog_migrate',
+        'catalog',
+        p_from::text,
+        jsonb_build_object('catalog_model_id', p_from, 'member_ids', jsonb_agg(c.id order by c.id)),
+        jsonb_build_object('catalog_model_id', p_to, 'member_ids', jsonb_agg(c.id order by c.id))
+      from _catalog_migration c
+      where not c.skipped;
+    end if;
+  end if;
+
+  return jsonb_build_object(
+    'dry_run', p_dry_run,
+    'from', jsonb_build_object('id', v_from.id, 'alias', v_from.alias, 'provider', v_from.provider, 'model', v_from.model),
+    'to', jsonb_build_object('id', v_to.id, 'alias', v_to.alias, 'provider', v_to.provider, 'model', v_to.model),
+    'members', v_report,
+    'migrated_count', v_migrated,
+    'skipped_count', v_skipped,
+    'notified_dons', v_notified
+  );
+end;
+$$ language plpgsql security definer set search_path = '';
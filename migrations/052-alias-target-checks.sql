@@ -0,0 +1,193 @@
+-- 052-alias-target-checks.sql
+-- Alias targets (037) were never checked against the alias itself, so an
+-- alias could route a member onto a model it can't use: one without the tool
+-- calling its role needs, or one above its Don's tier or on a provider the
+-- tier doesn't allow.
+--
+-- A target must now keep every capability the alias declares, and must be
+-- usable by every tier that can use the alias. set_catalog_targets enforces
+-- that and replaces an alias's targets in one transaction. Because entries can
+-- be edited after their targets are set, pick_catalog_target checks again at
+-- pick time, and also skips targets that lack what the member's role needs or
+-- sit outside its Don's tier. An alias with no target left answers with its
+-- own model, as before.
+--
+-- list_borrowed_soldiers (037) is unchanged here. Since 045 it still returns
+-- soldier_config, filtered through lendable_soldier_config.
+
+-- ============================================
+-- Why p_target can't serve p_alias, or null when it can
+-- ============================================
+create or replace function public.alias_target_problem(p_alias public.model_catalog, p_target public.model_catalog)
+returns text as $$
+  select coalesce(
+    (
+      select 'Target ' || p_target.alias || ' lacks ' || replace(c.key, '_', ' ') || ', which ' || p_alias.alias || ' supports'
+      from jsonb_each(p_alias.capabilities) c
+      where c.value = 'true'::jsonb and p_target.capabilities->c.key = 'false'::jsonb
+      order by c.key
+      limit 1
+    ),
+    (
+      select 'Target ' || p_target.alias || ' is not available to the ' || t.display_name || ' tier, which can use ' || p_alias.alias
+      from public.tiers t
+      where public.tier_can_use_model(t.key, p_alias.min_tier, p_alias.provider)
+        and not public.tier_can_use_model(t.key, p_target.min_tier, p_target.provider)
+      order by t.rank
+      limit 1
+    )
+  );
+$$ language sql stable security definer set search_path = '';
+
+-- ============================================
+-- RPC: Replace an alias's weighted targets
+-- ============================================
+-- p_targets is [{target_catalog_id, weight}]; an empty list makes the entry
+-- plain again. Returns the saved targets, highest weight first.
+create or replace function public.set_catalog_targets(p_catalog_id uuid, p_targets jsonb)
+returns jsonb as $$
+declare
+  v_alias public.model_catalog;
+  v_target public.model_catalog;
+  v_item jsonb;
+  v_problem text;
+begin
+  if not exists (
+    select 1 from public.profiles
+    where id = (select auth.uid()) and tier = 'godfather'
+  ) then
+    raise exception 'Access denied: godfather tier required';
+  end if;
+
+  select * into v_alias from public.model_catalog where id = p_catalog_id;
+  if not found then
+    raise exception 'Catalog entry not found';
+  end if;
+
+  for v_item in select * from jsonb_array_elements(coalesce(p_targets, '[]'::jsonb)) loop
+    select * into v_target from public.model_catalog where id = (v_item->>'target_catalog_id')::uuid;
+    if not found then
+      raise exception 'Target catalog entry not found: %', v_item->>'target_catalog_id';
+    end if;
+    v_problem := public.alias_target_problem(v_alias, v_target);
+    if v_problem is not null then
+      raise exception '%', v_problem;
+    end if;
+  end loop;
+
+  delete from public.model_catalog_targets where catalog_id = p_catalog_id;
+
+  insert into public.model_catalog_targets (catalog_id, target_catalog_id, weight)
+  select p_catalog_id, (t->>'target_catalog_id')::uuid, (t->>'weight')::int
+  from jsonb_array_elements(coalesce(p_targets, '[]'::jsonb)) t;
+
+  return coalesce((
+    select jsonb_agg(
+      jsonb_build_object('target_catalog_id', t.target_catalog_id, 'weight', t.weight)
+      order by t.weight desc
+    )
+    from public.model_catalog_targets t
+    where t.catalog_id = p_catalog_id
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- An alias's targets that may answer for a member
+-- ============================================
+-- Active targets that pass alias_target_problem and, when the member is known,
+-- have what its role needs and are open to its Don's tier.
+create or replace function public.usable_catalog_targets(
+  p_alias public.model_catalog,
+  p_member_type text,
+  p_soldier_type text,
+  p_owner_tier text
+) returns table (target_catalog_id uuid, weight int) as $$
+  select t.target_catalog_id, t.weight
+  from public.model_catalog_targets t
+  join public.model_catalog mc on mc.id = t.target_catalog_id
+  where t.catalog_id = p_alias.id
+    and mc.is_active
+    and public.alias_target_problem(p_alias, mc) is null
+    and (
+      p_member_type is null
+      or cardinality(public.missing_capabilities(
+        mc.capabilities,
+        public.member_required_capabilities(p_member_type, p_soldier_type)
+      )) = 0
+    )
+    and (p_owner_tier is null or public.tier_can_use_model(p_owner_tier, mc.min_tier, mc.provider));
+$$ language sql stable security definer set search_path = '';
+
+-- ============================================
+-- Pick the model that answers for a catalog entry, from usable targets only
+-- ============================================
+-- Returns {catalog_id, alias, provider, model, routed} and, when routed,
+-- {target_catalog_id, target_alias, sticky}. Only usable_catalog_targets are
+-- drawn from; p_member_id narrows them to what that member can use.
+create or replace function public.pick_catalog_target(p_catalog_id uuid, p_member_id uuid default null)
+returns jsonb as $$
+declare
+  v_entry public.model_catalog%rowtype;
+  v_member_type text;
+  v_soldier_type text;
+  v_owner_tier text;
+  v_total int;
+  v_point int;
+  v_target record;
+begin
+  select * into v_entry from public.model_catalog where id = p_catalog_id;
+  if not found then
+    return null;
+  end if;
+
+  if p_member_id is not null then
+    select m.member_type, m.soldier_type, p.tier
+    into v_member_type, v_soldier_type, v_owner_tier
+    from public.members m
+    join public.profiles p on p.id = m.owner_id
+    where m.id = p_member_id;
+  end if;
+
+  select coalesce(sum(u.weight), 0) into v_total
+  from public.usable_catalog_targets(v_entry, v_member_type, v_soldier_type, v_owner_tier) u;
+
+  if v_total = 0 then
+    return jsonb_build_object(
+      'catalog_id', v_entry.id,
+      'alias', v_entry.alias,
+      'provider', v_entry.provider,
+      'model', v_entry.model,
+      'routed', false
+    );
+  end if;
+
+  if v_entry.sticky_routing and p_member_id is not null then
+    v_point := mod(abs(hashtext(p_member_id::text || ':' || p_catalog_id::text)::bigint), v_total)::int;
+  else
+    v_point := floor(random() * v_total)::int;
+  end if;
+
+  select mc.id, mc.alias, mc.provider, mc.model into v_target
+  from (
+    select u.target_catalog_id,
+           sum(u.weight) over (order by u.target_catalog_id) as upper_bound
+    from public.usable_catalog_targets(v_entry, v_member_type, v_soldier_type, v_owner_tier) u
+  ) w
+  join public.model_catalog mc on mc.id = w.target_catalog_id
+  where w.upper_bound > v_point
+  order by w.upper_bound
+  limit 1;
+
+  return jsonb_build_object(
+    'catalog_id', v_entry.id,
+    'alias', v_entry.alias,
+    'provider', v_target.provider,
+    'model', v_target.model,
+    'routed', true,
+    'target_catalog_id', v_target.id,
+    'target_alias', v_target.alias,
+    'sticky', v_entry.sticky_routing and p_member_id is not null
+  );
+end;
+$$ language plpgsql volatile security definer set search_path = '';
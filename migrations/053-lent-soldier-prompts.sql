@@ -0,0 +1,79 @@
+-- 053-lent-soldier-prompts.sql
+-- list_borrowed_soldiers (045) still returned the lent soldier's
+-- system_prompt to the borrower. It now returns the same public fields as
+-- before minus the prompt.
+--
+-- The borrower's caporegime loads the prompt and full soldier_config itself
+-- when it runs the soldier: borrowed_soldier_id confirms, with the borrower's
+-- token, that the loan is theirs and still active, and the caporegime then
+-- reads the member row with the service role. Neither reaches the borrower.
+
+-- ============================================
+-- RPC: Soldiers lent to the caller that a caporegime may use right now
+-- ============================================
+create or replace function public.list_borrowed_soldiers(
+  p_caporegime_id uuid,
+  p_sit_down_id uuid default null
+) returns jsonb as $$
+begin
+  if not exists (
+    select 1 from public.members
+    where id = p_caporegime_id and owner_id = auth.uid() and member_type = 'caporegime'
+  ) then
+    raise exception 'CAPOREGIME_NOT_FOUND';
+  end if;
+
+  return coalesce((
+    select jsonb_agg(
+      jsonb_build_object(
+        'id', m.id,
+        'name', m.name,
+        'soldier_type', m.soldier_type,
+        'soldier_config', public.lendable_soldier_config(m.soldier_type, coalesce(m.soldier_config, '{}'::jsonb)),
+        'fallback_model_ids', m.fallback_model_ids,
+        'catalog_model', (
+          select jsonb_build_object('id', mc.id, 'provider', mc.provider, 'model', mc.model, 'alias', mc.alias)
+          from public.model_catalog mc where mc.id = m.catalog_model_id
+        ),
+        'loan_id', sl.id,
+        'lender_id', sl.lender_id,
+        'lender_name', p.display_name,
+        'loan_expires_at', sl.expires_at
+      )
+      order by m.name
+    )
+    from public.soldier_loans sl
+    join public.members m on m.id = sl.soldier_id
+    join public.profiles p on p.id = sl.lender_id
+    where sl.borrower_id = auth.uid()
+      and sl.status = 'active'
+      and sl.expires_at > now()
+      and m.soldier_type <> 'external'
+      and (sl.caporegime_id is null or sl.caporegime_id = p_caporegime_id)
+      and (sl.sit_down_id is null or sl.sit_down_id = p_sit_down_id)
+  ), '[]'::jsonb);
+end;
+$$ language plpgsql security definer set search_path = '';
+
+-- ============================================
+-- RPC: The soldier behind one of the caller's active loans
+-- ============================================
+create or replace function public.borrowed_soldier_id(p_loan_id uuid)
+returns uuid as $$
+declare
+  v_soldier_id uuid;
+begin
+  select sl.soldier_id into v_soldier_id
+  from public.soldier_loans sl
+  join public.members m on m.id = sl.soldier_id
+  where sl.id = p_loan_id
+    and sl.borrower_id = auth.uid()
+    and sl.status = 'active'
+    and sl.expires_at > now()
+    and m.soldier_type <> 'external';
+  if v_soldier_id is null then
+    raise exception 'LOAN_NOT_ACTIVE';
+  end if;
+  return v_soldier_id;
+end;
+$$ language plpgsql stable security definer set search_path = '';
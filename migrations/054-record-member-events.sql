@@ -0,0 +1,63 @@
+-- 054-record-member-events.sql
+-- 046 said the member formulas wrote events only after the Don's token had
+-- shown they sit at the table. That check never looked at member_id, so a Don
+-- in any sit-down could still write progress, text or errors as any member
+-- through a member formula.
+--
+-- Events are now written through record_member_events with the Don's token,
+-- by sit-down and the member formulas alike. Like insert_ai_message, it
+-- requires the caller and the member to both sit at the event's sit-down, and
+-- outside the Commission the member must also be the caller's own.
+
+-- ============================================
+-- RPC: Persist a batch of member events, returning their sequences in order
+-- ============================================
+-- p_events is [{sit_down_id, member_id, run_id, kind, payload}].
+create or replace function public.record_member_events(p_events jsonb)
+returns jsonb as $$
+declare
+  v_event jsonb;
+  v_sit_down_id uuid;
+  v_member_id uuid;
+  v_sequence bigint;
+  v_sequences jsonb := '[]'::jsonb;
+begin
+  for v_event in select * from jsonb_array_elements(coalesce(p_events, '[]'::jsonb)) loop
+    v_sit_down_id := (v_event->>'sit_down_id')::uuid;
+    v_member_id := (v_event->>'member_id')::uuid;
+
+    if not exists (
+      select 1 from public.sit_down_participants
+      where sit_down_id = v_sit_down_id and user_id = auth.uid()
+    ) then
+      raise exception 'Not a participant of this sit-down';
+    end if;
+
+    if not exists (
+      select 1
+      from public.sit_down_participants sp
+      join public.members m on m.id = sp.member_id
+      join public.sit_downs sd on sd.id = sp.sit_down_id
+      where sp.sit_down_id = v_sit_down_id
+        and sp.member_id = v_member_id
+        and (m.owner_id = auth.uid() or sd.is_commission)
+    ) then
+      raise exception 'Member is not a participant of this sit-down';
+    end if;
+
+    insert into public.member_events (sit_down_id, member_id, run_id, kind, payload)
+    values (
+      v_sit_down_id,
+      v_member_id,
+      v_event->>'run_id',
+      coalesce(v_event->>'kind', 'status'),
+      coalesce(v_event->'payload', '{}'::jsonb)
+    )
+    returning sequence into v_sequence;
+
+    v_sequences := v_sequences || to_jsonb(v_sequence);
+  end loop;
+
+  return v_sequences;
+end;
+$$ language plpgsql security definer set search_path = '';
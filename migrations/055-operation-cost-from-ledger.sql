@@ -0,0 +1,80 @@
+-- 055-operation-cost-from-ledger.sql
+-- operations_set_cost (031, 048) priced all of an operation's tokens at the
+-- model that gave the final answer, so the soldiers and sub-caporegimes it
+-- delegated to were billed at the caporegime's rates, whatever they ran on.
+--
+-- Caporegime runs now write one usage_ledger row per model they (and their
+-- soldiers and consulted members) called, priced by usage_ledger_set_cost.
+-- An operation's cost is the sum of its rows and those of its sub-caporegime
+-- operations, refreshed whenever rows are recorded against it. Operations with
+-- no ledger rows, such as those finished before this migration, keep the
+-- estimate from usage.answered_by.
+
+-- ============================================
+-- Priced ledger rows of an operation and its sub-operations (null when none)
+-- ============================================
+create or replace function public.operation_ledger_cost(p_operation_id uuid)
+returns numeric as $$
+  with recursive tree as (
+    select o.id from public.operations o where o.id = p_operation_id
+    union all
+    select o.id from public.operations o join tree t on o.parent_operation_id = t.id
+  )
+  select sum(ul.cost_usd)
+  from public.usage_ledger ul
+  where ul.operation_id in (select id from tree);
+$$ language sql stable set search_path = '';
+
+create or replace function public.operations_set_cost()
+returns trigger as $$
+declare
+  v_cost numeric;
+begin
+  if new.usage is null then
+    return new;
+  end if;
+
+  new.usage := new.usage - 'cost_usd';
+  v_cost := public.operation_ledger_cost(new.id);
+  if v_cost is null and new.usage->'answered_by' is not null then
+    v_cost := public.estimate_cost(
+      regexp_replace(new.usage->'answered_by'->>'catalyst_ref', '^.*\.', ''),
+      new.usage->'answered_by'->>'model',
+      (new.usage->>'input_tokens')::bigint,
+      (new.usage->>'output_tokens')::bigint
+    );
+  end if;
+  if v_cost is not null then
+    new.usage := new.usage || jsonb_build_object('cost_usd', v_cost);
+  end if;
+  return new;
+end;
+$$ language plpgsql set search_path = '';
+
+-- ============================================
+-- Re-price an operation and its parents when rows are recorded against it
+-- ============================================
+-- Touching usage fires operations_set_cost, which does the summing.
+create or replace function public.usage_ledger_refresh_operation_cost()
+returns trigger as $$
+begin
+  with recursive chain as (
+    select o.id, o.parent_operation_id
+    from public.operations o
+    where o.id in (select operation_id from new_rows where operation_id is not null)
+    union
+    select o.id, o.parent_operation_id
+    from public.operations o
+    join chain c on o.id = c.parent_operation_id
+  )
+  update public.operations o
+  set usage = coalesce(o.usage, '{}'::jsonb)
+  where o.id in (select id from chain);
+  return null;
+end;
+$$ language plpgsql security definer set search_path = '';
+
+create trigger usage_ledger_refresh_operation_cost
+  after insert on public.usage_ledger
+  referencing new table as new_rows
+  for each statement execute function public.usage_ledger_refresh_operation_cost();
@@ -0,0 +1,88 @@
+-- 056-drop-server-recorded.sql
+-- server_recorded (049) only repeated what 047 already guarantees. Since 047
+-- no client can insert usage_ledger rows, so every new row is written by the
+-- formulas or by database functions. Older rows can't be told apart anyway:
+-- 049 had to mark them all server-recorded. The column and its trigger are
+-- dropped, and check_quota goes back to counting every row of the Don's.
+
+drop trigger usage_ledger_set_server_recorded on public.usage_ledger;
+drop function public.usage_ledger_set_server_recorded();
+
+-- ============================================
+-- Quota status for the calling user
+-- ============================================
+-- Returns {allowed, exceeded, limits}. Each limit is
+-- {period, source, max_tokens, max_cost_usd, used_tokens, used_cost_usd, resets_at};
+-- `exceeded` is the first limit that is used up, or null.
+create or replace function public.check_quota()
+returns jsonb as $$
+declare
+  v_user_id uuid := auth.uid();
+  v_limits jsonb;
+  v_exceeded jsonb;
+begin
+  if v_user_id is null then
+    raise exception 'NOT_AUTHENTICATED';
+  end if;
+
+  with effective as (
+    select distinct on (q.period)
+      q.period,
+      case when q.user_id is not null then 'user' else 'tier' end as source,
+      q.max_tokens,
+      q.max_cost_usd
+    from public.usage_quotas q
+    join public.profiles p on p.id = v_user_id
+    where q.user_id = v_user_id or q.tier = p.tier
+    order by q.period, (q.user_id is not null) desc
+  ),
+  measured as (
+    select
+      e.*,
+      date_trunc(e.period, now()) as period_start,
+      date_trunc(e.period, now()) + ('1 ' || e.period)::interval as resets_at,
+      coalesce(u.tokens, 0) as used_tokens,
+      coalesce(u.cost, 0) as used_cost_usd
+    from effective e
+    left join lateral (
+      select
+        sum(ul.input_tokens + ul.output_tokens) as tokens,
+        sum(ul.cost_usd) as cost
+      from public.usage_ledger ul
+      where ul.user_id = v_user_id
+        and ul.created_at >= date_trunc(e.period, now())
+    ) u on true
+  )
+  select
+    coalesce(jsonb_agg(jsonb_build_object(
+      'period', m.period,
+      'source', m.source,
+      'max_tokens', m.max_tokens,
+      'max_cost_usd', m.max_cost_usd,
+      'used_tokens', m.used_tokens,
+      'used_cost_usd', m.used_cost_usd,
+      'resets_at', m.resets_at
+    ) order by m.period), '[]'::jsonb),
+    (array_agg(jsonb_build_object(
+      'period', m.period,
+      'source', m.source,
+      'metric', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then 'tokens' else 'cost' end,
+      'limit', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then m.max_tokens else m.max_cost_usd end,
+      'used', case when m.max_tokens is not null and m.used_tokens >= m.max_tokens then m.used_tokens else m.used_cost_usd end,
+      'resets_at', m.resets_at
+    ) order by m.period) filter (
+      where (m.max_tokens is not null and m.used_tokens >= m.max_tokens)
+         or (m.max_cost_usd is not null and m.used_cost_usd >= m.max_cost_usd)
+    ))[1]
+  into v_limits, v_exceeded
+  from measured m;
+
+  return jsonb_build_object(
+    'allowed', v_exceeded is null,
+    'exceeded', v_exceeded,
+    'limits', v_limits
+  );
+end;
+$$ language plpgsql stable security definer set search_path = '';
+
+alter table public.usage_ledger drop column server_recorded;